@@ -1,36 +1,73 @@
 //! Applied UI configuration snapshots and diffing.
 
 use crate::app::grid::GridConfig;
-use crate::app::ui::{EmGauge, EmLayerVisibility, EmMode, EqRender, FieldKind, GridUiState};
+use crate::app::morph;
+use crate::app::moving_frame;
+use crate::app::ui::{
+    Colormap, EmGauge, EmLayerVisibility, EmMode, EqRender, FieldKind, GridUiState,
+    InverseCurlBackend, LineQuantity, MorphHomotopy, VectorInput,
+};
+use crate::maths::glsl::expr_to_glsl;
 use mathhook_core::formatter::simple::SimpleContext;
 use mathhook_core::SimpleFormatter;
 
 #[derive(Clone, PartialEq)]
 pub(crate) struct AppliedConfig {
     pub(crate) grid_config: GridConfig,
-    pub(crate) coord_eqs: [String; 3],
+    pub(crate) coord_glsl: [String; 3],
+    pub(crate) morph: Option<AppliedMorph>,
+    /// Snapped `s` the CPU-side geometry is built at; zero without a morph.
+    morph_s_bits: u64,
+    /// Whether the embedding reads the scene time `t`.
+    pub(crate) embedding_uses_time: bool,
+    /// Snapped `t` the CPU-side geometry is built at; zero for a static embedding.
+    embedding_time_bits: u64,
+    pub(crate) line_coloring: AppliedLineColoring,
+    pub(crate) surface_axes: [bool; 3],
+    pub(crate) charts: Vec<AppliedChart>,
+    pub(crate) push_field_to: Option<usize>,
     field_kind: FieldKind,
+    /// Whether the Field-tab field reads the scene time `t`.
+    pub(crate) field_uses_time: bool,
+    /// Snapped `t` the field is sampled at; zero for a static field.
+    field_time_bits: u64,
     scalar_eq: String,
     vector_eqs: [String; 3],
+    vector_input: VectorInput,
     render_d: bool,
     pub(crate) normalize_field: bool,
     em_enabled: bool,
     em_mode: EmMode,
     em_gauge: EmGauge,
     em_phi: String,
+    em_gauge_function: String,
     em_a_eqs: [String; 3],
     em_e_eqs: [String; 3],
     em_b_eqs: [String; 3],
+    em_media_eqs: [String; 3],
+    em_phasor_eqs: [String; 3],
+    /// Charge density and current the covariant check expects.
+    em_source_eqs: [String; 4],
+    em_angular_frequency_bits: u64,
+    em_inverse_curl_backend: InverseCurlBackend,
+    em_solver_resolution: usize,
+    em_tree_opening_angle_bits: u64,
+    em_observer_beta_bits: [u64; 3],
     em_normalize_vectors: bool,
     em_light_speed_bits: u64,
     em_magnetic_vector_scale_bits: u64,
     em_layers: EmLayerVisibility,
+    em_pec_walls: [bool; 6],
+    em_conductor_eqs: Vec<String>,
+    em_domain_mask: String,
 }
 
 impl AppliedConfig {
     /// Builds the subset of UI state that drives world reconfiguration.
     pub(crate) fn from_ui(state: &GridUiState) -> Self {
         let context = SimpleContext::default();
+        let embedding_uses_time = moving_frame::embedding_uses_time(state);
+        let field_uses_time = moving_frame::field_uses_time(state);
         // EM mode lets normal Field-tab drafts stay unparsed. Diff against the last parsed
         // expression so hidden drafts are not treated as committed before EM is disabled.
         let scalar_eq = if state.em.enabled {
@@ -54,35 +91,70 @@ impl AppliedConfig {
 
         Self {
             grid_config: state.to_grid_config(),
-            coord_eqs: [
-                state
-                    .coords_sys
-                    .x
-                    .eq
-                    .to_simple(&context)
-                    .expect("Error while converting x eq"),
-                state
-                    .coords_sys
-                    .y
-                    .eq
-                    .to_simple(&context)
-                    .expect("Error while converting y eq"),
-                state
-                    .coords_sys
-                    .z
-                    .eq
-                    .to_simple(&context)
-                    .expect("Error while converting z eq"),
+            coord_glsl: [
+                coordinate_glsl(&state.coords_sys.x),
+                coordinate_glsl(&state.coords_sys.y),
+                coordinate_glsl(&state.coords_sys.z),
             ],
+            morph: state.morph.enabled.then(|| AppliedMorph {
+                target_glsl: [
+                    coordinate_glsl(&state.morph.target.x),
+                    coordinate_glsl(&state.morph.target.y),
+                    coordinate_glsl(&state.morph.target.z),
+                ],
+                homotopy: state.morph.homotopy,
+            }),
+            morph_s_bits: if state.morph.enabled {
+                morph::snap(state.morph.s).to_bits()
+            } else {
+                0
+            },
+            embedding_uses_time,
+            embedding_time_bits: if embedding_uses_time {
+                moving_frame::snap_time(state.scene_time).to_bits()
+            } else {
+                0
+            },
+            line_coloring: AppliedLineColoring {
+                quantity: state.line_coloring.quantity,
+                colormap: state.line_coloring.colormap,
+                scalar_glsl: (state.line_coloring.quantity == LineQuantity::ScalarField)
+                    .then(|| coordinate_glsl(&state.scalar_field)),
+            },
+            surface_axes: state.surface_axes,
+            charts: state
+                .atlas
+                .charts
+                .iter()
+                .map(|chart| AppliedChart {
+                    name: chart.name.clone(),
+                    coord_glsl: [
+                        coordinate_glsl(&chart.coords_sys.x),
+                        coordinate_glsl(&chart.coords_sys.y),
+                        coordinate_glsl(&chart.coords_sys.z),
+                    ],
+                    grid_config: chart.grid_config(),
+                    color: chart.color,
+                })
+                .collect(),
+            push_field_to: state.atlas.push_field_to,
             field_kind: state.field_kind,
+            field_uses_time,
+            field_time_bits: if field_uses_time {
+                moving_frame::snap_time(state.scene_time).to_bits()
+            } else {
+                0
+            },
             scalar_eq,
             vector_eqs,
+            vector_input: state.vector_input,
             render_d: state.render_d,
             normalize_field: state.normalize_field,
             em_enabled: state.em.enabled,
             em_mode: state.em.mode,
             em_gauge: state.em.gauge,
             em_phi: state.em.phi.eq_str.clone(),
+            em_gauge_function: state.em.gauge_function.eq_str.clone(),
             em_a_eqs: [
                 state.em.vector_potential.x.eq_str.clone(),
                 state.em.vector_potential.y.eq_str.clone(),
@@ -98,39 +170,138 @@ impl AppliedConfig {
                 state.em.magnetic_field.y.eq_str.clone(),
                 state.em.magnetic_field.z.eq_str.clone(),
             ],
+            em_media_eqs: [
+                state.em.permittivity.eq_str.clone(),
+                state.em.permeability.eq_str.clone(),
+                state.em.conductivity.eq_str.clone(),
+            ],
+            em_phasor_eqs: [
+                state.em.phasor_electric_field.x.eq_str.clone(),
+                state.em.phasor_electric_field.y.eq_str.clone(),
+                state.em.phasor_electric_field.z.eq_str.clone(),
+            ],
+            em_source_eqs: [
+                state.em.charge_density.eq_str.clone(),
+                state.em.current_density.x.eq_str.clone(),
+                state.em.current_density.y.eq_str.clone(),
+                state.em.current_density.z.eq_str.clone(),
+            ],
+            em_angular_frequency_bits: state.em.angular_frequency.to_bits(),
+            em_inverse_curl_backend: state.em.inverse_curl_backend,
+            em_solver_resolution: state.em.solver_resolution,
+            em_tree_opening_angle_bits: state.em.tree_opening_angle.to_bits(),
+            em_observer_beta_bits: state.em.observer_beta.map(f64::to_bits),
             em_normalize_vectors: state.em.normalize_vectors,
             em_light_speed_bits: state.em.light_speed.to_bits(),
             em_magnetic_vector_scale_bits: state.em.magnetic_vector_scale.to_bits(),
             em_layers: state.em.layers.clone(),
+            em_pec_walls: state.em.pec_walls,
+            em_conductor_eqs: state
+                .em
+                .conductors
+                .iter()
+                .map(|conductor| conductor.eq_str.clone())
+                .collect(),
+            em_domain_mask: state.em.domain_mask.eq_str.clone(),
         }
     }
 
+    /// Scene time the Field-tab field is sampled at.
+    pub(crate) fn field_time(&self) -> f64 {
+        f64::from_bits(self.field_time_bits)
+    }
+
+    /// Returns whether the embedding or the Field-tab field moves with the scene time.
+    pub(crate) fn uses_time(&self) -> bool {
+        self.embedding_uses_time || self.field_uses_time
+    }
+
     /// Computes which high-level parts of the world changed between two snapshots.
     pub(crate) fn diff(&self, next: &Self) -> ApplyDiff {
         ApplyDiff {
             grid_changed: self.grid_config != next.grid_config,
-            coords_changed: self.coord_eqs != next.coord_eqs,
+            coords_changed: self.coord_glsl != next.coord_glsl || self.morph != next.morph,
+            morph_step_changed: self.morph_s_bits != next.morph_s_bits,
+            time_step_changed: self.embedding_time_bits != next.embedding_time_bits,
+            line_coloring_changed: self.line_coloring != next.line_coloring,
+            surface_axes_changed: self.surface_axes != next.surface_axes,
+            charts_changed: self.charts != next.charts,
+            field_push_changed: self.push_field_to != next.push_field_to,
             field_kind_changed: self.field_kind != next.field_kind,
+            field_time_step_changed: self.field_time_bits != next.field_time_bits,
             scalar_changed: self.scalar_eq != next.scalar_eq,
-            vector_changed: self.vector_eqs != next.vector_eqs,
+            vector_changed: self.vector_eqs != next.vector_eqs
+                || self.vector_input != next.vector_input,
             render_d_changed: self.render_d != next.render_d,
             normalize_changed: self.normalize_field != next.normalize_field,
             em_enabled_changed: self.em_enabled != next.em_enabled,
             em_mode_changed: self.em_mode != next.em_mode,
             em_equations_changed: self.em_gauge != next.em_gauge
                 || self.em_phi != next.em_phi
+                || self.em_gauge_function != next.em_gauge_function
                 || self.em_a_eqs != next.em_a_eqs
                 || self.em_e_eqs != next.em_e_eqs
                 || self.em_b_eqs != next.em_b_eqs
+                || self.em_media_eqs != next.em_media_eqs
+                || self.em_phasor_eqs != next.em_phasor_eqs
+                || self.em_angular_frequency_bits != next.em_angular_frequency_bits
+                || self.em_inverse_curl_backend != next.em_inverse_curl_backend
+                || self.em_solver_resolution != next.em_solver_resolution
+                || self.em_tree_opening_angle_bits != next.em_tree_opening_angle_bits
                 || self.em_light_speed_bits != next.em_light_speed_bits,
+            em_sources_changed: self.em_source_eqs != next.em_source_eqs,
             em_magnetic_scale_changed: self.em_magnetic_vector_scale_bits
                 != next.em_magnetic_vector_scale_bits,
             em_normalize_changed: self.em_normalize_vectors != next.em_normalize_vectors,
+            em_observer_changed: self.em_observer_beta_bits != next.em_observer_beta_bits,
             em_layers_changed: self.em_layers != next.em_layers,
+            em_walls_changed: self.em_pec_walls != next.em_pec_walls,
+            em_regions_changed: self.em_conductor_eqs != next.em_conductor_eqs
+                || self.em_domain_mask != next.em_domain_mask,
+        }
+    }
+}
+
+/// Morph target and homotopy as last applied; `s` moves without an Apply.
+#[derive(Clone, PartialEq)]
+pub(crate) struct AppliedMorph {
+    pub(crate) target_glsl: [String; 3],
+    pub(crate) homotopy: MorphHomotopy,
+}
+
+/// Grid-line coloring as last applied, with the GLSL of the scalar field while the lines are
+/// colored by it. The default keeps the axis colors, as overlay charts do.
+#[derive(Clone, PartialEq)]
+pub struct AppliedLineColoring {
+    pub(crate) quantity: LineQuantity,
+    pub(crate) colormap: Colormap,
+    pub(crate) scalar_glsl: Option<String>,
+}
+
+impl Default for AppliedLineColoring {
+    fn default() -> Self {
+        Self {
+            quantity: LineQuantity::Axis,
+            colormap: Colormap::Viridis,
+            scalar_glsl: None,
         }
     }
 }
 
+/// One overlay chart of the atlas as last applied.
+#[derive(Clone, PartialEq)]
+pub(crate) struct AppliedChart {
+    pub(crate) name: String,
+    pub(crate) coord_glsl: [String; 3],
+    pub(crate) grid_config: GridConfig,
+    pub(crate) color: [f32; 3],
+}
+
+/// GLSL of one equation validated for the grid shader, which also keys its changes.
+fn coordinate_glsl(eq: &EqRender) -> String {
+    expr_to_glsl(&eq.eq).expect("Shader equations are validated for GLSL")
+}
+
 fn equation_key(eq: &EqRender, context: &SimpleContext) -> String {
     eq.eq
         .to_simple(context)
@@ -140,8 +311,19 @@ fn equation_key(eq: &EqRender, context: &SimpleContext) -> String {
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub(crate) struct ApplyDiff {
     pub(crate) grid_changed: bool,
+    /// The embedding equations, morph target or homotopy changed, so the shader is rebuilt.
     pub(crate) coords_changed: bool,
+    /// The morph moved to another step; only the CPU-side geometry is rebuilt.
+    pub(crate) morph_step_changed: bool,
+    /// A moving embedding reached another time step; only the CPU-side geometry is rebuilt.
+    pub(crate) time_step_changed: bool,
+    pub(crate) line_coloring_changed: bool,
+    pub(crate) surface_axes_changed: bool,
+    pub(crate) charts_changed: bool,
+    pub(crate) field_push_changed: bool,
     pub(crate) field_kind_changed: bool,
+    /// A moving Field-tab field reached another time step; only its samples are re-read.
+    pub(crate) field_time_step_changed: bool,
     pub(crate) scalar_changed: bool,
     pub(crate) vector_changed: bool,
     pub(crate) render_d_changed: bool,
@@ -149,20 +331,40 @@ pub(crate) struct ApplyDiff {
     pub(crate) em_enabled_changed: bool,
     pub(crate) em_mode_changed: bool,
     pub(crate) em_equations_changed: bool,
+    /// Only the sources the covariant check compares against changed.
+    pub(crate) em_sources_changed: bool,
     pub(crate) em_magnetic_scale_changed: bool,
     pub(crate) em_normalize_changed: bool,
+    pub(crate) em_observer_changed: bool,
     pub(crate) em_layers_changed: bool,
+    pub(crate) em_walls_changed: bool,
+    pub(crate) em_regions_changed: bool,
 }
 
 impl ApplyDiff {
+    /// Returns whether the coordinate system on the CPU must be rebuilt.
+    pub(crate) fn embedding_changed(self) -> bool {
+        self.coords_changed || self.morph_step_changed || self.time_step_changed
+    }
+
     /// Returns whether the grid geometry or coordinate embedding changed.
     pub(crate) fn geometry_changed(self) -> bool {
-        self.grid_changed || self.coords_changed
+        self.grid_changed || self.embedding_changed()
+    }
+
+    /// Returns whether the grid vertex shader must be recompiled.
+    pub(crate) fn grid_shader_changed(self) -> bool {
+        self.coords_changed || self.line_coloring_changed
+    }
+
+    /// Returns whether the range the grid lines are colored over must be resampled.
+    pub(crate) fn line_range_changed(self) -> bool {
+        self.geometry_changed() || self.line_coloring_changed
     }
 
     /// Returns whether the active runtime field must be rebuilt.
     pub(crate) fn runtime_field_changed(self) -> bool {
-        self.coords_changed
+        self.embedding_changed()
             || self.field_kind_changed
             || self.scalar_changed
             || self.vector_changed
@@ -170,11 +372,11 @@ impl ApplyDiff {
     }
 
     pub(crate) fn em_runtime_changed(self) -> bool {
-        self.coords_changed
-            || self.grid_changed
+        self.geometry_changed()
             || self.em_enabled_changed
             || self.em_mode_changed
             || self.em_equations_changed
+            || self.em_regions_changed
     }
 
     pub(crate) fn em_render_changed(self) -> bool {
@@ -182,11 +384,55 @@ impl ApplyDiff {
             || self.em_runtime_changed()
             || self.em_magnetic_scale_changed
             || self.em_normalize_changed
+            || self.em_observer_changed
             || self.em_layers_changed
     }
 
-    /// Returns whether cached field samples must be recomputed.
+    /// Returns whether the conducting-wall mesh must be rebuilt.
+    ///
+    /// Walls are drawn independently of the EM samples, so toggling one never touches the EM
+    /// runtime or its render cache.
+    pub(crate) fn pec_walls_changed(self) -> bool {
+        self.geometry_changed() || self.em_enabled_changed || self.em_walls_changed
+    }
+
+    /// Returns whether the conductor and mask surfaces must be re-extracted.
+    ///
+    /// Their shapes depend on the EM mode too, since regions only apply to the source modes.
+    pub(crate) fn region_surfaces_changed(self) -> bool {
+        self.geometry_changed()
+            || self.em_enabled_changed
+            || self.em_mode_changed
+            || self.em_regions_changed
+    }
+
+    /// Returns whether the coordinate-surface mesh must be re-tessellated.
+    pub(crate) fn coordinate_surfaces_changed(self) -> bool {
+        self.geometry_changed() || self.surface_axes_changed
+    }
+
+    /// Returns whether the chart overlaps must be recounted.
+    pub(crate) fn chart_overlaps_changed(self) -> bool {
+        self.geometry_changed() || self.charts_changed
+    }
+
+    /// Returns whether the field pushed onto an overlay chart must be recomputed.
+    pub(crate) fn pushed_field_changed(self) -> bool {
+        self.chart_overlaps_changed()
+            || self.field_push_changed
+            || self.normalize_changed
+            || self.runtime_field_changed()
+            || self.field_time_step_changed
+            || self.em_enabled_changed
+    }
+
+    /// Returns whether the field samples of every time visited so far are stale.
     pub(crate) fn field_cache_changed(self) -> bool {
         self.geometry_changed() || self.runtime_field_changed()
     }
+
+    /// Returns whether the field must be read at the samples, or fetched from earlier reads.
+    pub(crate) fn field_samples_changed(self) -> bool {
+        self.field_cache_changed() || self.field_time_step_changed
+    }
 }
@@ -0,0 +1,287 @@
+//! Transition maps between overlaid charts: overlap reports and fields pushed across charts.
+//!
+//! A chart is a coordinate system `Φ` restricted to its grid bounds. The transition map from
+//! chart `A` to chart `B` is `Φ_B⁻¹ ∘ Φ_A`; `Φ_B` is inverted numerically by damped Newton steps
+//! seeded from the nearest lattice samples of `B`, and only solutions inside `B`'s bounds, after
+//! shifting periodic coordinates by whole turns, count.
+
+use crate::app::coords_sys::{CoordSampleGeometry, CoordsSys};
+use crate::app::grid::GridConfig;
+use crate::app::ui::ChartOverlap;
+use crate::maths::field::VectorField;
+use crate::maths::Point;
+use nalgebra::{Matrix3, Vector3};
+
+/// Lattice samples of the target chart tried as Newton seeds, nearest first.
+const SEED_COUNT: usize = 3;
+
+/// Numerical transition map from one chart's abstract coordinates to another's.
+pub struct TransitionMap {
+    from: CoordSampleGeometry,
+    to: CoordSampleGeometry,
+    to_bounds: [(f64, f64); 3],
+    /// Lattice samples of the target chart with their world positions.
+    seeds: Vec<(Vector3<f64>, Vector3<f64>)>,
+}
+
+impl TransitionMap {
+    /// Builds the map from `from` into `to`, the latter restricted to `to_config`.
+    pub fn new(from: &CoordsSys, to: &CoordsSys, to_config: &GridConfig) -> Self {
+        let to = to.sample_geometry();
+        let seeds = lattice(to_config)
+            .into_iter()
+            .map(|point| (point, to.eval_position(point)))
+            .filter(|(_, world)| world.iter().all(|value| value.is_finite()))
+            .collect();
+        Self {
+            from: from.sample_geometry(),
+            to,
+            to_bounds: to_config.bounds(),
+            seeds,
+        }
+    }
+
+    /// Maps the source-chart point `point` into the target chart, if its image lies there.
+    pub fn map_point(&self, point: Vector3<f64>) -> Option<Vector3<f64>> {
+        let world = self.from.eval_position(point);
+        if !world.iter().all(|value| value.is_finite()) {
+            return None;
+        }
+        self.nearest_seeds(world)
+            .into_iter()
+            .filter_map(|seed| self.to.solve_inverse(world, seed).ok())
+            .find_map(|solution| {
+                self.to
+                    .wrap_into_bounds(solution, world, &self.to_bounds)
+                    .ok()
+            })
+    }
+
+    /// Maps `point` together with the differential of the transition map there,
+    /// `J_to⁻¹ · J_from`, which carries natural vector components from one chart to the other.
+    pub fn differential(&self, point: Vector3<f64>) -> Option<(Vector3<f64>, Matrix3<f64>)> {
+        let image = self.map_point(point)?;
+        let to_inverse = self.to.jacobian(image)?.try_inverse()?;
+        let differential = to_inverse * self.from.jacobian(point)?;
+        differential
+            .iter()
+            .all(|value| value.is_finite())
+            .then_some((image, differential))
+    }
+
+    /// Pushes the natural components `components` at `point` into the target chart.
+    pub fn push_vector(
+        &self,
+        point: Vector3<f64>,
+        components: Vector3<f64>,
+    ) -> Option<(Vector3<f64>, Vector3<f64>)> {
+        self.differential(point)
+            .map(|(image, differential)| (image, differential * components))
+    }
+
+    fn nearest_seeds(&self, world: Vector3<f64>) -> Vec<Vector3<f64>> {
+        let mut nearest: Vec<(f64, Vector3<f64>)> = Vec::with_capacity(SEED_COUNT + 1);
+        for (point, position) in &self.seeds {
+            let distance = (position - world).norm_squared();
+            if nearest.len() == SEED_COUNT && distance >= nearest[SEED_COUNT - 1].0 {
+                continue;
+            }
+            let slot = nearest.partition_point(|(other, _)| *other <= distance);
+            nearest.insert(slot, (distance, *point));
+            nearest.truncate(SEED_COUNT);
+        }
+        nearest.into_iter().map(|(_, point)| point).collect()
+    }
+}
+
+/// One lattice sample of a target chart carrying a field pushed from another chart.
+#[derive(Debug, Clone, Copy)]
+pub struct PushedSample {
+    pub abstract_pos: Vector3<f64>,
+    pub world_pos: Vector3<f64>,
+    /// Natural components in the target chart.
+    pub components: Vector3<f64>,
+    pub world_vector: Vector3<f64>,
+}
+
+/// Pushes `field`, defined on `source` within `source_config`, onto the lattice of `target`.
+///
+/// Each target lattice sample is first located in the source chart, where the field is read in
+/// natural components, and those are then pushed forward by the source-to-target map. Target
+/// samples outside the source chart, or at a singular point of either chart, are skipped.
+pub fn push_field(
+    field: &VectorField,
+    source: (&CoordsSys, &GridConfig),
+    target: (&CoordsSys, &GridConfig),
+) -> Vec<PushedSample> {
+    let to_source = TransitionMap::new(target.0, source.0, source.1);
+    let to_target = TransitionMap::new(source.0, target.0, target.1);
+    let source_geometry = source.0.sample_geometry();
+    let target_geometry = target.0.sample_geometry();
+    lattice(target.1)
+        .into_iter()
+        .filter_map(|point| {
+            let source_point = to_source.map_point(point)?;
+            let basis = source_geometry.eval_regular_tangent_basis(source_point)?;
+            let otn = field.at(Point {
+                x: source_point.x,
+                y: source_point.y,
+                z: source_point.z,
+            });
+            let world = source_geometry.vector_to_world(&basis, Vector3::new(otn.x, otn.y, otn.z));
+            let source_components = source_geometry.jacobian(source_point)?.try_inverse()? * world;
+            let (_, components) = to_target.push_vector(source_point, source_components)?;
+            let world_vector = target_geometry.jacobian(point)? * components;
+            let world_pos = target_geometry.eval_position(point);
+            [components, world_vector, world_pos]
+                .iter()
+                .all(|vector| vector.iter().all(|value| value.is_finite()))
+                .then_some(PushedSample {
+                    abstract_pos: point,
+                    world_pos,
+                    components,
+                    world_vector,
+                })
+        })
+        .collect()
+}
+
+/// Counts the lattice samples each chart shares with the other, in both directions.
+pub fn chart_overlap(
+    names: [&str; 2],
+    first: (&CoordsSys, &GridConfig),
+    second: (&CoordsSys, &GridConfig),
+) -> ChartOverlap {
+    let charts = [first, second];
+    let mut shared = [0; 2];
+    let mut totals = [0; 2];
+    let mut world_box: Option<([f64; 3], [f64; 3])> = None;
+    for (index, &(coords, config)) in charts.iter().enumerate() {
+        let (other_coords, other_config) = charts[1 - index];
+        let map = TransitionMap::new(coords, other_coords, other_config);
+        let geometry = coords.sample_geometry();
+        for point in lattice(config) {
+            totals[index] += 1;
+            if map.map_point(point).is_none() {
+                continue;
+            }
+            shared[index] += 1;
+            let world: [f64; 3] = geometry.eval_position(point).into();
+            let (min, max) = world_box.get_or_insert((world, world));
+            for axis in 0..3 {
+                min[axis] = min[axis].min(world[axis]);
+                max[axis] = max[axis].max(world[axis]);
+            }
+        }
+    }
+    ChartOverlap {
+        charts: names.map(str::to_string),
+        shared,
+        totals,
+        world_box,
+    }
+}
+
+/// Abstract points where three grid lines of `config` cross.
+fn lattice(config: &GridConfig) -> Vec<Vector3<f64>> {
+    let [us, vs, ws] = [0, 1, 2].map(|axis| config.line_positions(axis));
+    us.iter()
+        .flat_map(|&u| {
+            vs.iter()
+                .flat_map(move |&v| ws.iter().map(move |&w| Vector3::new(u, v, w)))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{chart_overlap, push_field, TransitionMap};
+    use crate::app::coords_sys::CoordsSys;
+    use crate::app::grid::GridConfig;
+    use crate::maths::differential::Form;
+    use crate::maths::field::VectorField;
+    use mathhook_core::Parser;
+    use nalgebra::vector;
+    use std::f64::consts::{FRAC_PI_2, PI};
+
+    fn coords(x: &str, y: &str, z: &str) -> CoordsSys {
+        let parse = |expr: &str| Parser::default().parse(expr).unwrap();
+        CoordsSys::new(parse(x), parse(y), parse(z))
+    }
+
+    #[test]
+    fn transition_map_inverts_cylindrical_coordinates_within_bounds() {
+        let cartesian = coords("x", "y", "z");
+        let cylindrical = coords("x*cos(y)", "x*sin(y)", "z");
+        let config = GridConfig::new(0.0, 3.0, 4.0, 0.0, 2.0 * PI, 9.0, -1.0, 1.0, 3.0);
+        let map = TransitionMap::new(&cartesian, &cylindrical, &config);
+
+        let image = map.map_point(vector![0.0, 2.0, 0.5]).unwrap();
+        let (_, pushed) = map
+            .push_vector(vector![0.0, 2.0, 0.5], vector![1.0, 0.0, 0.0])
+            .unwrap();
+
+        assert!(
+            (image - vector![2.0, FRAC_PI_2, 0.5]).norm() < 1.0e-8,
+            "{image}"
+        );
+        assert!(
+            (pushed - vector![0.0, -0.5, 0.0]).norm() < 1.0e-8,
+            "{pushed}"
+        );
+        assert!(map.map_point(vector![4.0, 0.0, 0.0]).is_none());
+        assert!(map.map_point(vector![1.0, 0.0, 2.0]).is_none());
+    }
+
+    #[test]
+    fn pushed_cartesian_field_takes_cylindrical_components() {
+        let cartesian = coords("x", "y", "z");
+        let cylindrical = coords("x*cos(y)", "x*sin(y)", "z");
+        let parse = |expr: &str| Parser::default().parse(expr).unwrap();
+        let field = VectorField::from_otn(
+            Form::new_otn(vec![parse("1"), parse("0"), parse("0")], 1),
+            cartesian.get_space(),
+        );
+        let source = GridConfig::new(-3.0, 3.0, 3.0, -3.0, 3.0, 3.0, -1.0, 1.0, 3.0);
+        let target = GridConfig::new(0.5, 2.0, 3.0, 0.0, PI, 5.0, -0.5, 0.5, 3.0);
+
+        let pushed = push_field(&field, (&cartesian, &source), (&cylindrical, &target));
+
+        assert_eq!(pushed.len(), 45);
+        for sample in pushed {
+            let (r, theta) = (sample.abstract_pos.x, sample.abstract_pos.y);
+            let expected = vector![theta.cos(), -theta.sin() / r, 0.0];
+            assert!((sample.components - expected).norm() < 1.0e-8);
+            assert!((sample.world_vector - vector![1.0, 0.0, 0.0]).norm() < 1.0e-8);
+        }
+    }
+
+    #[test]
+    fn overlap_counts_shared_samples_in_both_directions() {
+        let cartesian = coords("x", "y", "z");
+        let spherical = coords("x*cos(y)*sin(z)", "x*sin(y)*sin(z)", "x*cos(z)");
+        let box_config = GridConfig::new(0.5, 1.0, 3.0, 0.5, 1.0, 3.0, 0.5, 1.0, 3.0);
+        let ball_config = GridConfig::new(0.0, 3.0, 4.0, 0.0, 2.0 * PI, 5.0, 0.0, PI, 5.0);
+        let far_config = GridConfig::new(5.0, 6.0, 2.0, 5.0, 6.0, 2.0, 5.0, 6.0, 2.0);
+
+        let overlap = chart_overlap(
+            ["box", "ball"],
+            (&cartesian, &box_config),
+            (&spherical, &ball_config),
+        );
+        let disjoint = chart_overlap(
+            ["box", "far"],
+            (&cartesian, &box_config),
+            (&cartesian, &far_config),
+        );
+
+        assert_eq!(overlap.shared[0], 27);
+        assert_eq!(overlap.totals, [27, 100]);
+        assert!(overlap.shared[1] < overlap.totals[1]);
+        let (min, max) = overlap.world_box.unwrap();
+        assert!(min.iter().all(|value| *value >= -3.0 - 1.0e-9));
+        assert!(max.iter().all(|value| *value <= 3.0 + 1.0e-9));
+        assert_eq!(disjoint.shared, [0, 0]);
+        assert_eq!(disjoint.world_box, None);
+    }
+}
@@ -0,0 +1,188 @@
+//! Shaded meshes of constant-coordinate surfaces, such as the spheres, cones and half-planes of
+//! spherical coordinates.
+//!
+//! One surface is built per grid line of each selected axis. It spans the two other axes on a
+//! tensor lattice: the grid lines of those axes, with every gap cut into as many pieces as the
+//! grid edges would use for the same curvature, so neighbouring cells always share vertices.
+
+use crate::app::coords_sys::CoordsSys;
+use crate::app::grid::{curvature_to_vertices, GridConfig};
+use crate::app::lattice_mesh::lattice_triangles;
+use crate::{TriIndexes, Vertex};
+use nalgebra::Vector3;
+
+/// Most pieces one gap between grid lines is cut into.
+const MAX_GAP_PIECES: usize = 24;
+/// Half-length of the interval `CoordsSys::get_curvature` integrates over, as for grid edges.
+const CURVATURE_SPAN: f64 = 1.0;
+/// Base color of each family, matching the grid lines of the same axis.
+const FAMILY_COLORS: [[f32; 3]; 3] = [[0.90, 0.25, 0.20], [0.35, 0.40, 0.85], [0.20, 0.75, 0.30]];
+/// Largest blend toward white; later surfaces of a family are lighter.
+const MAX_LIGHTENING: f32 = 0.55;
+
+/// Vertex, normal, color and index buffers of the coordinate surfaces, ready to upload.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SurfaceMesh {
+    pub vertices: Vec<Vertex>,
+    pub normals: Vec<Vertex>,
+    pub colors: Vec<Vertex>,
+    pub indices: Vec<TriIndexes>,
+}
+
+impl SurfaceMesh {
+    /// Returns whether no surface produced any triangle.
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+}
+
+/// Tessellates the surfaces `axis = const` through every grid line of the axes in `axes`.
+pub fn build_coordinate_surfaces(
+    coords: &CoordsSys,
+    config: &GridConfig,
+    axes: [bool; 3],
+) -> SurfaceMesh {
+    let mut mesh = SurfaceMesh::default();
+    for axis in (0..3).filter(|&axis| axes[axis]) {
+        let values = config.line_positions(axis);
+        for (index, &value) in values.iter().enumerate() {
+            let lightening = if values.len() > 1 {
+                MAX_LIGHTENING * index as f32 / (values.len() - 1) as f32
+            } else {
+                0.0
+            };
+            let color = FAMILY_COLORS[axis].map(|channel| channel + (1.0 - channel) * lightening);
+            push_surface(&mut mesh, coords, config, axis, value, color);
+        }
+    }
+    mesh
+}
+
+fn push_surface(
+    mesh: &mut SurfaceMesh,
+    coords: &CoordsSys,
+    config: &GridConfig,
+    axis: usize,
+    value: f64,
+    color: [f32; 3],
+) {
+    let (first, second) = ((axis + 1) % 3, (axis + 2) % 3);
+    let bounds = config.bounds();
+    let centre = |along: usize| 0.5 * (bounds[along].0 + bounds[along].1);
+    let abstract_point = |s: f64, t: f64| {
+        let mut point = Vector3::zeros();
+        point[axis] = value;
+        point[first] = s;
+        point[second] = t;
+        point
+    };
+    let first_breaks = adaptive_breaks(config, first, |s| {
+        coords.get_curvature(abstract_point(s, centre(second)), CURVATURE_SPAN)
+    });
+    let second_breaks = adaptive_breaks(config, second, |t| {
+        coords.get_curvature(abstract_point(centre(first), t), CURVATURE_SPAN)
+    });
+
+    let side = second_breaks.len();
+    let offset = mesh.vertices.len();
+    let mut points = Vec::with_capacity(first_breaks.len() * side);
+    for &s in &first_breaks {
+        for &t in &second_breaks {
+            points.push(coords.eval_position(abstract_point(s, t)));
+        }
+    }
+
+    let mut normals = vec![Vector3::zeros(); points.len()];
+    for [a, b, c] in lattice_triangles(&points, side) {
+        // Unnormalised, so larger triangles weigh more in the vertex normals.
+        let normal = (points[b] - points[a]).cross(&(points[c] - points[a]));
+        for corner in [a, b, c] {
+            normals[corner] += normal;
+        }
+        mesh.indices
+            .push([a, b, c].map(|corner| (offset + corner) as u32));
+    }
+
+    for (point, normal) in points.iter().zip(normals) {
+        let normal = normal.try_normalize(f64::EPSILON).unwrap_or(Vector3::z());
+        mesh.vertices
+            .push([point.x as f32, point.y as f32, point.z as f32]);
+        mesh.normals
+            .push([normal.x as f32, normal.y as f32, normal.z as f32]);
+        mesh.colors.push(color);
+    }
+}
+
+/// Breaks along `along`: its grid lines, with each gap cut by the curvature at its midpoint.
+fn adaptive_breaks(
+    config: &GridConfig,
+    along: usize,
+    curvature: impl Fn(f64) -> (f64, f64, f64),
+) -> Vec<f64> {
+    let (min, max) = config.bounds()[along];
+    let mut lines = config.line_positions(along);
+    if lines.len() < 2 {
+        lines = vec![min, max];
+    }
+    let mut breaks = vec![lines[0]];
+    for pair in lines.windows(2) {
+        let (cx, cy, cz) = curvature(0.5 * (pair[0] + pair[1]));
+        let pieces = curvature_to_vertices([cx, cy, cz][along]).clamp(2, MAX_GAP_PIECES + 1) - 1;
+        breaks.extend(
+            (1..pieces).map(|piece| pair[0] + (pair[1] - pair[0]) * (piece as f64 / pieces as f64)),
+        );
+        breaks.push(pair[1]);
+    }
+    breaks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::build_coordinate_surfaces;
+    use crate::app::coords_sys::CoordsSys;
+    use crate::app::grid::GridConfig;
+    use mathhook_core::Parser;
+    use nalgebra::Vector3;
+    use std::f64::consts::PI;
+
+    fn coords(x: &str, y: &str, z: &str) -> CoordsSys {
+        let parse = |expr: &str| Parser::default().parse(expr).unwrap();
+        CoordsSys::new(parse(x), parse(y), parse(z))
+    }
+
+    #[test]
+    fn flat_planes_use_one_cell_per_gap() {
+        let config = GridConfig::new(0.0, 2.0, 3.0, 0.0, 1.0, 2.0, 0.0, 1.0, 2.0);
+
+        let mesh = build_coordinate_surfaces(&coords("x", "y", "z"), &config, [true, false, false]);
+
+        assert_eq!(mesh.vertices.len(), 3 * 2 * 2);
+        assert_eq!(mesh.indices.len(), 3 * 2);
+        for (vertex, normal) in mesh.vertices.iter().zip(&mesh.normals) {
+            assert!([0.0, 1.0, 2.0].contains(&vertex[0]));
+            assert!((normal[0].abs() - 1.0).abs() < 1.0e-6);
+        }
+    }
+
+    #[test]
+    fn spherical_shells_are_refined_and_have_radial_normals() {
+        let spherical = coords("x*sin(y)*cos(z)", "x*sin(y)*sin(z)", "x*cos(y)");
+        let config = GridConfig::new(1.0, 2.0, 2.0, 0.0, PI, 2.0, 0.0, 2.0 * PI, 2.0);
+
+        let mesh = build_coordinate_surfaces(&spherical, &config, [true, false, false]);
+
+        assert!(mesh.indices.len() > 2 * 2);
+        assert_eq!(mesh.colors.len(), mesh.vertices.len());
+        assert_ne!(mesh.colors[0], mesh.colors[mesh.colors.len() - 1]);
+        for (vertex, normal) in mesh.vertices.iter().zip(&mesh.normals) {
+            let position = Vector3::new(vertex[0], vertex[1], vertex[2]).cast::<f64>();
+            let normal = Vector3::new(normal[0], normal[1], normal[2]).cast::<f64>();
+            let radius = position.norm();
+            assert!((radius - 1.0).abs() < 1.0e-5 || (radius - 2.0).abs() < 1.0e-5);
+            assert!(
+                normal.dot(&position).abs() / radius > 0.9,
+                "{normal} at {position}"
+            );
+        }
+    }
+}
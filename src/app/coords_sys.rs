@@ -1,14 +1,22 @@
 //! Coordinate-system embedding, curvature estimation, and tangent-basis evaluation.
 
+mod inverse;
+
+pub use inverse::InverseFailure;
+
+use crate::app::morph;
+use crate::app::ui::MorphHomotopy;
+use crate::maths::differential::Form;
 use crate::maths::space::Space;
 use crate::maths::{
-    derivate, expr_to_fastexpr2dto1d, expr_to_fastexpr3d, Expr, FastExpr2dto1d, FastExpr3d,
+    derivate, expr_to_fastexpr2dto1d, expr_to_fastexpr3d, Expr, FastExpr2dto1d, FastExpr3d, COORD,
 };
 use integrate::prelude::trapezoidal_rule;
 use mathhook::prelude::*;
 use mathhook::Symbol;
 use nalgebra::{vector, Matrix3, Vector3};
-use std::ops::{Add, Deref};
+use std::collections::HashMap;
+use std::ops::{Add, Deref, Mul, Sub};
 
 const CARTESIAN_GEOMETRY_EPSILON: f64 = 1.0e-7;
 
@@ -73,6 +81,15 @@ impl CoordsSys {
         }
     }
 
+    /// Builds the coordinate system of the embedding reached at `s` along `homotopy`.
+    ///
+    /// `s = 0` is `source` and `s = 1` is `target`; anything in between is a fresh coordinate
+    /// system, so its metric and tangent bases are those of the blend itself.
+    pub fn morph(source: &[Expr; 3], target: &[Expr; 3], homotopy: MorphHomotopy, s: f64) -> Self {
+        let [x_eq, y_eq, z_eq] = morph::blend(source, target, homotopy, s);
+        Self::new(x_eq, y_eq, z_eq)
+    }
+
     /// Builds one-dimensional curvature integrands for each coordinate axis.
     ///
     /// Each returned closure measures the norm of the second derivative along one abstract axis
@@ -370,6 +387,54 @@ impl CoordsSys {
             && eqs[2] == self.z_eq.to_string()
     }
 
+    /// Builds `det J`, the signed volume spanned by the coordinate tangents `∂_x, ∂_y, ∂_z`,
+    /// simplified so that products such as `x^2*sin(z)` keep their factors.
+    pub fn jacobian_determinant(&self) -> Expr {
+        let eqs = [&self.x_eq, &self.y_eq, &self.z_eq];
+        let entry =
+            |row: usize, column: usize| derivate(eqs[row].clone(), &COORD[column].to_string());
+        (0..3)
+            .map(|column| {
+                let (next, last) = ((column + 1) % 3, (column + 2) % 3);
+                let cofactor = entry(1, next)
+                    .mul(entry(2, last))
+                    .sub(entry(1, last).mul(entry(2, next)));
+                entry(0, column).mul(cofactor)
+            })
+            .reduce(|sum, term| sum.add(term))
+            .expect("three columns")
+            .simplify()
+    }
+
+    /// Pulls the world-Cartesian field `F` over `world_variables` back to its natural 1-form
+    /// `F_i = Σ_a F_a(Φ) ∂Φ^a/∂x^i`, that is `Jᵀ F` with the embedding substituted.
+    pub fn pull_back_cartesian(&self, field: [&Expr; 3], world_variables: [&str; 3]) -> Form {
+        let eqs = [&self.x_eq, &self.y_eq, &self.z_eq];
+        let embedding = world_variables
+            .iter()
+            .zip(eqs)
+            .map(|(variable, eq)| (variable.to_string(), eq.clone()))
+            .collect::<HashMap<_, _>>();
+        let field = field.map(|component| component.substitute(&embedding));
+        let components = COORD
+            .iter()
+            .map(|coord| {
+                field
+                    .iter()
+                    .zip(eqs)
+                    .map(|(component, eq)| {
+                        component
+                            .clone()
+                            .mul(derivate(eq.clone(), &coord.to_string()))
+                    })
+                    .reduce(|sum, term| sum.add(term))
+                    .expect("three world axes")
+                    .simplify()
+            })
+            .collect();
+        Form::new(components, 1)
+    }
+
     /// Returns the metric-space descriptor derived from this coordinate system.
     ///
     /// The returned `Space` is reused by differential-form and vector-field code.
@@ -432,9 +497,14 @@ impl CoordSampleGeometry {
     }
 
     pub fn volume_density(&self, point: Vector3<f64>) -> Option<f64> {
+        self.jacobian_determinant(point).map(f64::abs)
+    }
+
+    /// Returns the signed `det J` at `point`; negative where the coordinates are left-handed.
+    pub fn jacobian_determinant(&self, point: Vector3<f64>) -> Option<f64> {
         let axes = self.raw_tangent_axes(point)?;
-        let density = axes[0].dot(&axes[1].cross(&axes[2])).abs();
-        density.is_finite().then_some(density)
+        let determinant = axes[0].dot(&axes[1].cross(&axes[2]));
+        determinant.is_finite().then_some(determinant)
     }
 
     pub fn axis_scale(&self, point: Vector3<f64>, axis_index: usize) -> Option<f64> {
@@ -444,6 +514,18 @@ impl CoordSampleGeometry {
             .filter(|scale| scale.is_finite() && *scale > 1.0e-9)
     }
 
+    /// Returns the metric `g_ij = ∂_i · ∂_j` of the embedding, or `None` where it is not finite.
+    pub fn metric(&self, point: Vector3<f64>) -> Option<Matrix3<f64>> {
+        let axes = self.raw_tangent_axes(point)?;
+        Some(Matrix3::from_fn(|row, column| axes[row].dot(&axes[column])))
+    }
+
+    /// Returns the Jacobian `∂Φ/∂(x, y, z)`, one coordinate tangent per column.
+    pub fn jacobian(&self, point: Vector3<f64>) -> Option<Matrix3<f64>> {
+        self.raw_tangent_axes(point)
+            .map(|axes| Matrix3::from_columns(&axes))
+    }
+
     fn raw_tangent_axes(&self, point: Vector3<f64>) -> Option<[Vector3<f64>; 3]> {
         CoordsSys::raw_tangent_axes_from_axes(
             point,
@@ -0,0 +1,156 @@
+//! Numeric inverse of the embedding: world points back to abstract coordinates.
+//!
+//! Damped Newton steps on `Φ(q) = world` start from a nearby sample whose abstract coordinates
+//! are known. Angles are only defined up to whole turns, so a preimage that lands outside the
+//! grid bounds is shifted by multiples of `2π` wherever the embedding maps the shifted point to
+//! the same world position.
+
+use super::{CoordSampleGeometry, CoordsSys};
+use crate::app::grid_world::GridWorld;
+use nalgebra::Vector3;
+use std::f64::consts::TAU;
+
+/// Newton iterations allowed per solve; convergence is linear next to a singular set.
+const NEWTON_STEPS: usize = 64;
+/// World-space residual accepted as a solution, relative to `1 + |world|`.
+const RESIDUAL_TOLERANCE: f64 = 1.0e-9;
+/// Slack on the bounds, relative to each axis extent.
+const BOUND_TOLERANCE: f64 = 1.0e-6;
+
+/// Why a world point could not be mapped back to abstract coordinates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InverseFailure {
+    /// The world point is not finite, or there is no sample to start from.
+    NoSeed,
+    /// The Jacobian is singular at `at`, so Newton cannot take another step.
+    Singular { at: Vector3<f64> },
+    /// The world-space residual stopped decreasing at `residual`.
+    NotConverged { residual: f64 },
+    /// The preimage lies outside the grid bounds, even after shifting periodic coordinates.
+    OutsideBounds { preimage: Vector3<f64> },
+}
+
+impl InverseFailure {
+    /// One-line explanation for the UI.
+    pub fn describe(&self) -> String {
+        let point =
+            |point: &Vector3<f64>| format!("({:.4}, {:.4}, {:.4})", point.x, point.y, point.z);
+        match self {
+            Self::NoSeed => "no grid sample to start the inverse from".to_string(),
+            Self::Singular { at } => format!("det J vanishes near {}", point(at)),
+            Self::NotConverged { residual } => {
+                format!("Newton stalled {residual:.2e} away from the point")
+            }
+            Self::OutsideBounds { preimage } => {
+                format!(
+                    "the preimage {} lies outside the grid bounds",
+                    point(preimage)
+                )
+            }
+        }
+    }
+}
+
+impl CoordsSys {
+    /// Maps `world` back to abstract coordinates within `bounds`, starting Newton from the
+    /// sample of `grid_world` nearest to it.
+    pub fn inverse(
+        &self,
+        world: Vector3<f64>,
+        grid_world: &GridWorld,
+        bounds: &[(f64, f64); 3],
+    ) -> Result<Vector3<f64>, InverseFailure> {
+        if !world.iter().all(|value| value.is_finite()) {
+            return Err(InverseFailure::NoSeed);
+        }
+        let seed = grid_world
+            .found_nearest(&world.into())
+            .ok_or(InverseFailure::NoSeed)?;
+        let geometry = self.sample_geometry();
+        let preimage = geometry.solve_inverse(world, seed.abstract_pos)?;
+        geometry.wrap_into_bounds(preimage, world, bounds)
+    }
+}
+
+impl CoordSampleGeometry {
+    /// Solves `Φ(q) = world` from `seed`, halving any step that does not reduce the residual.
+    pub fn solve_inverse(
+        &self,
+        world: Vector3<f64>,
+        seed: Vector3<f64>,
+    ) -> Result<Vector3<f64>, InverseFailure> {
+        let tolerance = residual_tolerance(world);
+        let mut point = seed;
+        let mut residual = self.eval_position(point) - world;
+        if !residual.iter().all(|value| value.is_finite()) {
+            return Err(InverseFailure::Singular { at: seed });
+        }
+        for _ in 0..NEWTON_STEPS {
+            if residual.norm() <= tolerance {
+                return Ok(point);
+            }
+            let step = self
+                .jacobian(point)
+                .and_then(|jacobian| jacobian.try_inverse())
+                .ok_or(InverseFailure::Singular { at: point })?
+                * residual;
+            let mut scale = 1.0;
+            loop {
+                let candidate = point - step * scale;
+                let candidate_residual = self.eval_position(candidate) - world;
+                if candidate_residual.norm() < residual.norm() {
+                    point = candidate;
+                    residual = candidate_residual;
+                    break;
+                }
+                scale *= 0.5;
+                if scale < 1.0e-6 {
+                    return Err(InverseFailure::NotConverged {
+                        residual: residual.norm(),
+                    });
+                }
+            }
+        }
+        if residual.norm() <= tolerance {
+            Ok(point)
+        } else {
+            Err(InverseFailure::NotConverged {
+                residual: residual.norm(),
+            })
+        }
+    }
+
+    /// Moves the coordinates of `preimage` that fall outside `bounds` into them by whole turns,
+    /// keeping a shift only where `Φ` still maps the point to `world`.
+    pub fn wrap_into_bounds(
+        &self,
+        preimage: Vector3<f64>,
+        world: Vector3<f64>,
+        bounds: &[(f64, f64); 3],
+    ) -> Result<Vector3<f64>, InverseFailure> {
+        let tolerance = residual_tolerance(world);
+        let mut point = preimage;
+        for (axis, &(min, max)) in bounds.iter().enumerate() {
+            let (low, high) = (min.min(max), min.max(max));
+            let slack = BOUND_TOLERANCE * (high - low).max(1.0);
+            let value = point[axis];
+            if value >= low - slack && value <= high + slack {
+                continue;
+            }
+            // The lowest value `value + k 2π` at or above the lower bound.
+            let shifted = value + ((low - slack - value) / TAU).ceil() * TAU;
+            let mut candidate = point;
+            candidate[axis] = shifted;
+            if shifted > high + slack || (self.eval_position(candidate) - world).norm() > tolerance
+            {
+                return Err(InverseFailure::OutsideBounds { preimage });
+            }
+            point = candidate;
+        }
+        Ok(point)
+    }
+}
+
+fn residual_tolerance(world: Vector3<f64>) -> f64 {
+    RESIDUAL_TOLERANCE * (1.0 + world.norm())
+}
@@ -1,13 +1,22 @@
 //! Electromagnetism runtime fields over animated 3D slices.
 
+mod conductors;
+mod covariant;
 mod fields;
 mod maxwell;
+mod media;
+mod observer;
+mod phasor;
 mod plane_wave;
 mod potentials;
 
 #[cfg(test)]
 mod cache_tests;
 #[cfg(test)]
+mod covariant_tests;
+#[cfg(test)]
+mod observer_tests;
+#[cfg(test)]
 mod plane_wave_tests;
 #[cfg(test)]
 mod runtime_tests;
@@ -16,27 +25,44 @@ use crate::app::grid::{Grid, GridConfig};
 use crate::app::ui::{EmLayerVisibility, EmMode, EmUiState};
 use crate::maths::differential::Form;
 use crate::maths::space::Space;
-use crate::maths::{derivate, Expr, ExternalDerivative, Point};
+use crate::maths::{derivate, Expr, Point};
+use conductors::{ConductorBoundary, SurfaceCondition};
+use covariant::FaradayTensor;
 use fields::{TimedScalarField, TimedVectorField};
 use mathhook_core::Simplify;
 use maxwell::{
     maxwell_ampere_source_exprs, maxwell_faraday_source_exprs, maxwell_inverse_curl,
-    MaxwellSolveConfig,
+    InverseCurlSettings, MaxwellSolveConfig,
 };
+use media::LinearMedia;
 use nalgebra::Vector3;
+use phasor::{phasor_magnetic_exprs, phasor_vector_potential_exprs, PhasorVectorField};
 use plane_wave::{
     plane_wave_electric_exprs, plane_wave_magnetic_exprs, plane_wave_vector_potential_exprs,
 };
-use potentials::{scalar_potential_for_gauge, zero_scalar_potential};
-use std::ops::{Add, Mul};
+use potentials::{
+    gauge_transform_potentials, scalar_potential_for_gauge, zero_scalar_potential,
+    GaugeTransformedPotentials,
+};
+use std::ops::Mul;
+
+pub(crate) use covariant::{CovariantMaxwellCheck, MaxwellSources};
+pub(crate) use media::unsupported_media_note;
+pub use observer::{LorentzBoost, MAX_OBSERVER_SPEED};
+pub use phasor::PolarizationEllipse;
 
 pub struct EmRuntime {
     pub layers: EmLayerVisibility,
     magnetic_vector_scale: f64,
+    light_speed: f64,
     phi: TimedScalarField,
     vector_potential: TimedVectorField,
     electric_field: TimedVectorField,
     magnetic_field: TimedVectorField,
+    electric_phasor: Option<PhasorVectorField>,
+    gauge_potentials: Option<GaugeTransformedPotentials>,
+    faraday: Option<FaradayTensor>,
+    observer: Option<LorentzBoost>,
 }
 
 impl EmRuntime {
@@ -47,43 +73,62 @@ impl EmRuntime {
 
     pub fn from_ui_with_config(state: &EmUiState, grid: &Grid, grid_config: GridConfig) -> Self {
         let geometry = grid.get_coords().sample_geometry();
-        let maxwell_config = MaxwellSolveConfig::from_grid_config(grid_config, geometry.clone());
-        match state.mode {
+        let maxwell_config = MaxwellSolveConfig::with_settings(
+            grid_config,
+            geometry.clone(),
+            InverseCurlSettings::from_state(state),
+            &ConductorBoundary::from_state(state),
+        );
+        let mut runtime = match state.mode {
             EmMode::Potentials => Self::from_potentials(state, grid.get_coords().get_space()),
             EmMode::Electric => Self::from_electric(state, maxwell_config, geometry),
             EmMode::Magnetic => Self::from_magnetic(state, maxwell_config, geometry),
-        }
+            EmMode::Phasor => Self::from_phasor(state, grid.get_coords().get_space()),
+            EmMode::ClosedForm => Self::from_closed_form(state),
+        };
+        runtime.observer = LorentzBoost::from_state(state);
+        runtime
     }
 
     fn from_potentials(state: &EmUiState, space: &Space) -> Self {
-        let phi_expr = state.phi.eq.clone();
-        let a_otn_exprs = exprs_from_spacial(&state.vector_potential);
+        let mut phi_expr = state.phi.eq.clone();
+        let mut a_otn_exprs = exprs_from_spacial(&state.vector_potential);
         let a_otn = Form::new_otn(a_otn_exprs.to_vec(), 1);
-        let a_natural = a_otn.to_dual_base(space);
-
-        let mut phi_form = Form::new(vec![phi_expr.clone()], 0);
-        let grad_phi = phi_form.d();
-        let electric_natural = Form::new(
-            (0..3)
-                .map(|index| {
-                    negate(grad_phi.get_expr(index).clone())
-                        .add(negate(partial_t(a_natural.get_expr(index).clone())))
-                        .simplify()
-                })
-                .collect(),
-            1,
-        );
-        let electric_otn = electric_natural.to_otn_base(space);
-        let mut a_for_d = a_natural;
-        let magnetic_otn = a_for_d.d().to_otn_base(space).hodge_star_otn_3d();
+        let mut a_natural = a_otn.to_dual_base(space);
+        let gauge_potentials = (!state.gauge_function.eq.simplify().is_zero()).then(|| {
+            gauge_transform_potentials(
+                phi_expr.clone(),
+                &a_otn_exprs,
+                &a_natural,
+                &state.gauge_function.eq,
+                space,
+            )
+        });
+        if let Some(transformed) = &gauge_potentials {
+            phi_expr = transformed.phi.clone();
+            a_otn_exprs = transformed.vector_potential_otn.clone();
+            a_natural = transformed.vector_potential_natural.clone();
+        }
+
+        let faraday = FaradayTensor::from_potentials(&phi_expr, &a_natural, space);
+        let electric_otn = faraday.electric_natural().to_otn_base(space);
+        let magnetic_otn = faraday
+            .magnetic_natural()
+            .to_otn_base(space)
+            .hodge_star_otn_3d();
 
         Self {
             layers: state.layers.clone(),
             magnetic_vector_scale: state.magnetic_vector_scale,
+            light_speed: state.light_speed.max(1.0e-6),
             phi: TimedScalarField::new(phi_expr),
             vector_potential: TimedVectorField::from_exprs(a_otn_exprs),
             electric_field: TimedVectorField::from_exprs(form_exprs(&electric_otn)),
             magnetic_field: TimedVectorField::from_exprs(form_exprs(&magnetic_otn)),
+            electric_phasor: None,
+            gauge_potentials,
+            faraday: Some(faraday),
+            observer: None,
         }
     }
 
@@ -94,12 +139,14 @@ impl EmRuntime {
     ) -> Self {
         let electric_exprs = exprs_from_spacial(&state.electric_field);
         let c = state.light_speed.max(1.0e-6);
+        let media = LinearMedia::from_state(state);
 
         let electric_field = TimedVectorField::from_exprs(electric_exprs.clone());
         let plane_wave_fields = maxwell_config
             .supports_plane_wave_shortcut()
             .then(|| {
-                let magnetic_exprs = plane_wave_magnetic_exprs(&electric_exprs, c)?;
+                let wave_speed = media.uniform_lossless_light_speed(c)?;
+                let magnetic_exprs = plane_wave_magnetic_exprs(&electric_exprs, wave_speed)?;
                 let vector_potential_exprs = plane_wave_vector_potential_exprs(&electric_exprs)?;
                 Some((magnetic_exprs, vector_potential_exprs))
             })
@@ -113,9 +160,14 @@ impl EmRuntime {
                     zero_scalar_potential(),
                 )
             } else {
-                let ampere_source_exprs = maxwell_ampere_source_exprs(&electric_exprs, c);
+                let ampere_source_exprs = maxwell_ampere_source_exprs(&electric_exprs, c, &media);
                 let ampere_source = TimedVectorField::from_exprs(ampere_source_exprs);
-                let magnetic_field = maxwell_inverse_curl(ampere_source, maxwell_config.clone());
+                let magnetic_field = maxwell_inverse_curl(
+                    ampere_source,
+                    maxwell_config
+                        .clone()
+                        .with_condition(SurfaceCondition::Normal),
+                );
                 let vector_potential = maxwell_inverse_curl(magnetic_field.clone(), maxwell_config);
                 let phi = scalar_potential_for_gauge(
                     state.gauge,
@@ -129,13 +181,22 @@ impl EmRuntime {
         Self {
             layers: state.layers.clone(),
             magnetic_vector_scale: state.magnetic_vector_scale,
+            light_speed: c,
             phi,
             vector_potential,
             electric_field,
             magnetic_field,
+            electric_phasor: None,
+            gauge_potentials: None,
+            faraday: None,
+            observer: None,
         }
     }
 
+    /// Derives `E` from `B` through Faraday's law.
+    ///
+    /// Media only enter through the plane-wave speed; see `unsupported_media_note` for what
+    /// the Media panel reports otherwise.
     fn from_magnetic(
         state: &EmUiState,
         maxwell_config: MaxwellSolveConfig,
@@ -143,12 +204,14 @@ impl EmRuntime {
     ) -> Self {
         let magnetic_exprs = exprs_from_spacial(&state.magnetic_field);
         let c = state.light_speed.max(1.0e-6);
+        let media = LinearMedia::from_state(state);
 
         let magnetic_field = TimedVectorField::from_exprs(magnetic_exprs.clone());
         let plane_wave_fields = maxwell_config
             .supports_plane_wave_shortcut()
             .then(|| {
-                let electric_exprs = plane_wave_electric_exprs(&magnetic_exprs, c)?;
+                let wave_speed = media.uniform_lossless_light_speed(c)?;
+                let electric_exprs = plane_wave_electric_exprs(&magnetic_exprs, wave_speed)?;
                 let vector_potential_exprs = plane_wave_vector_potential_exprs(&electric_exprs)?;
                 Some((electric_exprs, vector_potential_exprs))
             })
@@ -178,10 +241,64 @@ impl EmRuntime {
         Self {
             layers: state.layers.clone(),
             magnetic_vector_scale: state.magnetic_vector_scale,
+            light_speed: c,
             phi,
             vector_potential,
             electric_field,
             magnetic_field,
+            electric_phasor: None,
+            gauge_potentials: None,
+            faraday: None,
+            observer: None,
+        }
+    }
+
+    /// Builds real fields from the complex amplitude `E` at angular frequency `omega`.
+    ///
+    /// Faraday's law gives `B` directly in the frequency domain, and the temporal gauge keeps
+    /// `V = 0`, so this mode needs no inverse-curl solve.
+    fn from_phasor(state: &EmUiState, space: &Space) -> Self {
+        let electric_exprs = exprs_from_spacial(&state.phasor_electric_field);
+        let omega = state.angular_frequency.max(1.0e-6);
+        let electric_phasor = PhasorVectorField::from_exprs(electric_exprs.clone());
+        let magnetic_phasor =
+            PhasorVectorField::from_exprs(phasor_magnetic_exprs(&electric_exprs, omega, space));
+        let vector_potential_phasor =
+            PhasorVectorField::from_exprs(phasor_vector_potential_exprs(&electric_exprs, omega));
+
+        Self {
+            layers: state.layers.clone(),
+            magnetic_vector_scale: state.magnetic_vector_scale,
+            light_speed: state.light_speed.max(1.0e-6),
+            phi: zero_scalar_potential(),
+            vector_potential: vector_potential_phasor.into_timed_field(omega),
+            electric_field: electric_phasor.clone().into_timed_field(omega),
+            magnetic_field: magnetic_phasor.into_timed_field(omega),
+            electric_phasor: Some(electric_phasor),
+            gauge_potentials: None,
+            faraday: None,
+            observer: None,
+        }
+    }
+
+    /// Uses `V`, `A`, `E` and `B` exactly as entered, e.g. for generated waveguide modes.
+    ///
+    /// Nothing is derived, so consistency between the four fields is up to the caller.
+    fn from_closed_form(state: &EmUiState) -> Self {
+        Self {
+            layers: state.layers.clone(),
+            magnetic_vector_scale: state.magnetic_vector_scale,
+            light_speed: state.light_speed.max(1.0e-6),
+            phi: TimedScalarField::new(state.phi.eq.clone()),
+            vector_potential: TimedVectorField::from_exprs(exprs_from_spacial(
+                &state.vector_potential,
+            )),
+            electric_field: TimedVectorField::from_exprs(exprs_from_spacial(&state.electric_field)),
+            magnetic_field: TimedVectorField::from_exprs(exprs_from_spacial(&state.magnetic_field)),
+            electric_phasor: None,
+            gauge_potentials: None,
+            faraday: None,
+            observer: None,
         }
     }
 
@@ -201,13 +318,60 @@ impl EmRuntime {
         self.magnetic_field.at(point, time)
     }
 
+    /// Returns `|E|` of the complex amplitude, or `None` outside phasor mode.
+    pub fn electric_amplitude_at(&self, point: Point) -> Option<f64> {
+        Some(self.electric_phasor.as_ref()?.amplitude_at(point))
+    }
+
+    /// Returns the phase of the dominant `E` component, or `None` outside phasor mode.
+    pub fn electric_phase_at(&self, point: Point) -> Option<f64> {
+        Some(self.electric_phasor.as_ref()?.phase_at(point))
+    }
+
+    /// Returns the ellipse traced by `E` over one period, or `None` outside phasor mode.
+    pub fn polarization_ellipse_at(&self, point: Point) -> Option<PolarizationEllipse> {
+        Some(
+            self.electric_phasor
+                .as_ref()?
+                .polarization_ellipse_at(point),
+        )
+    }
+
+    /// Returns `(V, A)` after the gauge transformation, or `None` when no gauge function is set.
+    ///
+    /// `A` is given in orthonormal frame components, like the other runtime vector fields.
+    pub fn gauge_transformed_potentials(&self) -> Option<(&Expr, &[Expr; 3])> {
+        let potentials = self.gauge_potentials.as_ref()?;
+        Some((&potentials.phi, &potentials.vector_potential_otn))
+    }
+
+    /// Checks `dF = 0` and `d*F = mu_0 *J` on the Faraday 2-form, or `None` outside potentials
+    /// mode where `F` is not built from symbolic potentials.
+    pub(crate) fn covariant_maxwell_check(
+        &self,
+        space: &Space,
+        sources: &MaxwellSources,
+    ) -> Option<CovariantMaxwellCheck> {
+        Some(
+            self.faraday
+                .as_ref()?
+                .maxwell_check(space, self.light_speed, sources),
+        )
+    }
+
     pub fn magnetic_render_scale(&self) -> f64 {
         self.magnetic_vector_scale
     }
 
+    /// Returns the boost to the moving observer frame, or `None` when viewing from the lab.
+    pub fn observer_boost(&self) -> Option<LorentzBoost> {
+        self.observer
+    }
+
     pub(crate) fn update_render_controls(&mut self, state: &EmUiState) {
         self.layers = state.layers.clone();
         self.magnetic_vector_scale = state.magnetic_vector_scale;
+        self.observer = LorentzBoost::from_state(state);
     }
 
     pub fn active_layers(&self) -> EmLayerVisibility {
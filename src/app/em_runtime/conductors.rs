@@ -0,0 +1,336 @@
+//! Perfect conductors and the domain mask of the inverse-curl solve.
+//!
+//! Both are implicit regions `f(x, y, z) <= 0` in world Cartesian coordinates. Source cells
+//! inside any region are dropped. Conductors whose `f` is affine or a sphere quadric also
+//! mirror every remaining cell through their surface, so the image source cancels the field
+//! component the surface condition forbids. Images are first order: each conductor reflects the
+//! real cells once, and images of images are not placed.
+//!
+//! Planes enforce both conditions exactly. Spheres enforce only the normal one, through Kelvin's
+//! image: a current element has no point image that makes its Biot-Savart field normal on a
+//! sphere, so tangential `E` is left free there and the sphere only masks its cells.
+
+use super::fields::TimedScalarField;
+use crate::app::ui::EmUiState;
+use crate::maths::{Expr, Point};
+use nalgebra::{Matrix3, Vector3};
+
+/// Relative tolerance of the quadric fit that recognizes planes and spheres.
+const SHAPE_FIT_TOLERANCE: f64 = 1.0e-9;
+/// Points, besides the axis stencil, where a fitted quadric must reproduce `f`.
+const SHAPE_CHECK_POINTS: [[f64; 3]; 3] = [[0.3, -0.7, 1.1], [1.7, 0.2, -0.4], [-2.1, 1.3, 0.6]];
+/// Sources closer than this to a sphere center have no finite Kelvin image.
+const MIN_IMAGE_DISTANCE: f64 = 1.0e-9;
+
+/// Field component an inverse curl must cancel on conductor surfaces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum SurfaceCondition {
+    /// `n x F = 0`, satisfied by `E`, and by `A` in the temporal gauge.
+    Tangential,
+    /// `n . F = 0`, satisfied by `B`.
+    Normal,
+}
+
+/// Implicit region `f(x, y, z) <= 0`; points where `f` is not finite are outside.
+#[derive(Clone)]
+pub(super) struct ImplicitRegion {
+    field: TimedScalarField,
+}
+
+impl ImplicitRegion {
+    pub(super) fn new(expr: Expr) -> Self {
+        Self {
+            field: TimedScalarField::new(expr),
+        }
+    }
+
+    pub(super) fn value(&self, point: Vector3<f64>) -> f64 {
+        self.field.at(
+            Point {
+                x: point.x,
+                y: point.y,
+                z: point.z,
+            },
+            0.0,
+        )
+    }
+
+    pub(super) fn contains(&self, point: Vector3<f64>) -> bool {
+        self.value(point) <= 0.0
+    }
+}
+
+/// Surface shape recognized from a conductor's implicit function.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum ConductorShape {
+    /// Half-space `normal . p <= offset`, with a unit `normal`.
+    Plane { normal: Vector3<f64>, offset: f64 },
+    /// Ball `|p - center| <= radius`, or everything outside it when `hollow`.
+    Sphere {
+        center: Vector3<f64>,
+        radius: f64,
+        hollow: bool,
+    },
+    /// Any other region: its cells are masked, but it places no image.
+    General,
+}
+
+impl ConductorShape {
+    /// Fits `f = a + g . p + k |p|^2` on an axis stencil and keeps the fit only if it also
+    /// reproduces `f` elsewhere.
+    pub(super) fn classify(region: &ImplicitRegion) -> Self {
+        let origin = region.value(Vector3::zeros());
+        let mut gradient = Vector3::zeros();
+        let mut curvatures = [0.0; 3];
+        for axis in 0..3 {
+            let unit = Vector3::ith(axis, 1.0);
+            let (forward, backward) = (region.value(unit), region.value(-unit));
+            gradient[axis] = 0.5 * (forward - backward);
+            curvatures[axis] = 0.5 * (forward + backward) - origin;
+        }
+        let curvature = curvatures[0];
+        let scale = origin
+            .abs()
+            .max(gradient.norm())
+            .max(curvature.abs())
+            .max(1.0);
+        let tolerance = SHAPE_FIT_TOLERANCE * scale;
+        let fit =
+            |point: Vector3<f64>| origin + gradient.dot(&point) + curvature * point.norm_squared();
+        let fits = origin.is_finite()
+            && gradient.iter().all(|value| value.is_finite())
+            && curvatures
+                .iter()
+                .all(|value| (value - curvature).abs() <= tolerance)
+            && SHAPE_CHECK_POINTS.iter().all(|point| {
+                let point = Vector3::from(*point);
+                (region.value(point) - fit(point)).abs() <= tolerance * (1.0 + point.norm_squared())
+            });
+        if !fits {
+            return Self::General;
+        }
+
+        if curvature.abs() <= tolerance {
+            let length = gradient.norm();
+            if length <= tolerance {
+                return Self::General;
+            }
+            return Self::Plane {
+                normal: gradient / length,
+                offset: -origin / length,
+            };
+        }
+        let center = -gradient / (2.0 * curvature);
+        let radius_squared = center.norm_squared() - origin / curvature;
+        if radius_squared <= 0.0 {
+            return Self::General;
+        }
+        Self::Sphere {
+            center,
+            radius: radius_squared.sqrt(),
+            hollow: curvature < 0.0,
+        }
+    }
+
+    /// Image of a source cell at `point`, or `None` when this shape places no image.
+    fn image(&self, point: Vector3<f64>) -> Option<SourceImage> {
+        match *self {
+            Self::Plane { normal, offset } => {
+                let distance = normal.dot(&point) - offset;
+                let reflection = Matrix3::identity() - 2.0 * normal * normal.transpose();
+                Some(SourceImage {
+                    position: point - 2.0 * distance * normal,
+                    tangential_map: reflection,
+                    normal_map: -reflection,
+                })
+            }
+            Self::Sphere { center, radius, .. } => {
+                let offset = point - center;
+                let distance = offset.norm();
+                if distance <= MIN_IMAGE_DISTANCE {
+                    return None;
+                }
+                let ratio = radius / distance;
+                Some(SourceImage {
+                    position: center + ratio * ratio * offset,
+                    // No image source cancels the tangential field on a sphere; the tangent-plane
+                    // reflection only helps sources close to the surface and hurts distant ones.
+                    tangential_map: Matrix3::zeros(),
+                    // Kelvin's image makes each Cartesian source component's potential vanish
+                    // on the sphere, so the Biot-Savart field is tangential there.
+                    normal_map: Matrix3::identity() * -ratio,
+                })
+            }
+            Self::General => None,
+        }
+    }
+}
+
+/// Mirror of one source cell: where it sits and how the source vector maps onto it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct SourceImage {
+    pub(super) position: Vector3<f64>,
+    tangential_map: Matrix3<f64>,
+    normal_map: Matrix3<f64>,
+}
+
+impl SourceImage {
+    /// Linear map from the real source vector to the image source for `condition`.
+    pub(super) fn source_map(&self, condition: SurfaceCondition) -> Matrix3<f64> {
+        match condition {
+            SurfaceCondition::Tangential => self.tangential_map,
+            SurfaceCondition::Normal => self.normal_map,
+        }
+    }
+}
+
+#[derive(Clone)]
+struct Conductor {
+    region: ImplicitRegion,
+    shape: ConductorShape,
+}
+
+/// Conductors and domain mask of one EM configuration; the default is free space.
+#[derive(Clone, Default)]
+pub(super) struct ConductorBoundary {
+    conductors: Vec<Conductor>,
+    mask: Option<ImplicitRegion>,
+}
+
+impl ConductorBoundary {
+    pub(super) fn from_state(state: &EmUiState) -> Self {
+        Self {
+            conductors: state
+                .conductors
+                .iter()
+                .map(|conductor| {
+                    let region = ImplicitRegion::new(conductor.eq.clone());
+                    let shape = ConductorShape::classify(&region);
+                    Conductor { region, shape }
+                })
+                .collect(),
+            mask: Some(ImplicitRegion::new(state.domain_mask.eq.clone())),
+        }
+    }
+
+    /// Returns whether a source cell at `point` lies inside the mask or a conductor.
+    pub(super) fn excludes(&self, point: Vector3<f64>) -> bool {
+        self.mask.iter().any(|mask| mask.contains(point))
+            || self
+                .conductors
+                .iter()
+                .any(|conductor| conductor.region.contains(point))
+    }
+
+    /// Images of a source cell at `point`, one per plane or sphere conductor.
+    pub(super) fn images(&self, point: Vector3<f64>) -> impl Iterator<Item = SourceImage> + '_ {
+        self.conductors
+            .iter()
+            .filter_map(move |conductor| conductor.shape.image(point))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ConductorShape, ImplicitRegion, SurfaceCondition};
+    use mathhook_core::Parser;
+    use nalgebra::{Matrix3, Vector3};
+
+    fn region(expr: &str) -> ImplicitRegion {
+        ImplicitRegion::new(Parser::default().parse(expr).unwrap())
+    }
+
+    #[test]
+    fn affine_and_sphere_regions_are_recognized() {
+        let ConductorShape::Plane { normal, offset } = ConductorShape::classify(&region("2*z - 1"))
+        else {
+            panic!("expected a plane");
+        };
+        assert!((normal - Vector3::z()).norm() < 1.0e-12);
+        assert!((offset - 0.5).abs() < 1.0e-12);
+
+        let sphere = ConductorShape::classify(&region("(x - 1)^2 + y^2 + (z + 2)^2 - 4"));
+        let ConductorShape::Sphere {
+            center,
+            radius,
+            hollow,
+        } = sphere
+        else {
+            panic!("expected a sphere, got {sphere:?}");
+        };
+        assert!((center - Vector3::new(1.0, 0.0, -2.0)).norm() < 1.0e-9);
+        assert!((radius - 2.0).abs() < 1.0e-9);
+        assert!(!hollow);
+
+        assert!(matches!(
+            ConductorShape::classify(&region("9 - x^2 - y^2 - z^2")),
+            ConductorShape::Sphere { hollow: true, .. }
+        ));
+        assert_eq!(
+            ConductorShape::classify(&region("x^2 + 2*y^2 - 1")),
+            ConductorShape::General
+        );
+        assert_eq!(
+            ConductorShape::classify(&region("1")),
+            ConductorShape::General
+        );
+    }
+
+    #[test]
+    fn plane_images_mirror_position_and_source() {
+        let shape = ConductorShape::classify(&region("z"));
+        let image = shape.image(Vector3::new(0.5, -1.0, 2.0)).unwrap();
+        let source = Vector3::new(1.0, 2.0, 3.0);
+
+        assert_eq!(image.position, Vector3::new(0.5, -1.0, -2.0));
+        assert_eq!(
+            image.source_map(SurfaceCondition::Tangential) * source,
+            Vector3::new(1.0, 2.0, -3.0)
+        );
+        assert_eq!(
+            image.source_map(SurfaceCondition::Normal) * source,
+            Vector3::new(-1.0, -2.0, 3.0)
+        );
+    }
+
+    #[test]
+    fn sphere_images_sit_at_the_kelvin_inverse_point() {
+        let shape = ConductorShape::classify(&region("x^2 + y^2 + z^2 - 1"));
+        let image = shape.image(Vector3::new(0.0, 0.0, 4.0)).unwrap();
+
+        assert!((image.position - Vector3::new(0.0, 0.0, 0.25)).norm() < 1.0e-9);
+        let mapped = image.source_map(SurfaceCondition::Normal) * Vector3::x();
+        assert!((mapped - Vector3::new(-0.25, 0.0, 0.0)).norm() < 1.0e-9);
+        assert_eq!(
+            image.source_map(SurfaceCondition::Tangential),
+            Matrix3::zeros()
+        );
+    }
+
+    #[test]
+    fn sphere_images_make_the_biot_savart_field_tangential_on_the_surface() {
+        let center = Vector3::new(1.0, 0.0, -2.0);
+        let shape = ConductorShape::classify(&region("(x - 1)^2 + y^2 + (z + 2)^2 - 4"));
+        let source = Vector3::new(0.7, -1.1, 0.4);
+        let biot_savart = |current: Vector3<f64>, from: Vector3<f64>, to: Vector3<f64>| {
+            let radius = to - from;
+            current.cross(&radius) / radius.norm().powi(3)
+        };
+
+        for position in [Vector3::new(1.5, 2.4, -0.3), Vector3::new(-2.0, 1.0, -4.5)] {
+            let image = shape.image(position).unwrap();
+            let image_source = image.source_map(SurfaceCondition::Normal) * source;
+            for normal in [
+                Vector3::new(0.0, 0.6, 0.8),
+                Vector3::new(-1.0, 0.0, 0.0),
+                Vector3::new(0.48, -0.6, 0.64),
+            ] {
+                let surface = center + 2.0 * normal;
+                let field = biot_savart(source, position, surface)
+                    + biot_savart(image_source, image.position, surface);
+                assert!(normal.dot(&field).abs() < 1.0e-12, "{field:?}");
+                assert!(field.norm() > 1.0e-3, "{field:?}");
+            }
+        }
+    }
+}
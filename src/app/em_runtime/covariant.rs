@@ -0,0 +1,140 @@
+//! Covariant potentials-mode fields: the Faraday 2-form `F = dA` on spacetime `(t, x, y, z)`.
+//!
+//! With the potential 1-form `A = -V dt + A_i dx^i`, the time-space components of `F` are
+//! `-E_i` and the purely spatial ones form the magnetic 2-form, so `E` and `B` are read straight
+//! off `F`. Units follow the rest of the runtime: `epsilon_0 = 1` and `mu_0 = 1 / c^2`.
+
+use super::{exprs_from_spacial, negate};
+use crate::app::ui::EmUiState;
+use crate::maths::differential::{Form, FormBasis};
+use crate::maths::space::{Space, Variables};
+use crate::maths::spacetime::{Spacetime, LORENTZIAN_SIGNATURE, TIME};
+use crate::maths::{Expr, ExternalDerivative};
+use mathhook_core::Simplify;
+use std::ops::Mul;
+
+pub(super) struct FaradayTensor {
+    /// `F` in the natural spacetime coframe.
+    form: Form,
+    spatial_variables: Variables,
+}
+
+/// Residuals of the covariant Maxwell equations, as natural spacetime 3-forms.
+pub(crate) struct CovariantMaxwellCheck {
+    /// `dF`, the homogeneous equations.
+    pub(crate) bianchi: Form,
+    /// `d*F - mu_0 *J`, with the charge and current entered next to the potentials.
+    pub(crate) source: Form,
+}
+
+/// Charge density and current the potentials are expected to be sourced by.
+///
+/// They are entered independently of `V` and `A`, so a zero source residual says the potentials
+/// solve Maxwell's equations for these sources rather than restating `F`.
+pub(crate) struct MaxwellSources {
+    charge_density: Expr,
+    /// Orthonormal components over the abstract coordinates.
+    current_otn: [Expr; 3],
+}
+
+impl MaxwellSources {
+    pub(crate) fn from_state(state: &EmUiState) -> Self {
+        Self {
+            charge_density: state.charge_density.eq.clone(),
+            current_otn: exprs_from_spacial(&state.current_density),
+        }
+    }
+}
+
+impl FaradayTensor {
+    /// Builds `F = dA` from `V` and the natural spatial components of `A`.
+    pub(super) fn from_potentials(
+        phi: &Expr,
+        vector_potential_natural: &Form,
+        space: &Space,
+    ) -> Self {
+        let mut potential = Form::new_over(
+            space.variables().prepended(TIME),
+            std::iter::once(negate(phi.clone()))
+                .chain(vector_potential_natural.exprs.iter().cloned())
+                .collect(),
+            1,
+            FormBasis::Natural,
+        );
+
+        Self {
+            form: potential.d(),
+            spatial_variables: space.variables().clone(),
+        }
+    }
+
+    /// Returns `E` as a natural spatial 1-form, `E_i = -F_ti`.
+    pub(super) fn electric_natural(&self) -> Form {
+        self.spatial_form(
+            (1..=3)
+                .map(|index| negate(self.form.component(&[0, index])).simplify())
+                .collect(),
+            1,
+        )
+    }
+
+    /// Returns the spatial part of `F`, the magnetic flux 2-form, in natural components.
+    pub(super) fn magnetic_natural(&self) -> Form {
+        self.spatial_form(
+            [[1, 2], [2, 3], [3, 1]]
+                .iter()
+                .map(|indices| self.form.component(indices))
+                .collect(),
+            2,
+        )
+    }
+
+    /// Evaluates `dF` and `d*F - mu_0 *J` symbolically, with `J` built from `sources`.
+    ///
+    /// The current 1-form has the orthonormal components `(-c rho, J_x, J_y, J_z)`.
+    pub(super) fn maxwell_check(
+        &self,
+        space: &Space,
+        light_speed: f64,
+        sources: &MaxwellSources,
+    ) -> CovariantMaxwellCheck {
+        let spacetime = Spacetime::new(space, light_speed);
+        let bianchi = self.form.clone().d();
+        let divergence_of_dual = spacetime.hodge_star(&self.form).d();
+
+        let c = light_speed;
+        let current_otn = Form::new_over(
+            spacetime.variables().clone(),
+            std::iter::once(Expr::number(-c).mul(sources.charge_density.clone()))
+                .chain(sources.current_otn.iter().cloned())
+                .collect(),
+            1,
+            FormBasis::Orthonormal,
+        );
+        let dual_current = spacetime.to_natural(&current_otn.hodge_star_otn(&LORENTZIAN_SIGNATURE));
+        let scaled_dual_current = Form::new_over(
+            dual_current.variables().clone(),
+            dual_current
+                .exprs
+                .iter()
+                .map(|expr| Expr::number(1.0 / (c * c)).mul(expr.clone()))
+                .collect(),
+            dual_current.n_forms(),
+            FormBasis::Natural,
+        );
+
+        CovariantMaxwellCheck {
+            bianchi,
+            source: divergence_of_dual - scaled_dual_current,
+        }
+    }
+
+    fn spatial_form(&self, exprs: Vec<Expr>, n_forms: usize) -> Form {
+        Form::new_over(
+            self.spatial_variables.clone(),
+            exprs,
+            n_forms,
+            FormBasis::Natural,
+        )
+    }
+}
@@ -0,0 +1,89 @@
+use super::{EmRuntime, MaxwellSources};
+use crate::app::coords_sys::CoordsSys;
+use crate::app::grid::Grid;
+use crate::app::ui::{EmMode, EmUiState};
+use mathhook_core::Parser;
+
+fn grid(x: &str, y: &str, z: &str) -> Grid {
+    let parse = |expr: &str| Parser::default().parse(expr).unwrap();
+    Grid::new(CoordsSys::new(parse(x), parse(y), parse(z)))
+}
+
+fn potentials_state(phi: &str, vector_potential: [&str; 3]) -> EmUiState {
+    let parse = |expr: &str| Parser::default().parse(expr).unwrap();
+    let mut state = EmUiState::default();
+    state.mode = EmMode::Potentials;
+    state.phi.eq = parse(phi);
+    state.vector_potential.x.eq = parse(vector_potential[0]);
+    state.vector_potential.y.eq = parse(vector_potential[1]);
+    state.vector_potential.z.eq = parse(vector_potential[2]);
+    state
+}
+
+fn sources(state: &EmUiState, rho: &str, current: [&str; 3]) -> MaxwellSources {
+    let parse = |expr: &str| Parser::default().parse(expr).unwrap();
+    let mut state = state.clone();
+    state.charge_density.eq = parse(rho);
+    state.current_density.x.eq = parse(current[0]);
+    state.current_density.y.eq = parse(current[1]);
+    state.current_density.z.eq = parse(current[2]);
+    MaxwellSources::from_state(&state)
+}
+
+#[test]
+fn faraday_tensor_satisfies_both_maxwell_pairs_in_cartesian_space() {
+    let grid = grid("x", "y", "z");
+    let state = potentials_state("x * t", ["0", "sin(z - t)", "0"]);
+
+    // E = (-t, cos(z - t), 0) and B = (-cos(z - t), 0, 0), so J = c^2 curl B - dE/dt = (1, 0, 0).
+    let runtime = EmRuntime::from_ui(&state, &grid);
+    let space = grid.get_coords().get_space();
+    let check = runtime
+        .covariant_maxwell_check(space, &sources(&state, "0", ["1", "0", "0"]))
+        .expect("potentials mode builds F");
+
+    assert!(check.bianchi.is_zero());
+    assert!(check.source.is_zero());
+
+    let unsourced = runtime
+        .covariant_maxwell_check(space, &sources(&state, "0", ["0", "0", "0"]))
+        .expect("potentials mode builds F");
+    assert!(!unsourced.source.is_zero());
+}
+
+#[test]
+fn faraday_tensor_satisfies_both_maxwell_pairs_in_scaled_space() {
+    let grid = grid("2*x", "3*y", "4*z");
+    let state = potentials_state("3 * y * t", ["z", "0", "x * t"]);
+
+    // With X = 2x, Y = 3y, Z = 4z: E = (0, -t, -X/2) and B = (0, 1/4 - t/2, 0), so J = (0, 1, 0).
+    let runtime = EmRuntime::from_ui(&state, &grid);
+    let space = grid.get_coords().get_space();
+    let check = runtime
+        .covariant_maxwell_check(space, &sources(&state, "0", ["0", "1", "0"]))
+        .expect("potentials mode builds F");
+
+    assert!(check.bianchi.is_zero());
+    assert!(check.source.is_zero());
+
+    let misplaced = runtime
+        .covariant_maxwell_check(space, &sources(&state, "0", ["1", "0", "0"]))
+        .expect("potentials mode builds F");
+    assert!(!misplaced.source.is_zero());
+}
+
+#[test]
+fn covariant_check_is_only_available_in_potentials_mode() {
+    let grid = grid("x", "y", "z");
+    let mut state = potentials_state("0", ["0", "0", "0"]);
+    state.mode = EmMode::Electric;
+
+    let runtime = EmRuntime::from_ui(&state, &grid);
+
+    assert!(runtime
+        .covariant_maxwell_check(
+            grid.get_coords().get_space(),
+            &sources(&state, "0", ["0"; 3])
+        )
+        .is_none());
+}
@@ -1,17 +1,30 @@
+mod multigrid;
+mod octree;
+#[cfg(test)]
+mod solver_tests;
+mod time_cache;
+
+use super::conductors::{ConductorBoundary, SourceImage, SurfaceCondition};
 use super::fields::TimedVectorField;
+use super::media::LinearMedia;
 use super::plane_wave::scale_exprs;
 use crate::app::coords_sys::CoordSampleGeometry;
 use crate::app::em_profile::{self, EmProfileMetric};
 use crate::app::grid::GridConfig;
+use crate::app::ui::{EmUiState, InverseCurlBackend};
 use crate::maths::{derivate, Expr, Point};
 use mathhook_core::Simplify;
+use multigrid::PoissonLattice;
 use nalgebra::Vector3;
+use octree::{NodeMoments, SourceOctree};
+use std::ops::{Add, Mul};
 use std::sync::{Arc, Condvar, Mutex};
+use time_cache::{MaxwellSamplingGuard, MaxwellSourceCacheLookup, MaxwellSourceTimeCache};
 
 const MAXWELL_MIN_AXIS_SAMPLES: usize = 5;
 const MAXWELL_MAX_AXIS_SAMPLES: usize = 7;
+const TREE_CODE_MAX_AXIS_SAMPLES: usize = 64;
 const MAXWELL_SINGULAR_EPSILON_SQUARED: f64 = 1.0e-18;
-const MAXWELL_SOURCE_TIME_CACHE_LIMIT: usize = 64;
 const MIN_CELL_SOFTENING_RADIUS_SQUARED: f64 = 1.0e-12;
 
 #[derive(Clone, Copy)]
@@ -23,16 +36,122 @@ struct MaxwellCell {
     softening_radius_squared: f64,
 }
 
+/// Inverse-curl method and its tuning knobs, as chosen in the EM tab.
+#[derive(Clone, Copy)]
+pub(super) struct InverseCurlSettings {
+    pub(super) backend: InverseCurlBackend,
+    /// Source cells per axis for the tree code, lattice cells per axis for multigrid.
+    pub(super) resolution: usize,
+    /// Barnes-Hut opening angle; smaller is more accurate and slower.
+    pub(super) opening_angle: f64,
+}
+
+impl InverseCurlSettings {
+    pub(super) fn from_state(state: &EmUiState) -> Self {
+        Self {
+            backend: state.inverse_curl_backend,
+            resolution: state.solver_resolution,
+            opening_angle: state.tree_opening_angle,
+        }
+    }
+}
+
+#[derive(Clone)]
+enum MaxwellBackend {
+    DirectSum,
+    TreeCode {
+        octree: Arc<SourceOctree>,
+        opening_angle: f64,
+    },
+    Multigrid(Arc<PoissonLattice>),
+}
+
+/// Image of real cell `source`, stored after the real cells in `MaxwellSolveConfig::cells`.
+#[derive(Clone, Copy)]
+struct MaxwellCellImage {
+    source: usize,
+    mirror: SourceImage,
+}
+
 #[derive(Clone)]
 pub(super) struct MaxwellSolveConfig {
+    /// Real source cells followed by one cell per entry of `images`.
     cells: Arc<[MaxwellCell]>,
+    images: Arc<[MaxwellCellImage]>,
+    condition: SurfaceCondition,
     geometry: CoordSampleGeometry,
+    backend: MaxwellBackend,
 }
 
 impl MaxwellSolveConfig {
+    /// Samples the whole grid box as free space on the clamped direct-sum source grid.
     pub(super) fn from_grid_config(grid_config: GridConfig, geometry: CoordSampleGeometry) -> Self {
-        let bounds = grid_config.bounds();
+        Self::direct_sum(grid_config, geometry, &ConductorBoundary::default())
+    }
+
+    fn direct_sum(
+        grid_config: GridConfig,
+        geometry: CoordSampleGeometry,
+        boundary: &ConductorBoundary,
+    ) -> Self {
         let counts = grid_config.sample_counts().map(Self::axis_sample_count);
+        Self::sampled(grid_config, geometry, counts, boundary)
+    }
+
+    /// Builds the solve configuration for the backend selected in the EM tab.
+    ///
+    /// The direct sum keeps its clamped source grid. The tree code samples the source at
+    /// `resolution` cells per axis, and multigrid also solves on a lattice of that resolution.
+    /// Every backend sees the cells left by `boundary` together with their conductor images.
+    pub(super) fn with_settings(
+        grid_config: GridConfig,
+        geometry: CoordSampleGeometry,
+        settings: InverseCurlSettings,
+        boundary: &ConductorBoundary,
+    ) -> Self {
+        match settings.backend {
+            InverseCurlBackend::DirectSum => Self::direct_sum(grid_config, geometry, boundary),
+            InverseCurlBackend::TreeCode => {
+                let cells_per_axis = settings
+                    .resolution
+                    .clamp(MAXWELL_MIN_AXIS_SAMPLES, TREE_CODE_MAX_AXIS_SAMPLES);
+                let mut config =
+                    Self::sampled(grid_config, geometry, [cells_per_axis; 3], boundary);
+                let points = config.cells.iter().map(|cell| cell.world_point).collect();
+                config.backend = MaxwellBackend::TreeCode {
+                    octree: Arc::new(SourceOctree::build(points)),
+                    opening_angle: settings.opening_angle.max(0.0),
+                };
+                config
+            }
+            InverseCurlBackend::Multigrid => {
+                let cells_per_axis = multigrid::lattice_cells(settings.resolution);
+                let mut config =
+                    Self::sampled(grid_config, geometry, [cells_per_axis; 3], boundary);
+                config.backend = MaxwellBackend::Multigrid(Arc::new(PoissonLattice::enclosing(
+                    config.cells.iter().map(|cell| cell.world_point),
+                    cells_per_axis,
+                )));
+                config
+            }
+        }
+    }
+
+    /// Sets which field component the conductor images cancel on conductor surfaces.
+    ///
+    /// The default, `Tangential`, suits `E` and `A`; the Ampere solve for `B` needs `Normal`.
+    pub(super) fn with_condition(mut self, condition: SurfaceCondition) -> Self {
+        self.condition = condition;
+        self
+    }
+
+    fn sampled(
+        grid_config: GridConfig,
+        geometry: CoordSampleGeometry,
+        counts: [usize; 3],
+        boundary: &ConductorBoundary,
+    ) -> Self {
+        let bounds = grid_config.bounds();
         let normalized_bounds = bounds.map(Self::normalized_bounds);
         let steps = [
             (normalized_bounds[0].1 - normalized_bounds[0].0) / counts[0] as f64,
@@ -58,10 +177,14 @@ impl MaxwellSolveConfig {
                     if volume_density <= 1.0e-12 {
                         continue;
                     }
+                    let world_point = geometry.eval_position(abstract_point);
+                    if boundary.excludes(world_point) {
+                        continue;
+                    }
                     let physical_weight = weight * volume_density;
                     cells.push(MaxwellCell {
                         point: Point { x, y, z },
-                        world_point: geometry.eval_position(abstract_point),
+                        world_point,
                         basis,
                         weight: physical_weight,
                         softening_radius_squared: cell_softening_radius_squared(physical_weight),
@@ -70,9 +193,27 @@ impl MaxwellSolveConfig {
             }
         }
 
+        // Images share their real cell's weight and softening; the source map carries any
+        // Kelvin scaling.
+        let mut images = Vec::new();
+        for (source, cell) in cells.iter().enumerate() {
+            for mirror in boundary.images(cell.world_point) {
+                let image_cell = MaxwellCell {
+                    world_point: mirror.position,
+                    ..*cell
+                };
+                images.push((image_cell, MaxwellCellImage { source, mirror }));
+            }
+        }
+        let (image_cells, images): (Vec<_>, Vec<_>) = images.into_iter().unzip();
+        cells.extend(image_cells);
+
         Self {
             cells: Arc::from(cells),
+            images: Arc::from(images),
+            condition: SurfaceCondition::Tangential,
             geometry,
+            backend: MaxwellBackend::DirectSum,
         }
     }
 
@@ -89,9 +230,14 @@ impl MaxwellSolveConfig {
         }
     }
 
+    /// Number of real source cells, which are the only ones sampled.
     #[cfg(test)]
     pub(super) fn cell_count(&self) -> usize {
-        self.cells.len()
+        self.real_cell_count()
+    }
+
+    fn real_cell_count(&self) -> usize {
+        self.cells.len() - self.images.len()
     }
 
     pub(super) fn supports_plane_wave_shortcut(&self) -> bool {
@@ -99,10 +245,40 @@ impl MaxwellSolveConfig {
     }
 }
 
-pub(super) fn maxwell_ampere_source_exprs(electric_exprs: &[Expr; 3], c: f64) -> [Expr; 3] {
-    scale_exprs(partial_t_exprs(electric_exprs), 1.0 / (c * c))
+/// Builds the curl of `B` required by Ampere's law in a linear medium.
+///
+/// In runtime units this is `mu / c^2 * (sigma * E + epsilon * dE/dt)`, where the conduction
+/// current `J = sigma * E` is what damps fields in lossy media.
+pub(super) fn maxwell_ampere_source_exprs(
+    electric_exprs: &[Expr; 3],
+    c: f64,
+    media: &LinearMedia,
+) -> [Expr; 3] {
+    let displacement_exprs = partial_t_exprs(electric_exprs);
+    if media.is_vacuum() {
+        return scale_exprs(displacement_exprs, 1.0 / (c * c));
+    }
+
+    let source = |index: usize| {
+        let displacement = media
+            .permittivity
+            .clone()
+            .mul(displacement_exprs[index].clone());
+        let conduction = media
+            .conductivity
+            .clone()
+            .mul(electric_exprs[index].clone());
+        Expr::number(1.0 / (c * c))
+            .mul(media.permeability.clone())
+            .mul(displacement.add(conduction))
+            .simplify()
+    };
+    [source(0), source(1), source(2)]
 }
 
+/// Builds the curl of `E` required by Faraday's law.
+///
+/// Faraday's law carries no constitutive parameters, so it is the same in every linear medium.
 pub(super) fn maxwell_faraday_source_exprs(magnetic_exprs: &[Expr; 3]) -> [Expr; 3] {
     scale_exprs(partial_t_exprs(magnetic_exprs), -1.0)
 }
@@ -142,26 +318,19 @@ struct MaxwellSampledSource {
     cache_ready: Condvar,
 }
 
-struct MaxwellSourceTimeCache {
-    entries: Vec<MaxwellSourceCacheEntry>,
-}
-
-struct MaxwellSourceCacheEntry {
-    time_bits: u64,
-    state: MaxwellSourceCacheState,
-}
-
-enum MaxwellSourceCacheState {
-    Sampling,
-    Ready(Arc<[Vector3<f64>]>),
-    Failed,
-}
-
-enum MaxwellSourceCacheLookup {
-    Ready(Arc<[Vector3<f64>]>),
-    Sampling,
-    Failed,
-    Missing,
+/// Per-time data the active backend evaluates targets from.
+///
+/// Each slice is built once per sampled time and shared by every target evaluated at it.
+enum PreparedTimeSlice {
+    /// World-space source value per cell.
+    Sources(Vec<Vector3<f64>>),
+    /// World-space source value per cell plus the aggregated octree moments.
+    Tree {
+        sources: Vec<Vector3<f64>>,
+        moments: Vec<NodeMoments>,
+    },
+    /// Solved field at the multigrid lattice nodes.
+    Lattice(Vec<Vector3<f64>>),
 }
 
 impl MaxwellSampledSource {
@@ -169,9 +338,7 @@ impl MaxwellSampledSource {
         Self {
             source,
             config,
-            cache: Mutex::new(MaxwellSourceTimeCache {
-                entries: Vec::new(),
-            }),
+            cache: Mutex::new(MaxwellSourceTimeCache::new()),
             cache_ready: Condvar::new(),
         }
     }
@@ -182,28 +349,26 @@ impl MaxwellSampledSource {
                 return Vector3::zeros();
             };
             let target_world = self.config.geometry.eval_position(target);
-            let source_values = self.source_values_at(time);
-            let mut value = Vector3::zeros();
-
-            for (cell, source_value) in self.config.cells.iter().zip(source_values.iter()) {
-                let radius = target_world - cell.world_point;
-                let radius_squared = radius.norm_squared();
-                if radius_squared <= MAXWELL_SINGULAR_EPSILON_SQUARED {
-                    continue;
+            let slice = self.prepared_slice_at(time);
+            let value = match (&self.config.backend, slice.as_ref()) {
+                (
+                    MaxwellBackend::TreeCode {
+                        octree,
+                        opening_angle,
+                    },
+                    PreparedTimeSlice::Tree { sources, moments },
+                ) => octree.evaluate(target_world, *opening_angle, moments, |index| {
+                    cell_contribution(&self.config.cells[index], sources[index], target_world)
+                }),
+                (MaxwellBackend::Multigrid(lattice), PreparedTimeSlice::Lattice(values)) => {
+                    lattice.interpolate(values, target_world)
                 }
-
-                if !source_value.x.is_finite()
-                    || !source_value.y.is_finite()
-                    || !source_value.z.is_finite()
-                {
-                    continue;
-                }
-
-                let kernel_radius_squared = radius_squared.max(cell.softening_radius_squared);
-                let kernel_scale = cell.weight
-                    / (4.0 * std::f64::consts::PI * kernel_radius_squared.sqrt().powi(3));
-                value += source_value.cross(&radius) * kernel_scale;
-            }
+                (
+                    _,
+                    PreparedTimeSlice::Sources(sources) | PreparedTimeSlice::Tree { sources, .. },
+                ) => self.direct_sum(target_world, sources),
+                (_, PreparedTimeSlice::Lattice(_)) => Vector3::zeros(),
+            };
 
             self.config
                 .geometry
@@ -211,7 +376,25 @@ impl MaxwellSampledSource {
         })
     }
 
-    fn source_values_at(&self, time: f64) -> Arc<[Vector3<f64>]> {
+    /// Sums the softened Biot-Savart kernel over every source cell.
+    fn direct_sum(
+        &self,
+        target_world: Vector3<f64>,
+        source_values: &[Vector3<f64>],
+    ) -> Vector3<f64> {
+        self.config
+            .cells
+            .iter()
+            .zip(source_values.iter())
+            .map(|(cell, source_value)| cell_contribution(cell, *source_value, target_world))
+            .sum()
+    }
+
+    /// Returns the cached time slice for `time`, preparing it on a miss.
+    ///
+    /// The tree code aggregates its node moments and multigrid solves its lattice here, so
+    /// each sampled time is processed only once however many targets read it.
+    fn prepared_slice_at(&self, time: f64) -> Arc<PreparedTimeSlice> {
         let time_bits = time.to_bits();
         let mut cache = self.cache.lock().unwrap();
         loop {
@@ -232,18 +415,44 @@ impl MaxwellSampledSource {
         drop(cache);
 
         let sampling_guard = MaxwellSamplingGuard::new(self, time_bits);
-        let values = em_profile::measure(EmProfileMetric::SourceSampling, || {
-            self.sample_source_values(time)
+        let slice = em_profile::measure(EmProfileMetric::SourceSampling, || {
+            Arc::new(self.prepare_slice(time))
         });
-        sampling_guard.finish(values.clone());
-        values
+        sampling_guard.finish(slice.clone());
+        slice
     }
 
-    fn sample_source_values(&self, time: f64) -> Arc<[Vector3<f64>]> {
+    fn prepare_slice(&self, time: f64) -> PreparedTimeSlice {
+        let sources = self.sample_source_values(time);
+        let weighted_sources = || {
+            self.config
+                .cells
+                .iter()
+                .zip(sources.iter())
+                .map(|(cell, value)| value * cell.weight)
+        };
+        match &self.config.backend {
+            MaxwellBackend::DirectSum => PreparedTimeSlice::Sources(sources),
+            MaxwellBackend::TreeCode { octree, .. } => {
+                let moments = octree.moments(&weighted_sources().collect::<Vec<_>>());
+                PreparedTimeSlice::Tree { sources, moments }
+            }
+            MaxwellBackend::Multigrid(lattice) => PreparedTimeSlice::Lattice(
+                lattice.inverse_curl(
+                    self.config
+                        .cells
+                        .iter()
+                        .map(|cell| cell.world_point)
+                        .zip(weighted_sources()),
+                ),
+            ),
+        }
+    }
+
+    fn sample_source_values(&self, time: f64) -> Vec<Vector3<f64>> {
         // Keep this sequential so a cache miss from the outer parallel render pass cannot nest
         // another Rayon job while sibling worker threads are waiting for the same cache entry.
-        self.config
-            .cells
+        let mut values = self.config.cells[..self.config.real_cell_count()]
             .iter()
             .map(|cell| {
                 let source_value = self.source.at(cell.point, time);
@@ -251,125 +460,46 @@ impl MaxwellSampledSource {
                     .geometry
                     .vector_to_world(&cell.basis, source_value)
             })
-            .collect::<Vec<_>>()
-            .into()
-    }
-}
-
-fn cell_softening_radius_squared(volume: f64) -> f64 {
-    if !volume.is_finite() || volume <= 0.0 {
-        return MIN_CELL_SOFTENING_RADIUS_SQUARED;
-    }
-    let radius = (3.0 * volume / (4.0 * std::f64::consts::PI)).cbrt();
-    (radius * radius).max(MIN_CELL_SOFTENING_RADIUS_SQUARED)
-}
-
-impl MaxwellSourceTimeCache {
-    fn lookup(&self, time_bits: u64) -> MaxwellSourceCacheLookup {
-        let Some(entry) = self
-            .entries
-            .iter()
-            .find(|entry| entry.time_bits == time_bits)
-        else {
-            return MaxwellSourceCacheLookup::Missing;
-        };
-
-        match &entry.state {
-            MaxwellSourceCacheState::Ready(values) => {
-                MaxwellSourceCacheLookup::Ready(values.clone())
-            }
-            MaxwellSourceCacheState::Sampling => MaxwellSourceCacheLookup::Sampling,
-            MaxwellSourceCacheState::Failed => MaxwellSourceCacheLookup::Failed,
-        }
-    }
-
-    fn reserve(&mut self, time_bits: u64) {
-        if self
-            .entries
+            .collect::<Vec<_>>();
+        let image_values = self
+            .config
+            .images
             .iter()
-            .any(|entry| entry.time_bits == time_bits)
-        {
-            return;
-        }
-        if self.entries.len() >= MAXWELL_SOURCE_TIME_CACHE_LIMIT {
-            self.evict_one_ready_entry();
-        }
-        self.entries.push(MaxwellSourceCacheEntry {
-            time_bits,
-            state: MaxwellSourceCacheState::Sampling,
-        });
-    }
-
-    fn finish_sampling(&mut self, time_bits: u64, values: Arc<[Vector3<f64>]>) {
-        if let Some(entry) = self
-            .entries
-            .iter_mut()
-            .find(|entry| entry.time_bits == time_bits)
-        {
-            entry.state = MaxwellSourceCacheState::Ready(values);
-            return;
-        }
-
-        if self.entries.len() >= MAXWELL_SOURCE_TIME_CACHE_LIMIT {
-            self.evict_one_ready_entry();
-        }
-        self.entries.push(MaxwellSourceCacheEntry {
-            time_bits,
-            state: MaxwellSourceCacheState::Ready(values),
-        });
-    }
-
-    fn evict_one_ready_entry(&mut self) {
-        if let Some(index) = self
-            .entries
-            .iter()
-            .position(|entry| !matches!(entry.state, MaxwellSourceCacheState::Sampling))
-        {
-            self.entries.remove(index);
-        }
-    }
-
-    fn fail_sampling(&mut self, time_bits: u64) {
-        if let Some(entry) = self
-            .entries
-            .iter_mut()
-            .find(|entry| entry.time_bits == time_bits)
-        {
-            entry.state = MaxwellSourceCacheState::Failed;
-        }
+            .map(|image| image.mirror.source_map(self.config.condition) * values[image.source])
+            .collect::<Vec<_>>();
+        values.extend(image_values);
+        values
     }
 }
 
-struct MaxwellSamplingGuard<'a> {
-    source: &'a MaxwellSampledSource,
-    time_bits: u64,
-    active: bool,
-}
-
-impl<'a> MaxwellSamplingGuard<'a> {
-    fn new(source: &'a MaxwellSampledSource, time_bits: u64) -> Self {
-        Self {
-            source,
-            time_bits,
-            active: true,
-        }
+/// Softened Biot-Savart contribution of one source cell at `target_world`.
+///
+/// Coincident targets and non-finite sources contribute nothing.
+fn cell_contribution(
+    cell: &MaxwellCell,
+    source_value: Vector3<f64>,
+    target_world: Vector3<f64>,
+) -> Vector3<f64> {
+    let radius = target_world - cell.world_point;
+    let radius_squared = radius.norm_squared();
+    if radius_squared <= MAXWELL_SINGULAR_EPSILON_SQUARED
+        || !source_value.x.is_finite()
+        || !source_value.y.is_finite()
+        || !source_value.z.is_finite()
+    {
+        return Vector3::zeros();
     }
 
-    fn finish(mut self, values: Arc<[Vector3<f64>]>) {
-        let mut cache = self.source.cache.lock().unwrap();
-        cache.finish_sampling(self.time_bits, values);
-        self.active = false;
-        self.source.cache_ready.notify_all();
-    }
+    let kernel_radius_squared = radius_squared.max(cell.softening_radius_squared);
+    let kernel_scale =
+        cell.weight / (4.0 * std::f64::consts::PI * kernel_radius_squared.sqrt().powi(3));
+    source_value.cross(&radius) * kernel_scale
 }
 
-impl Drop for MaxwellSamplingGuard<'_> {
-    fn drop(&mut self) {
-        if self.active {
-            if let Ok(mut cache) = self.source.cache.lock() {
-                cache.fail_sampling(self.time_bits);
-                self.source.cache_ready.notify_all();
-            }
-        }
+fn cell_softening_radius_squared(volume: f64) -> f64 {
+    if !volume.is_finite() || volume <= 0.0 {
+        return MIN_CELL_SOFTENING_RADIUS_SQUARED;
     }
+    let radius = (3.0 * volume / (4.0 * std::f64::consts::PI)).cbrt();
+    (radius * radius).max(MIN_CELL_SOFTENING_RADIUS_SQUARED)
 }
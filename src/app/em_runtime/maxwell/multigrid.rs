@@ -0,0 +1,411 @@
+//! Geometric multigrid Poisson solver behind the grid inverse-curl backend.
+//!
+//! The solver works on a cubic world-space lattice so curvilinear source cells keep their
+//! embedded placement: cell sources are spread onto lattice nodes, solved there, and the result
+//! is interpolated back at arbitrary world targets.
+
+use nalgebra::Vector3;
+use std::f64::consts::PI;
+
+/// Lattice half-width relative to the half-extent of the source cells.
+///
+/// The outer shell stays source free so the multipole boundary values are accurate.
+const LATTICE_PADDING: f64 = 1.25;
+const MIN_LATTICE_CELLS: usize = 8;
+const MAX_LATTICE_CELLS: usize = 128;
+const COARSEST_LATTICE_CELLS: usize = 2;
+const SMOOTHING_SWEEPS: usize = 2;
+const COARSEST_SMOOTHING_SWEEPS: usize = 16;
+const MAX_V_CYCLES: usize = 12;
+const RESIDUAL_TOLERANCE: f64 = 1.0e-8;
+const MIN_LATTICE_HALF_WIDTH: f64 = 1.0e-6;
+
+/// Cubic world-space lattice with `cells + 1` nodes per axis.
+#[derive(Clone, Debug)]
+pub(super) struct PoissonLattice {
+    origin: Vector3<f64>,
+    spacing: f64,
+    cells: usize,
+}
+
+impl PoissonLattice {
+    /// Builds a lattice centered on `points` with empty padding around them.
+    ///
+    /// `cells` is rounded up to a power of two so every level coarsens by exactly one half.
+    pub(super) fn enclosing(points: impl Iterator<Item = Vector3<f64>>, cells: usize) -> Self {
+        let cells = lattice_cells(cells);
+        let mut min = Vector3::repeat(f64::INFINITY);
+        let mut max = Vector3::repeat(f64::NEG_INFINITY);
+        for point in points.filter(|point| point.iter().all(|value| value.is_finite())) {
+            min = min.inf(&point);
+            max = max.sup(&point);
+        }
+        if min.x > max.x {
+            min = Vector3::zeros();
+            max = Vector3::zeros();
+        }
+
+        let center = (min + max) * 0.5;
+        let half_width = ((max - min).max() * 0.5 * LATTICE_PADDING).max(MIN_LATTICE_HALF_WIDTH);
+        Self {
+            origin: center - Vector3::repeat(half_width),
+            spacing: 2.0 * half_width / cells as f64,
+            cells,
+        }
+    }
+
+    fn node_count(&self) -> usize {
+        node_count(self.cells)
+    }
+
+    /// Solves for the divergence-free field whose curl is the deposited source density.
+    ///
+    /// Each `(world_point, weighted_value)` pair is a source cell with its volume folded in.
+    /// The solve is `lap(psi) = -F` per component followed by `A = curl(psi)`, which is the
+    /// Biot-Savart field and satisfies `lap(A) = -curl(F)` without differentiating `F`.
+    pub(super) fn inverse_curl(
+        &self,
+        sources: impl Iterator<Item = (Vector3<f64>, Vector3<f64>)>,
+    ) -> Vec<Vector3<f64>> {
+        let sources = sources
+            .filter(|(point, value)| {
+                point.iter().all(|coord| coord.is_finite())
+                    && value.iter().all(|coord| coord.is_finite())
+            })
+            .collect::<Vec<_>>();
+        let density = self.deposit(&sources);
+        let boundary = MultipoleMoments::from_sources(&sources);
+
+        let potential_components = [0, 1, 2].map(|axis| {
+            let rhs = density.iter().map(|value| -value[axis]).collect::<Vec<_>>();
+            let mut potential = vec![0.0; self.node_count()];
+            self.for_each_boundary_node(|index, position| {
+                potential[index] = boundary.potential_at(position, axis);
+            });
+            solve_poisson(self.cells, self.spacing, &rhs, &mut potential);
+            potential
+        });
+
+        self.curl(&potential_components)
+    }
+
+    /// Trilinearly interpolates node values at `point`; points outside the lattice read zero.
+    pub(super) fn interpolate(&self, values: &[Vector3<f64>], point: Vector3<f64>) -> Vector3<f64> {
+        let Some(stencil) = self.trilinear_stencil(point) else {
+            return Vector3::zeros();
+        };
+        stencil
+            .into_iter()
+            .map(|(index, weight)| values[index] * weight)
+            .sum()
+    }
+
+    /// Spreads source cells onto nodes with cloud-in-cell weights, returning a density.
+    fn deposit(&self, sources: &[(Vector3<f64>, Vector3<f64>)]) -> Vec<Vector3<f64>> {
+        let node_volume = self.spacing.powi(3);
+        let mut density = vec![Vector3::zeros(); self.node_count()];
+        for (point, value) in sources {
+            let Some(stencil) = self.trilinear_stencil(*point) else {
+                continue;
+            };
+            for (index, weight) in stencil {
+                density[index] += value * (weight / node_volume);
+            }
+        }
+        density
+    }
+
+    fn trilinear_stencil(&self, point: Vector3<f64>) -> Option<[(usize, f64); 8]> {
+        let local = (point - self.origin) / self.spacing;
+        let mut base = [0; 3];
+        let mut fraction = [0.0; 3];
+        for axis in 0..3 {
+            if !(0.0..=self.cells as f64).contains(&local[axis]) {
+                return None;
+            }
+            base[axis] = (local[axis].floor() as usize).min(self.cells - 1);
+            fraction[axis] = local[axis] - base[axis] as f64;
+        }
+
+        let mut stencil = [(0, 0.0); 8];
+        for (corner, entry) in stencil.iter_mut().enumerate() {
+            let offset = [(corner >> 2) & 1, (corner >> 1) & 1, corner & 1];
+            let weight = (0..3)
+                .map(|axis| {
+                    if offset[axis] == 1 {
+                        fraction[axis]
+                    } else {
+                        1.0 - fraction[axis]
+                    }
+                })
+                .product();
+            *entry = (
+                node_index(
+                    self.cells,
+                    base[0] + offset[0],
+                    base[1] + offset[1],
+                    base[2] + offset[2],
+                ),
+                weight,
+            );
+        }
+        Some(stencil)
+    }
+
+    fn for_each_boundary_node(&self, mut visit: impl FnMut(usize, Vector3<f64>)) {
+        for i in 0..=self.cells {
+            for j in 0..=self.cells {
+                for k in 0..=self.cells {
+                    if [i, j, k]
+                        .iter()
+                        .all(|&index| index > 0 && index < self.cells)
+                    {
+                        continue;
+                    }
+                    let position =
+                        self.origin + Vector3::new(i as f64, j as f64, k as f64) * self.spacing;
+                    visit(node_index(self.cells, i, j, k), position);
+                }
+            }
+        }
+    }
+
+    /// Finite-difference curl of a vector potential given per component.
+    ///
+    /// Interior nodes use central differences and boundary nodes fall back to one-sided ones.
+    fn curl(&self, potential: &[Vec<f64>; 3]) -> Vec<Vector3<f64>> {
+        let mut curl = vec![Vector3::zeros(); self.node_count()];
+        for i in 0..=self.cells {
+            for j in 0..=self.cells {
+                for k in 0..=self.cells {
+                    let node = [i, j, k];
+                    let derivative = |component: usize, axis: usize| {
+                        self.partial_derivative(&potential[component], node, axis)
+                    };
+                    curl[node_index(self.cells, i, j, k)] = Vector3::new(
+                        derivative(2, 1) - derivative(1, 2),
+                        derivative(0, 2) - derivative(2, 0),
+                        derivative(1, 0) - derivative(0, 1),
+                    );
+                }
+            }
+        }
+        curl
+    }
+
+    fn partial_derivative(&self, values: &[f64], node: [usize; 3], axis: usize) -> f64 {
+        let shifted = |delta: isize| {
+            let mut shifted = node;
+            shifted[axis] = (node[axis] as isize + delta) as usize;
+            values[node_index(self.cells, shifted[0], shifted[1], shifted[2])]
+        };
+        if node[axis] == 0 {
+            (shifted(1) - shifted(0)) / self.spacing
+        } else if node[axis] == self.cells {
+            (shifted(0) - shifted(-1)) / self.spacing
+        } else {
+            (shifted(1) - shifted(-1)) / (2.0 * self.spacing)
+        }
+    }
+}
+
+/// Rounds a requested lattice resolution to the power of two used by the solver.
+pub(super) fn lattice_cells(requested: usize) -> usize {
+    requested
+        .clamp(MIN_LATTICE_CELLS, MAX_LATTICE_CELLS)
+        .next_power_of_two()
+}
+
+/// Monopole and dipole moments of the source, used for free-space boundary values.
+struct MultipoleMoments {
+    center: Vector3<f64>,
+    monopole: Vector3<f64>,
+    dipole: [Vector3<f64>; 3],
+}
+
+impl MultipoleMoments {
+    fn from_sources(sources: &[(Vector3<f64>, Vector3<f64>)]) -> Self {
+        let total_weight = sources.iter().map(|(_, value)| value.norm()).sum::<f64>();
+        let center = if total_weight > 0.0 {
+            sources
+                .iter()
+                .map(|(point, value)| point * value.norm())
+                .sum::<Vector3<f64>>()
+                / total_weight
+        } else {
+            Vector3::zeros()
+        };
+        let monopole = sources.iter().map(|(_, value)| value).sum();
+        let dipole = [0, 1, 2].map(|component| {
+            sources
+                .iter()
+                .map(|(point, value)| (point - center) * value[component])
+                .sum()
+        });
+
+        Self {
+            center,
+            monopole,
+            dipole,
+        }
+    }
+
+    /// Returns component `axis` of the free-space potential `sum(F / (4 pi r))` at `point`.
+    fn potential_at(&self, point: Vector3<f64>, axis: usize) -> f64 {
+        let offset = point - self.center;
+        let distance = offset.norm();
+        if distance <= f64::EPSILON {
+            return 0.0;
+        }
+        (self.monopole[axis] / distance + self.dipole[axis].dot(&offset) / distance.powi(3))
+            / (4.0 * PI)
+    }
+}
+
+/// Solves `lap(u) = rhs` on the interior nodes, keeping the boundary values of `solution`.
+fn solve_poisson(cells: usize, spacing: f64, rhs: &[f64], solution: &mut [f64]) {
+    let rhs_scale = rhs.iter().fold(0.0_f64, |max, value| max.max(value.abs()));
+    if rhs_scale == 0.0 && solution.iter().all(|value| *value == 0.0) {
+        return;
+    }
+    let tolerance = RESIDUAL_TOLERANCE * rhs_scale.max(1.0);
+
+    for _ in 0..MAX_V_CYCLES {
+        v_cycle(cells, spacing, solution, rhs);
+        let residual = residual(cells, spacing, solution, rhs);
+        if residual.iter().all(|value| value.abs() <= tolerance) {
+            break;
+        }
+    }
+}
+
+fn v_cycle(cells: usize, spacing: f64, solution: &mut [f64], rhs: &[f64]) {
+    if cells <= COARSEST_LATTICE_CELLS {
+        for _ in 0..COARSEST_SMOOTHING_SWEEPS {
+            smooth(cells, spacing, solution, rhs);
+        }
+        return;
+    }
+
+    for _ in 0..SMOOTHING_SWEEPS {
+        smooth(cells, spacing, solution, rhs);
+    }
+    let coarse_cells = cells / 2;
+    let coarse_rhs = restrict(cells, &residual(cells, spacing, solution, rhs));
+    let mut correction = vec![0.0; node_count(coarse_cells)];
+    v_cycle(coarse_cells, spacing * 2.0, &mut correction, &coarse_rhs);
+    prolong_add(cells, &correction, solution);
+    for _ in 0..SMOOTHING_SWEEPS {
+        smooth(cells, spacing, solution, rhs);
+    }
+}
+
+/// One red-black Gauss-Seidel sweep of the 7-point Laplacian.
+fn smooth(cells: usize, spacing: f64, solution: &mut [f64], rhs: &[f64]) {
+    let spacing_squared = spacing * spacing;
+    for color in 0..2 {
+        for i in 1..cells {
+            for j in 1..cells {
+                for k in 1..cells {
+                    if (i + j + k) % 2 != color {
+                        continue;
+                    }
+                    let index = node_index(cells, i, j, k);
+                    let neighbors = neighbor_sum(cells, solution, i, j, k);
+                    solution[index] = (neighbors - spacing_squared * rhs[index]) / 6.0;
+                }
+            }
+        }
+    }
+}
+
+fn residual(cells: usize, spacing: f64, solution: &[f64], rhs: &[f64]) -> Vec<f64> {
+    let spacing_squared = spacing * spacing;
+    let mut residual = vec![0.0; node_count(cells)];
+    for i in 1..cells {
+        for j in 1..cells {
+            for k in 1..cells {
+                let index = node_index(cells, i, j, k);
+                let laplacian = (neighbor_sum(cells, solution, i, j, k) - 6.0 * solution[index])
+                    / spacing_squared;
+                residual[index] = rhs[index] - laplacian;
+            }
+        }
+    }
+    residual
+}
+
+/// Full-weighting restriction onto the lattice with half as many cells per axis.
+fn restrict(fine_cells: usize, fine: &[f64]) -> Vec<f64> {
+    let coarse_cells = fine_cells / 2;
+    let mut coarse = vec![0.0; node_count(coarse_cells)];
+    for i in 1..coarse_cells {
+        for j in 1..coarse_cells {
+            for k in 1..coarse_cells {
+                let mut value = 0.0;
+                for di in 0..3 {
+                    for dj in 0..3 {
+                        for dk in 0..3 {
+                            let distance = [di, dj, dk].iter().filter(|&&d| d != 1).count();
+                            let weight = 0.5_f64.powi(distance as i32) / 8.0;
+                            value += weight
+                                * fine[node_index(
+                                    fine_cells,
+                                    2 * i + di - 1,
+                                    2 * j + dj - 1,
+                                    2 * k + dk - 1,
+                                )];
+                        }
+                    }
+                }
+                coarse[node_index(coarse_cells, i, j, k)] = value;
+            }
+        }
+    }
+    coarse
+}
+
+/// Adds the trilinearly interpolated coarse correction to the interior fine nodes.
+fn prolong_add(fine_cells: usize, coarse: &[f64], fine: &mut [f64]) {
+    let coarse_cells = fine_cells / 2;
+    let axis_stencil = |index: usize| {
+        if index % 2 == 0 {
+            [(index / 2, 1.0), (index / 2, 0.0)]
+        } else {
+            [(index / 2, 0.5), (index / 2 + 1, 0.5)]
+        }
+    };
+    for i in 1..fine_cells {
+        for j in 1..fine_cells {
+            for k in 1..fine_cells {
+                let mut value = 0.0;
+                for (ci, wi) in axis_stencil(i) {
+                    for (cj, wj) in axis_stencil(j) {
+                        for (ck, wk) in axis_stencil(k) {
+                            value += wi * wj * wk * coarse[node_index(coarse_cells, ci, cj, ck)];
+                        }
+                    }
+                }
+                fine[node_index(fine_cells, i, j, k)] += value;
+            }
+        }
+    }
+}
+
+fn neighbor_sum(cells: usize, values: &[f64], i: usize, j: usize, k: usize) -> f64 {
+    values[node_index(cells, i - 1, j, k)]
+        + values[node_index(cells, i + 1, j, k)]
+        + values[node_index(cells, i, j - 1, k)]
+        + values[node_index(cells, i, j + 1, k)]
+        + values[node_index(cells, i, j, k - 1)]
+        + values[node_index(cells, i, j, k + 1)]
+}
+
+fn node_count(cells: usize) -> usize {
+    (cells + 1).pow(3)
+}
+
+fn node_index(cells: usize, i: usize, j: usize, k: usize) -> usize {
+    let nodes = cells + 1;
+    (i * nodes + j) * nodes + k
+}
@@ -0,0 +1,212 @@
+//! Barnes-Hut octree over Maxwell source cells for the tree-code inverse-curl backend.
+//!
+//! The tree is built once from the embedded cell positions. Source moments depend on time, so
+//! they are aggregated separately for each cached time slice and passed back in for evaluation.
+
+use nalgebra::{Matrix3, Vector3};
+use std::f64::consts::PI;
+use std::ops::Range;
+
+const LEAF_CELL_COUNT: usize = 8;
+const MAX_TREE_DEPTH: usize = 24;
+
+/// Octree over source cell indices; each node owns a contiguous range of `order`.
+pub(super) struct SourceOctree {
+    points: Vec<Vector3<f64>>,
+    nodes: Vec<OctreeNode>,
+    order: Vec<usize>,
+}
+
+struct OctreeNode {
+    /// Expansion center, the mean position of the cells in this node.
+    center: Vector3<f64>,
+    /// Largest distance from `center` to a cell in this node.
+    radius: f64,
+    cells: Range<usize>,
+    children: Vec<usize>,
+}
+
+/// Source moments of one node about its expansion center.
+///
+/// `monopole` is the sum of weighted source values and `dipole` the sum of
+/// `weighted_source * (cell - center)^T`, enough for a first-order far-field kernel.
+#[derive(Clone, Copy)]
+pub(super) struct NodeMoments {
+    monopole: Vector3<f64>,
+    dipole: Matrix3<f64>,
+}
+
+impl SourceOctree {
+    /// Builds the tree over `points`, skipping non-finite positions.
+    pub(super) fn build(points: Vec<Vector3<f64>>) -> Self {
+        let mut order = (0..points.len())
+            .filter(|&index| points[index].iter().all(|value| value.is_finite()))
+            .collect::<Vec<_>>();
+        let mut nodes = Vec::new();
+        let cell_count = order.len();
+        build_node(&points, &mut order, 0..cell_count, 0, &mut nodes);
+        Self {
+            points,
+            nodes,
+            order,
+        }
+    }
+
+    /// Aggregates `weighted_sources` (volume-weighted world source values) into node moments.
+    ///
+    /// Non-finite sources contribute nothing, matching the direct sum.
+    pub(super) fn moments(&self, weighted_sources: &[Vector3<f64>]) -> Vec<NodeMoments> {
+        let mut moments = vec![
+            NodeMoments {
+                monopole: Vector3::zeros(),
+                dipole: Matrix3::zeros(),
+            };
+            self.nodes.len()
+        ];
+
+        // Children are always pushed after their parent, so a reverse pass is bottom-up.
+        for (node_index, node) in self.nodes.iter().enumerate().rev() {
+            let mut node_moments = NodeMoments {
+                monopole: Vector3::zeros(),
+                dipole: Matrix3::zeros(),
+            };
+            if node.children.is_empty() {
+                for &cell in &self.order[node.cells.clone()] {
+                    let source = weighted_sources[cell];
+                    if !source.iter().all(|value| value.is_finite()) {
+                        continue;
+                    }
+                    node_moments.monopole += source;
+                    node_moments.dipole += source * (self.points[cell] - node.center).transpose();
+                }
+            } else {
+                for &child in &node.children {
+                    let child_moments = moments[child];
+                    let shift = self.nodes[child].center - node.center;
+                    node_moments.monopole += child_moments.monopole;
+                    node_moments.dipole +=
+                        child_moments.dipole + child_moments.monopole * shift.transpose();
+                }
+            }
+            moments[node_index] = node_moments;
+        }
+
+        moments
+    }
+
+    /// Evaluates the Biot-Savart sum at `target` with the opening-angle criterion.
+    ///
+    /// A node whose diameter seen from `target` is below `opening_angle` uses its moments;
+    /// otherwise it is opened, and leaves call `exact` for each of their cell indices.
+    pub(super) fn evaluate(
+        &self,
+        target: Vector3<f64>,
+        opening_angle: f64,
+        moments: &[NodeMoments],
+        exact: impl Fn(usize) -> Vector3<f64>,
+    ) -> Vector3<f64> {
+        let mut value = Vector3::zeros();
+        if self.nodes.is_empty() {
+            return value;
+        }
+
+        let mut stack = vec![0];
+        while let Some(node_index) = stack.pop() {
+            let node = &self.nodes[node_index];
+            let offset = target - node.center;
+            let distance = offset.norm();
+            if distance > node.radius && 2.0 * node.radius < opening_angle * distance {
+                value += moments[node_index].far_field(offset);
+            } else if node.children.is_empty() {
+                for &cell in &self.order[node.cells.clone()] {
+                    value += exact(cell);
+                }
+            } else {
+                stack.extend(node.children.iter().copied());
+            }
+        }
+        value
+    }
+}
+
+impl NodeMoments {
+    /// First-order multipole expansion of `sum(s_i x (r - d_i) / |r - d_i|^3) / 4 pi`.
+    fn far_field(&self, offset: Vector3<f64>) -> Vector3<f64> {
+        let distance_squared = offset.norm_squared();
+        let distance = distance_squared.sqrt();
+        let inverse_cube = 1.0 / (distance_squared * distance);
+        let kernel = offset * inverse_cube;
+        let kernel_gradient = Matrix3::identity() * inverse_cube
+            - offset * offset.transpose() * (3.0 * inverse_cube / distance_squared);
+        let dipole_term = self.dipole * kernel_gradient;
+        let dipole_cross = Vector3::new(
+            dipole_term[(1, 2)] - dipole_term[(2, 1)],
+            dipole_term[(2, 0)] - dipole_term[(0, 2)],
+            dipole_term[(0, 1)] - dipole_term[(1, 0)],
+        );
+
+        (self.monopole.cross(&kernel) - dipole_cross) / (4.0 * PI)
+    }
+}
+
+fn build_node(
+    points: &[Vector3<f64>],
+    order: &mut [usize],
+    cells: Range<usize>,
+    depth: usize,
+    nodes: &mut Vec<OctreeNode>,
+) -> usize {
+    let members = &order[cells.clone()];
+    let center = if members.is_empty() {
+        Vector3::zeros()
+    } else {
+        members
+            .iter()
+            .map(|&cell| points[cell])
+            .sum::<Vector3<f64>>()
+            / members.len() as f64
+    };
+    let radius = members
+        .iter()
+        .map(|&cell| (points[cell] - center).norm())
+        .fold(0.0, f64::max);
+    let node_index = nodes.len();
+    nodes.push(OctreeNode {
+        center,
+        radius,
+        cells: cells.clone(),
+        children: Vec::new(),
+    });
+    if members.len() <= LEAF_CELL_COUNT || depth >= MAX_TREE_DEPTH || radius == 0.0 {
+        return node_index;
+    }
+
+    let (min, max) = members.iter().fold(
+        (
+            Vector3::repeat(f64::INFINITY),
+            Vector3::repeat(f64::NEG_INFINITY),
+        ),
+        |(min, max), &cell| (min.inf(&points[cell]), max.sup(&points[cell])),
+    );
+    let split = (min + max) * 0.5;
+    let octant = |cell: usize| {
+        let point = points[cell];
+        (usize::from(point.x >= split.x) << 2)
+            | (usize::from(point.y >= split.y) << 1)
+            | usize::from(point.z >= split.z)
+    };
+    order[cells.clone()].sort_by_key(|&cell| octant(cell));
+
+    let mut children = Vec::new();
+    let mut start = cells.start;
+    while start < cells.end {
+        let current = octant(order[start]);
+        let end = (start..cells.end)
+            .find(|&index| octant(order[index]) != current)
+            .unwrap_or(cells.end);
+        children.push(build_node(points, order, start..end, depth + 1, nodes));
+        start = end;
+    }
+    nodes[node_index].children = children;
+    node_index
+}
@@ -0,0 +1,254 @@
+use super::multigrid::lattice_cells;
+use super::{InverseCurlSettings, MaxwellSampledSource, MaxwellSolveConfig};
+use crate::app::coords_sys::CoordsSys;
+use crate::app::em_runtime::conductors::{ConductorBoundary, SurfaceCondition};
+use crate::app::em_runtime::fields::TimedVectorField;
+use crate::app::grid::GridConfig;
+use crate::app::ui::{EmUiState, EqRender, InverseCurlBackend};
+use mathhook_core::Parser;
+use nalgebra::Vector3;
+
+const GAUSSIAN: &str = "exp(-(x*x + y*y + z*z))";
+
+/// Curl of the vortex `X = (-2 y g, 2 x g, 0)` with `g = exp(-r^2)`.
+///
+/// `X` is divergence free and decays fast, so its Biot-Savart reconstruction is `X` itself.
+fn gaussian_vortex_source() -> TimedVectorField {
+    let parse = |expr: &str| Parser::default().parse(expr).unwrap();
+    TimedVectorField::from_exprs([
+        parse(&format!("4*x*z*{GAUSSIAN}")),
+        parse(&format!("4*y*z*{GAUSSIAN}")),
+        parse(&format!("(4 - 4*x*x - 4*y*y)*{GAUSSIAN}")),
+    ])
+}
+
+fn gaussian_vortex(point: Vector3<f64>) -> Vector3<f64> {
+    let gaussian = (-point.norm_squared()).exp();
+    Vector3::new(-2.0 * point.y * gaussian, 2.0 * point.x * gaussian, 0.0)
+}
+
+fn vortex_config(backend: InverseCurlBackend, resolution: usize) -> MaxwellSolveConfig {
+    vortex_config_with_angle(backend, resolution, 0.5)
+}
+
+fn vortex_config_with_angle(
+    backend: InverseCurlBackend,
+    resolution: usize,
+    opening_angle: f64,
+) -> MaxwellSolveConfig {
+    vortex_config_with_boundary(
+        backend,
+        resolution,
+        opening_angle,
+        &ConductorBoundary::default(),
+    )
+}
+
+fn vortex_config_with_boundary(
+    backend: InverseCurlBackend,
+    resolution: usize,
+    opening_angle: f64,
+    boundary: &ConductorBoundary,
+) -> MaxwellSolveConfig {
+    let parse = |expr: &str| Parser::default().parse(expr).unwrap();
+    let coords = CoordsSys::new(parse("x"), parse("y"), parse("z"));
+    MaxwellSolveConfig::with_settings(
+        GridConfig::new(-3.0, 3.0, 5.0, -3.0, 3.0, 5.0, -3.0, 3.0, 5.0),
+        coords.sample_geometry(),
+        InverseCurlSettings {
+            backend,
+            resolution,
+            opening_angle,
+        },
+        boundary,
+    )
+}
+
+fn boundary(conductors: &[&str], domain_mask: &str) -> ConductorBoundary {
+    let eq = |expr: &str| EqRender::new(Parser::default().parse(expr).unwrap(), expr.to_string());
+    ConductorBoundary::from_state(&EmUiState {
+        conductors: conductors.iter().map(|expr| eq(expr)).collect(),
+        domain_mask: eq(domain_mask),
+        ..EmUiState::default()
+    })
+}
+
+fn vortex_targets() -> [Vector3<f64>; 4] {
+    [
+        Vector3::new(0.5, 0.3, 0.2),
+        Vector3::new(-0.7, 0.4, -0.3),
+        Vector3::new(0.0, 1.0, 0.5),
+        Vector3::new(0.8, -0.6, 0.0),
+    ]
+}
+
+/// Largest target error relative to the largest reference magnitude.
+fn relative_error(
+    actual: impl Fn(Vector3<f64>) -> Vector3<f64>,
+    expected: impl Fn(Vector3<f64>) -> Vector3<f64>,
+) -> f64 {
+    let targets = vortex_targets();
+    let scale = targets
+        .iter()
+        .map(|target| expected(*target).norm())
+        .fold(0.0, f64::max);
+    targets
+        .iter()
+        .map(|target| (actual(*target) - expected(*target)).norm())
+        .fold(0.0, f64::max)
+        / scale
+}
+
+#[test]
+fn lattice_resolution_rounds_to_supported_powers_of_two() {
+    assert_eq!(lattice_cells(20), 32);
+    assert_eq!(lattice_cells(32), 32);
+    assert_eq!(lattice_cells(1), 8);
+    assert_eq!(lattice_cells(1_000), 128);
+}
+
+#[test]
+fn multigrid_inverse_curl_recovers_gaussian_vortex() {
+    let source = MaxwellSampledSource::new(
+        gaussian_vortex_source(),
+        vortex_config(InverseCurlBackend::Multigrid, 32),
+    );
+
+    let error = relative_error(
+        |target| source.inverse_curl_at(target, 0.0),
+        gaussian_vortex,
+    );
+
+    assert!(error < 0.15, "relative error {error}");
+}
+
+#[test]
+fn multigrid_inverse_curl_tracks_direct_sum_on_the_same_cells() {
+    let source = MaxwellSampledSource::new(
+        gaussian_vortex_source(),
+        vortex_config(InverseCurlBackend::Multigrid, 32),
+    );
+    let source_values = source.sample_source_values(0.0);
+
+    let error = relative_error(
+        |target| source.inverse_curl_at(target, 0.0),
+        |target| source.direct_sum(target, &source_values),
+    );
+
+    assert!(error < 0.05, "relative error {error}");
+}
+
+#[test]
+fn direct_sum_inverse_curl_recovers_gaussian_vortex() {
+    let source = MaxwellSampledSource::new(
+        gaussian_vortex_source(),
+        vortex_config(InverseCurlBackend::DirectSum, 32),
+    );
+
+    let error = relative_error(
+        |target| source.inverse_curl_at(target, 0.0),
+        gaussian_vortex,
+    );
+
+    assert!(error < 0.15, "relative error {error}");
+}
+
+#[test]
+fn multigrid_error_decreases_with_resolution() {
+    let error_at = |resolution: usize| {
+        let source = MaxwellSampledSource::new(
+            gaussian_vortex_source(),
+            vortex_config(InverseCurlBackend::Multigrid, resolution),
+        );
+        relative_error(
+            |target| source.inverse_curl_at(target, 0.0),
+            gaussian_vortex,
+        )
+    };
+
+    let coarse = error_at(16);
+    let fine = error_at(32);
+
+    assert!(fine < coarse, "{fine} >= {coarse}");
+}
+
+#[test]
+fn tree_code_matches_direct_sum_on_the_same_cells() {
+    let source = MaxwellSampledSource::new(
+        gaussian_vortex_source(),
+        vortex_config(InverseCurlBackend::TreeCode, 32),
+    );
+    let source_values = source.sample_source_values(0.0);
+
+    let error = relative_error(
+        |target| source.inverse_curl_at(target, 0.0),
+        |target| source.direct_sum(target, &source_values),
+    );
+
+    assert!(error < 1.0e-2, "relative error {error}");
+}
+
+#[test]
+fn tree_code_error_shrinks_with_opening_angle() {
+    let error_at = |opening_angle: f64| {
+        let source = MaxwellSampledSource::new(
+            gaussian_vortex_source(),
+            vortex_config_with_angle(InverseCurlBackend::TreeCode, 24, opening_angle),
+        );
+        let source_values = source.sample_source_values(0.0);
+        relative_error(
+            |target| source.inverse_curl_at(target, 0.0),
+            |target| source.direct_sum(target, &source_values),
+        )
+    };
+
+    let exact = error_at(0.0);
+    let tight = error_at(0.2);
+    let loose = error_at(1.0);
+
+    assert!(exact < 1.0e-12, "{exact}");
+    assert!(tight < loose, "{tight} >= {loose}");
+}
+
+#[test]
+fn plane_conductor_images_cancel_the_constrained_component_on_its_surface() {
+    let metal = boundary(&["z + 1"], "1");
+    let config = vortex_config_with_boundary(InverseCurlBackend::DirectSum, 5, 0.5, &metal);
+    assert!(!config.images.is_empty());
+    let surface = [
+        Vector3::new(0.4, -0.2, -1.0),
+        Vector3::new(-0.9, 0.6, -1.0),
+        Vector3::new(1.3, 1.1, -1.0),
+    ];
+
+    let electric = MaxwellSampledSource::new(
+        gaussian_vortex_source(),
+        config.clone().with_condition(SurfaceCondition::Tangential),
+    );
+    let magnetic = MaxwellSampledSource::new(
+        gaussian_vortex_source(),
+        config.with_condition(SurfaceCondition::Normal),
+    );
+    for target in surface {
+        let tangential = electric.inverse_curl_at(target, 0.0);
+        let normal = magnetic.inverse_curl_at(target, 0.0);
+        assert!(tangential.xy().norm() < 1.0e-12, "{tangential:?}");
+        assert!(normal.z.abs() < 1.0e-12, "{normal:?}");
+        assert!(normal.xy().norm() > 1.0e-3, "{normal:?}");
+    }
+}
+
+#[test]
+fn masked_and_conducting_cells_are_dropped() {
+    let free = vortex_config(InverseCurlBackend::TreeCode, 16);
+    let masked = vortex_config_with_boundary(
+        InverseCurlBackend::TreeCode,
+        16,
+        0.5,
+        &boundary(&["x^2 + y^2 + z^2 - 1"], "2 - x"),
+    );
+
+    assert!(masked.cell_count() < free.cell_count());
+    assert_eq!(masked.images.len(), masked.cell_count());
+    assert_eq!(free.images.len(), 0);
+}
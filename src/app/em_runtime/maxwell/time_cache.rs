@@ -0,0 +1,144 @@
+//! Per-time cache of prepared Maxwell source slices shared by parallel target evaluations.
+
+use super::{MaxwellSampledSource, PreparedTimeSlice};
+use std::sync::Arc;
+
+const MAXWELL_SOURCE_TIME_CACHE_LIMIT: usize = 64;
+
+pub(super) struct MaxwellSourceTimeCache {
+    entries: Vec<MaxwellSourceCacheEntry>,
+}
+
+struct MaxwellSourceCacheEntry {
+    time_bits: u64,
+    state: MaxwellSourceCacheState,
+}
+
+enum MaxwellSourceCacheState {
+    Sampling,
+    Ready(Arc<PreparedTimeSlice>),
+    Failed,
+}
+
+pub(super) enum MaxwellSourceCacheLookup {
+    Ready(Arc<PreparedTimeSlice>),
+    Sampling,
+    Failed,
+    Missing,
+}
+
+impl MaxwellSourceTimeCache {
+    pub(super) fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+
+    pub(super) fn lookup(&self, time_bits: u64) -> MaxwellSourceCacheLookup {
+        let Some(entry) = self
+            .entries
+            .iter()
+            .find(|entry| entry.time_bits == time_bits)
+        else {
+            return MaxwellSourceCacheLookup::Missing;
+        };
+
+        match &entry.state {
+            MaxwellSourceCacheState::Ready(values) => {
+                MaxwellSourceCacheLookup::Ready(values.clone())
+            }
+            MaxwellSourceCacheState::Sampling => MaxwellSourceCacheLookup::Sampling,
+            MaxwellSourceCacheState::Failed => MaxwellSourceCacheLookup::Failed,
+        }
+    }
+
+    pub(super) fn reserve(&mut self, time_bits: u64) {
+        if self
+            .entries
+            .iter()
+            .any(|entry| entry.time_bits == time_bits)
+        {
+            return;
+        }
+        if self.entries.len() >= MAXWELL_SOURCE_TIME_CACHE_LIMIT {
+            self.evict_one_ready_entry();
+        }
+        self.entries.push(MaxwellSourceCacheEntry {
+            time_bits,
+            state: MaxwellSourceCacheState::Sampling,
+        });
+    }
+
+    fn finish_sampling(&mut self, time_bits: u64, values: Arc<PreparedTimeSlice>) {
+        if let Some(entry) = self
+            .entries
+            .iter_mut()
+            .find(|entry| entry.time_bits == time_bits)
+        {
+            entry.state = MaxwellSourceCacheState::Ready(values);
+            return;
+        }
+
+        if self.entries.len() >= MAXWELL_SOURCE_TIME_CACHE_LIMIT {
+            self.evict_one_ready_entry();
+        }
+        self.entries.push(MaxwellSourceCacheEntry {
+            time_bits,
+            state: MaxwellSourceCacheState::Ready(values),
+        });
+    }
+
+    fn evict_one_ready_entry(&mut self) {
+        if let Some(index) = self
+            .entries
+            .iter()
+            .position(|entry| !matches!(entry.state, MaxwellSourceCacheState::Sampling))
+        {
+            self.entries.remove(index);
+        }
+    }
+
+    fn fail_sampling(&mut self, time_bits: u64) {
+        if let Some(entry) = self
+            .entries
+            .iter_mut()
+            .find(|entry| entry.time_bits == time_bits)
+        {
+            entry.state = MaxwellSourceCacheState::Failed;
+        }
+    }
+}
+
+pub(super) struct MaxwellSamplingGuard<'a> {
+    source: &'a MaxwellSampledSource,
+    time_bits: u64,
+    active: bool,
+}
+
+impl<'a> MaxwellSamplingGuard<'a> {
+    pub(super) fn new(source: &'a MaxwellSampledSource, time_bits: u64) -> Self {
+        Self {
+            source,
+            time_bits,
+            active: true,
+        }
+    }
+
+    pub(super) fn finish(mut self, values: Arc<PreparedTimeSlice>) {
+        let mut cache = self.source.cache.lock().unwrap();
+        cache.finish_sampling(self.time_bits, values);
+        self.active = false;
+        self.source.cache_ready.notify_all();
+    }
+}
+
+impl Drop for MaxwellSamplingGuard<'_> {
+    fn drop(&mut self) {
+        if self.active {
+            if let Ok(mut cache) = self.source.cache.lock() {
+                cache.fail_sampling(self.time_bits);
+                self.source.cache_ready.notify_all();
+            }
+        }
+    }
+}
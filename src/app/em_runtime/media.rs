@@ -0,0 +1,77 @@
+use crate::app::ui::{EmMode, EmUiState};
+use crate::maths::Expr;
+
+/// Relative permittivity, permeability and conductivity of a linear isotropic medium.
+///
+/// Values are relative to the vacuum of the runtime unit system, where `epsilon_0 = 1` and
+/// `mu_0 = 1 / c^2`, so the default `(1, 1, 0)` reproduces the vacuum equations exactly.
+#[derive(Clone)]
+pub(super) struct LinearMedia {
+    pub(super) permittivity: Expr,
+    pub(super) permeability: Expr,
+    pub(super) conductivity: Expr,
+}
+
+impl LinearMedia {
+    pub(super) fn from_state(state: &EmUiState) -> Self {
+        Self {
+            permittivity: state.permittivity.eq.clone(),
+            permeability: state.permeability.eq.clone(),
+            conductivity: state.conductivity.eq.clone(),
+        }
+    }
+
+    /// Returns whether every medium parameter is the vacuum constant.
+    ///
+    /// Vacuum media skip the constitutive factors entirely so existing source expressions stay
+    /// bit-for-bit identical.
+    pub(super) fn is_vacuum(&self) -> bool {
+        constant_value(&self.permittivity) == Some(1.0)
+            && constant_value(&self.permeability) == Some(1.0)
+            && constant_value(&self.conductivity) == Some(0.0)
+    }
+
+    /// Returns the phase speed when the medium is uniform and lossless.
+    ///
+    /// The plane-wave shortcut is only exact for that case; graded or conducting media go
+    /// through the inverse-curl solve instead.
+    pub(super) fn uniform_lossless_light_speed(&self, c: f64) -> Option<f64> {
+        let permittivity = constant_value(&self.permittivity)?;
+        let permeability = constant_value(&self.permeability)?;
+        let conductivity = constant_value(&self.conductivity)?;
+        (conductivity == 0.0 && permittivity > 0.0 && permeability > 0.0)
+            .then(|| c / (permittivity * permeability).sqrt())
+    }
+}
+
+/// Says which of the entered media the source mode of `state` cannot apply, if any.
+///
+/// The E source mode applies graded `eps`, `mu` and `sigma` point by point in Ampere's law, but
+/// conduction only adds a current there: the entered `E` is not damped. The B source mode gets
+/// `E` from Faraday's law, so beyond a uniform lossless medium's plane-wave speed it applies no
+/// media at all. The Media panel shows the note so such media are never dropped silently.
+pub(crate) fn unsupported_media_note(state: &EmUiState) -> Option<&'static str> {
+    let media = LinearMedia::from_state(state);
+    let lossless = constant_value(&media.conductivity) == Some(0.0);
+    match state.mode {
+        EmMode::Electric if !lossless => Some(
+            "Conductivity adds the current sigma * E to Ampere's law for B, but the entered E \
+             is not damped: put the decay into E itself.",
+        ),
+        EmMode::Magnetic if media.uniform_lossless_light_speed(1.0).is_none() => Some(
+            "The B source mode derives E from Faraday's law alone, so graded or conducting \
+             media are not applied and nothing is damped. Only a uniform lossless medium is \
+             supported, through the plane-wave speed.",
+        ),
+        _ => None,
+    }
+}
+
+fn constant_value(expr: &Expr) -> Option<f64> {
+    if expr.find_variables().into_iter().next().is_some() {
+        return None;
+    }
+    expr.evaluate_to_f64()
+        .ok()
+        .filter(|value| value.is_finite())
+}
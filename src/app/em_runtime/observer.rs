@@ -0,0 +1,107 @@
+//! Lorentz boost to an observer frame moving at constant velocity through the lab frame.
+//!
+//! Everything here works on world-space Cartesian vectors: the boost direction is a world
+//! direction, so sampled fields are expanded through their tangent basis before transforming.
+
+use crate::app::ui::{EmMode, EmUiState};
+use nalgebra::Vector3;
+
+/// Speeds at or above this fraction of `c` are rejected as unphysical.
+pub const MAX_OBSERVER_SPEED: f64 = 0.99;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LorentzBoost {
+    beta: Vector3<f64>,
+    gamma: f64,
+    light_speed: f64,
+}
+
+/// A lab-frame event expressed in the observer frame.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BoostedEvent {
+    pub position: Vector3<f64>,
+    pub time: f64,
+}
+
+impl LorentzBoost {
+    /// Returns the boost to a frame moving with velocity `beta * c` relative to the lab.
+    ///
+    /// A zero, non-finite, or too fast `beta` yields `None`, since the lab frame is then either
+    /// the observer frame already or not reachable by a boost.
+    pub fn new(beta: Vector3<f64>, light_speed: f64) -> Option<Self> {
+        let speed = beta.norm();
+        if !speed.is_finite() || speed == 0.0 || speed > MAX_OBSERVER_SPEED {
+            return None;
+        }
+        if !light_speed.is_finite() || light_speed <= 0.0 {
+            return None;
+        }
+
+        Some(Self {
+            beta,
+            gamma: 1.0 / (1.0 - speed * speed).sqrt(),
+            light_speed,
+        })
+    }
+
+    /// Reads the observer velocity from the EM settings.
+    ///
+    /// Phasor mode stays in the lab frame: its amplitude and phase layers are period averages
+    /// at fixed positions, which a boost does not preserve.
+    pub(super) fn from_state(state: &EmUiState) -> Option<Self> {
+        if state.mode == EmMode::Phasor {
+            return None;
+        }
+        Self::new(
+            Vector3::from(state.observer_beta),
+            state.light_speed.max(1.0e-6),
+        )
+    }
+
+    pub fn gamma(&self) -> f64 {
+        self.gamma
+    }
+
+    /// Maps the lab event `(x, t)` to observer coordinates `(x', t')`.
+    pub fn event(&self, position: Vector3<f64>, time: f64) -> BoostedEvent {
+        let c = self.light_speed;
+        let beta_dot_x = self.beta.dot(&position);
+        BoostedEvent {
+            position: position
+                + self.beta * ((self.gamma - 1.0) * beta_dot_x / self.beta.norm_squared())
+                - self.beta * (self.gamma * c * time),
+            time: self.gamma * (time - beta_dot_x / c),
+        }
+    }
+
+    /// Transforms lab `E` and `B` into the observer frame.
+    ///
+    /// Components along `beta` are unchanged; transverse components mix as
+    /// `E' = gamma (E + c beta x B)` and `B' = gamma (B - beta x E / c)`.
+    pub fn fields(
+        &self,
+        electric: Vector3<f64>,
+        magnetic: Vector3<f64>,
+    ) -> (Vector3<f64>, Vector3<f64>) {
+        let c = self.light_speed;
+        let parallel = self.gamma * self.gamma / (self.gamma + 1.0);
+        (
+            (electric + self.beta.cross(&magnetic) * c) * self.gamma
+                - self.beta * (parallel * self.beta.dot(&electric)),
+            (magnetic - self.beta.cross(&electric) / c) * self.gamma
+                - self.beta * (parallel * self.beta.dot(&magnetic)),
+        )
+    }
+
+    /// Transforms the four-potential `(V / c, A)` into the observer frame.
+    pub fn potentials(&self, phi: f64, vector_potential: Vector3<f64>) -> (f64, Vector3<f64>) {
+        let c = self.light_speed;
+        let beta_dot_a = self.beta.dot(&vector_potential);
+        (
+            self.gamma * (phi - c * beta_dot_a),
+            vector_potential
+                + self.beta * ((self.gamma - 1.0) * beta_dot_a / self.beta.norm_squared())
+                - self.beta * (self.gamma * phi / c),
+        )
+    }
+}
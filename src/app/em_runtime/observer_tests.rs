@@ -0,0 +1,105 @@
+use super::observer::LorentzBoost;
+use super::EmRuntime;
+use crate::app::coords_sys::CoordsSys;
+use crate::app::grid::Grid;
+use crate::app::ui::{EmMode, EmUiState};
+use mathhook_core::Parser;
+use nalgebra::{vector, Vector3};
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() < 1.0e-9,
+        "expected {expected}, got {actual}"
+    );
+}
+
+fn assert_vector_close(actual: Vector3<f64>, expected: Vector3<f64>) {
+    assert!(
+        (actual - expected).norm() < 1.0e-9,
+        "expected {expected:?}, got {actual:?}"
+    );
+}
+
+#[test]
+fn boost_rejects_rest_and_superluminal_velocities() {
+    assert!(LorentzBoost::new(Vector3::zeros(), 1.0).is_none());
+    assert!(LorentzBoost::new(vector![0.0, 1.0, 0.0], 1.0).is_none());
+    assert!(LorentzBoost::new(vector![f64::NAN, 0.0, 0.0], 1.0).is_none());
+    assert!(LorentzBoost::new(vector![0.6, 0.0, 0.0], 1.0).is_some());
+}
+
+#[test]
+fn pure_electric_field_acquires_magnetic_field_in_moving_frame() {
+    let boost = LorentzBoost::new(vector![0.0, 0.6, 0.0], 2.0).unwrap();
+    let electric = vector![3.0, 4.0, 0.0];
+
+    let (boosted_electric, boosted_magnetic) = boost.fields(electric, Vector3::zeros());
+
+    assert_close(boost.gamma(), 1.25);
+    assert_vector_close(boosted_electric, vector![3.75, 4.0, 0.0]);
+    assert_vector_close(boosted_magnetic, vector![0.0, 0.0, 1.125]);
+}
+
+#[test]
+fn boosted_fields_preserve_lorentz_invariants() {
+    let c = 1.5;
+    let boost = LorentzBoost::new(vector![0.3, -0.4, 0.5], c).unwrap();
+    let electric = vector![1.0, -2.0, 0.5];
+    let magnetic = vector![0.25, 0.75, -1.0];
+
+    let (boosted_electric, boosted_magnetic) = boost.fields(electric, magnetic);
+
+    assert_close(
+        boosted_electric.dot(&boosted_magnetic),
+        electric.dot(&magnetic),
+    );
+    assert_close(
+        boosted_electric.norm_squared() - c * c * boosted_magnetic.norm_squared(),
+        electric.norm_squared() - c * c * magnetic.norm_squared(),
+    );
+}
+
+#[test]
+fn boosted_events_preserve_the_spacetime_interval() {
+    let c = 2.0;
+    let boost = LorentzBoost::new(vector![0.5, 0.2, -0.1], c).unwrap();
+    let position = vector![1.0, -3.0, 2.0];
+    let time = 0.7;
+
+    let event = boost.event(position, time);
+
+    assert_close(
+        c * c * event.time * event.time - event.position.norm_squared(),
+        c * c * time * time - position.norm_squared(),
+    );
+}
+
+#[test]
+fn boosted_potentials_preserve_the_four_potential_norm() {
+    let c = 1.0;
+    let boost = LorentzBoost::new(vector![0.0, 0.0, 0.8], c).unwrap();
+    let phi = 2.0;
+    let vector_potential = vector![0.5, 1.0, -1.5];
+
+    let (boosted_phi, boosted_vector_potential) = boost.potentials(phi, vector_potential);
+
+    assert_close(
+        boosted_phi * boosted_phi / (c * c) - boosted_vector_potential.norm_squared(),
+        phi * phi / (c * c) - vector_potential.norm_squared(),
+    );
+}
+
+#[test]
+fn runtime_keeps_phasor_mode_in_the_lab_frame() {
+    let parse = |expr: &str| Parser::default().parse(expr).unwrap();
+    let grid = Grid::new(CoordsSys::new(parse("x"), parse("y"), parse("z")));
+    let mut state = EmUiState::default();
+    state.observer_beta = [0.5, 0.0, 0.0];
+
+    let potentials = EmRuntime::from_ui(&state, &grid);
+    state.mode = EmMode::Phasor;
+    let phasor = EmRuntime::from_ui(&state, &grid);
+
+    assert!(potentials.observer_boost().is_some());
+    assert!(phasor.observer_boost().is_none());
+}
@@ -0,0 +1,139 @@
+use super::fields::TimedVectorField;
+use crate::maths::differential::Form;
+use crate::maths::space::Space;
+use crate::maths::{
+    expr_to_fast_complex_expr3d, Expr, ExternalDerivative, FastComplexExpr3d, Point, IMAGINARY_UNIT,
+};
+use mathhook_core::{Simplify, Symbol};
+use nalgebra::{Complex, Vector3};
+use std::ops::Mul;
+use std::sync::Arc;
+
+/// Semi-axes of the ellipse traced by `Re(E e^{i omega t})` over one period.
+///
+/// Both axes are expressed in the same orthonormal frame components as the runtime fields, so
+/// the tip of the electric arrow moves exactly along this ellipse.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PolarizationEllipse {
+    pub semi_major: Vector3<f64>,
+    pub semi_minor: Vector3<f64>,
+}
+
+impl PolarizationEllipse {
+    /// Returns the point of the ellipse at parameter `angle`.
+    pub fn point_at(&self, angle: f64) -> Vector3<f64> {
+        self.semi_major * angle.cos() + self.semi_minor * angle.sin()
+    }
+}
+
+/// Complex amplitude of a time-harmonic vector field.
+#[derive(Clone)]
+pub(super) struct PhasorVectorField {
+    components: [FastComplexExpr3d; 3],
+}
+
+impl PhasorVectorField {
+    pub(super) fn from_exprs(exprs: [Expr; 3]) -> Self {
+        Self {
+            components: exprs.map(expr_to_fast_complex_expr3d),
+        }
+    }
+
+    pub(super) fn at(&self, point: Point) -> [Complex<f64>; 3] {
+        [
+            (self.components[0])(point.x, point.y, point.z),
+            (self.components[1])(point.x, point.y, point.z),
+            (self.components[2])(point.x, point.y, point.z),
+        ]
+    }
+
+    /// Reconstructs the physical field `Re(F e^{i omega t})`.
+    pub(super) fn real_at(&self, point: Point, angular_frequency: f64, time: f64) -> Vector3<f64> {
+        let rotation = Complex::from_polar(1.0, angular_frequency * time);
+        let [x, y, z] = self.at(point).map(|component| (component * rotation).re);
+        Vector3::new(x, y, z)
+    }
+
+    /// Returns the real amplitude `sqrt(|F_x|^2 + |F_y|^2 + |F_z|^2)`.
+    pub(super) fn amplitude_at(&self, point: Point) -> f64 {
+        self.at(point)
+            .iter()
+            .map(|component| component.norm_sqr())
+            .sum::<f64>()
+            .sqrt()
+    }
+
+    /// Returns the phase of the dominant component, in `(-pi, pi]`.
+    ///
+    /// A vector phasor has one phase per component; the largest one is the phase a learner
+    /// reads off the arrows, and it is well defined for linear polarization.
+    pub(super) fn phase_at(&self, point: Point) -> f64 {
+        self.at(point)
+            .into_iter()
+            .max_by(|lhs, rhs| lhs.norm_sqr().total_cmp(&rhs.norm_sqr()))
+            .map_or(f64::NAN, |component| component.arg())
+    }
+
+    /// Decomposes the phasor `a + i b` into the semi-axes of its polarization ellipse.
+    ///
+    /// Rotating the phasor by `e^{i theta}` with `tan(2 theta) = -2 a.b / (|a|^2 - |b|^2)`
+    /// makes its real and imaginary parts orthogonal; those are the ellipse axes.
+    pub(super) fn polarization_ellipse_at(&self, point: Point) -> PolarizationEllipse {
+        let components = self.at(point);
+        let real = Vector3::new(components[0].re, components[1].re, components[2].re);
+        let imaginary = Vector3::new(components[0].im, components[1].im, components[2].im);
+        let theta = 0.5
+            * (-2.0 * real.dot(&imaginary)).atan2(real.norm_squared() - imaginary.norm_squared());
+        let first = real * theta.cos() - imaginary * theta.sin();
+        let second = real * theta.sin() + imaginary * theta.cos();
+
+        if first.norm_squared() >= second.norm_squared() {
+            PolarizationEllipse {
+                semi_major: first,
+                semi_minor: second,
+            }
+        } else {
+            PolarizationEllipse {
+                semi_major: second,
+                semi_minor: first,
+            }
+        }
+    }
+
+    pub(super) fn into_timed_field(self, angular_frequency: f64) -> TimedVectorField {
+        TimedVectorField::from_vector_expr(Arc::new(move |x, y, z, t| {
+            self.real_at(Point { x, y, z }, angular_frequency, t)
+        }))
+    }
+}
+
+/// Builds the magnetic phasor from Faraday's law, `B = (i / omega) curl E`.
+///
+/// The curl goes through `Space` so curvilinear coordinates use the right scale factors.
+pub(super) fn phasor_magnetic_exprs(
+    electric_exprs: &[Expr; 3],
+    angular_frequency: f64,
+    space: &Space,
+) -> [Expr; 3] {
+    let electric_otn = Form::new_otn(electric_exprs.to_vec(), 1);
+    let mut electric_natural = electric_otn.to_dual_base(space);
+    let curl_otn = electric_natural.d().to_otn_base(space).hodge_star_otn_3d();
+    [0, 1, 2].map(|index| i_over_omega(curl_otn.get_expr(index).clone(), angular_frequency))
+}
+
+/// Builds the vector-potential phasor in the temporal gauge `V = 0`, where `A = (i / omega) E`.
+pub(super) fn phasor_vector_potential_exprs(
+    electric_exprs: &[Expr; 3],
+    angular_frequency: f64,
+) -> [Expr; 3] {
+    electric_exprs
+        .clone()
+        .map(|expr| i_over_omega(expr, angular_frequency))
+}
+
+fn i_over_omega(expr: Expr, angular_frequency: f64) -> Expr {
+    Expr::number(1.0 / angular_frequency)
+        .mul(Expr::symbol(Symbol::new(IMAGINARY_UNIT)))
+        .mul(expr)
+        .simplify()
+}
@@ -1,13 +1,63 @@
 use super::fields::{TimedScalarField, TimedVectorField};
+use super::{form_exprs, negate, partial_t};
 use crate::app::coords_sys::CoordSampleGeometry;
 use crate::app::ui::EmGauge;
-use crate::maths::{Expr, Point};
+use crate::maths::differential::Form;
+use crate::maths::space::Space;
+use crate::maths::{Expr, ExternalDerivative, Point};
+use mathhook_core::Simplify;
 use nalgebra::Vector3;
+use std::ops::Add;
 use std::sync::Arc;
 
 const SCALAR_POTENTIAL_LINE_STEPS: usize = 24;
 const VECTOR_POTENTIAL_TIME_EPSILON: f64 = 1.0e-4;
 
+/// Potentials after the gauge transformation `V - d(lambda)/dt`, `A + grad(lambda)`.
+pub(super) struct GaugeTransformedPotentials {
+    pub(super) phi: Expr,
+    pub(super) vector_potential_otn: [Expr; 3],
+    pub(super) vector_potential_natural: Form,
+}
+
+/// Applies the gauge function `lambda` to potentials given in both component bases.
+///
+/// The gradient is the exterior derivative of `lambda`, so its natural components are plain
+/// partial derivatives and `Space` supplies the scale factors for the orthonormal frame.
+pub(super) fn gauge_transform_potentials(
+    phi: Expr,
+    vector_potential_otn: &[Expr; 3],
+    vector_potential_natural: &Form,
+    lambda: &Expr,
+    space: &Space,
+) -> GaugeTransformedPotentials {
+    let mut lambda_form = Form::new(vec![lambda.clone()], 0);
+    let gradient_natural = lambda_form.d();
+    let gradient_otn = form_exprs(&gradient_natural.to_otn_base(space));
+
+    GaugeTransformedPotentials {
+        phi: phi.add(negate(partial_t(lambda.clone()))).simplify(),
+        vector_potential_otn: [0, 1, 2].map(|index| {
+            vector_potential_otn[index]
+                .clone()
+                .add(gradient_otn[index].clone())
+                .simplify()
+        }),
+        vector_potential_natural: Form::new(
+            (0..3)
+                .map(|index| {
+                    vector_potential_natural
+                        .get_expr(index)
+                        .clone()
+                        .add(gradient_natural.get_expr(index).clone())
+                        .simplify()
+                })
+                .collect(),
+            1,
+        ),
+    }
+}
+
 pub(super) fn zero_scalar_potential() -> TimedScalarField {
     TimedScalarField::new(Expr::number(0.0))
 }
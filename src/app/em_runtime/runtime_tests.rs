@@ -1,4 +1,4 @@
-use super::EmRuntime;
+use super::{unsupported_media_note, EmRuntime};
 use crate::app::coords_sys::CoordsSys;
 use crate::app::grid::{Grid, GridConfig};
 use crate::app::ui::{EmLayerVisibility, EmMode, EmUiState};
@@ -64,6 +64,7 @@ fn render_control_update_refreshes_magnetic_scale_without_runtime_rebuild() {
         magnetic: true,
         scalar_potential: false,
         vector_potential: false,
+        ..EmLayerVisibility::default()
     };
     let mut runtime = EmRuntime::from_ui(&state, &identity_grid());
 
@@ -73,6 +74,7 @@ fn render_control_update_refreshes_magnetic_scale_without_runtime_rebuild() {
         magnetic: false,
         scalar_potential: false,
         vector_potential: false,
+        ..EmLayerVisibility::default()
     };
     runtime.update_render_controls(&state);
 
@@ -401,3 +403,297 @@ fn magnetic_source_uses_faraday_time_derivative_for_electric_field() {
 
     assert!(value.norm() > 1.0e-6);
 }
+
+#[test]
+fn vacuum_media_keep_ampere_source_bit_for_bit() {
+    let parse = |expr: &str| Parser::default().parse(expr).unwrap();
+    let mut state = EmUiState::default();
+    state.mode = EmMode::Electric;
+    state.electric_field.x.eq = parse("0");
+    state.electric_field.y.eq = parse("t");
+    state.electric_field.z.eq = parse("0");
+    let point = Point {
+        x: 2.0,
+        y: 3.0,
+        z: 4.0,
+    };
+    let default_value = EmRuntime::from_ui(&state, &identity_grid()).magnetic_at(point, 0.0);
+
+    state.permittivity.eq = parse("1.0");
+    state.permeability.eq = parse("1");
+    state.conductivity.eq = parse("0.0");
+    let explicit_value = EmRuntime::from_ui(&state, &identity_grid()).magnetic_at(point, 0.0);
+
+    assert_eq!(default_value.x.to_bits(), explicit_value.x.to_bits());
+    assert_eq!(default_value.y.to_bits(), explicit_value.y.to_bits());
+    assert_eq!(default_value.z.to_bits(), explicit_value.z.to_bits());
+}
+
+#[test]
+fn uniform_dielectric_scales_ampere_source_by_permittivity_and_permeability() {
+    let parse = |expr: &str| Parser::default().parse(expr).unwrap();
+    let mut state = EmUiState::default();
+    state.mode = EmMode::Electric;
+    state.electric_field.x.eq = parse("0");
+    state.electric_field.y.eq = parse("t");
+    state.electric_field.z.eq = parse("0");
+    let point = Point {
+        x: 2.0,
+        y: 3.0,
+        z: 4.0,
+    };
+    let vacuum_value = EmRuntime::from_ui(&state, &identity_grid())
+        .magnetic_at(point, 0.0)
+        .norm();
+
+    state.permittivity.eq = parse("4");
+    state.permeability.eq = parse("2");
+    let runtime = EmRuntime::from_ui(&state, &identity_grid());
+    let dielectric_value = runtime.magnetic_at(point, 0.0).norm();
+
+    assert!(vacuum_value > 1.0e-6);
+    assert_close_tol(dielectric_value, vacuum_value * 8.0, 1.0e-6);
+}
+
+#[test]
+fn media_the_source_mode_cannot_apply_are_reported() {
+    let parse = |expr: &str| Parser::default().parse(expr).unwrap();
+    let mut state = EmUiState::default();
+    state.mode = EmMode::Magnetic;
+    state.permittivity.eq = parse("4");
+    assert!(unsupported_media_note(&state).is_none());
+
+    state.permittivity.eq = parse("1 + x^2");
+    assert!(unsupported_media_note(&state).is_some());
+
+    state.mode = EmMode::Electric;
+    assert!(unsupported_media_note(&state).is_none());
+
+    state.conductivity.eq = parse("0.5");
+    assert!(unsupported_media_note(&state).is_some());
+}
+
+#[test]
+fn conductivity_adds_ohmic_current_to_ampere_source() {
+    let parse = |expr: &str| Parser::default().parse(expr).unwrap();
+    let mut state = EmUiState::default();
+    state.mode = EmMode::Electric;
+    state.electric_field.x.eq = parse("0");
+    state.electric_field.y.eq = parse("1");
+    state.electric_field.z.eq = parse("0");
+    let point = Point {
+        x: 2.0,
+        y: 3.0,
+        z: 4.0,
+    };
+    let static_vacuum = EmRuntime::from_ui(&state, &identity_grid()).magnetic_at(point, 0.0);
+
+    state.conductivity.eq = parse("0.5");
+    let lossy = EmRuntime::from_ui(&state, &identity_grid()).magnetic_at(point, 0.0);
+
+    assert!(static_vacuum.norm() < 1.0e-9);
+    assert!(
+        lossy.norm() > 1.0e-6,
+        "sigma * E should source B: {lossy:?}"
+    );
+}
+
+#[test]
+fn uniform_dielectric_plane_wave_uses_local_light_speed() {
+    let parse = |expr: &str| Parser::default().parse(expr).unwrap();
+    let mut state = EmUiState::default();
+    state.mode = EmMode::Electric;
+    state.electric_field.x.eq = parse("0");
+    state.electric_field.y.eq = parse("cos(2*z - t)");
+    state.electric_field.z.eq = parse("0");
+    state.permittivity.eq = parse("4");
+
+    let runtime = EmRuntime::from_ui(&state, &identity_grid());
+    let value = runtime.magnetic_at(
+        Point {
+            x: 0.0,
+            y: 0.0,
+            z: 0.0,
+        },
+        0.0,
+    );
+
+    assert_close(value.x, -2.0);
+    assert_near_zero(value.y);
+    assert_near_zero(value.z);
+}
+
+#[test]
+fn phasor_mode_reconstructs_circularly_polarized_electric_field() {
+    let mut state = EmUiState::default();
+    state.mode = EmMode::Phasor;
+
+    let runtime = EmRuntime::from_ui(&state, &identity_grid());
+    let origin = Point {
+        x: 0.0,
+        y: 0.0,
+        z: 0.0,
+    };
+    let initial = runtime.electric_at(origin, 0.0);
+    let quarter_turn = runtime.electric_at(origin, 0.5 * PI);
+
+    assert_close(initial.x, 1.0);
+    assert_near_zero(initial.y);
+    assert_near_zero(quarter_turn.x);
+    assert_close(quarter_turn.y, 1.0);
+    assert_close(
+        runtime.electric_amplitude_at(origin).unwrap(),
+        2.0_f64.sqrt(),
+    );
+    assert_near_zero(runtime.phi_at(origin, 0.3));
+}
+
+#[test]
+fn phasor_mode_magnetic_field_follows_faraday_law() {
+    let parse = |expr: &str| Parser::default().parse(expr).unwrap();
+    let mut state = EmUiState::default();
+    state.mode = EmMode::Phasor;
+    state.angular_frequency = 2.0;
+    state.phasor_electric_field.x.eq = parse("exp(-2*i*z)");
+    state.phasor_electric_field.y.eq = parse("0");
+    state.phasor_electric_field.z.eq = parse("0");
+
+    let runtime = EmRuntime::from_ui(&state, &identity_grid());
+    let point = Point {
+        x: 0.0,
+        y: 0.0,
+        z: 0.4,
+    };
+    let time = 0.3;
+    let magnetic = runtime.magnetic_at(point, time);
+    let vector_potential = runtime.vector_potential_at(point, time);
+
+    assert_near_zero(magnetic.x);
+    assert_close(magnetic.y, (2.0 * time - 2.0 * point.z).cos());
+    assert_near_zero(magnetic.z);
+    assert_close(
+        vector_potential.x,
+        -0.5 * (2.0 * time - 2.0 * point.z).sin(),
+    );
+}
+
+#[test]
+fn phasor_polarization_ellipse_matches_polarization_state() {
+    let parse = |expr: &str| Parser::default().parse(expr).unwrap();
+    let origin = Point {
+        x: 0.0,
+        y: 0.0,
+        z: 0.0,
+    };
+    let mut state = EmUiState::default();
+    state.mode = EmMode::Phasor;
+    let circular = EmRuntime::from_ui(&state, &identity_grid())
+        .polarization_ellipse_at(origin)
+        .unwrap();
+
+    state.phasor_electric_field.x.eq = parse("i");
+    state.phasor_electric_field.y.eq = parse("i");
+    let linear = EmRuntime::from_ui(&state, &identity_grid())
+        .polarization_ellipse_at(origin)
+        .unwrap();
+
+    assert_close(circular.semi_major.norm(), 1.0);
+    assert_close(circular.semi_minor.norm(), 1.0);
+    assert_near_zero(circular.semi_major.dot(&circular.semi_minor));
+    assert_close(linear.semi_major.norm(), 2.0_f64.sqrt());
+    assert_near_zero(linear.semi_minor.norm());
+}
+
+#[test]
+fn phasor_queries_are_unavailable_outside_phasor_mode() {
+    let runtime = EmRuntime::from_ui(&EmUiState::default(), &identity_grid());
+    let origin = Point {
+        x: 0.0,
+        y: 0.0,
+        z: 0.0,
+    };
+
+    assert!(runtime.electric_amplitude_at(origin).is_none());
+    assert!(runtime.polarization_ellipse_at(origin).is_none());
+}
+
+#[test]
+fn gauge_function_shifts_potentials_in_cartesian_geometry() {
+    let parse = |expr: &str| Parser::default().parse(expr).unwrap();
+    let mut state = EmUiState::default();
+    state.mode = EmMode::Potentials;
+    state.phi.eq = parse("x * t");
+    state.gauge_function.eq = parse("x * y * t");
+
+    let runtime = EmRuntime::from_ui(&state, &identity_grid());
+    let point = Point {
+        x: 2.0,
+        y: 3.0,
+        z: 0.5,
+    };
+    let vector_potential = runtime.vector_potential_at(point, 1.5);
+
+    assert!(runtime.gauge_transformed_potentials().is_some());
+    assert_close(runtime.phi_at(point, 1.5), 2.0 * 1.5 - 2.0 * 3.0);
+    assert_close(vector_potential.x, 3.0 * 1.5);
+    assert_close(vector_potential.y, (0.5_f64 - 1.5).sin() + 2.0 * 1.5);
+    assert_close(vector_potential.z, 0.0);
+}
+
+#[test]
+fn gauge_function_leaves_fields_unchanged_in_spherical_geometry() {
+    let parse = |expr: &str| Parser::default().parse(expr).unwrap();
+    let mut state = EmUiState::default();
+    state.mode = EmMode::Potentials;
+    state.phi.eq = parse("x * cos(z)");
+    state.vector_potential.x.eq = parse("0");
+    state.vector_potential.y.eq = parse("x * sin(z) * t");
+    state.vector_potential.z.eq = parse("0");
+    let original = EmRuntime::from_ui(&state, &spherical_grid());
+    state.gauge_function.eq = parse("t * t * x * cos(z) + x * sin(y)");
+    let transformed = EmRuntime::from_ui(&state, &spherical_grid());
+    let point = Point {
+        x: 2.0,
+        y: 0.5,
+        z: 1.0,
+    };
+
+    let electric_delta = transformed.electric_at(point, 1.3) - original.electric_at(point, 1.3);
+    let magnetic_delta = transformed.magnetic_at(point, 1.3) - original.magnetic_at(point, 1.3);
+
+    assert_near_zero(electric_delta.norm());
+    assert_near_zero(magnetic_delta.norm());
+    assert!(original.gauge_transformed_potentials().is_none());
+}
+
+#[test]
+fn closed_form_mode_uses_every_field_as_entered() {
+    let parse = |expr: &str| Parser::default().parse(expr).unwrap();
+    let mut state = EmUiState::default();
+    state.mode = EmMode::ClosedForm;
+    state.phi.eq = parse("0");
+    state.vector_potential.x.eq = parse("cos(t)");
+    state.vector_potential.y.eq = parse("0");
+    state.vector_potential.z.eq = parse("0");
+    // Deliberately unrelated to A so a derived field would be caught.
+    state.electric_field.x.eq = parse("besselj(1, x)");
+    state.electric_field.y.eq = parse("0");
+    state.electric_field.z.eq = parse("z * t");
+    state.magnetic_field.x.eq = parse("0");
+    state.magnetic_field.y.eq = parse("y");
+    state.magnetic_field.z.eq = parse("0");
+
+    let runtime = EmRuntime::from_ui(&state, &identity_grid());
+    let point = Point {
+        x: 2.5,
+        y: 3.0,
+        z: 2.0,
+    };
+    let electric = runtime.electric_at(point, 1.5);
+    let magnetic = runtime.magnetic_at(point, 1.5);
+
+    assert_close(electric.x, 0.497_094_102_464_274_4);
+    assert_close(electric.z, 3.0);
+    assert_close(magnetic.y, 3.0);
+    assert_close(runtime.vector_potential_at(point, 0.0).x, 1.0);
+}
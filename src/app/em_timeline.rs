@@ -0,0 +1,136 @@
+//! Playback arithmetic of the EM timeline: keyframed rates, looping and snapped seeks.
+//!
+//! Maxwell sources prepared for one time are cached under the exact bits of that time, so seek
+//! targets are snapped onto multiples of the timeline step. Scrubbing back to a position that
+//! was already shown then lands on the same `f64` and reuses its prepared slice.
+
+use crate::app::ui::{EmTimelineState, TimeScaleKeyframe};
+
+/// Rate multiplier at `time`, piecewise linear between keyframes sorted by time.
+pub(crate) fn keyframe_scale_at(keyframes: &[TimeScaleKeyframe], time: f64) -> f64 {
+    let mut sorted = keyframes
+        .iter()
+        .filter(|keyframe| keyframe.time.is_finite() && keyframe.scale.is_finite())
+        .copied()
+        .collect::<Vec<_>>();
+    sorted.sort_by(|left, right| left.time.total_cmp(&right.time));
+
+    let (Some(first), Some(last)) = (sorted.first(), sorted.last()) else {
+        return 1.0;
+    };
+    if time <= first.time {
+        return first.scale;
+    }
+    if time >= last.time {
+        return last.scale;
+    }
+    sorted
+        .windows(2)
+        .find(|pair| time <= pair[1].time)
+        .map(|pair| {
+            let span = pair[1].time - pair[0].time;
+            if span <= 0.0 {
+                return pair[1].scale;
+            }
+            let fraction = (time - pair[0].time) / span;
+            pair[0].scale + fraction * (pair[1].scale - pair[0].scale)
+        })
+        .unwrap_or(last.scale)
+}
+
+/// Rounds `time` to the nearest multiple of `step`; non-positive steps leave it unchanged.
+pub(crate) fn snap_to_step(time: f64, step: f64) -> f64 {
+    if !(step > 0.0 && step.is_finite()) {
+        return time;
+    }
+    (time / step).round() * step
+}
+
+/// Folds `time` back into `[loop_start, loop_end)` when looping over a non-empty range.
+pub(crate) fn wrap_into_loop(timeline: &EmTimelineState, time: f64) -> f64 {
+    let span = timeline.loop_end - timeline.loop_start;
+    if !timeline.loop_enabled || !(span > 0.0 && span.is_finite()) {
+        return time;
+    }
+    timeline.loop_start + (time - timeline.loop_start).rem_euclid(span)
+}
+
+/// Time after playing for `dt` seconds from `time` at `base_scale`, in the timeline direction.
+pub(crate) fn advance_time(timeline: &EmTimelineState, base_scale: f64, time: f64, dt: f64) -> f64 {
+    let direction = if timeline.reverse { -1.0 } else { 1.0 };
+    let rate = direction * base_scale * keyframe_scale_at(&timeline.keyframes, time);
+    wrap_into_loop(timeline, time + dt * rate)
+}
+
+/// Time reached by the pending seek request, snapped to the step grid.
+pub(crate) fn seek_target(timeline: &EmTimelineState) -> f64 {
+    let time = snap_to_step(timeline.seek_time, timeline.step);
+    if timeline.loop_enabled && timeline.loop_end > timeline.loop_start {
+        time.clamp(timeline.loop_start, timeline.loop_end)
+    } else {
+        time
+    }
+}
+
+/// Time restored by "Reset time": the loop start while looping, zero otherwise.
+pub(crate) fn reset_time(timeline: &EmTimelineState) -> f64 {
+    if timeline.loop_enabled {
+        timeline.loop_start
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{advance_time, keyframe_scale_at, seek_target, snap_to_step, wrap_into_loop};
+    use crate::app::ui::{EmTimelineState, TimeScaleKeyframe};
+
+    fn keyframe(time: f64, scale: f64) -> TimeScaleKeyframe {
+        TimeScaleKeyframe { time, scale }
+    }
+
+    #[test]
+    fn keyframe_scale_interpolates_and_holds_the_ends() {
+        let keyframes = [keyframe(4.0, 0.5), keyframe(0.0, 1.0), keyframe(2.0, 3.0)];
+
+        assert_eq!(keyframe_scale_at(&[], 7.0), 1.0);
+        assert_eq!(keyframe_scale_at(&keyframes, -1.0), 1.0);
+        assert_eq!(keyframe_scale_at(&keyframes, 1.0), 2.0);
+        assert_eq!(keyframe_scale_at(&keyframes, 3.0), 1.75);
+        assert_eq!(keyframe_scale_at(&keyframes, 9.0), 0.5);
+    }
+
+    #[test]
+    fn looping_wraps_both_playback_directions() {
+        let timeline = EmTimelineState {
+            loop_enabled: true,
+            loop_start: 1.0,
+            loop_end: 3.0,
+            ..EmTimelineState::default()
+        };
+        let reversed = EmTimelineState {
+            reverse: true,
+            ..timeline.clone()
+        };
+
+        assert!((advance_time(&timeline, 1.0, 2.5, 1.0) - 1.5).abs() < 1.0e-12);
+        assert!((advance_time(&reversed, 1.0, 1.25, 0.5) - 2.75).abs() < 1.0e-12);
+        assert_eq!(wrap_into_loop(&EmTimelineState::default(), 50.0), 50.0);
+    }
+
+    #[test]
+    fn scrubbed_times_snap_to_identical_bits() {
+        let mut timeline = EmTimelineState {
+            step: 0.1,
+            ..EmTimelineState::default()
+        };
+        timeline.seek(0.7 + 1.0e-4);
+        let forward = seek_target(&timeline);
+        timeline.seek(0.7 - 2.0e-3);
+        let back = seek_target(&timeline);
+
+        assert_eq!(forward.to_bits(), back.to_bits());
+        assert_eq!(snap_to_step(0.31, 0.0), 0.31);
+    }
+}
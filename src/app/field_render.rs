@@ -3,16 +3,22 @@
 mod em_cache;
 #[cfg(test)]
 mod tests;
+mod time_cache;
 
 pub use em_cache::EmRenderCache;
+pub use time_cache::FieldTimeCache;
 
+use crate::app::em_runtime::PolarizationEllipse;
 use crate::app::field_runtime::RuntimeField;
 use crate::app::tangent_space::TangentSpace;
 use crate::app::ui::legend::sampled_value_color;
 use crate::app::ui::{LegendKind, LegendState};
 use crate::graphics::model::{RenderVField, Sphere};
 use crate::maths::Point;
-use nalgebra::{Vector3, Vector4};
+use nalgebra::{Matrix3, Vector3, Vector4};
+use std::f64::consts::TAU;
+
+const POLARIZATION_GLYPH_SEGMENTS: usize = 16;
 
 #[derive(Clone)]
 pub struct FieldSample {
@@ -26,6 +32,17 @@ impl FieldSample {
     pub fn vector_to_world(&self, vector: Vector3<f64>) -> Vector3<f64> {
         self.basis[0] * vector.x + self.basis[1] * vector.y + self.basis[2] * vector.z
     }
+
+    /// Expresses a world-space vector in basis components, inverting `vector_to_world`.
+    ///
+    /// The basis is not orthogonal for every coordinate system, so this solves the full 3x3
+    /// system; a degenerate basis yields `NaN` components.
+    pub fn vector_from_world(&self, vector: Vector3<f64>) -> Vector3<f64> {
+        Matrix3::from_columns(&self.basis)
+            .lu()
+            .solve(&vector)
+            .unwrap_or_else(|| Vector3::from_element(f64::NAN))
+    }
 }
 
 pub enum FieldRenderCache {
@@ -263,3 +280,41 @@ pub fn build_vector_render_with_color(
 
     render_field
 }
+
+/// Builds one ring of small spheres per sample tracing its polarization ellipse.
+///
+/// The ellipse is centered on the sample and uses the same world scale as the electric arrows,
+/// so an animated arrow tip runs along its ring.
+pub fn build_polarization_render(
+    samples: &[FieldSample],
+    ellipses: &[PolarizationEllipse],
+    tangent_space: &TangentSpace,
+    dot_size: f64,
+    color: Vector4<f64>,
+) -> Vec<Sphere> {
+    let mut render_samples = Vec::with_capacity(samples.len() * POLARIZATION_GLYPH_SEGMENTS);
+
+    for (sample, ellipse) in samples.iter().zip(ellipses.iter()) {
+        if !tangent_space.contains_local_sample(sample.abstract_pos) {
+            continue;
+        }
+        let center = tangent_space.blend_position(sample.world_pos, sample.abstract_pos);
+        if !is_finite_vec3(&center) {
+            continue;
+        }
+
+        for segment in 0..POLARIZATION_GLYPH_SEGMENTS {
+            let angle = TAU * segment as f64 / POLARIZATION_GLYPH_SEGMENTS as f64;
+            let components = ellipse.point_at(angle);
+            let tangent_components = tangent_space.blend_field_components(components, None);
+            let offset =
+                tangent_space.blend_vector(sample.vector_to_world(components), tangent_components);
+            if !is_finite_vec3(&offset) {
+                continue;
+            }
+            render_samples.push(Sphere::from_rgba(center + offset, color, dot_size));
+        }
+    }
+
+    render_samples
+}
@@ -1,6 +1,6 @@
 use super::FieldSample;
 use crate::app::em_profile::{self, EmProfileMetric};
-use crate::app::em_runtime::EmRuntime;
+use crate::app::em_runtime::{EmRuntime, LorentzBoost, PolarizationEllipse};
 use crate::app::ui::EmLayerVisibility;
 use crate::maths::Point;
 use nalgebra::Vector3;
@@ -15,6 +15,19 @@ pub struct EmRenderCache {
     pub electric: Option<CachedVectorLayer>,
     pub magnetic: Option<CachedVectorLayer>,
     pub vector_potential: Option<CachedVectorLayer>,
+    pub amplitude: Option<Vec<f64>>,
+    pub phase: Option<Vec<f64>>,
+    pub polarization: Option<Vec<PolarizationEllipse>>,
+    /// Samples moved to their observer-frame positions, present while a boost is active.
+    pub observer_frame: Option<ObserverFrame>,
+}
+
+/// Where the sampled lab events land in the boosted observer frame.
+pub struct ObserverFrame {
+    /// Copies of the field samples with `world_pos` replaced by the boosted event position.
+    pub samples: Vec<FieldSample>,
+    /// Smallest and largest observer time `t'` over the samples.
+    pub time_range: (f64, f64),
 }
 
 pub struct CachedVectorLayer {
@@ -33,6 +46,9 @@ struct EmSampleLayers {
     electric: Option<CachedVectorSample>,
     magnetic: Option<CachedVectorSample>,
     vector_potential: Option<CachedVectorSample>,
+    amplitude: Option<f64>,
+    phase: Option<f64>,
+    polarization: Option<PolarizationEllipse>,
 }
 
 impl EmRenderCache {
@@ -45,7 +61,7 @@ impl EmRenderCache {
         let layers = runtime.active_layers();
         let profile_start = em_profile::snapshot();
         let vector_layer_count = runtime.active_vector_layer_count();
-        let has_scalar_layer = layers.scalar_potential;
+        let has_scalar_layer = layers.scalar_potential || layers.amplitude || layers.phase;
         if vector_layer_count > 0 {
             prewarm_em_vector_times(runtime, samples, time, normalize_vectors_by_time, &layers);
         }
@@ -85,6 +101,27 @@ impl EmRenderCache {
                         samples.len(),
                     )
                 }),
+                amplitude: layers.amplitude.then(|| {
+                    sample_layers
+                        .iter()
+                        .filter_map(|sample| sample.amplitude)
+                        .collect()
+                }),
+                phase: layers.phase.then(|| {
+                    sample_layers
+                        .iter()
+                        .filter_map(|sample| sample.phase)
+                        .collect()
+                }),
+                polarization: layers.polarization.then(|| {
+                    sample_layers
+                        .iter()
+                        .filter_map(|sample| sample.polarization)
+                        .collect()
+                }),
+                observer_frame: runtime
+                    .observer_boost()
+                    .map(|boost| ObserverFrame::from_samples(boost, samples, time)),
             }
         });
 
@@ -97,6 +134,54 @@ impl EmRenderCache {
         );
         cache
     }
+
+    /// Returns the largest per-sample `|E|` and `|B|` differences against another cache.
+    ///
+    /// Both caches must come from the same sample list. A layer missing from either side
+    /// reports `None`, and non-finite samples are skipped.
+    pub fn max_field_deviation(&self, other: &Self) -> (Option<f64>, Option<f64>) {
+        (
+            max_layer_deviation(self.electric.as_ref(), other.electric.as_ref()),
+            max_layer_deviation(self.magnetic.as_ref(), other.magnetic.as_ref()),
+        )
+    }
+}
+
+impl ObserverFrame {
+    fn from_samples(boost: LorentzBoost, samples: &[FieldSample], time: f64) -> Self {
+        let mut time_range = (f64::INFINITY, f64::NEG_INFINITY);
+        let samples = samples
+            .iter()
+            .map(|sample| {
+                let event = boost.event(sample.world_pos, time);
+                time_range = (time_range.0.min(event.time), time_range.1.max(event.time));
+                FieldSample {
+                    world_pos: event.position,
+                    ..sample.clone()
+                }
+            })
+            .collect();
+
+        Self {
+            samples,
+            time_range,
+        }
+    }
+}
+
+fn max_layer_deviation(
+    lhs: Option<&CachedVectorLayer>,
+    rhs: Option<&CachedVectorLayer>,
+) -> Option<f64> {
+    let (lhs, rhs) = (lhs?, rhs?);
+    Some(
+        lhs.components
+            .iter()
+            .zip(&rhs.components)
+            .map(|(lhs, rhs)| (lhs - rhs).norm())
+            .filter(|deviation| deviation.is_finite())
+            .fold(0.0, f64::max),
+    )
 }
 
 fn prewarm_em_vector_times(
@@ -119,7 +204,14 @@ fn prewarm_em_vector_times(
     if normalize_vectors_by_time {
         times.extend(time_normalization_times(current_time));
     }
-    runtime.prewarm_vector_layer_times(point, &times, layers);
+    // A boost mixes `E` and `B`, so showing either samples both.
+    let mut layers = layers.clone();
+    if runtime.observer_boost().is_some() {
+        let mixed = layers.electric || layers.magnetic;
+        layers.electric = mixed;
+        layers.magnetic = mixed;
+    }
+    runtime.prewarm_vector_layer_times(point, &times, &layers);
 }
 
 impl CachedVectorLayer {
@@ -166,47 +258,127 @@ fn sample_em_layers(
         z: sample.abstract_pos.z,
     };
 
-    let phi = layers.scalar_potential.then(|| runtime.phi_at(point, time));
+    // With an observer boost, each lab event `(x, t)` is sampled as usual and the fields are
+    // transformed in world axes, so a shown `E` also needs `B` (or `A` needs `V`) and the other
+    // way round. Fields neither shown nor mixed in by the boost are not evaluated.
+    let boost = runtime.observer_boost();
+    let needs_electric = layers.electric || (boost.is_some() && layers.magnetic);
+    let needs_magnetic = layers.magnetic || (boost.is_some() && layers.electric);
+    let fields_at = |time: f64| {
+        let electric = needs_electric
+            .then(|| runtime.electric_at(point, time))
+            .unwrap_or_else(Vector3::zeros);
+        let magnetic = needs_magnetic
+            .then(|| runtime.magnetic_at(point, time))
+            .unwrap_or_else(Vector3::zeros);
+        match boost {
+            Some(boost) => {
+                let (electric, magnetic) = boost.fields(
+                    sample.vector_to_world(electric),
+                    sample.vector_to_world(magnetic),
+                );
+                (
+                    sample.vector_from_world(electric),
+                    sample.vector_from_world(magnetic),
+                )
+            }
+            None => (electric, magnetic),
+        }
+    };
+    // Both layers read the same evaluations, at `time` and at every normalization time.
+    let field_times = (layers.electric || layers.magnetic).then(|| {
+        let normalization_times = normalize_vectors_by_time
+            .then(|| time_normalization_times(time).collect::<Vec<_>>())
+            .unwrap_or_default();
+        std::iter::once(time)
+            .chain(normalization_times)
+            .map(|time| (time.to_bits(), fields_at(time)))
+            .collect::<Vec<_>>()
+    });
+    let field_at = |time: f64| {
+        field_times
+            .iter()
+            .flatten()
+            .find(|(bits, _)| *bits == time.to_bits())
+            .map(|(_, fields)| *fields)
+            .unwrap_or_else(|| fields_at(time))
+    };
+    let electric_at = |time: f64| field_at(time).0;
+    let magnetic_at = |time: f64| field_at(time).1;
+    let potentials_at = |time: f64| {
+        let phi = runtime.phi_at(point, time);
+        let vector_potential = runtime.vector_potential_at(point, time);
+        match boost {
+            Some(boost) => {
+                let (phi, vector_potential) =
+                    boost.potentials(phi, sample.vector_to_world(vector_potential));
+                (phi, sample.vector_from_world(vector_potential))
+            }
+            None => (phi, vector_potential),
+        }
+    };
+    let vector_potential_at = |time: f64| match boost {
+        Some(_) => potentials_at(time).1,
+        None => runtime.vector_potential_at(point, time),
+    };
+
+    let phi = layers.scalar_potential.then(|| match boost {
+        Some(_) => potentials_at(time).0,
+        None => runtime.phi_at(point, time),
+    });
     let electric = layers.electric.then(|| {
-        let component = runtime.electric_at(point, time);
+        let component = electric_at(time);
         let scale = normalize_vectors_by_time
-            .then(|| {
-                time_normalization_scale(sample, time, component, |time| {
-                    runtime.electric_at(point, time)
-                })
-            })
+            .then(|| time_normalization_scale(sample, time, component, electric_at))
             .unwrap_or(1.0);
         CachedVectorSample::scaled(sample, component, scale)
     });
     let magnetic = layers.magnetic.then(|| {
-        let component = runtime.magnetic_at(point, time);
+        let component = magnetic_at(time);
         let scale = normalize_vectors_by_time
-            .then(|| {
-                time_normalization_scale(sample, time, component, |time| {
-                    runtime.magnetic_at(point, time)
-                })
-            })
+            .then(|| time_normalization_scale(sample, time, component, magnetic_at))
             .unwrap_or(1.0)
             * runtime.magnetic_render_scale();
         CachedVectorSample::scaled(sample, component, scale)
     });
     let vector_potential = layers.vector_potential.then(|| {
-        let component = runtime.vector_potential_at(point, time);
+        let component = vector_potential_at(time);
         let scale = normalize_vectors_by_time
-            .then(|| {
-                time_normalization_scale(sample, time, component, |time| {
-                    runtime.vector_potential_at(point, time)
-                })
-            })
+            .then(|| time_normalization_scale(sample, time, component, vector_potential_at))
             .unwrap_or(1.0);
         CachedVectorSample::scaled(sample, component, scale)
     });
 
+    // Phasor layers are time independent; outside phasor mode they sample as `NaN` so the
+    // per-sample vectors stay aligned with `samples`.
+    let amplitude = layers
+        .amplitude
+        .then(|| runtime.electric_amplitude_at(point).unwrap_or(f64::NAN));
+    let phase = layers
+        .phase
+        .then(|| runtime.electric_phase_at(point).unwrap_or(f64::NAN));
+    let polarization = layers.polarization.then(|| {
+        let ellipse = runtime
+            .polarization_ellipse_at(point)
+            .unwrap_or(PolarizationEllipse {
+                semi_major: Vector3::from_element(f64::NAN),
+                semi_minor: Vector3::from_element(f64::NAN),
+            });
+        if normalize_vectors_by_time {
+            polarization_normalized(sample, ellipse)
+        } else {
+            ellipse
+        }
+    });
+
     EmSampleLayers {
         phi,
         electric,
         magnetic,
         vector_potential,
+        amplitude,
+        phase,
+        polarization,
     }
 }
 
@@ -243,6 +415,23 @@ pub(super) fn time_normalization_scale(
     })
 }
 
+/// Scales an ellipse so its world-space semi-major axis has unit length, matching the
+/// time-normalized electric arrows whose tips trace it.
+fn polarization_normalized(
+    sample: &FieldSample,
+    ellipse: PolarizationEllipse,
+) -> PolarizationEllipse {
+    let magnitude = sample.vector_to_world(ellipse.semi_major).norm();
+    if magnitude > MIN_NORMALIZATION_AMPLITUDE && magnitude.is_finite() {
+        PolarizationEllipse {
+            semi_major: ellipse.semi_major / magnitude,
+            semi_minor: ellipse.semi_minor / magnitude,
+        }
+    } else {
+        ellipse
+    }
+}
+
 fn time_normalization_times(current_time: f64) -> impl Iterator<Item = f64> {
     (0..EM_TIME_NORMALIZATION_STEPS)
         .map(move |step| normalization_time_at_step(current_time, step))
@@ -1,13 +1,15 @@
 use super::{
     build_scalar_render, build_scalar_render_with_kind, build_vector_render_with_color,
-    em_cache::time_normalization_scale, normalized_or_original, EmRenderCache, FieldSample,
-    VectorNormalization, VectorRenderConfig,
+    em_cache::time_normalization_scale, normalized_or_original, EmRenderCache, FieldRenderCache,
+    FieldSample, FieldTimeCache, VectorNormalization, VectorRenderConfig,
 };
 use crate::app::coords_sys::CoordsSys;
 use crate::app::em_runtime::EmRuntime;
+use crate::app::field_runtime::RuntimeField;
 use crate::app::grid::{Grid, GridConfig};
 use crate::app::tangent_space::TangentSpace;
 use crate::app::ui::{EmLayerVisibility, EmMode, EmUiState, LegendKind};
+use crate::maths::field::ScalarField;
 use mathhook_core::Parser;
 use nalgebra::{vector, Vector3, Vector4};
 use std::f64::consts::{FRAC_PI_2, TAU};
@@ -117,6 +119,7 @@ fn em_time_normalization_keeps_magnetic_render_scale_visible() {
         magnetic: true,
         scalar_potential: false,
         vector_potential: false,
+        ..EmLayerVisibility::default()
     };
     state.magnetic_field.x.eq = parse("sin(t)");
     state.magnetic_field.x.eq_str = "sin(t)".to_string();
@@ -149,6 +152,7 @@ fn em_time_normalization_keeps_spherical_e_y_amplitude_across_radius() {
         magnetic: false,
         scalar_potential: false,
         vector_potential: false,
+        ..EmLayerVisibility::default()
     };
     state.electric_field.x.eq = parse("0");
     state.electric_field.y.eq = parse("cos(x - t)");
@@ -185,3 +189,121 @@ fn em_time_normalization_keeps_spherical_e_y_amplitude_across_radius() {
 
     assert!((electric.world_vectors[0].norm() - electric.world_vectors[1].norm()).abs() < 1.0e-6);
 }
+
+#[test]
+fn em_cache_field_deviation_reports_electric_and_magnetic_differences() {
+    let parse = |expr: &str| Parser::default().parse(expr).unwrap();
+    let grid = Grid::new(CoordsSys::new(parse("x"), parse("y"), parse("z")));
+    let config = GridConfig::new(0.0, 1.0, 2.0, 0.0, 1.0, 2.0, 0.0, 1.0, 2.0);
+    let mut state = EmUiState::default();
+    state.mode = EmMode::Potentials;
+    state.phi.eq = parse("0");
+    let reference = EmRuntime::from_ui_with_config(&state, &grid, config);
+    state.phi.eq = parse("2 * x");
+    let shifted = EmRuntime::from_ui_with_config(&state, &grid, config);
+
+    let (electric, magnetic) =
+        EmRenderCache::from_runtime(&reference, &[origin_sample()], 0.0, false)
+            .max_field_deviation(&EmRenderCache::from_runtime(
+                &shifted,
+                &[origin_sample()],
+                0.0,
+                false,
+            ));
+
+    assert!((electric.expect("electric layer is on by default") - 2.0).abs() < 1.0e-6);
+    assert!(magnetic.expect("magnetic layer is on by default").abs() < 1.0e-6);
+}
+
+#[test]
+fn em_cache_boosted_observer_sees_magnetic_field_of_static_electric_field() {
+    let parse = |expr: &str| Parser::default().parse(expr).unwrap();
+    let grid = Grid::new(CoordsSys::new(parse("x"), parse("y"), parse("z")));
+    let mut state = EmUiState::default();
+    state.mode = EmMode::Potentials;
+    state.phi.eq = parse("x");
+    state.vector_potential.x.eq = parse("0");
+    state.vector_potential.y.eq = parse("0");
+    state.vector_potential.z.eq = parse("0");
+    state.observer_beta = [0.0, 0.6, 0.0];
+    let runtime = EmRuntime::from_ui_with_config(
+        &state,
+        &grid,
+        GridConfig::new(0.0, 1.0, 2.0, 0.0, 1.0, 2.0, 0.0, 1.0, 2.0),
+    );
+    let sample = FieldSample {
+        abstract_pos: vector![0.0, 1.0, 0.0],
+        world_pos: vector![0.0, 1.0, 0.0],
+        ..origin_sample()
+    };
+
+    let cache = EmRenderCache::from_runtime(&runtime, &[sample], 0.0, false);
+    let electric = cache.electric.expect("electric layer is on by default");
+    let magnetic = cache.magnetic.expect("magnetic layer is on by default");
+    let frame = cache
+        .observer_frame
+        .expect("a non-zero beta should boost the cache");
+
+    assert!((electric.world_vectors[0] - vector![-1.25, 0.0, 0.0]).norm() < 1.0e-6);
+    assert!((magnetic.world_vectors[0] - vector![0.0, 0.0, -0.75]).norm() < 1.0e-6);
+    assert!((frame.samples[0].world_pos - vector![0.0, 1.25, 0.0]).norm() < 1.0e-9);
+    assert!((frame.time_range.0 + 0.75).abs() < 1.0e-9);
+}
+
+#[test]
+fn em_cache_boost_still_mixes_in_a_hidden_electric_field() {
+    let parse = |expr: &str| Parser::default().parse(expr).unwrap();
+    let grid = Grid::new(CoordsSys::new(parse("x"), parse("y"), parse("z")));
+    let mut state = EmUiState::default();
+    state.mode = EmMode::Potentials;
+    state.phi.eq = parse("x");
+    state.vector_potential.x.eq = parse("0");
+    state.vector_potential.y.eq = parse("0");
+    state.vector_potential.z.eq = parse("0");
+    state.observer_beta = [0.0, 0.6, 0.0];
+    state.layers = EmLayerVisibility {
+        electric: false,
+        ..EmLayerVisibility::default()
+    };
+    let runtime = EmRuntime::from_ui_with_config(
+        &state,
+        &grid,
+        GridConfig::new(0.0, 1.0, 2.0, 0.0, 1.0, 2.0, 0.0, 1.0, 2.0),
+    );
+
+    let cache = EmRenderCache::from_runtime(&runtime, &[origin_sample()], 0.0, true);
+    let magnetic = cache.magnetic.expect("magnetic layer is shown");
+
+    assert!(cache.electric.is_none());
+    assert!((magnetic.world_vectors[0] - vector![0.0, 0.0, -1.0]).norm() < 1.0e-6);
+}
+
+#[test]
+fn field_time_cache_reads_each_time_once_and_follows_the_field_clock() {
+    let parse = |expr: &str| Parser::default().parse(expr).unwrap();
+    let mut field = RuntimeField::Scalar(ScalarField::new(parse("x + t")));
+    let samples = [FieldSample {
+        abstract_pos: vector![1.0, 0.0, 0.0],
+        ..origin_sample()
+    }];
+    let mut cache = FieldTimeCache::default();
+    let value = |cache: &FieldTimeCache| match cache.current() {
+        Some(FieldRenderCache::Scalar(values)) => values[0],
+        _ => panic!("a scalar field caches scalar values"),
+    };
+
+    for time in [0.0, 0.5, 0.0] {
+        field.set_time(time);
+        cache.select(&field, &samples, time);
+        assert_eq!(value(&cache), 1.0 + time);
+    }
+    assert_eq!(cache.len(), 2);
+
+    // A revisited time is served from the cache, even by a field that has since changed.
+    let other = RuntimeField::Scalar(ScalarField::new(parse("0")));
+    cache.select(&other, &samples, 0.5);
+    assert_eq!(value(&cache), 1.5);
+
+    cache.clear();
+    assert!(cache.current().is_none());
+}
@@ -0,0 +1,61 @@
+//! Field-tab samples per scene time, reused while the field and the samples stay the same.
+
+use super::{FieldRenderCache, FieldSample};
+use crate::app::field_runtime::RuntimeField;
+
+/// Snapped times whose samples are kept; the oldest visit is dropped beyond it.
+const FIELD_TIME_CACHE_LIMIT: usize = 64;
+
+struct FieldTimeCacheEntry {
+    time_bits: u64,
+    cache: FieldRenderCache,
+}
+
+/// Field values at the samples for every snapped time visited since the field or the samples
+/// last changed, so a looping or rewound clock does not evaluate them again.
+#[derive(Default)]
+pub struct FieldTimeCache {
+    entries: Vec<FieldTimeCacheEntry>,
+    current: Option<usize>,
+}
+
+impl FieldTimeCache {
+    /// Forgets every visited time; the field or the samples changed.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.current = None;
+    }
+
+    /// Makes the values at `time` current, evaluating `field` only on the first visit.
+    ///
+    /// The field is expected to be set to `time` already; the time only keys the entry.
+    pub fn select(&mut self, field: &RuntimeField, samples: &[FieldSample], time: f64) {
+        let time_bits = time.to_bits();
+        if let Some(index) = self
+            .entries
+            .iter()
+            .position(|entry| entry.time_bits == time_bits)
+        {
+            self.current = Some(index);
+            return;
+        }
+        if self.entries.len() >= FIELD_TIME_CACHE_LIMIT {
+            self.entries.remove(0);
+        }
+        self.entries.push(FieldTimeCacheEntry {
+            time_bits,
+            cache: FieldRenderCache::from_field(field, samples),
+        });
+        self.current = Some(self.entries.len() - 1);
+    }
+
+    /// Returns the values of the last selected time.
+    pub fn current(&self) -> Option<&FieldRenderCache> {
+        self.current.map(|index| &self.entries[index].cache)
+    }
+
+    #[cfg(test)]
+    pub(super) fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
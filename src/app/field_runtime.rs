@@ -1,7 +1,7 @@
 //! Runtime field construction for direct scalar/vector input and derived `d(field)` renders.
 
 use crate::app::grid::Grid;
-use crate::app::ui::{GridUiState, SpacialEqs};
+use crate::app::ui::{FieldKind, GridUiState, PulledBackField, SpacialEqs, VectorInput};
 use crate::maths::differential::Form;
 use crate::maths::field::{ScalarField, VectorField};
 
@@ -15,32 +15,46 @@ impl RuntimeField {
     /// Builds the active runtime field from the committed UI state and current grid space.
     ///
     /// Scalar input normally stays scalar, but `render_d` turns it into the gradient field.
-    /// Vector input is interpreted as orthonormal-tangent components, and `render_d` renders the
-    /// associated curl field after conversion through the current coordinate space.
+    /// Vector input is interpreted as orthonormal-tangent components, or pulled back from world
+    /// Cartesian ones, and `render_d` renders the associated curl field after conversion through
+    /// the current coordinate space.
     pub fn from_ui(state: &GridUiState, grid: &Grid) -> Self {
         let space = grid.get_coords().get_space();
 
         match (state.field_kind, state.render_d) {
-            (crate::app::ui::FieldKind::Scalar, false) => {
+            (FieldKind::Scalar, false) => {
                 RuntimeField::Scalar(ScalarField::new(state.scalar_field.eq.clone()))
             }
-            (crate::app::ui::FieldKind::Scalar, true) => RuntimeField::Vector(
-                VectorField::gradient_from_scalar(state.scalar_field.eq.clone(), space),
-            ),
-            (crate::app::ui::FieldKind::Vector, false) => {
-                RuntimeField::Vector(build_vector_field(&state.field, grid))
-            }
-            (crate::app::ui::FieldKind::Vector, true) => {
-                let field_eqs = vec![
-                    state.field.x.eq.clone(),
-                    state.field.y.eq.clone(),
-                    state.field.z.eq.clone(),
-                ];
-                RuntimeField::Vector(VectorField::curl_from_otn(
-                    Form::new_otn(field_eqs, 1),
+            (FieldKind::Scalar, true) => RuntimeField::Vector(VectorField::gradient_from_scalar(
+                state.scalar_field.eq.clone(),
+                space,
+            )),
+            (FieldKind::Vector, false) => RuntimeField::Vector(build_vector_field(state, grid)),
+            (FieldKind::Vector, true) => match state.vector_input {
+                VectorInput::Orthonormal => {
+                    let field_eqs = vec![
+                        state.field.x.eq.clone(),
+                        state.field.y.eq.clone(),
+                        state.field.z.eq.clone(),
+                    ];
+                    RuntimeField::Vector(VectorField::curl_from_otn(
+                        Form::new_otn(field_eqs, 1),
+                        space,
+                    ))
+                }
+                VectorInput::WorldCartesian => RuntimeField::Vector(VectorField::curl_from_dual(
+                    pull_back(&state.field, grid),
                     space,
-                ))
-            }
+                )),
+            },
+        }
+    }
+
+    /// Sets the scene time the field is evaluated at; static fields ignore it.
+    pub fn set_time(&mut self, time: f64) {
+        match self {
+            RuntimeField::Scalar(field) => field.set_time(time),
+            RuntimeField::Vector(field) => field.set_time(time),
         }
     }
 
@@ -60,9 +74,39 @@ impl RuntimeField {
 
 /// Builds the runtime vector field from UI equations in the current active coordinates.
 ///
-/// UI vector components are treated as orthonormal-tangent components, matching the basis shown
-/// by vector arrows in the renderer.
-fn build_vector_field(field: &SpacialEqs, grid: &Grid) -> VectorField {
-    let field_eqs = vec![field.x.eq.clone(), field.y.eq.clone(), field.z.eq.clone()];
-    VectorField::from_otn(Form::new_otn(field_eqs, 1), grid.get_coords().get_space())
+/// Orthonormal input is treated as orthonormal-tangent components, matching the basis shown by
+/// vector arrows in the renderer; world Cartesian input becomes natural components.
+fn build_vector_field(state: &GridUiState, grid: &Grid) -> VectorField {
+    let space = grid.get_coords().get_space();
+    match state.vector_input {
+        VectorInput::Orthonormal => {
+            let field = &state.field;
+            let field_eqs = vec![field.x.eq.clone(), field.y.eq.clone(), field.z.eq.clone()];
+            VectorField::from_otn(Form::new_otn(field_eqs, 1), space)
+        }
+        VectorInput::WorldCartesian => VectorField::new(pull_back(&state.field, grid), space),
+    }
+}
+
+/// Natural 1-form of world Cartesian components over `X, Y, Z` in the grid's coordinates.
+fn pull_back(field: &SpacialEqs, grid: &Grid) -> Form {
+    grid.get_coords().pull_back_cartesian(
+        [&field.x.eq, &field.y.eq, &field.z.eq],
+        VectorInput::WorldCartesian.variables(),
+    )
+}
+
+/// Returns the curvilinear expressions of a world Cartesian vector input, shown in the Field
+/// tab; `None` for any other input.
+pub fn pulled_back_field(state: &GridUiState, grid: &Grid) -> Option<PulledBackField> {
+    if state.field_kind != FieldKind::Vector || state.vector_input != VectorInput::WorldCartesian {
+        return None;
+    }
+    let natural = pull_back(&state.field, grid);
+    let orthonormal = natural.to_otn_base(grid.get_coords().get_space());
+    let strings = |form: &Form| [0, 1, 2].map(|index| form.get_expr(index).to_string());
+    Some(PulledBackField {
+        natural: strings(&natural),
+        orthonormal: strings(&orthonormal),
+    })
 }
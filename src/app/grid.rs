@@ -5,8 +5,13 @@ use crate::toolbox::logging::LOGGER;
 use crate::toolbox::opengl::vao::VAO;
 use crate::Vertex;
 
+mod config;
+mod spacing;
+
+pub use config::{GridConfig, MAX_AXIS_LINES, MAX_GRID_SEGMENTS};
+pub use spacing::{CustomLines, LineSpacing, MAX_CUSTOM_LINES};
+
 use nalgebra::{Matrix4, Rotation3, Translation3, Unit, Vector3};
-use ndarray::Array;
 use rustc_hash::{FxHashMap, FxHashSet};
 use typed_floats::NonNaN;
 
@@ -123,7 +128,7 @@ impl Edge {
 /// Convert curvature to the number of vertices in the edge.
 /// Parameters are tuned.
 #[inline]
-fn curvature_to_vertices(cu: f64) -> usize {
+pub(crate) fn curvature_to_vertices(cu: f64) -> usize {
     (cu * 3.0 + 2.0 * (1.0 + cu.abs()).ln() + 2.0).round() as usize
 }
 
@@ -135,6 +140,10 @@ pub enum SegmentDir {
     Z,
 }
 
+/// Resolution of segment-key coordinates. Starts and lengths are snapped to this step so the
+/// same line reached through different float arithmetic keeps one cache entry.
+const KEY_QUANTUM: f64 = 1.0 / (1u64 << 32) as f64;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct SegmentKey {
     dir: SegmentDir,
@@ -144,72 +153,22 @@ struct SegmentKey {
     len: NonNaN<f64>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub struct GridConfig {
-    u_min: f64,
-    u_max: f64,
-    v_min: f64,
-    v_max: f64,
-    w_min: f64,
-    w_max: f64,
-    nb_u: f64,
-    nb_v: f64,
-    nb_w: f64,
-}
-
-impl GridConfig {
-    /// Creates an empty value for the surrounding grid subsystem.
-    ///
-    /// Callers are expected to populate the returned value or derive its cached render data
-    /// before rendering it.
-    pub fn new(
-        u_min: f64,
-        u_max: f64,
-        nb_u: f64,
-        v_min: f64,
-        v_max: f64,
-        nb_v: f64,
-        w_min: f64,
-        w_max: f64,
-        nb_w: f64,
-    ) -> Self {
-        Self {
-            u_min,
-            u_max,
-            v_min,
-            v_max,
-            w_min,
-            w_max,
-            nb_u,
-            nb_v,
-            nb_w,
-        }
-    }
-
-    /// Returns the abstract-coordinate bounds used to build the grid.
-    pub fn bounds(&self) -> [(f64, f64); 3] {
-        [
-            (self.u_min, self.u_max),
-            (self.v_min, self.v_max),
-            (self.w_min, self.w_max),
-        ]
-    }
-
-    /// Returns the rounded sampling counts configured for each abstract axis.
-    pub fn sample_counts(&self) -> [usize; 3] {
-        [
-            self.nb_u.round().max(2.0) as usize,
-            self.nb_v.round().max(2.0) as usize,
-            self.nb_w.round().max(2.0) as usize,
-        ]
+impl SegmentKey {
+    /// Keys the segment starting at `start` and running `len` along `dir`.
+    fn new(dir: SegmentDir, start: [f64; 3], len: f64) -> Option<Self> {
+        Some(Self {
+            dir,
+            u: key_coordinate(start[0])?,
+            v: key_coordinate(start[1])?,
+            w: key_coordinate(start[2])?,
+            len: key_coordinate(len)?,
+        })
     }
 }
 
-impl Default for GridConfig {
-    /// Builds the default `GridConfig`.
-    fn default() -> Self {
-        Self::new(0.0, 7.0, 2.0, 0.0, 7.0, 2.0, 0.0, 7.0, 2.0)
-    }
+/// Snaps one key coordinate onto the key lattice, folding `-0.0` into `0.0`.
+fn key_coordinate(value: f64) -> Option<NonNaN<f64>> {
+    NonNaN::<f64>::new((value / KEY_QUANTUM).round() * KEY_QUANTUM + 0.0).ok()
 }
 
 pub struct Grid {
@@ -233,77 +192,28 @@ impl Grid {
 
     /// Builds the set of segment keys implied by a grid configuration.
     ///
-    /// Each key captures one axis-aligned abstract segment together with its start coordinates
-    /// and effective length.
+    /// Each line along one axis sits at a line position of the two other axes and is cut at the
+    /// segment breaks of its own axis. Each key captures one such segment together with its
+    /// start coordinates and effective length.
     #[inline]
     fn build_keys_for_indices(indices: &GridConfig) -> FxHashSet<SegmentKey> {
         let mut keys = FxHashSet::default();
-
-        let total_u = Array::<f64, _>::range(indices.u_min, indices.u_max, 1.);
-        let total_v = Array::<f64, _>::range(indices.v_min, indices.v_max, 1.);
-        let total_w = Array::<f64, _>::range(indices.w_min, indices.w_max, 1.);
-
-        let us = Array::<f64, _>::linspace(indices.u_min, indices.u_max, indices.nb_u as usize);
-        let vs = Array::<f64, _>::linspace(indices.v_min, indices.v_max, indices.nb_v as usize);
-        let ws = Array::<f64, _>::linspace(indices.w_min, indices.w_max, indices.nb_w as usize);
-
-        for ui in total_u.iter() {
-            let u = NonNaN::<f64>::new(*ui).unwrap();
-            for vj in vs.iter() {
-                let v = NonNaN::<f64>::new(*vj).unwrap();
-                for wk in ws.iter() {
-                    let w = NonNaN::<f64>::new(*wk).unwrap();
-                    let mut len = NonNaN::<f64>::new(1.0).unwrap();
-                    if ui == &indices.u_max.floor() {
-                        len = NonNaN::<f64>::new(indices.u_max - ui).unwrap();
-                    }
-                    keys.insert(SegmentKey {
-                        dir: SegmentDir::X,
-                        u,
-                        v,
-                        w,
-                        len,
-                    });
-                }
-            }
-        }
-        for vi in total_v.iter() {
-            let v = NonNaN::<f64>::new(*vi).unwrap();
-            for wk in ws.iter() {
-                let w = NonNaN::<f64>::new(*wk).unwrap();
-                for uk in us.iter() {
-                    let u = NonNaN::<f64>::new(*uk).unwrap();
-                    let mut len = NonNaN::<f64>::new(1.0).unwrap();
-                    if vi == &indices.v_max.floor() {
-                        len = NonNaN::<f64>::new(indices.v_max - vi).unwrap();
-                    }
-                    keys.insert(SegmentKey {
-                        dir: SegmentDir::Y,
-                        u,
-                        v,
-                        w,
-                        len,
-                    });
-                }
-            }
-        }
-        for wi in total_w.iter() {
-            let w = NonNaN::<f64>::new(*wi).unwrap();
-            for ui in us.iter() {
-                let u = NonNaN::<f64>::new(*ui).unwrap();
-                for vj in vs.iter() {
-                    let v = NonNaN::<f64>::new(*vj).unwrap();
-                    let mut len = NonNaN::<f64>::new(1.0).unwrap();
-                    if wi == &indices.w_max.floor() {
-                        len = NonNaN::<f64>::new(indices.w_max - wi).unwrap();
+        let lines = [0, 1, 2].map(|axis| indices.line_positions(axis));
+        let dirs = [SegmentDir::X, SegmentDir::Y, SegmentDir::Z];
+
+        for (axis, dir) in dirs.into_iter().enumerate() {
+            let (first, second) = ((axis + 1) % 3, (axis + 2) % 3);
+            for pair in indices.segment_breaks(axis).windows(2) {
+                for &a in &lines[first] {
+                    for &b in &lines[second] {
+                        let mut start = [0.0; 3];
+                        start[axis] = pair[0];
+                        start[first] = a;
+                        start[second] = b;
+                        if let Some(key) = SegmentKey::new(dir, start, pair[1] - pair[0]) {
+                            keys.insert(key);
+                        }
                     }
-                    keys.insert(SegmentKey {
-                        dir: SegmentDir::Z,
-                        u,
-                        v,
-                        w,
-                        len,
-                    });
                 }
             }
         }
@@ -471,3 +381,37 @@ impl Grid {
         &self.render_data
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Grid, GridConfig, LineSpacing, SegmentDir, SegmentKey};
+    use std::f64::consts::PI;
+
+    #[test]
+    fn segment_keys_ignore_float_noise() {
+        let direct = SegmentKey::new(SegmentDir::X, [PI / 2.0, -0.0, 0.1 + 0.2], 0.25);
+        let derived = SegmentKey::new(SegmentDir::X, [PI - PI / 2.0, 0.0, 0.3], 1.0 / 4.0);
+
+        assert_eq!(direct, derived);
+        assert!(SegmentKey::new(SegmentDir::Y, [f64::NAN, 0.0, 0.0], 1.0).is_none());
+    }
+
+    #[test]
+    fn keys_cover_every_counted_segment() {
+        let config = GridConfig::new(0.5, 2.25, 3.0, 0.0, PI / 2.0, 2.0, -1.0, 1.0, 5.0)
+            .with_line_spacing([
+                LineSpacing::Chebyshev,
+                LineSpacing::Step(0.2),
+                LineSpacing::Logarithmic { ratio: 4.0 },
+            ])
+            .with_segment_lengths([0.3, 1.0, 1.0]);
+
+        let keys = Grid::build_keys_for_indices(&config);
+
+        assert_eq!(keys.len(), config.segment_count());
+        assert!(keys
+            .iter()
+            .filter(|key| key.dir == SegmentDir::X)
+            .all(|key| key.len.get() <= 0.3 + 1.0e-9));
+    }
+}
@@ -0,0 +1,201 @@
+//! Abstract extent of the grid: bounds, line placement and segment subdivision per axis.
+
+use super::LineSpacing;
+
+/// Upper bound on grid lines across one axis, whatever its spacing asks for.
+pub const MAX_AXIS_LINES: usize = 256;
+/// Upper bound on the straight segments one grid is cut into.
+pub const MAX_GRID_SEGMENTS: usize = 200_000;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GridConfig {
+    u_min: f64,
+    u_max: f64,
+    v_min: f64,
+    v_max: f64,
+    w_min: f64,
+    w_max: f64,
+    nb_u: f64,
+    nb_v: f64,
+    nb_w: f64,
+    /// Distribution of the lines crossing each axis; most modes place `nb_*` lines.
+    line_spacing: [LineSpacing; 3],
+    /// Longest straight segment per axis before a line is subdivided further.
+    segment_lengths: [f64; 3],
+}
+
+impl GridConfig {
+    /// Creates an empty value for the surrounding grid subsystem.
+    ///
+    /// Callers are expected to populate the returned value or derive its cached render data
+    /// before rendering it.
+    pub fn new(
+        u_min: f64,
+        u_max: f64,
+        nb_u: f64,
+        v_min: f64,
+        v_max: f64,
+        nb_v: f64,
+        w_min: f64,
+        w_max: f64,
+        nb_w: f64,
+    ) -> Self {
+        Self {
+            u_min,
+            u_max,
+            v_min,
+            v_max,
+            w_min,
+            w_max,
+            nb_u,
+            nb_v,
+            nb_w,
+            line_spacing: [LineSpacing::Uniform; 3],
+            segment_lengths: [1.0; 3],
+        }
+    }
+
+    /// Replaces the line distribution of every axis.
+    pub fn with_line_spacing(mut self, line_spacing: [LineSpacing; 3]) -> Self {
+        self.line_spacing = line_spacing;
+        self
+    }
+
+    /// Caps the length of the straight segments along each axis.
+    pub fn with_segment_lengths(mut self, segment_lengths: [f64; 3]) -> Self {
+        self.segment_lengths = segment_lengths;
+        self
+    }
+
+    /// Returns the abstract-coordinate bounds used to build the grid.
+    pub fn bounds(&self) -> [(f64, f64); 3] {
+        [
+            (self.u_min, self.u_max),
+            (self.v_min, self.v_max),
+            (self.w_min, self.w_max),
+        ]
+    }
+
+    /// Returns the sampling counts for each abstract axis: its number of grid lines, at least
+    /// two.
+    pub fn sample_counts(&self) -> [usize; 3] {
+        [0, 1, 2].map(|axis| self.line_count(axis).clamp(2, MAX_AXIS_LINES))
+    }
+
+    /// Number of lines crossing `axis`, before the `MAX_AXIS_LINES` cap.
+    pub fn line_count(&self, axis: usize) -> usize {
+        let requested = [self.nb_u, self.nb_v, self.nb_w][axis].round().max(0.0) as usize;
+        self.line_spacing[axis].count(self.bounds()[axis], requested)
+    }
+
+    /// Positions of the lines crossing `axis`, following its `LineSpacing`.
+    pub fn line_positions(&self, axis: usize) -> Vec<f64> {
+        let count = self.line_count(axis).min(MAX_AXIS_LINES);
+        self.line_spacing[axis].positions(self.bounds()[axis], count)
+    }
+
+    /// Cuts along `axis`: its line positions, with each gap split into equal pieces no longer
+    /// than the segment length. Fewer than two lines still span the whole bounds.
+    pub(super) fn segment_breaks(&self, axis: usize) -> Vec<f64> {
+        let (min, max) = self.bounds()[axis];
+        let mut lines = self.line_positions(axis);
+        if lines.len() < 2 {
+            lines = vec![min, max];
+        }
+        let mut breaks = vec![lines[0]];
+        for pair in lines.windows(2) {
+            let pieces = self.piece_count(axis, pair[1] - pair[0]);
+            breaks.extend(
+                (1..pieces)
+                    .map(|piece| pair[0] + (pair[1] - pair[0]) * (piece as f64 / pieces as f64)),
+            );
+            breaks.push(pair[1]);
+        }
+        breaks
+    }
+
+    /// Number of segments `Grid::update_config` keys, computed without building them.
+    pub fn segment_count(&self) -> usize {
+        let lines = [0, 1, 2].map(|axis| self.line_count(axis).min(MAX_AXIS_LINES));
+        (0..3)
+            .map(|axis| {
+                let (min, max) = self.bounds()[axis];
+                let positions = self.line_positions(axis);
+                let pieces = if positions.len() < 2 {
+                    self.piece_count(axis, max - min)
+                } else {
+                    positions
+                        .windows(2)
+                        .map(|pair| self.piece_count(axis, pair[1] - pair[0]))
+                        .sum()
+                };
+                pieces
+                    .saturating_mul(lines[(axis + 1) % 3])
+                    .saturating_mul(lines[(axis + 2) % 3])
+            })
+            .fold(0, usize::saturating_add)
+    }
+
+    fn piece_count(&self, axis: usize, gap: f64) -> usize {
+        let pieces = (gap.abs() / self.segment_lengths[axis]).ceil();
+        if pieces.is_finite() {
+            pieces.clamp(1.0, MAX_GRID_SEGMENTS as f64) as usize
+        } else {
+            1
+        }
+    }
+}
+
+impl Default for GridConfig {
+    /// Builds the default `GridConfig`.
+    fn default() -> Self {
+        Self::new(0.0, 7.0, 2.0, 0.0, 7.0, 2.0, 0.0, 7.0, 2.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GridConfig, LineSpacing};
+    use std::f64::consts::PI;
+
+    fn config() -> GridConfig {
+        GridConfig::new(0.5, 2.25, 3.0, 0.0, PI / 2.0, 2.0, -1.0, 1.0, 5.0)
+    }
+
+    #[test]
+    fn counted_lines_span_real_bounds() {
+        let config = config();
+
+        assert_eq!(config.line_positions(0), vec![0.5, 1.375, 2.25]);
+        assert_eq!(config.line_positions(1), vec![0.0, PI / 2.0]);
+        assert_eq!(config.sample_counts(), [3, 2, 5]);
+    }
+
+    #[test]
+    fn spaced_lines_keep_the_upper_bound() {
+        let config = config().with_line_spacing([
+            LineSpacing::Step(0.5),
+            LineSpacing::Uniform,
+            LineSpacing::Step(0.1),
+        ]);
+
+        assert_eq!(config.line_positions(0), vec![0.5, 1.0, 1.5, 2.0, 2.25]);
+        let tenths = config.line_positions(2);
+        assert_eq!(tenths.len(), 21);
+        assert!((tenths[19] - 0.9).abs() < 1.0e-12);
+        assert_eq!(tenths[20], 1.0);
+    }
+
+    #[test]
+    fn segments_break_at_lines_and_respect_the_length_cap() {
+        let config = config().with_segment_lengths([0.5, 10.0, 10.0]);
+
+        let breaks = config.segment_breaks(0);
+
+        assert_eq!(breaks.len(), 5);
+        assert_eq!(breaks[2], 1.375);
+        assert!(breaks.windows(2).all(|pair| pair[1] - pair[0] <= 0.5));
+        assert_eq!(config.segment_breaks(1), vec![0.0, PI / 2.0]);
+        assert_eq!(config.segment_count(), 4 * 2 * 5 + 5 * 3 + 4 * 3 * 2);
+    }
+}
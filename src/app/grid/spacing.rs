@@ -0,0 +1,156 @@
+//! Placement of the grid lines crossing one abstract axis.
+
+use std::f64::consts::PI;
+
+/// Most positions a custom line list may hold. The list is stored inline so `GridConfig` stays
+/// `Copy`.
+pub const MAX_CUSTOM_LINES: usize = 64;
+/// Slack, in spacings, that keeps a span which is a whole number of spacings from gaining a
+/// sliver gap to float error.
+const SPACING_TOLERANCE: f64 = 1.0e-9;
+/// Ratios this close to one are treated as uniform, where the geometric formula is `0 / 0`.
+const UNIFORM_RATIO_TOLERANCE: f64 = 1.0e-9;
+
+/// How the lines crossing one axis are distributed between its bounds.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum LineSpacing {
+    /// The requested number of lines, evenly spaced.
+    #[default]
+    Uniform,
+    /// Lines every `step` from the lower bound; the upper bound is always kept, so the last gap
+    /// may be shorter.
+    Step(f64),
+    /// The requested number of lines with geometrically growing gaps, `ratio` being the last gap
+    /// over the first. Ratios above one crowd lines toward the lower bound, e.g. a radial origin.
+    Logarithmic { ratio: f64 },
+    /// The requested number of Chebyshev-Lobatto nodes, crowding toward both bounds.
+    Chebyshev,
+    /// Explicit positions, in increasing order.
+    Custom(CustomLines),
+}
+
+impl LineSpacing {
+    /// Number of lines for bounds `(min, max)` when `requested` lines were asked for.
+    pub(super) fn count(&self, (min, max): (f64, f64), requested: usize) -> usize {
+        match self {
+            Self::Step(step) => {
+                let steps = ((max - min).abs() / step - SPACING_TOLERANCE)
+                    .ceil()
+                    .max(0.0);
+                if steps.is_finite() {
+                    steps.min((usize::MAX / 2) as f64) as usize + 1
+                } else {
+                    1
+                }
+            }
+            Self::Custom(lines) => lines.as_slice().len(),
+            Self::Uniform | Self::Logarithmic { .. } | Self::Chebyshev => requested,
+        }
+    }
+
+    /// Positions of `count` lines between `min` and `max`, both bounds included. Custom lists
+    /// are returned as given.
+    pub(super) fn positions(&self, (min, max): (f64, f64), count: usize) -> Vec<f64> {
+        if let Self::Custom(lines) = self {
+            return lines.as_slice().iter().copied().take(count).collect();
+        }
+        match count {
+            0 => return Vec::new(),
+            1 => return vec![min],
+            _ => {}
+        }
+        let last = (count - 1) as f64;
+        let fraction = |index: usize| -> f64 {
+            let k = index as f64;
+            match *self {
+                Self::Logarithmic { ratio } if (ratio - 1.0).abs() > UNIFORM_RATIO_TOLERANCE => {
+                    let growth = ratio.powf(1.0 / (last - 1.0).max(1.0));
+                    (growth.powf(k) - 1.0) / (growth.powf(last) - 1.0)
+                }
+                Self::Chebyshev => 0.5 * (1.0 - (PI * k / last).cos()),
+                _ => k / last,
+            }
+        };
+        (0..count - 1)
+            .map(|index| match *self {
+                Self::Step(step) => min + index as f64 * step.copysign(max - min),
+                _ => min + (max - min) * fraction(index),
+            })
+            .chain([max])
+            .collect()
+    }
+}
+
+/// Fixed-capacity list of custom line positions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CustomLines {
+    len: usize,
+    positions: [f64; MAX_CUSTOM_LINES],
+}
+
+impl CustomLines {
+    /// Keeps the finite entries of `positions`, sorted and deduplicated, up to
+    /// `MAX_CUSTOM_LINES`.
+    pub fn new(positions: &[f64]) -> Self {
+        let mut sorted = positions
+            .iter()
+            .copied()
+            .filter(|value| value.is_finite())
+            .collect::<Vec<_>>();
+        sorted.sort_by(f64::total_cmp);
+        sorted.dedup();
+        let mut lines = Self {
+            len: sorted.len().min(MAX_CUSTOM_LINES),
+            positions: [0.0; MAX_CUSTOM_LINES],
+        };
+        lines.positions[..lines.len].copy_from_slice(&sorted[..lines.len]);
+        lines
+    }
+
+    pub fn as_slice(&self) -> &[f64] {
+        &self.positions[..self.len]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CustomLines, LineSpacing};
+
+    #[test]
+    fn logarithmic_gaps_grow_by_the_requested_ratio() {
+        let positions = LineSpacing::Logarithmic { ratio: 8.0 }.positions((0.0, 6.0), 5);
+
+        let gaps = positions
+            .windows(2)
+            .map(|pair| pair[1] - pair[0])
+            .collect::<Vec<_>>();
+        assert_eq!(positions[0], 0.0);
+        assert_eq!(positions[4], 6.0);
+        assert!((gaps[3] / gaps[0] - 8.0).abs() < 1.0e-9);
+        assert!((gaps[1] / gaps[0] - 2.0).abs() < 1.0e-9);
+        assert_eq!(
+            LineSpacing::Logarithmic { ratio: 1.0 }.positions((0.0, 3.0), 4),
+            LineSpacing::Uniform.positions((0.0, 3.0), 4)
+        );
+    }
+
+    #[test]
+    fn chebyshev_nodes_crowd_toward_both_bounds() {
+        let positions = LineSpacing::Chebyshev.positions((-1.0, 1.0), 5);
+
+        assert_eq!(positions.len(), 5);
+        assert!((positions[1] + 0.5_f64.sqrt()).abs() < 1.0e-12);
+        assert!(positions[2].abs() < 1.0e-12);
+        assert!(positions[1] - positions[0] < positions[2] - positions[1]);
+    }
+
+    #[test]
+    fn custom_lines_are_sorted_and_keep_their_positions() {
+        let lines = CustomLines::new(&[2.0, 0.5, f64::NAN, 0.5, 1.0]);
+        let spacing = LineSpacing::Custom(lines);
+
+        assert_eq!(lines.as_slice(), &[0.5, 1.0, 2.0]);
+        assert_eq!(spacing.count((0.0, 10.0), 7), 3);
+        assert_eq!(spacing.positions((0.0, 10.0), 3), vec![0.5, 1.0, 2.0]);
+    }
+}
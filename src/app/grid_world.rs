@@ -107,7 +107,6 @@ impl GridWorld {
     ///
     /// The returned value contains both the hit world position and the matching abstract
     /// coordinates.
-    #[allow(dead_code)]
     pub fn found_nearest(&self, pos: &[f64; 3]) -> Option<GridSample> {
         let (world_pos, abstract_pos) = self.data.nearest(pos)?.item;
         Some(GridSample {
@@ -0,0 +1,144 @@
+//! Numeric evaluation of the point inspector: local geometry and field values at one sample.
+
+use crate::app::coords_sys::CoordsSys;
+use crate::app::em_runtime::EmRuntime;
+use crate::app::field_runtime::RuntimeField;
+use crate::app::ui::{InspectedEm, InspectedField, InspectorReport};
+use crate::maths::Point;
+use nalgebra::{Matrix3, Vector3};
+
+/// Evaluates everything the inspector window shows at the abstract point `position`.
+///
+/// `em` carries the EM runtime together with the time to sample it at, when EM is enabled.
+pub(crate) fn inspect_point(
+    coords: &CoordsSys,
+    field: &RuntimeField,
+    em: Option<(&EmRuntime, f64)>,
+    position: Vector3<f64>,
+    pinned: bool,
+) -> InspectorReport {
+    let geometry = coords.sample_geometry();
+    let metric = geometry.metric(position);
+    let point = Point {
+        x: position.x,
+        y: position.y,
+        z: position.z,
+    };
+
+    InspectorReport {
+        pinned,
+        snapped_because: None,
+        abstract_position: position.into(),
+        world_position: geometry.eval_position(position).into(),
+        metric: metric.map(rows),
+        metric_determinant: metric.map(|metric| metric.determinant()),
+        scale_factors: [0, 1, 2].map(|axis| geometry.axis_scale(position, axis)),
+        vielbein: metric
+            .and_then(|metric| metric.cholesky())
+            .map(|cholesky| rows(cholesky.l())),
+        field: Some(inspect_field(field, point, metric)),
+        em: em.map(|(runtime, time)| InspectedEm {
+            time,
+            phi: runtime.phi_at(point, time),
+            vector_potential: runtime.vector_potential_at(point, time).into(),
+            electric: runtime.electric_at(point, time).into(),
+            magnetic: runtime.magnetic_at(point, time).into(),
+        }),
+    }
+}
+
+/// Natural components are raised from the dual ones with `g⁻¹`; they read zero where the metric
+/// is singular.
+fn inspect_field(
+    field: &RuntimeField,
+    point: Point,
+    metric: Option<Matrix3<f64>>,
+) -> InspectedField {
+    match field {
+        RuntimeField::Scalar(scalar) => InspectedField::Scalar(scalar.at(point)),
+        RuntimeField::Vector(vector) => {
+            let dual = to_vector(vector.dual_at(point));
+            let natural = metric
+                .and_then(|metric| metric.try_inverse())
+                .map(|inverse| inverse * dual)
+                .unwrap_or_else(Vector3::zeros);
+            InspectedField::Vector {
+                natural: natural.into(),
+                dual: dual.into(),
+                otn: to_vector(vector.at(point)).into(),
+                jacobian: vector.otn_jacobian_at(point),
+            }
+        }
+    }
+}
+
+fn to_vector(point: Point) -> Vector3<f64> {
+    Vector3::new(point.x, point.y, point.z)
+}
+
+fn rows(matrix: Matrix3<f64>) -> [[f64; 3]; 3] {
+    [0, 1, 2].map(|row| [0, 1, 2].map(|column| matrix[(row, column)]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::inspect_point;
+    use crate::app::coords_sys::CoordsSys;
+    use crate::app::field_runtime::RuntimeField;
+    use crate::app::ui::InspectedField;
+    use crate::maths::differential::Form;
+    use crate::maths::field::VectorField;
+    use mathhook_core::Parser;
+    use nalgebra::Vector3;
+
+    #[test]
+    fn cylindrical_sample_reports_metric_scales_and_raised_components() {
+        let parse = |expr: &str| Parser::default().parse(expr).unwrap();
+        let coords = CoordsSys::new(parse("x*cos(y)"), parse("x*sin(y)"), parse("z"));
+        let dual = Form::new(vec![parse("0"), parse("x^2"), parse("0")], 1);
+        let field = RuntimeField::Vector(VectorField::new(dual, coords.get_space()));
+
+        let report = inspect_point(&coords, &field, None, Vector3::new(2.0, 0.5, 1.0), true);
+
+        let metric = report.metric.unwrap();
+        for (row, expected) in [[1.0, 0.0, 0.0], [0.0, 4.0, 0.0], [0.0, 0.0, 1.0]]
+            .iter()
+            .enumerate()
+        {
+            for column in 0..3 {
+                assert!((metric[row][column] - expected[column]).abs() < 1.0e-9);
+            }
+        }
+        assert!((report.metric_determinant.unwrap() - 4.0).abs() < 1.0e-9);
+        assert!((report.scale_factors[1].unwrap() - 2.0).abs() < 1.0e-9);
+        assert!((report.vielbein.unwrap()[1][1] - 2.0).abs() < 1.0e-9);
+        assert!((report.world_position[0] - 2.0 * 0.5_f64.cos()).abs() < 1.0e-9);
+        let Some(InspectedField::Vector {
+            natural, dual, otn, ..
+        }) = report.field
+        else {
+            panic!("expected a vector field");
+        };
+        assert!((dual[1] - 4.0).abs() < 1.0e-9);
+        assert!((natural[1] - 1.0).abs() < 1.0e-9);
+        assert!((otn[1] - 2.0).abs() < 1.0e-9);
+        assert!(report.em.is_none());
+    }
+
+    #[test]
+    fn polar_axis_leaves_singular_geometry_empty() {
+        let parse = |expr: &str| Parser::default().parse(expr).unwrap();
+        let coords = CoordsSys::new(parse("x*cos(y)"), parse("x*sin(y)"), parse("z"));
+        let field = RuntimeField::Vector(VectorField::new(
+            Form::new(vec![parse("1"), parse("0"), parse("0")], 1),
+            coords.get_space(),
+        ));
+
+        let report = inspect_point(&coords, &field, None, Vector3::zeros(), false);
+
+        assert!(report.vielbein.is_none());
+        assert_eq!(report.scale_factors[1], None);
+        assert!(report.metric_determinant.unwrap().abs() < 1.0e-12);
+        assert!(report.to_text().contains("vielbein e = singular"));
+    }
+}
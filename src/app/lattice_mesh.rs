@@ -0,0 +1,55 @@
+//! Triangulation of embedded quad lattices, shared by the coordinate surfaces and the walls.
+
+use nalgebra::Vector3;
+
+/// Splits every quad of a row-major lattice of `points`, `side` points per row, into the
+/// triangles `[a, b, c]` and `[a, c, d]`, indexed into `points`.
+///
+/// Vertices whose embedding is not finite drop every triangle that touches them.
+pub(crate) fn lattice_triangles(points: &[Vector3<f64>], side: usize) -> Vec<[usize; 3]> {
+    let rows = if side == 0 { 0 } else { points.len() / side };
+    let finite: Vec<bool> = points
+        .iter()
+        .map(|point| point.iter().all(|value| value.is_finite()))
+        .collect();
+    let index = |i: usize, j: usize| i * side + j;
+    let mut triangles = Vec::new();
+    for i in 0..rows.saturating_sub(1) {
+        for j in 0..side - 1 {
+            let [a, b, c, d] = [
+                index(i, j),
+                index(i + 1, j),
+                index(i + 1, j + 1),
+                index(i, j + 1),
+            ];
+            if [a, b, c, d].iter().all(|&corner| finite[corner]) {
+                triangles.push([a, b, c]);
+                triangles.push([a, c, d]);
+            }
+        }
+    }
+    triangles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::lattice_triangles;
+    use nalgebra::Vector3;
+
+    #[test]
+    fn non_finite_vertices_drop_the_quads_around_them() {
+        let mut points: Vec<_> = (0..3)
+            .flat_map(|i| (0..3).map(move |j| Vector3::new(i as f64, j as f64, 0.0)))
+            .collect();
+        assert_eq!(lattice_triangles(&points, 3).len(), 2 * 4);
+
+        points[4].z = f64::NAN;
+        assert!(lattice_triangles(&points, 3).is_empty());
+
+        points[4].z = 0.0;
+        points[0].x = f64::INFINITY;
+        let triangles = lattice_triangles(&points, 3);
+        assert_eq!(triangles.len(), 2 * 3);
+        assert!(triangles.iter().flatten().all(|&corner| corner != 0));
+    }
+}
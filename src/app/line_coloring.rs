@@ -0,0 +1,131 @@
+//! Geometric quantities the primary grid lines can be colored by.
+//!
+//! `grid_edit.vert` evaluates the quantity at every vertex on the GPU. The CPU only samples it
+//! at the ends and midpoint of each segment to fix the range the shader maps onto the colormap
+//! and the legend shows.
+
+use crate::app::coords_sys::CoordSampleGeometry;
+use crate::app::grid::{Grid, SegmentDir};
+use crate::app::ui::LineQuantity;
+use crate::maths::{expr_to_fastexpr3d, Expr, FastExpr3d};
+use nalgebra::{Vector3, Vector4};
+
+/// Step of the central differences along a line, in abstract units; matches `DIFF_STEP` in
+/// `grid_edit.vert`.
+const DIFF_STEP: f64 = 0.01;
+
+/// Share of the samples left out at each end of the range, so that `h_i` or the curvature
+/// blowing up next to a singularity does not flatten the colors everywhere else.
+const RANGE_CLIP: f64 = 0.02;
+
+/// Evaluates `quantity` at `point` of a line running along `axis`; `None` where it is not
+/// finite or, for the curvature, where the line stalls.
+pub(crate) fn line_quantity(
+    geometry: &CoordSampleGeometry,
+    scalar_field: Option<&FastExpr3d>,
+    quantity: LineQuantity,
+    point: Vector3<f64>,
+    axis: usize,
+) -> Option<f64> {
+    let value = match quantity {
+        LineQuantity::Axis => return None,
+        LineQuantity::ScaleFactor => geometry.jacobian(point)?.column(axis).norm(),
+        LineQuantity::JacobianDeterminant => geometry.jacobian_determinant(point)?,
+        LineQuantity::Curvature => line_curvature(geometry, point, axis)?,
+        LineQuantity::ScalarField => scalar_field?(point.x, point.y, point.z),
+    };
+    value.is_finite().then_some(value)
+}
+
+/// `|Φ' × Φ''| / |Φ'|³` along `axis`, from central differences like the shader.
+fn line_curvature(geometry: &CoordSampleGeometry, point: Vector3<f64>, axis: usize) -> Option<f64> {
+    let step = Vector3::ith(axis, DIFF_STEP);
+    let ahead = geometry.eval_position(point + step);
+    let here = geometry.eval_position(point);
+    let behind = geometry.eval_position(point - step);
+    let velocity = (ahead - behind) / (2.0 * DIFF_STEP);
+    let acceleration = (ahead - here * 2.0 + behind) / DIFF_STEP.powi(2);
+    let speed = velocity.norm();
+    (speed > 1.0e-6).then(|| velocity.cross(&acceleration).norm() / speed.powi(3))
+}
+
+/// Returns the clipped range of `quantity` over the segments of `grid`, or `None` for the axis
+/// colors and grids without a finite sample.
+pub(crate) fn line_range(
+    grid: &Grid,
+    quantity: LineQuantity,
+    scalar_field: &Expr,
+) -> Option<(f64, f64)> {
+    if quantity == LineQuantity::Axis {
+        return None;
+    }
+    let geometry = grid.get_coords().sample_geometry();
+    let scalar_field =
+        (quantity == LineQuantity::ScalarField).then(|| expr_to_fastexpr3d(scalar_field.clone()));
+    let mut values = Vec::new();
+    for (transform, dir) in grid.get_data().values().flatten() {
+        let axis = match dir {
+            SegmentDir::X => 0,
+            SegmentDir::Y => 1,
+            SegmentDir::Z => 2,
+        };
+        for along in [0.0, 0.5, 1.0] {
+            let point = (transform * Vector4::new(along, 0.0, 0.0, 1.0)).xyz();
+            values.extend(line_quantity(
+                &geometry,
+                scalar_field.as_ref(),
+                quantity,
+                point,
+                axis,
+            ));
+        }
+    }
+    clipped_range(values)
+}
+
+fn clipped_range(mut values: Vec<f64>) -> Option<(f64, f64)> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_by(f64::total_cmp);
+    let last = values.len() - 1;
+    let clip = (last as f64 * RANGE_CLIP).round() as usize;
+    Some((values[clip], values[last - clip]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{clipped_range, line_quantity};
+    use crate::app::coords_sys::CoordsSys;
+    use crate::app::ui::LineQuantity;
+    use mathhook_core::Parser;
+    use nalgebra::vector;
+
+    #[test]
+    fn polar_lines_have_the_textbook_scale_factors_and_curvature() {
+        let parse = |expr: &str| Parser::default().parse(expr).unwrap();
+        let polar = CoordsSys::new(parse("x*cos(y)"), parse("x*sin(y)"), parse("z"));
+        let geometry = polar.sample_geometry();
+        let point = vector![2.0, 0.3, 0.0];
+        let at = |quantity, axis| line_quantity(&geometry, None, quantity, point, axis).unwrap();
+
+        assert!((at(LineQuantity::ScaleFactor, 0) - 1.0).abs() < 1.0e-9);
+        assert!((at(LineQuantity::ScaleFactor, 1) - 2.0).abs() < 1.0e-9);
+        assert!((at(LineQuantity::JacobianDeterminant, 2) - 2.0).abs() < 1.0e-9);
+        assert!((at(LineQuantity::Curvature, 1) - 0.5).abs() < 1.0e-4);
+        assert!(at(LineQuantity::Curvature, 0).abs() < 1.0e-4);
+        assert_eq!(
+            line_quantity(&geometry, None, LineQuantity::ScalarField, point, 0),
+            None
+        );
+    }
+
+    #[test]
+    fn the_range_leaves_out_outliers_at_both_ends() {
+        let mut values = (0..=100).map(f64::from).collect::<Vec<_>>();
+        values.push(1.0e9);
+
+        assert_eq!(clipped_range(values), Some((2.0, 99.0)));
+        assert_eq!(clipped_range(Vec::new()), None);
+    }
+}
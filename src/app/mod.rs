@@ -1,11 +1,24 @@
 pub mod applied_config;
+pub mod atlas;
+pub mod coordinate_surfaces;
 pub mod coords_sys;
 pub(crate) mod em_profile;
 pub mod em_runtime;
+pub(crate) mod em_timeline;
 pub mod field_render;
 pub mod field_runtime;
 pub mod grid;
 pub mod grid_world;
+pub(crate) mod inspector;
+pub(crate) mod lattice_mesh;
+pub(crate) mod line_coloring;
+pub(crate) mod morph;
+pub(crate) mod moving_frame;
+pub mod pec_walls;
+pub(crate) mod probes;
+pub mod region_surfaces;
+pub mod singularities;
 pub mod tangent_space;
+pub(crate) mod tissot;
 pub mod ui;
 pub mod world;
@@ -0,0 +1,179 @@
+//! Morph arithmetic: the blended embedding and the clock that sweeps `s`.
+//!
+//! The grid shader blends the two embeddings at the live `s` every frame. The CPU-side
+//! coordinate system, and with it the metric, field samples and arrows, is only rebuilt when `s`
+//! lands on a new multiple of `1 / MORPH_STEPS`, since each rebuild differentiates the blend anew.
+
+use crate::app::ui::{MorphHomotopy, MorphUiState};
+use crate::maths::{num, Expr};
+use std::ops::{Add, Mul};
+
+/// Number of steps between the source and the target at which the geometry is rebuilt.
+pub(crate) const MORPH_STEPS: f64 = 32.0;
+
+/// Clamps `s` into `[0, 1]` and rounds it to the nearest rebuild step.
+pub(crate) fn snap(s: f64) -> f64 {
+    (s.clamp(0.0, 1.0) * MORPH_STEPS).round() / MORPH_STEPS
+}
+
+/// Blend weight of the target at `s` along `homotopy`.
+pub(crate) fn ease(homotopy: MorphHomotopy, s: f64) -> f64 {
+    match homotopy {
+        MorphHomotopy::Eased => s * s * (3.0 - 2.0 * s),
+        MorphHomotopy::Linear | MorphHomotopy::Radial => s,
+    }
+}
+
+/// Embedding equations of the homotopy from `source` to `target` at `s`.
+///
+/// The endpoints return the input equations untouched, so a radial blend does not add a
+/// `0 / 0` at the origin of either embedding.
+pub(crate) fn blend(
+    source: &[Expr; 3],
+    target: &[Expr; 3],
+    homotopy: MorphHomotopy,
+    s: f64,
+) -> [Expr; 3] {
+    if s <= 0.0 {
+        return source.clone();
+    }
+    if s >= 1.0 {
+        return target.clone();
+    }
+    let weight = ease(homotopy, s);
+    let linear = std::array::from_fn(|axis| {
+        num(1.0 - weight)
+            .mul(source[axis].clone())
+            .add(num(weight).mul(target[axis].clone()))
+    });
+    if homotopy != MorphHomotopy::Radial {
+        return linear;
+    }
+    let radius = num(1.0 - weight)
+        .mul(norm(source))
+        .add(num(weight).mul(norm(target)));
+    let scale = radius.mul(Expr::pow(norm(&linear), num(-1.0)));
+    linear.map(|eq| eq.mul(scale.clone()))
+}
+
+fn norm(eqs: &[Expr; 3]) -> Expr {
+    let squares = eqs
+        .iter()
+        .map(|eq| Expr::pow(eq.clone(), num(2.0)))
+        .reduce(|sum, square| sum.add(square))
+        .expect("three equations");
+    Expr::sqrt(squares)
+}
+
+/// Morph parameter as the render thread last read or advanced it.
+pub(crate) struct MorphClock {
+    pub(crate) s: f64,
+    animate: bool,
+    period: f64,
+    /// `1.0` while sweeping toward the target, `-1.0` on the way back.
+    direction: f64,
+}
+
+impl MorphClock {
+    pub(crate) fn new(morph: &MorphUiState) -> Self {
+        let mut clock = Self {
+            s: 0.0,
+            animate: false,
+            period: 1.0,
+            direction: 1.0,
+        };
+        clock.read_live(morph);
+        clock
+    }
+
+    /// Copies the live settings; the slider only drives `s` while the sweep is stopped.
+    pub(crate) fn read_live(&mut self, morph: &MorphUiState) {
+        self.animate = morph.animate;
+        self.period = morph.period;
+        if !self.animate {
+            self.s = morph.s.clamp(0.0, 1.0);
+        }
+    }
+
+    /// Sweeps `s` by `dt / period` while animating, reflecting off 0 and 1.
+    pub(crate) fn advance(&mut self, dt: f64) {
+        if !self.animate || !(self.period > 0.0 && self.period.is_finite()) {
+            return;
+        }
+        let phase = if self.direction > 0.0 {
+            self.s
+        } else {
+            2.0 - self.s
+        };
+        let phase = (phase + dt / self.period).rem_euclid(2.0);
+        self.s = 1.0 - (1.0 - phase).abs();
+        self.direction = if phase < 1.0 { 1.0 } else { -1.0 };
+    }
+
+    /// Writes the swept `s` back for the slider, unless the sweep was stopped meanwhile.
+    pub(crate) fn publish(&self, morph: &mut MorphUiState) {
+        if self.animate && morph.animate {
+            morph.s = self.s;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{snap, MorphClock};
+    use crate::app::coords_sys::CoordsSys;
+    use crate::app::ui::{MorphHomotopy, MorphUiState};
+    use mathhook_core::Parser;
+    use nalgebra::vector;
+
+    #[test]
+    fn the_clock_ping_pongs_and_only_runs_while_animating() {
+        let mut morph = MorphUiState {
+            s: 0.75,
+            period: 2.0,
+            ..MorphUiState::default()
+        };
+        let mut clock = MorphClock::new(&morph);
+        clock.advance(0.5);
+        assert_eq!(clock.s, 0.75);
+
+        morph.animate = true;
+        clock.read_live(&morph);
+        clock.advance(0.5);
+        assert_eq!(clock.s, 1.0);
+        clock.advance(1.0);
+        assert!((clock.s - 0.5).abs() < 1.0e-12);
+        clock.advance(2.0);
+        clock.advance(0.5);
+        assert!((clock.s - 0.75).abs() < 1.0e-12);
+
+        clock.publish(&mut morph);
+        assert_eq!(morph.s, clock.s);
+        assert_eq!(snap(0.49), 0.5);
+        assert_eq!(snap(-3.0), 0.0);
+    }
+
+    #[test]
+    fn morphed_coordinates_blend_positions_along_the_homotopy() {
+        let parse = |expr: &str| Parser::default().parse(expr).unwrap();
+        let spherical = [
+            parse("x*cos(y)*sin(z)"),
+            parse("x*sin(y)*sin(z)"),
+            parse("x*cos(z)"),
+        ];
+        let cartesian = [parse("x"), parse("y"), parse("z")];
+        let point = vector![2.0, 0.3, 1.1];
+        let from = CoordsSys::morph(&spherical, &cartesian, MorphHomotopy::Linear, 0.0);
+        let to = CoordsSys::morph(&spherical, &cartesian, MorphHomotopy::Linear, 1.0);
+        let (start, end) = (from.eval_position(point), to.eval_position(point));
+
+        let linear = CoordsSys::morph(&spherical, &cartesian, MorphHomotopy::Linear, 0.5);
+        let radial = CoordsSys::morph(&spherical, &cartesian, MorphHomotopy::Radial, 0.5);
+
+        assert!((end - point).norm() < 1.0e-12);
+        assert!((linear.eval_position(point) - (start + end) / 2.0).norm() < 1.0e-9);
+        let radial_pos = radial.eval_position(point);
+        assert!((radial_pos.norm() - (start.norm() + end.norm()) / 2.0).abs() < 1.0e-9);
+        assert!(radial_pos.normalize().dot(&(start + end).normalize()) > 1.0 - 1.0e-9);
+    }
+}
@@ -0,0 +1,119 @@
+//! Embeddings and Field-tab fields that move with the scene time `t`, such as rotating frames,
+//! breathing spheres or unsteady flows.
+//!
+//! The grid shader reads `t` from a uniform every frame. The CPU-side coordinate system, with
+//! its metric, field samples and arrows, is rebuilt with `t` frozen at multiples of
+//! `TIME_STEP`, and never more often than the rebuilds themselves take: a slow symbolic rebuild
+//! thins out the steps instead of the frame rate.
+//!
+//! `t` is the time of the EM timeline, played, scrubbed and looped by the same controls whether
+//! EM is enabled or not.
+
+use crate::app::em_timeline::snap_to_step;
+use crate::app::ui::{FieldKind, GridUiState, SpacialEqs};
+use crate::maths::{num, Expr};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Scene time between two CPU-side rebuilds of a moving embedding.
+pub(crate) const TIME_STEP: f64 = 0.05;
+
+const TIME_VARIABLE: &str = "t";
+
+/// Rounds `time` to the step the CPU-side geometry is built at.
+pub(crate) fn snap_time(time: f64) -> f64 {
+    snap_to_step(time, TIME_STEP)
+}
+
+fn reads_time(eq: &Expr) -> bool {
+    eq.find_variables()
+        .iter()
+        .any(|variable| variable.name() == TIME_VARIABLE)
+}
+
+/// Returns whether any of the three equations reads `t`.
+pub(crate) fn uses_time(eqs: &SpacialEqs) -> bool {
+    [&eqs.x, &eqs.y, &eqs.z].iter().any(|eq| reads_time(&eq.eq))
+}
+
+/// Returns whether the drawn embedding, morph target included, moves with `t`.
+pub(crate) fn embedding_uses_time(state: &GridUiState) -> bool {
+    uses_time(&state.coords_sys) || (state.morph.enabled && uses_time(&state.morph.target))
+}
+
+/// Returns whether the Field-tab field reads `t`; while EM is enabled its fields are shown instead.
+pub(crate) fn field_uses_time(state: &GridUiState) -> bool {
+    !state.em.enabled
+        && match state.field_kind {
+            FieldKind::Scalar => reads_time(&state.scalar_field.eq),
+            FieldKind::Vector => uses_time(&state.field),
+        }
+}
+
+/// The three equations with `t` replaced by `time`; static equations are returned as they are.
+pub(crate) fn freeze(eqs: &SpacialEqs, time: f64) -> [Expr; 3] {
+    let eqs = [eqs.x.eq.clone(), eqs.y.eq.clone(), eqs.z.eq.clone()];
+    if !eqs.iter().any(reads_time) {
+        return eqs;
+    }
+    let vars = HashMap::from([(TIME_VARIABLE.to_string(), num(time))]);
+    eqs.map(|eq| eq.substitute(&vars))
+}
+
+/// Spaces CPU-side rebuilds by at least the wall time the last one took.
+#[derive(Default)]
+pub(crate) struct StepThrottle {
+    finished: Option<Instant>,
+    cost: Duration,
+}
+
+impl StepThrottle {
+    pub(crate) fn ready(&self) -> bool {
+        self.finished
+            .is_none_or(|finished| finished.elapsed() >= self.cost)
+    }
+
+    /// Records a rebuild that began at `started` and has just finished.
+    pub(crate) fn record(&mut self, started: Instant) {
+        let finished = Instant::now();
+        self.cost = finished - started;
+        self.finished = Some(finished);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{embedding_uses_time, field_uses_time, freeze, snap_time};
+    use crate::app::ui::{FieldKind, GridUiState, SpacialEqs};
+    use crate::maths::expr_to_fastexpr3d;
+    use mathhook_core::Parser;
+
+    #[test]
+    fn frozen_equations_are_evaluated_at_the_given_time() {
+        let rotating = SpacialEqs::from_defaults("x*cos(t) - y*sin(t)", "x*sin(t) + y*cos(t)", "z");
+        let [x, y, _] = freeze(&rotating, std::f64::consts::FRAC_PI_2);
+
+        assert!((expr_to_fastexpr3d(x)(1.0, 2.0, 0.0) + 2.0).abs() < 1.0e-12);
+        assert!((expr_to_fastexpr3d(y)(1.0, 2.0, 0.0) - 1.0).abs() < 1.0e-12);
+        assert_eq!(snap_time(0.26), 0.25);
+
+        let mut state = GridUiState::default();
+        assert!(!embedding_uses_time(&state));
+        state.morph.target = rotating;
+        assert!(!embedding_uses_time(&state));
+        state.morph.enabled = true;
+        assert!(embedding_uses_time(&state));
+    }
+
+    #[test]
+    fn only_the_shown_field_tab_field_counts_as_moving() {
+        let mut state = GridUiState::default();
+        state.field.x.eq = Parser::default().parse("-y * cos(t)").unwrap();
+        state.field_kind = FieldKind::Scalar;
+        assert!(!field_uses_time(&state));
+        state.field_kind = FieldKind::Vector;
+        assert!(field_uses_time(&state));
+        state.em.enabled = true;
+        assert!(!field_uses_time(&state));
+    }
+}
@@ -0,0 +1,126 @@
+//! Triangle meshes for the perfectly conducting faces of the grid box.
+//!
+//! Walls are selected faces of the abstract-coordinate box, so each face is tessellated in
+//! abstract coordinates and pushed through the coordinate embedding. Curved embeddings, such as
+//! the outer `r = a` face of a cylindrical grid, therefore come out curved.
+
+use crate::app::coords_sys::CoordsSys;
+use crate::app::grid::GridConfig;
+use crate::app::lattice_mesh::lattice_triangles;
+use crate::{TriIndexes, Vertex};
+use nalgebra::Vector3;
+
+/// Quads per face edge; fine enough to follow a full turn of a cylinder.
+const WALL_SUBDIVISIONS: usize = 32;
+
+/// Vertex and index buffers of the selected walls, ready to upload to a VAO.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct WallMesh {
+    pub vertices: Vec<Vertex>,
+    pub indices: Vec<TriIndexes>,
+}
+
+impl WallMesh {
+    /// Returns whether no wall produced any triangle.
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+}
+
+/// Tessellates the walls selected in `walls`, ordered `x_min, x_max, y_min, y_max, z_min, z_max`.
+pub fn build_wall_mesh(coords: &CoordsSys, config: &GridConfig, walls: [bool; 6]) -> WallMesh {
+    let bounds = config.bounds();
+    let mut mesh = WallMesh::default();
+    for (face, _) in walls.iter().enumerate().filter(|(_, selected)| **selected) {
+        let axis = face / 2;
+        let fixed = if face % 2 == 0 {
+            bounds[axis].0
+        } else {
+            bounds[axis].1
+        };
+        let (first, second) = ((axis + 1) % 3, (axis + 2) % 3);
+        push_face(&mut mesh, |s, t| {
+            let mut point = Vector3::zeros();
+            point[axis] = fixed;
+            point[first] = lerp(bounds[first], s);
+            point[second] = lerp(bounds[second], t);
+            coords.eval_position(point)
+        });
+    }
+    mesh
+}
+
+fn push_face(mesh: &mut WallMesh, position: impl Fn(f64, f64) -> Vector3<f64>) {
+    let side = WALL_SUBDIVISIONS + 1;
+    let offset = mesh.vertices.len();
+    let mut points = Vec::with_capacity(side * side);
+    for i in 0..side {
+        for j in 0..side {
+            points.push(position(
+                i as f64 / WALL_SUBDIVISIONS as f64,
+                j as f64 / WALL_SUBDIVISIONS as f64,
+            ));
+        }
+    }
+
+    for triangle in lattice_triangles(&points, side) {
+        mesh.indices
+            .push(triangle.map(|corner| (offset + corner) as u32));
+    }
+    mesh.vertices.extend(
+        points
+            .iter()
+            .map(|point| [point.x as f32, point.y as f32, point.z as f32]),
+    );
+}
+
+fn lerp((min, max): (f64, f64), fraction: f64) -> f64 {
+    min + (max - min) * fraction
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build_wall_mesh, WALL_SUBDIVISIONS};
+    use crate::app::coords_sys::CoordsSys;
+    use crate::app::grid::GridConfig;
+    use mathhook_core::Parser;
+    use std::f64::consts::PI;
+
+    fn coords(x: &str, y: &str, z: &str) -> CoordsSys {
+        let parse = |expr: &str| Parser::default().parse(expr).unwrap();
+        CoordsSys::new(parse(x), parse(y), parse(z))
+    }
+
+    #[test]
+    fn selected_cartesian_walls_lie_on_their_faces() {
+        let config = GridConfig::new(0.0, 2.0, 3.0, 0.0, 1.0, 3.0, 0.0, 4.0, 3.0);
+        let mesh = build_wall_mesh(
+            &coords("x", "y", "z"),
+            &config,
+            [false, true, false, false, true, false],
+        );
+
+        let quads = WALL_SUBDIVISIONS * WALL_SUBDIVISIONS;
+        assert_eq!(mesh.indices.len(), 2 * 2 * quads);
+        let (x_max, z_min) = mesh.vertices.split_at(mesh.vertices.len() / 2);
+        assert!(x_max.iter().all(|vertex| vertex[0] == 2.0));
+        assert!(z_min.iter().all(|vertex| vertex[2] == 0.0));
+        assert!(build_wall_mesh(&coords("x", "y", "z"), &config, [false; 6]).is_empty());
+    }
+
+    #[test]
+    fn cylindrical_outer_wall_follows_the_embedding() {
+        let config = GridConfig::new(0.0, 1.5, 4.0, 0.0, 2.0 * PI, 12.0, 0.0, 3.0, 7.0);
+        let mesh = build_wall_mesh(
+            &coords("x*cos(y)", "x*sin(y)", "z"),
+            &config,
+            [false, true, false, false, false, false],
+        );
+
+        assert!(!mesh.is_empty());
+        for vertex in &mesh.vertices {
+            let radius = (vertex[0] as f64).hypot(vertex[1] as f64);
+            assert!((radius - 1.5).abs() < 1.0e-5, "radius {radius}");
+        }
+    }
+}
@@ -0,0 +1,204 @@
+//! Ring-buffered EM histories recorded at probe markers.
+
+use crate::app::em_runtime::EmRuntime;
+use crate::app::ui::{ProbeChannel, ProbeMarker, ProbeTrace, PROBE_CHANNEL_COUNT};
+use crate::maths::Point;
+use std::collections::VecDeque;
+use std::fmt::Write;
+
+/// Samples kept per probe; about 17 s of history at 60 frames per second.
+pub(crate) const PROBE_HISTORY: usize = 1024;
+
+/// Fixed-capacity history of one probe, kept in increasing time.
+pub(crate) struct ProbeRecorder {
+    marker: ProbeMarker,
+    times: VecDeque<f64>,
+    values: VecDeque<[f64; PROBE_CHANNEL_COUNT]>,
+    /// Bumped on every change, so the history is only republished when it moved.
+    revision: u64,
+}
+
+impl ProbeRecorder {
+    pub(crate) fn new(marker: ProbeMarker) -> Self {
+        Self {
+            marker,
+            times: VecDeque::with_capacity(PROBE_HISTORY),
+            values: VecDeque::with_capacity(PROBE_HISTORY),
+            revision: 0,
+        }
+    }
+
+    pub(crate) fn marker(&self) -> ProbeMarker {
+        self.marker
+    }
+
+    pub(crate) fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// Inserts one sample at its place in time.
+    ///
+    /// A sample at an already recorded time is ignored, so a paused clock or a replayed stretch
+    /// adds nothing, while reverse playback, loop wraps and scrubbing back fill in the series
+    /// instead of folding it onto itself. Once the history is full, the sample at the end farther
+    /// from the new one is dropped, which keeps the window around the current time.
+    pub(crate) fn push(&mut self, time: f64, values: [f64; PROBE_CHANNEL_COUNT]) {
+        let mut slot = match self
+            .times
+            .binary_search_by(|recorded| recorded.total_cmp(&time))
+        {
+            Ok(_) => return,
+            Err(slot) => slot,
+        };
+        if self.times.len() == PROBE_HISTORY {
+            if 2 * slot >= PROBE_HISTORY {
+                self.times.pop_front();
+                self.values.pop_front();
+                slot -= 1;
+            } else {
+                self.times.pop_back();
+                self.values.pop_back();
+            }
+        }
+        self.times.insert(slot, time);
+        self.values.insert(slot, values);
+        self.revision += 1;
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.times.clear();
+        self.values.clear();
+        self.revision += 1;
+    }
+
+    pub(crate) fn trace(&self) -> ProbeTrace {
+        ProbeTrace {
+            id: self.marker.id,
+            position: self.marker.position,
+            times: self.times.iter().copied().collect(),
+            values: self.values.iter().copied().collect(),
+        }
+    }
+}
+
+/// Evaluates every probe channel of the lab-frame fields at `position` and `time`.
+pub(crate) fn probe_values(
+    runtime: &EmRuntime,
+    position: [f64; 3],
+    time: f64,
+) -> [f64; PROBE_CHANNEL_COUNT] {
+    let point = Point {
+        x: position[0],
+        y: position[1],
+        z: position[2],
+    };
+    let electric = runtime.electric_at(point, time);
+    let magnetic = runtime.magnetic_at(point, time);
+    ProbeChannel::ALL.map(|channel| match channel {
+        ProbeChannel::ScalarPotential => runtime.phi_at(point, time),
+        ProbeChannel::ElectricMagnitude => electric.norm(),
+        ProbeChannel::MagneticMagnitude => magnetic.norm(),
+        ProbeChannel::ElectricX => electric.x,
+        ProbeChannel::ElectricY => electric.y,
+        ProbeChannel::ElectricZ => electric.z,
+        ProbeChannel::MagneticX => magnetic.x,
+        ProbeChannel::MagneticY => magnetic.y,
+        ProbeChannel::MagneticZ => magnetic.z,
+    })
+}
+
+/// Serializes probe histories as CSV, one row per probe sample.
+pub(crate) fn probe_csv(traces: &[ProbeTrace]) -> String {
+    let mut csv = String::from("probe,x,y,z,t");
+    for channel in ProbeChannel::ALL {
+        csv.push(',');
+        csv.push_str(channel.label());
+    }
+    csv.push('\n');
+
+    for trace in traces {
+        let [x, y, z] = trace.position;
+        for (time, values) in trace.times.iter().zip(&trace.values) {
+            let _ = write!(csv, "{},{x},{y},{z},{time}", trace.id);
+            for value in values {
+                let _ = write!(csv, ",{value}");
+            }
+            csv.push('\n');
+        }
+    }
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{probe_csv, ProbeRecorder, PROBE_HISTORY};
+    use crate::app::ui::{ProbeChannel, ProbeMarker, PROBE_CHANNEL_COUNT};
+
+    fn recorder() -> ProbeRecorder {
+        ProbeRecorder::new(ProbeMarker {
+            id: 3,
+            position: [1.0, 2.0, 0.5],
+        })
+    }
+
+    #[test]
+    fn recorder_keeps_the_most_recent_history() {
+        let mut recorder = recorder();
+        for step in 0..PROBE_HISTORY + 10 {
+            recorder.push(step as f64, [step as f64; PROBE_CHANNEL_COUNT]);
+        }
+
+        let trace = recorder.trace();
+
+        assert_eq!(trace.times.len(), PROBE_HISTORY);
+        assert_eq!(trace.times[0], 10.0);
+        assert_eq!(trace.values.last().unwrap()[0], (PROBE_HISTORY + 9) as f64);
+    }
+
+    #[test]
+    fn recorder_skips_paused_samples_and_inserts_earlier_ones_in_order() {
+        let mut recorder = recorder();
+        recorder.push(1.0, [1.0; PROBE_CHANNEL_COUNT]);
+        recorder.push(2.0, [2.0; PROBE_CHANNEL_COUNT]);
+        recorder.push(2.0, [9.0; PROBE_CHANNEL_COUNT]);
+        assert_eq!(recorder.trace().times, vec![1.0, 2.0]);
+
+        recorder.push(0.0, [0.0; PROBE_CHANNEL_COUNT]);
+        recorder.push(1.5, [1.5; PROBE_CHANNEL_COUNT]);
+
+        let trace = recorder.trace();
+        assert_eq!(recorder.revision(), 4);
+        assert_eq!(trace.times, vec![0.0, 1.0, 1.5, 2.0]);
+        assert_eq!(trace.channel(ProbeChannel::ScalarPotential), trace.times);
+    }
+
+    #[test]
+    fn full_recorder_drops_the_end_farther_from_reverse_playback() {
+        let mut recorder = recorder();
+        for step in 0..PROBE_HISTORY {
+            recorder.push(step as f64, [0.0; PROBE_CHANNEL_COUNT]);
+        }
+
+        recorder.push(-1.0, [0.0; PROBE_CHANNEL_COUNT]);
+
+        let trace = recorder.trace();
+        assert_eq!(trace.times.len(), PROBE_HISTORY);
+        assert_eq!(trace.times[0], -1.0);
+        assert_eq!(*trace.times.last().unwrap(), (PROBE_HISTORY - 2) as f64);
+    }
+
+    #[test]
+    fn csv_has_a_header_and_one_row_per_sample() {
+        let mut recorder = recorder();
+        recorder.push(0.25, [1.5; PROBE_CHANNEL_COUNT]);
+        recorder.push(0.5, [-2.0; PROBE_CHANNEL_COUNT]);
+
+        let csv = probe_csv(&[recorder.trace()]);
+        let lines = csv.lines().collect::<Vec<_>>();
+
+        assert_eq!(lines[0], "probe,x,y,z,t,V,|E|,|B|,Ex,Ey,Ez,Bx,By,Bz");
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1].starts_with("3,1,2,0.5,0.25,1.5,"));
+        assert_eq!(lines[2].split(',').count(), 5 + PROBE_CHANNEL_COUNT);
+    }
+}
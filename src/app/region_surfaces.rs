@@ -0,0 +1,227 @@
+//! Triangle meshes of implicit conductor and mask surfaces `f(x, y, z) = 0`.
+//!
+//! Regions are given in world coordinates, so the zero set is extracted by marching tetrahedra
+//! over a lattice spanning the world-space bounding box of the grid.
+
+use crate::app::coords_sys::CoordsSys;
+use crate::app::grid::GridConfig;
+use crate::app::pec_walls::WallMesh;
+use crate::maths::{expr_to_fastexpr4d, Expr};
+use nalgebra::Vector3;
+
+/// Lattice cells per axis of the extraction box.
+const REGION_LATTICE_CELLS: usize = 40;
+/// Abstract samples per axis used to find the world-space extent of the grid.
+const BOUNDS_SAMPLES: usize = 9;
+/// Relative margin added around the grid so surfaces on its boundary are not clipped.
+const BOUNDS_MARGIN: f64 = 0.02;
+
+/// Cube corner offsets, indexed by `x + 2 y + 4 z`.
+const CUBE_CORNERS: [[usize; 3]; 8] = [
+    [0, 0, 0],
+    [1, 0, 0],
+    [0, 1, 0],
+    [1, 1, 0],
+    [0, 0, 1],
+    [1, 0, 1],
+    [0, 1, 1],
+    [1, 1, 1],
+];
+/// Six tetrahedra around the `0 - 7` diagonal that tile one cube.
+const CUBE_TETRAHEDRA: [[usize; 4]; 6] = [
+    [0, 1, 3, 7],
+    [0, 3, 2, 7],
+    [0, 2, 6, 7],
+    [0, 6, 4, 7],
+    [0, 4, 5, 7],
+    [0, 5, 1, 7],
+];
+
+/// Extracts the surfaces of every region in `exprs` over the world extent of the grid.
+pub fn build_region_mesh(coords: &CoordsSys, config: &GridConfig, exprs: &[Expr]) -> WallMesh {
+    let mut mesh = WallMesh::default();
+    let Some(bounds) = world_bounds(coords, config) else {
+        return mesh;
+    };
+    for expr in exprs {
+        let field = expr_to_fastexpr4d(expr.clone());
+        push_zero_set(&mut mesh, bounds, |point| {
+            field(point.x, point.y, point.z, 0.0)
+        });
+    }
+    mesh
+}
+
+/// Axis-aligned world box around the embedded grid, or `None` if no sample is finite.
+fn world_bounds(coords: &CoordsSys, config: &GridConfig) -> Option<[(f64, f64); 3]> {
+    let bounds = config.bounds();
+    let mut extent: Option<[(f64, f64); 3]> = None;
+    let fraction = |index: usize| index as f64 / (BOUNDS_SAMPLES - 1) as f64;
+    for i in 0..BOUNDS_SAMPLES {
+        for j in 0..BOUNDS_SAMPLES {
+            for k in 0..BOUNDS_SAMPLES {
+                let abstract_point = Vector3::new(
+                    lerp(bounds[0], fraction(i)),
+                    lerp(bounds[1], fraction(j)),
+                    lerp(bounds[2], fraction(k)),
+                );
+                let point = coords.eval_position(abstract_point);
+                if !point.iter().all(|value| value.is_finite()) {
+                    continue;
+                }
+                let extent = extent.get_or_insert([
+                    (point.x, point.x),
+                    (point.y, point.y),
+                    (point.z, point.z),
+                ]);
+                for (axis, (min, max)) in extent.iter_mut().enumerate() {
+                    *min = min.min(point[axis]);
+                    *max = max.max(point[axis]);
+                }
+            }
+        }
+    }
+
+    let extent = extent?;
+    let span = extent
+        .iter()
+        .map(|(min, max)| max - min)
+        .fold(0.0, f64::max)
+        .max(1.0e-6);
+    Some(extent.map(|(min, max)| (min - BOUNDS_MARGIN * span, max + BOUNDS_MARGIN * span)))
+}
+
+/// Appends the triangles of `field = 0` inside `bounds` to `mesh`.
+fn push_zero_set(
+    mesh: &mut WallMesh,
+    bounds: [(f64, f64); 3],
+    field: impl Fn(Vector3<f64>) -> f64,
+) {
+    let side = REGION_LATTICE_CELLS + 1;
+    let node = |i: usize, j: usize, k: usize| {
+        let fraction = |index: usize| index as f64 / REGION_LATTICE_CELLS as f64;
+        Vector3::new(
+            lerp(bounds[0], fraction(i)),
+            lerp(bounds[1], fraction(j)),
+            lerp(bounds[2], fraction(k)),
+        )
+    };
+    let mut values = Vec::with_capacity(side * side * side);
+    for i in 0..side {
+        for j in 0..side {
+            for k in 0..side {
+                values.push(field(node(i, j, k)));
+            }
+        }
+    }
+    let value = |i: usize, j: usize, k: usize| values[(i * side + j) * side + k];
+
+    for i in 0..REGION_LATTICE_CELLS {
+        for j in 0..REGION_LATTICE_CELLS {
+            for k in 0..REGION_LATTICE_CELLS {
+                let corners = CUBE_CORNERS.map(|[di, dj, dk]| {
+                    (node(i + di, j + dj, k + dk), value(i + di, j + dj, k + dk))
+                });
+                for tetrahedron in CUBE_TETRAHEDRA {
+                    push_tetrahedron(mesh, tetrahedron.map(|corner| corners[corner]));
+                }
+            }
+        }
+    }
+}
+
+/// Emits the one or two triangles where `field = 0` crosses a tetrahedron.
+///
+/// Corners with a non-finite value count as outside, so undefined points never close a surface.
+fn push_tetrahedron(mesh: &mut WallMesh, corners: [(Vector3<f64>, f64); 4]) {
+    let (inside, outside): (Vec<_>, Vec<_>) =
+        corners.into_iter().partition(|(_, value)| *value <= 0.0);
+    let crossing = |a: &(Vector3<f64>, f64), b: &(Vector3<f64>, f64)| {
+        let denominator = a.1 - b.1;
+        let fraction = if denominator.is_finite() && denominator != 0.0 {
+            (a.1 / denominator).clamp(0.0, 1.0)
+        } else {
+            0.5
+        };
+        a.0 + (b.0 - a.0) * fraction
+    };
+    match (inside.as_slice(), outside.as_slice()) {
+        ([lone], [a, b, c]) | ([a, b, c], [lone]) => {
+            push_triangle(
+                mesh,
+                [crossing(lone, a), crossing(lone, b), crossing(lone, c)],
+            );
+        }
+        ([a, b], [c, d]) => {
+            let quad = [
+                crossing(a, c),
+                crossing(a, d),
+                crossing(b, d),
+                crossing(b, c),
+            ];
+            push_triangle(mesh, [quad[0], quad[1], quad[2]]);
+            push_triangle(mesh, [quad[0], quad[2], quad[3]]);
+        }
+        _ => {}
+    }
+}
+
+fn push_triangle(mesh: &mut WallMesh, points: [Vector3<f64>; 3]) {
+    let offset = mesh.vertices.len() as u32;
+    for point in points {
+        mesh.vertices
+            .push([point.x as f32, point.y as f32, point.z as f32]);
+    }
+    mesh.indices.push([offset, offset + 1, offset + 2]);
+}
+
+fn lerp((min, max): (f64, f64), fraction: f64) -> f64 {
+    min + (max - min) * fraction
+}
+
+#[cfg(test)]
+mod tests {
+    use super::build_region_mesh;
+    use crate::app::coords_sys::CoordsSys;
+    use crate::app::grid::GridConfig;
+    use mathhook_core::Parser;
+
+    fn parse(expr: &str) -> crate::maths::Expr {
+        Parser::default().parse(expr).unwrap()
+    }
+
+    fn cartesian() -> CoordsSys {
+        CoordsSys::new(parse("x"), parse("y"), parse("z"))
+    }
+
+    #[test]
+    fn sphere_and_plane_surfaces_lie_on_their_zero_sets() {
+        let config = GridConfig::new(-2.0, 2.0, 5.0, -2.0, 2.0, 5.0, -2.0, 2.0, 5.0);
+
+        let sphere = build_region_mesh(&cartesian(), &config, &[parse("x^2 + y^2 + z^2 - 1")]);
+        assert!(!sphere.is_empty());
+        for vertex in &sphere.vertices {
+            let radius = vertex
+                .iter()
+                .map(|value| (*value as f64).powi(2))
+                .sum::<f64>();
+            assert!((radius.sqrt() - 1.0).abs() < 1.0e-2, "radius {radius}");
+        }
+
+        let plane = build_region_mesh(&cartesian(), &config, &[parse("z - 0.5")]);
+        assert!(!plane.is_empty());
+        assert!(plane
+            .vertices
+            .iter()
+            .all(|vertex| (vertex[2] - 0.5).abs() < 1.0e-5));
+    }
+
+    #[test]
+    fn regions_outside_the_grid_draw_nothing() {
+        let config = GridConfig::new(-1.0, 1.0, 3.0, -1.0, 1.0, 3.0, -1.0, 1.0, 3.0);
+
+        let mesh = build_region_mesh(&cartesian(), &config, &[parse("z - 5"), parse("1")]);
+
+        assert!(mesh.is_empty());
+    }
+}
@@ -0,0 +1,784 @@
+//! Where the coordinate embedding stops being a chart inside the grid bounds.
+//!
+//! Field caches and tangent frames skip samples whose coordinate tangents do not span space, so
+//! arrows silently disappear there. This analysis finds those sets and the places where several
+//! abstract points share one world position.
+//!
+//! `det J` is factored symbolically first: a factor in a single coordinate, such as the `sin(z)`
+//! of spherical coordinates, vanishes on whole constant-coordinate surfaces, which a scan along
+//! that axis locates and bisection refines. Everything else is sampled on a lattice: near-zero
+//! samples are kept as they are, sign changes along lattice edges are bisected, and world
+//! positions shared by regular samples reveal seams and overlaps.
+
+use crate::app::coords_sys::{CoordSampleGeometry, CoordsSys};
+use crate::app::grid::GridConfig;
+use crate::app::ui::{SingularKind, SingularSet};
+use crate::maths::{expr_to_fastexpr3d, Expr, COORD};
+use crate::Vertex;
+use mathhook::Expression;
+use mathhook_core::Simplify;
+use nalgebra::Vector3;
+use rustc_hash::FxHashMap;
+use std::ops::{Add, Mul};
+
+/// Lattice nodes per axis for the sampled checks.
+const LATTICE_NODES: usize = 17;
+/// Steps of the scan along the axis of a one-coordinate factor of `det J`.
+const FACTOR_SCAN_STEPS: usize = 256;
+/// Halvings of a bracketed sign change.
+const BISECTION_STEPS: usize = 48;
+/// `|det J|`, or a factor of it, below this fraction of its largest sample counts as zero.
+const ZERO_TOLERANCE: f64 = 1.0e-6;
+/// Distance, as a fraction of the sampled extent, under which two positions coincide.
+const COINCIDENCE_TOLERANCE: f64 = 1.0e-6;
+
+/// Red line segments and markers over the singular sets, ready to upload.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SingularityMesh {
+    pub vertices: Vec<Vertex>,
+    pub indices: Vec<[u32; 2]>,
+    /// World positions of sets that collapse to a point, such as the origin of polar
+    /// coordinates, or of isolated singular samples.
+    pub points: Vec<Vector3<f64>>,
+}
+
+impl SingularityMesh {
+    /// Returns whether neither a segment nor a point was produced.
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty() && self.points.is_empty()
+    }
+
+    /// Adds one segment; non-finite and zero-length segments are dropped.
+    fn push_segment(&mut self, start: Vector3<f64>, end: Vector3<f64>) {
+        if !start
+            .iter()
+            .chain(end.iter())
+            .all(|value| value.is_finite())
+            || start == end
+        {
+            return;
+        }
+        let offset = self.vertices.len() as u32;
+        for point in [start, end] {
+            self.vertices
+                .push([point.x as f32, point.y as f32, point.z as f32]);
+        }
+        self.indices.push([offset, offset + 1]);
+    }
+}
+
+/// Singular sets for the Grid tab together with their highlight geometry.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SingularityAnalysis {
+    pub sets: Vec<SingularSet>,
+    pub mesh: SingularityMesh,
+}
+
+/// A zero of a one-coordinate factor of `det J`: the surface `COORD[axis] = value`.
+#[derive(Debug, Clone, PartialEq)]
+struct FactorRoot {
+    axis: usize,
+    value: f64,
+    factor: String,
+}
+
+/// Finds the singular sets of `coords` inside the bounds of `config`.
+pub fn analyze_singularities(coords: &CoordsSys, config: &GridConfig) -> SingularityAnalysis {
+    let bounds = config.bounds();
+    let mut analysis = SingularityAnalysis::default();
+    let Some(roots) = factor_roots(&coords.jacobian_determinant(), bounds) else {
+        analysis.sets.push(SingularSet {
+            kind: SingularKind::Degenerate,
+            symbolic: true,
+            description: "det J vanishes identically: the equations do not depend on all three \
+                          coordinates"
+                .to_string(),
+        });
+        return analysis;
+    };
+
+    let geometry = coords.sample_geometry();
+    let lattice = Lattice::sample(&geometry, bounds);
+    for root in &roots {
+        analysis.sets.push(SingularSet {
+            kind: SingularKind::Degenerate,
+            symbolic: true,
+            description: format!(
+                "det J = 0 on {} = {:.4}, a zero of the factor {}",
+                COORD[root.axis], root.value, root.factor
+            ),
+        });
+        lattice.push_surface_image(&mut analysis.mesh, root.axis, root.value);
+    }
+    let explained = |point: Vector3<f64>| {
+        roots.iter().any(|root| {
+            let (min, max) = bounds[root.axis];
+            (point[root.axis] - root.value).abs()
+                <= COINCIDENCE_TOLERANCE * (max - min).abs().max(1.0)
+        })
+    };
+    lattice.push_degenerate_samples(&mut analysis, &explained);
+    lattice.push_sign_changes(&mut analysis, &explained);
+    lattice.push_coincidences(&mut analysis);
+    analysis
+}
+
+/// Zeros of the one-coordinate factors of `det`, or `None` when `det` is identically zero.
+///
+/// Factors in two or more coordinates are left to the sampled checks.
+fn factor_roots(det: &Expr, bounds: [(f64, f64); 3]) -> Option<Vec<FactorRoot>> {
+    let mut roots = Vec::new();
+    for factor in determinant_factors(det) {
+        let variables = factor.find_variables();
+        if variables.is_empty() {
+            if factor.evaluate_to_f64().is_ok_and(|value| value == 0.0) {
+                return None;
+            }
+            continue;
+        }
+        let [variable] = variables.as_slice() else {
+            continue;
+        };
+        let Some(axis) = COORD.iter().position(|name| *name == variable.name()) else {
+            continue;
+        };
+        let evaluate = expr_to_fastexpr3d(factor.clone());
+        let along = |value: f64| {
+            let mut point = [0.0; 3];
+            point[axis] = value;
+            evaluate(point[0], point[1], point[2])
+        };
+        roots.extend(
+            scan_roots(along, bounds[axis])
+                .into_iter()
+                .map(|value| FactorRoot {
+                    axis,
+                    value,
+                    factor: factor.to_string(),
+                }),
+        );
+    }
+    Some(roots)
+}
+
+/// Multiplicative factors of `det`, with positive constant powers reduced to their base.
+///
+/// Sums are factored by what all their terms share: spherical coordinates give
+/// `x^2 sin(z)^3 cos(y)^2 + x^2 sin(z)^3 sin(y)^2 + x^2 sin(z) cos(z)^2`, which yields `x` and
+/// `sin(z)` next to the remaining sum.
+fn determinant_factors(det: &Expr) -> Vec<Expr> {
+    let mut factors = Vec::new();
+    collect_factors(det, &mut factors);
+    factors
+}
+
+fn collect_factors(expr: &Expr, factors: &mut Vec<Expr>) {
+    match expr {
+        Expression::Mul(terms) => terms.iter().for_each(|term| collect_factors(term, factors)),
+        Expression::Pow(base, exponent) if positive_constant(exponent).is_some() => {
+            collect_factors(base, factors)
+        }
+        Expression::Add(terms) => {
+            let terms = terms.iter().map(powered_factors).collect::<Vec<_>>();
+            let common = common_factors(&terms);
+            if common.is_empty() {
+                factors.push(expr.clone());
+                return;
+            }
+            common
+                .iter()
+                .for_each(|(base, _)| collect_factors(base, factors));
+            let remainder = terms
+                .into_iter()
+                .map(|term| {
+                    term.into_iter()
+                        .filter_map(|(base, exponent)| {
+                            let shared = common
+                                .iter()
+                                .find(|(common, _)| common.to_string() == base.to_string())
+                                .map_or(0.0, |(_, shared)| *shared);
+                            (exponent > shared)
+                                .then(|| Expr::pow(base, Expr::number(exponent - shared)))
+                        })
+                        .fold(Expr::number(1.0), |product, factor| product.mul(factor))
+                })
+                .reduce(|sum, term| sum.add(term))
+                .expect("a sum has terms")
+                .simplify();
+            collect_factors(&remainder, factors);
+        }
+        _ => factors.push(expr.clone()),
+    }
+}
+
+/// A product term as `(base, exponent)` pairs, constant exponents only; numbers count as `1`.
+fn powered_factors(term: &Expr) -> Vec<(Expr, f64)> {
+    let elements = match term {
+        Expression::Mul(elements) => elements.iter().collect::<Vec<_>>(),
+        _ => vec![term],
+    };
+    elements
+        .into_iter()
+        .map(|element| match element {
+            Expression::Pow(base, exponent) => match positive_constant(exponent) {
+                Some(exponent) => ((**base).clone(), exponent),
+                None => (element.clone(), 1.0),
+            },
+            _ => (element.clone(), 1.0),
+        })
+        .collect()
+}
+
+/// Non-constant bases present in every term, with the smallest exponent they carry.
+fn common_factors(terms: &[Vec<(Expr, f64)>]) -> Vec<(Expr, f64)> {
+    let Some((first, rest)) = terms.split_first() else {
+        return Vec::new();
+    };
+    first
+        .iter()
+        .filter(|(base, _)| !base.find_variables().is_empty())
+        .filter_map(|(base, exponent)| {
+            let key = base.to_string();
+            rest.iter()
+                .map(|term| {
+                    term.iter()
+                        .find(|(other, _)| other.to_string() == key)
+                        .map(|(_, exponent)| *exponent)
+                })
+                .try_fold(*exponent, |smallest, exponent| {
+                    Some(smallest.min(exponent?))
+                })
+                .map(|smallest| (base.clone(), smallest))
+        })
+        .collect()
+}
+
+fn positive_constant(exponent: &Expr) -> Option<f64> {
+    if !exponent.find_variables().is_empty() {
+        return None;
+    }
+    exponent.evaluate_to_f64().ok().filter(|value| *value > 0.0)
+}
+
+/// Zeros of `f` in `(min, max)`: samples that are zero relative to the largest one, and
+/// bisected sign changes between neighbouring samples.
+fn scan_roots(f: impl Fn(f64) -> f64, (min, max): (f64, f64)) -> Vec<f64> {
+    let step = (max - min) / FACTOR_SCAN_STEPS as f64;
+    let samples = (0..=FACTOR_SCAN_STEPS)
+        .map(|index| {
+            let value = min + step * index as f64;
+            (value, f(value))
+        })
+        .collect::<Vec<_>>();
+    let scale = samples
+        .iter()
+        .map(|(_, value)| value.abs())
+        .filter(|value| value.is_finite())
+        .fold(0.0, f64::max);
+    if scale == 0.0 {
+        return Vec::new();
+    }
+    let is_zero = |value: f64| value.abs() <= ZERO_TOLERANCE * scale;
+
+    let mut roots = Vec::new();
+    for (index, &(at, value)) in samples.iter().enumerate() {
+        if is_zero(value) {
+            roots.push(at);
+        } else if let Some(&(next_at, next_value)) = samples.get(index + 1) {
+            if !is_zero(next_value) && value * next_value < 0.0 {
+                roots.push(bisect(&f, at, next_at));
+            }
+        }
+    }
+    // Neighbouring samples of one flat zero report it once.
+    roots.dedup_by(|later, earlier| (*later - *earlier).abs() <= step.abs() * 1.5);
+    roots
+}
+
+/// Narrows a sign change of `f` between `start` and `end` to its midpoint.
+fn bisect(f: impl Fn(f64) -> f64, mut start: f64, mut end: f64) -> f64 {
+    let start_sign = f(start).signum();
+    for _ in 0..BISECTION_STEPS {
+        let middle = 0.5 * (start + end);
+        let value = f(middle);
+        if value == 0.0 {
+            return middle;
+        }
+        if value.signum() == start_sign {
+            start = middle;
+        } else {
+            end = middle;
+        }
+    }
+    0.5 * (start + end)
+}
+
+/// `LATTICE_NODES³` samples of the embedding over the grid bounds.
+struct Lattice<'a> {
+    geometry: &'a CoordSampleGeometry,
+    axes: [Vec<f64>; 3],
+    positions: Vec<Vector3<f64>>,
+    determinants: Vec<Option<f64>>,
+    /// Largest sampled `|det J|`.
+    scale: f64,
+    /// Diagonal of the box around the finite sampled positions.
+    extent: f64,
+}
+
+impl<'a> Lattice<'a> {
+    fn sample(geometry: &'a CoordSampleGeometry, bounds: [(f64, f64); 3]) -> Self {
+        let axes = bounds.map(|(min, max)| {
+            (0..LATTICE_NODES)
+                .map(|index| min + (max - min) * index as f64 / (LATTICE_NODES - 1) as f64)
+                .collect::<Vec<_>>()
+        });
+        let mut lattice = Self {
+            geometry,
+            axes,
+            positions: Vec::with_capacity(LATTICE_NODES.pow(3)),
+            determinants: Vec::with_capacity(LATTICE_NODES.pow(3)),
+            scale: 0.0,
+            extent: 0.0,
+        };
+        for index in 0..LATTICE_NODES.pow(3) {
+            let point = lattice.abstract_point(index);
+            lattice.positions.push(geometry.eval_position(point));
+            lattice
+                .determinants
+                .push(geometry.jacobian_determinant(point));
+        }
+        lattice.scale = lattice
+            .determinants
+            .iter()
+            .flatten()
+            .fold(0.0, |scale: f64, det| scale.max(det.abs()));
+        lattice.extent = extent(lattice.positions.iter().copied());
+        lattice
+    }
+
+    fn index(&self, [i, j, k]: [usize; 3]) -> usize {
+        (i * LATTICE_NODES + j) * LATTICE_NODES + k
+    }
+
+    fn node(&self, index: usize) -> [usize; 3] {
+        [
+            index / (LATTICE_NODES * LATTICE_NODES),
+            index / LATTICE_NODES % LATTICE_NODES,
+            index % LATTICE_NODES,
+        ]
+    }
+
+    fn abstract_point(&self, index: usize) -> Vector3<f64> {
+        let node = self.node(index);
+        Vector3::from_fn(|axis, _| self.axes[axis][node[axis]])
+    }
+
+    /// Index of the node one step up `axis`, if it is inside the lattice.
+    fn neighbour(&self, index: usize, axis: usize) -> Option<usize> {
+        let mut node = self.node(index);
+        node[axis] += 1;
+        (node[axis] < LATTICE_NODES).then(|| self.index(node))
+    }
+
+    fn is_regular(&self, index: usize) -> bool {
+        self.determinants[index].is_some_and(|det| det.abs() > ZERO_TOLERANCE * self.scale)
+    }
+
+    fn tolerance(&self) -> f64 {
+        COINCIDENCE_TOLERANCE * self.extent.max(f64::EPSILON)
+    }
+
+    /// Draws the image of `COORD[axis] = value` as lattice lines, or as one point when the whole
+    /// surface collapses, as at a radial origin.
+    fn push_surface_image(&self, mesh: &mut SingularityMesh, axis: usize, value: f64) {
+        let (first, second) = ((axis + 1) % 3, (axis + 2) % 3);
+        let position = |s: f64, t: f64| {
+            let mut point = Vector3::zeros();
+            point[axis] = value;
+            point[first] = s;
+            point[second] = t;
+            self.geometry.eval_position(point)
+        };
+        let image = self.axes[first]
+            .iter()
+            .map(|&s| {
+                self.axes[second]
+                    .iter()
+                    .map(|&t| position(s, t))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        if extent(image.iter().flatten().copied()) <= self.tolerance() {
+            if image[0][0].iter().all(|value| value.is_finite()) {
+                mesh.points.push(image[0][0]);
+            }
+            return;
+        }
+        for (i, row) in image.iter().enumerate() {
+            for (j, &point) in row.iter().enumerate() {
+                if let Some(&next) = row.get(j + 1) {
+                    mesh.push_segment(point, next);
+                }
+                if let Some(next_row) = image.get(i + 1) {
+                    mesh.push_segment(point, next_row[j]);
+                }
+            }
+        }
+    }
+
+    /// Lists and draws samples where `det J` is zero or undefined that no factor root explains.
+    fn push_degenerate_samples(
+        &self,
+        analysis: &mut SingularityAnalysis,
+        explained: &impl Fn(Vector3<f64>) -> bool,
+    ) {
+        let flagged = (0..self.positions.len())
+            .map(|index| !self.is_regular(index) && !explained(self.abstract_point(index)))
+            .collect::<Vec<_>>();
+        let (undefined, vanishing): (Vec<_>, Vec<_>) = (0..flagged.len())
+            .filter(|&index| flagged[index])
+            .partition(|&index| self.determinants[index].is_none());
+        for (samples, what) in [
+            (&vanishing, "det J ≈ 0"),
+            (&undefined, "the coordinate tangents are undefined"),
+        ] {
+            if let Some(&first) = samples.first() {
+                analysis.sets.push(SingularSet {
+                    kind: SingularKind::Degenerate,
+                    symbolic: false,
+                    description: format!(
+                        "{what} at {} sampled points, e.g. {}",
+                        samples.len(),
+                        format_point(self.abstract_point(first))
+                    ),
+                });
+            }
+        }
+        self.push_flagged(&mut analysis.mesh, &flagged);
+    }
+
+    /// Bisects every lattice edge across which `det J` changes sign, then joins the crossings
+    /// on each lattice face, which traces the zero surface like marching squares.
+    fn push_sign_changes(
+        &self,
+        analysis: &mut SingularityAnalysis,
+        explained: &impl Fn(Vector3<f64>) -> bool,
+    ) {
+        let det_along = |start: Vector3<f64>, end: Vector3<f64>| {
+            move |fraction: f64| {
+                self.geometry
+                    .jacobian_determinant(start + (end - start) * fraction)
+                    .unwrap_or(0.0)
+            }
+        };
+        let mut crossings = FxHashMap::default();
+        for index in 0..self.positions.len() {
+            for axis in 0..3 {
+                let Some(next) = self.neighbour(index, axis) else {
+                    continue;
+                };
+                let (Some(det), Some(next_det)) =
+                    (self.determinants[index], self.determinants[next])
+                else {
+                    continue;
+                };
+                if !self.is_regular(index) || !self.is_regular(next) || det * next_det >= 0.0 {
+                    continue;
+                }
+                let (start, end) = (self.abstract_point(index), self.abstract_point(next));
+                let crossing = start + (end - start) * bisect(det_along(start, end), 0.0, 1.0);
+                if !explained(crossing) {
+                    crossings.insert((index, axis), crossing);
+                }
+            }
+        }
+        let Some(example) = crossings.values().next().copied() else {
+            return;
+        };
+        analysis.sets.push(SingularSet {
+            kind: SingularKind::Degenerate,
+            symbolic: false,
+            description: format!(
+                "det J changes sign across a surface, bisected on {} lattice edges, e.g. near {}",
+                crossings.len(),
+                format_point(example)
+            ),
+        });
+
+        for index in 0..self.positions.len() {
+            for (a, b) in [(0, 1), (1, 2), (0, 2)] {
+                let (Some(up_a), Some(up_b)) = (self.neighbour(index, a), self.neighbour(index, b))
+                else {
+                    continue;
+                };
+                let face = [(index, a), (index, b), (up_a, b), (up_b, a)]
+                    .iter()
+                    .filter_map(|edge| crossings.get(edge))
+                    .map(|&point| self.geometry.eval_position(point))
+                    .collect::<Vec<_>>();
+                for pair in face.chunks_exact(2) {
+                    analysis.mesh.push_segment(pair[0], pair[1]);
+                }
+            }
+        }
+    }
+
+    /// Lists and draws regular samples whose world positions coincide, such as both sides of
+    /// the seam of an angle that spans a full turn.
+    fn push_coincidences(&self, analysis: &mut SingularityAnalysis) {
+        let cell = self.tolerance();
+        let key = |point: Vector3<f64>| point.map(|value| (value / cell).floor() as i64);
+        let mut cells = FxHashMap::<Vector3<i64>, Vec<usize>>::default();
+        let regular = (0..self.positions.len())
+            .filter(|&index| {
+                self.is_regular(index) && self.positions[index].iter().all(|v| v.is_finite())
+            })
+            .collect::<Vec<_>>();
+        for &index in &regular {
+            cells
+                .entry(key(self.positions[index]))
+                .or_default()
+                .push(index);
+        }
+
+        let mut pairs = Vec::new();
+        for &index in &regular {
+            let home = key(self.positions[index]);
+            for offset in (0..27).map(|n| Vector3::new(n / 9 - 1, n / 3 % 3 - 1, n % 3 - 1)) {
+                for &other in cells.get(&(home + offset)).into_iter().flatten() {
+                    if other > index
+                        && (self.positions[other] - self.positions[index]).norm() <= cell
+                    {
+                        pairs.push((index, other));
+                    }
+                }
+            }
+        }
+        let Some(&(first, second)) = pairs.first() else {
+            return;
+        };
+
+        let description = match self.common_seam(&pairs) {
+            Some(axis) => {
+                let (min, max) = (self.axes[axis][0], self.axes[axis][LATTICE_NODES - 1]);
+                format!(
+                    "{name} = {min:.4} and {name} = {max:.4} map to the same points: the grid \
+                     wraps around and its seam is drawn twice",
+                    name = COORD[axis]
+                )
+            }
+            None => format!(
+                "{} pairs of sampled points share a world position, e.g. {} and {}",
+                pairs.len(),
+                format_point(self.abstract_point(first)),
+                format_point(self.abstract_point(second))
+            ),
+        };
+        analysis.sets.push(SingularSet {
+            kind: SingularKind::NonInjective,
+            symbolic: false,
+            description,
+        });
+
+        let mut flagged = vec![false; self.positions.len()];
+        for (index, other) in pairs {
+            flagged[index] = true;
+            flagged[other] = true;
+        }
+        self.push_flagged(&mut analysis.mesh, &flagged);
+    }
+
+    /// Returns the axis when every pair joins the two bound faces of that axis, node for node.
+    fn common_seam(&self, pairs: &[(usize, usize)]) -> Option<usize> {
+        let seam = |&(index, other): &(usize, usize)| {
+            let (node, other) = (self.node(index), self.node(other));
+            let differing = (0..3)
+                .filter(|&axis| node[axis] != other[axis])
+                .collect::<Vec<_>>();
+            match differing.as_slice() {
+                [axis]
+                    if node[*axis].min(other[*axis]) == 0
+                        && node[*axis].max(other[*axis]) == LATTICE_NODES - 1 =>
+                {
+                    Some(*axis)
+                }
+                _ => None,
+            }
+        };
+        let axis = seam(&pairs[0])?;
+        pairs
+            .iter()
+            .all(|pair| seam(pair) == Some(axis))
+            .then_some(axis)
+    }
+
+    /// Joins flagged lattice neighbours with segments and marks flagged samples left alone.
+    fn push_flagged(&self, mesh: &mut SingularityMesh, flagged: &[bool]) {
+        for index in (0..flagged.len()).filter(|&index| flagged[index]) {
+            let mut joined = false;
+            for axis in 0..3 {
+                let below = {
+                    let mut node = self.node(index);
+                    (node[axis] > 0).then(|| {
+                        node[axis] -= 1;
+                        self.index(node)
+                    })
+                };
+                joined |= below.is_some_and(|below| flagged[below]);
+                if let Some(next) = self.neighbour(index, axis).filter(|&next| flagged[next]) {
+                    mesh.push_segment(self.positions[index], self.positions[next]);
+                    joined = true;
+                }
+            }
+            if !joined && self.positions[index].iter().all(|value| value.is_finite()) {
+                mesh.points.push(self.positions[index]);
+            }
+        }
+    }
+}
+
+/// Diagonal of the box around the finite `points`, zero when there are none.
+fn extent(points: impl Iterator<Item = Vector3<f64>>) -> f64 {
+    let finite = points
+        .filter(|point| point.iter().all(|value| value.is_finite()))
+        .collect::<Vec<_>>();
+    let Some(&first) = finite.first() else {
+        return 0.0;
+    };
+    let (min, max) = finite.iter().fold((first, first), |(min, max), point| {
+        (min.inf(point), max.sup(point))
+    });
+    (max - min).norm()
+}
+
+fn format_point(point: Vector3<f64>) -> String {
+    format!("({:.3}, {:.3}, {:.3})", point.x, point.y, point.z)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{analyze_singularities, factor_roots};
+    use crate::app::coords_sys::CoordsSys;
+    use crate::app::grid::GridConfig;
+    use crate::app::ui::SingularKind;
+    use mathhook_core::Parser;
+    use std::f64::consts::PI;
+
+    fn coords(x: &str, y: &str, z: &str) -> CoordsSys {
+        let parse = |expr: &str| Parser::default().parse(expr).unwrap();
+        CoordsSys::new(parse(x), parse(y), parse(z))
+    }
+
+    #[test]
+    fn factor_roots_locate_zeros_of_one_coordinate_factors() {
+        let det = Parser::default().parse("x^2*sin(z)*(x + y)").unwrap();
+
+        let roots = factor_roots(&det, [(-1.0, 2.0), (0.0, 1.0), (0.5, 7.0)]).unwrap();
+
+        let found = roots
+            .iter()
+            .map(|root| (root.axis, root.value))
+            .collect::<Vec<_>>();
+        assert_eq!(found.len(), 3, "{found:?}");
+        assert!(found
+            .iter()
+            .any(|&(axis, value)| axis == 0 && value.abs() < 1.0e-9));
+        assert!(found
+            .iter()
+            .any(|&(axis, value)| axis == 2 && (value - PI).abs() < 1.0e-9));
+        assert!(found
+            .iter()
+            .any(|&(axis, value)| axis == 2 && (value - 2.0 * PI).abs() < 1.0e-9));
+        assert!(factor_roots(&Parser::default().parse("0").unwrap(), [(0.0, 1.0); 3]).is_none());
+    }
+
+    #[test]
+    fn factor_roots_pull_common_factors_out_of_sums() {
+        let det = Parser::default()
+            .parse("x^2*sin(z)^3*cos(y)^2 + x^2*sin(z)^3*sin(y)^2 + x^2*sin(z)*cos(z)^2")
+            .unwrap();
+
+        let roots = factor_roots(&det, [(-1.0, 2.0), (0.0, 1.0), (0.5, 4.0)]).unwrap();
+
+        let found = roots
+            .iter()
+            .map(|root| (root.axis, root.value))
+            .collect::<Vec<_>>();
+        assert_eq!(found.len(), 2, "{found:?}");
+        assert!(found
+            .iter()
+            .any(|&(axis, value)| axis == 0 && value.abs() < 1.0e-9));
+        assert!(found
+            .iter()
+            .any(|&(axis, value)| axis == 2 && (value - PI).abs() < 1.0e-9));
+    }
+
+    #[test]
+    fn cartesian_grid_has_no_singular_sets() {
+        let config = GridConfig::new(-1.0, 1.0, 3.0, -1.0, 1.0, 3.0, -1.0, 1.0, 3.0);
+
+        let analysis = analyze_singularities(&coords("x", "y", "z"), &config);
+
+        assert!(analysis.sets.is_empty(), "{:?}", analysis.sets);
+        assert!(analysis.mesh.is_empty());
+    }
+
+    #[test]
+    fn spherical_grid_reports_poles_and_the_azimuthal_seam() {
+        let spherical = coords("x*cos(y)*sin(z)", "x*sin(y)*sin(z)", "x*cos(z)");
+        let config = GridConfig::new(0.0, 2.0, 3.0, 0.0, 2.0 * PI, 3.0, 0.0, PI, 3.0);
+
+        let analysis = analyze_singularities(&spherical, &config);
+
+        let symbolic = analysis
+            .sets
+            .iter()
+            .filter(|set| set.kind == SingularKind::Degenerate && set.symbolic)
+            .map(|set| set.description.as_str())
+            .collect::<Vec<_>>();
+        for surface in ["x = 0.0000", "z = 0.0000", "z = 3.1416"] {
+            assert!(
+                symbolic
+                    .iter()
+                    .any(|description| description.starts_with(&format!("det J = 0 on {surface}"))),
+                "{surface} missing from {symbolic:?}"
+            );
+        }
+        let seam = analysis
+            .sets
+            .iter()
+            .find(|set| set.kind == SingularKind::NonInjective)
+            .expect("the y seam is shared");
+        assert!(seam.description.starts_with("y = 0.0000 and y = 6.2832"));
+        assert!(!analysis.mesh.indices.is_empty());
+        assert!(analysis
+            .mesh
+            .vertices
+            .iter()
+            .all(|vertex| vertex.iter().all(|value| value.is_finite())));
+    }
+
+    #[test]
+    fn sign_change_of_det_j_is_bisected_onto_the_zero_surface() {
+        let cylindrical = coords("x*cos(y)", "x*sin(y)", "z");
+        let config = GridConfig::new(-1.0, 1.0, 3.0, 0.0, 1.0, 3.0, 0.0, 1.0, 3.0);
+
+        let analysis = analyze_singularities(&cylindrical, &config);
+
+        assert!(analysis
+            .sets
+            .iter()
+            .any(|set| set.kind == SingularKind::Degenerate));
+        assert!(analysis
+            .mesh
+            .points
+            .iter()
+            .all(|point| point.x.abs() < 1.0e-6 && point.y.abs() < 1.0e-6));
+        assert!(analysis
+            .mesh
+            .vertices
+            .iter()
+            .all(|vertex| { vertex[0].abs() < 1.0e-5 && vertex[1].abs() < 1.0e-5 }));
+        assert!(!analysis.mesh.is_empty());
+    }
+}
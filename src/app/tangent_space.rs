@@ -6,7 +6,7 @@ mod types;
 #[cfg(test)]
 mod tests;
 
-use crate::app::coords_sys::CoordsSys;
+use crate::app::coords_sys::{CoordsSys, InverseFailure};
 use crate::app::grid_world::{GridSample, GridWorld};
 use crate::app::ui::legend::sampled_value_color;
 use crate::app::ui::{LegendKind, LegendState};
@@ -36,6 +36,8 @@ const DEFAULT_GEOMETRIC_ARROW_SCALE: f64 = 0.55;
 
 pub struct TangentSpace {
     hovered_sample: Option<GridSample>,
+    /// Why the hovered point could not be inverted exactly, leaving the sample snapped.
+    hover_failure: Option<InverseFailure>,
     dive: DiveState,
     geometric_local_scale: f64,
     geometric_arrow_scale: f64,
@@ -49,6 +51,7 @@ impl TangentSpace {
     pub fn new() -> Self {
         Self {
             hovered_sample: None,
+            hover_failure: None,
             dive: DiveState::new(),
             geometric_local_scale: DEFAULT_GEOMETRIC_LOCAL_SCALE,
             geometric_arrow_scale: DEFAULT_GEOMETRIC_ARROW_SCALE,
@@ -84,7 +87,9 @@ impl TangentSpace {
     /// This code is deliberately self-contained and lock-free: it consumes the already-owned
     /// camera, display snapshot, grid lookup, and coordinate system, then updates only local
     /// tangent state. In world mode it performs hover picking; in tangent mode it preserves the
-    /// dive camera relationship while still allowing user translation.
+    /// dive camera relationship while still allowing user translation. `bounds` are the applied
+    /// grid bounds the hovered point is inverted within.
+    #[allow(clippy::too_many_arguments)]
     pub fn update(
         &mut self,
         input: &Input,
@@ -93,14 +98,23 @@ impl TangentSpace {
         display_manager: &DisplayManager,
         grid_world: &GridWorld,
         coords: &CoordsSys,
+        bounds: &[(f64, f64); 3],
         projection: Matrix4<f64>,
     ) {
         let requested_view = requested_view(input);
         match self.dive.mode {
             DiveMode::World => {
                 camera.update(input);
-                self.hovered_sample =
-                    self.pick_hover_sample(camera, display_manager, grid_world, projection);
+                let picked = self.pick_hover_sample(
+                    camera,
+                    display_manager,
+                    grid_world,
+                    coords,
+                    bounds,
+                    projection,
+                );
+                self.hover_failure = picked.as_ref().and_then(|(_, failure)| *failure);
+                self.hovered_sample = picked.map(|(sample, _)| sample);
                 if let Some(view) = requested_view {
                     if let Some(sample) = self.hovered_sample.clone() {
                         self.start_enter(camera, coords, sample, view);
@@ -221,6 +235,21 @@ impl TangentSpace {
         }
     }
 
+    /// Returns the abstract-space position of the grid sample under the cursor, if any.
+    ///
+    /// Only world mode picks samples, so this is `None` during a dive.
+    pub fn hovered_abstract_position(&self) -> Option<Vector3<f64>> {
+        self.hovered_sample
+            .as_ref()
+            .map(|sample| sample.abstract_pos)
+    }
+
+    /// Returns why the hovered position is the snapped grid sample rather than the exact
+    /// preimage of the point under the cursor.
+    pub fn hover_failure(&self) -> Option<InverseFailure> {
+        self.hovered_sample.as_ref().and(self.hover_failure)
+    }
+
     /// Returns the abstract-space position of the active tangent anchor, if any.
     ///
     /// Callers use this to sample fields and build tangent-only overlays around the anchor.
@@ -418,16 +447,31 @@ impl TangentSpace {
     /// Casts the current mouse ray into the sampled grid and returns the hovered sample.
     ///
     /// The display manager and projection matrix are used to convert the cursor position into a
-    /// world-space ray first.
+    /// world-space ray first. The point of the ray closest to the hit sample is then inverted to
+    /// exact abstract coordinates; where that fails, the snapped sample is returned with the
+    /// reason.
     fn pick_hover_sample(
         &self,
         camera: &Camera,
         display_manager: &DisplayManager,
         grid_world: &GridWorld,
+        coords: &CoordsSys,
+        bounds: &[(f64, f64); 3],
         projection: Matrix4<f64>,
-    ) -> Option<GridSample> {
-        let mouse_info = camera.mouse_pos_to_world_pos(display_manager, projection);
-        grid_world.ray_cast(&mouse_info.0, &mouse_info.1, PICK_RADIUS, PICK_LENGTH)
+    ) -> Option<(GridSample, Option<InverseFailure>)> {
+        let (origin, direction) = camera.mouse_pos_to_world_pos(display_manager, projection);
+        let sample = grid_world.ray_cast(&origin, &direction, PICK_RADIUS, PICK_LENGTH)?;
+        let hit = origin + direction * (sample.world_pos - origin).dot(&direction);
+        match coords.inverse(hit, grid_world, bounds) {
+            Ok(abstract_pos) => Some((
+                GridSample {
+                    world_pos: coords.eval_position(abstract_pos),
+                    abstract_pos,
+                },
+                None,
+            )),
+            Err(failure) => Some((sample, Some(failure))),
+        }
     }
 
     /// Initializes a new dive from world space into one tangent view.
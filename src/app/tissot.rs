@@ -0,0 +1,196 @@
+//! Tissot indicatrices: how the embedding stretches a small abstract ball at each field sample.
+//!
+//! The eigenvectors `v_i` of the metric `g = JᵀJ` are the principal stretch directions and the
+//! square roots `σ_i` of its eigenvalues are the singular values of `J`. A ball of radius `r`
+//! around the sample is mapped to an ellipsoid with semi-axes `r J v_i`, of lengths `r σ_i`,
+//! which the glyph draws by scaling the unit sphere with those three columns.
+
+use crate::app::coords_sys::CoordSampleGeometry;
+use crate::app::field_render::FieldSample;
+use crate::app::ui::legend::sampled_value_color;
+use crate::app::ui::TissotReport;
+use crate::graphics::model::Sphere;
+use nalgebra::{Matrix3, Vector3};
+
+/// Relative spread of the stretches, or of their product around one, that still counts as
+/// conformal or volume-preserving.
+const DISTORTION_TOLERANCE: f64 = 1.0e-3;
+
+/// Principal stretches of the embedding at one field sample.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Indicatrix {
+    pub(crate) abstract_pos: Vector3<f64>,
+    pub(crate) world_pos: Vector3<f64>,
+    /// World semi-axes `J v_i` of the image of the unit ball, longest first.
+    pub(crate) axes: [Vector3<f64>; 3],
+    /// Singular values `σ_i`, largest first.
+    pub(crate) stretches: [f64; 3],
+}
+
+impl Indicatrix {
+    /// `σ_max / σ_min`; infinite where a direction is crushed, even if every direction is.
+    fn anisotropy(&self) -> f64 {
+        if self.stretches[2] == 0.0 {
+            f64::INFINITY
+        } else {
+            self.stretches[0] / self.stretches[2]
+        }
+    }
+
+    /// `σ₁σ₂σ₃ = |det J|`, the factor by which small volumes grow.
+    fn volume_ratio(&self) -> f64 {
+        self.stretches.iter().product()
+    }
+
+    fn is_conformal(&self) -> bool {
+        self.anisotropy() - 1.0 < DISTORTION_TOLERANCE
+    }
+
+    fn is_volume_preserving(&self) -> bool {
+        (self.volume_ratio() - 1.0).abs() < DISTORTION_TOLERANCE
+    }
+}
+
+/// Decomposes the metric at `sample`; `None` where the metric is not finite.
+fn indicatrix(geometry: &CoordSampleGeometry, sample: &FieldSample) -> Option<Indicatrix> {
+    let metric = geometry.metric(sample.abstract_pos)?;
+    let jacobian = geometry.jacobian(sample.abstract_pos)?;
+    if !metric
+        .iter()
+        .chain(jacobian.iter())
+        .all(|entry| entry.is_finite())
+    {
+        return None;
+    }
+    let eigen = metric.symmetric_eigen();
+    let mut order = [0, 1, 2];
+    order.sort_by(|&a, &b| eigen.eigenvalues[b].total_cmp(&eigen.eigenvalues[a]));
+    Some(Indicatrix {
+        abstract_pos: sample.abstract_pos,
+        world_pos: sample.world_pos,
+        axes: order.map(|index| jacobian * eigen.eigenvectors.column(index)),
+        stretches: order.map(|index| eigen.eigenvalues[index].max(0.0).sqrt()),
+    })
+}
+
+pub(crate) fn build_indicatrices(
+    geometry: &CoordSampleGeometry,
+    samples: &[FieldSample],
+) -> Vec<Indicatrix> {
+    samples
+        .iter()
+        .filter_map(|sample| indicatrix(geometry, sample))
+        .collect()
+}
+
+/// Counts conformal and volume-preserving samples; `None` without samples.
+pub(crate) fn tissot_report(indicatrices: &[Indicatrix]) -> Option<TissotReport> {
+    let most_anisotropic = indicatrices
+        .iter()
+        .max_by(|a, b| a.anisotropy().total_cmp(&b.anisotropy()))?;
+    Some(TissotReport {
+        samples: indicatrices.len(),
+        conformal: indicatrices
+            .iter()
+            .filter(|indicatrix| indicatrix.is_conformal())
+            .count(),
+        volume_preserving: indicatrices
+            .iter()
+            .filter(|indicatrix| indicatrix.is_volume_preserving())
+            .count(),
+        max_anisotropy: most_anisotropic.anisotropy(),
+        max_anisotropy_at: most_anisotropic.abstract_pos.into(),
+    })
+}
+
+/// Ellipsoids of abstract radius `radius`, colored on the diverging ramp by `ln |det J|`: blue
+/// where volumes shrink, white where they are preserved and red where they grow.
+pub(crate) fn tissot_glyphs(indicatrices: &[Indicatrix], radius: f64) -> Vec<Sphere> {
+    let log_volume =
+        |indicatrix: &Indicatrix| indicatrix.volume_ratio().max(f64::MIN_POSITIVE).ln();
+    let spread = indicatrices
+        .iter()
+        .map(log_volume)
+        .fold(0.0_f64, |spread, value| spread.max(value.abs()));
+    indicatrices
+        .iter()
+        .map(|indicatrix| {
+            Sphere::from_axes(
+                indicatrix.world_pos,
+                Matrix3::from_columns(&indicatrix.axes) * radius,
+                sampled_value_color(log_volume(indicatrix), -spread, spread),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build_indicatrices, tissot_report};
+    use crate::app::coords_sys::CoordsSys;
+    use crate::app::field_render::FieldSample;
+    use mathhook_core::Parser;
+    use nalgebra::{vector, Vector3};
+
+    fn samples(coords: &CoordsSys, points: &[Vector3<f64>]) -> Vec<FieldSample> {
+        points
+            .iter()
+            .map(|&abstract_pos| FieldSample {
+                abstract_pos,
+                world_pos: coords.eval_position(abstract_pos),
+                basis: coords.eval_tangent_basis(abstract_pos),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn polar_coordinates_stretch_the_angle_by_the_radius() {
+        let parse = |expr: &str| Parser::default().parse(expr).unwrap();
+        let polar = CoordsSys::new(parse("x*cos(y)"), parse("x*sin(y)"), parse("z"));
+        let points = [vector![1.0, 0.4, 0.0], vector![3.0, 0.4, 0.0]];
+        let indicatrices = build_indicatrices(&polar.sample_geometry(), &samples(&polar, &points));
+
+        let far = &indicatrices[1];
+        assert!((far.stretches[0] - 3.0).abs() < 1.0e-9);
+        assert!((far.stretches[1] - 1.0).abs() < 1.0e-9);
+        assert!((far.stretches[2] - 1.0).abs() < 1.0e-9);
+        // The longest semi-axis is the image of `∂_y`, tangent to the circle of radius three.
+        assert!(far.axes[0].dot(&far.world_pos).abs() < 1.0e-9);
+        assert!((far.axes[0].norm() - 3.0).abs() < 1.0e-9);
+
+        let report = tissot_report(&indicatrices).unwrap();
+        assert_eq!(report.samples, 2);
+        assert_eq!(report.conformal, 1);
+        assert_eq!(report.volume_preserving, 1);
+        assert!((report.max_anisotropy - 3.0).abs() < 1.0e-9);
+        assert_eq!(report.max_anisotropy_at, [3.0, 0.4, 0.0]);
+    }
+
+    #[test]
+    fn a_uniform_scaling_is_conformal_but_not_volume_preserving() {
+        let parse = |expr: &str| Parser::default().parse(expr).unwrap();
+        let scaled = CoordsSys::new(parse("2*x"), parse("2*y"), parse("2*z"));
+        let points = [vector![0.5, -1.0, 2.0]];
+        let indicatrices =
+            build_indicatrices(&scaled.sample_geometry(), &samples(&scaled, &points));
+
+        let report = tissot_report(&indicatrices).unwrap();
+        assert_eq!((report.conformal, report.volume_preserving), (1, 0));
+        assert!(tissot_report(&[]).is_none());
+    }
+
+    #[test]
+    fn a_fully_collapsed_sample_is_the_most_anisotropic() {
+        let parse = |expr: &str| Parser::default().parse(expr).unwrap();
+        let squares = CoordsSys::new(parse("x*x"), parse("y*y"), parse("z*z"));
+        let points = [vector![0.5, 0.5, 0.5], vector![0.0, 0.0, 0.0]];
+        let indicatrices =
+            build_indicatrices(&squares.sample_geometry(), &samples(&squares, &points));
+
+        assert_eq!(indicatrices[1].stretches, [0.0; 3]);
+        let report = tissot_report(&indicatrices).unwrap();
+        assert_eq!(report.max_anisotropy, f64::INFINITY);
+        assert_eq!(report.max_anisotropy_at, [0.0, 0.0, 0.0]);
+        assert_eq!(report.conformal, 1);
+    }
+}
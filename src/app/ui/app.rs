@@ -1,10 +1,24 @@
 //! egui control panel for editing the grid, field, and tangent-view settings.
 
+mod atlas;
+mod conductors;
+mod em_reports;
+mod em_tab;
+mod guided_modes;
+mod inspector;
+mod line_coloring;
+mod morph;
+mod probe_panel;
+mod scene_time;
+mod singularities;
 mod tabs;
+mod timeline;
+mod tissot;
+mod vector_input;
 
 use crate::app::ui::legend::show_legend_window;
 use crate::app::ui::presets::{EmPreset, FieldPreset, GridPreset};
-use crate::app::ui::state::{ControlTab, FieldKind, GridUiState};
+use crate::app::ui::state::{ChartPreset, ControlTab, FieldKind, GridBound, GridUiState};
 use crate::app::ui::theme::{
     self, ACCENT, BORDER, CRAYOLA_BLUE, JET_BLACK, MUTED, PANEL, RASPBERRY, SHADOW_GREY, TEXT,
 };
@@ -35,6 +49,12 @@ impl PresetLabel for EmPreset {
     }
 }
 
+impl PresetLabel for ChartPreset {
+    fn label(self) -> &'static str {
+        ChartPreset::label(self)
+    }
+}
+
 pub(crate) struct ControlApp {
     state: Arc<Mutex<GridUiState>>,
     styled: bool,
@@ -89,9 +109,13 @@ impl ControlApp {
         match validate_ui_state(data) {
             Ok(validated) => {
                 data.coords_sys = validated.coords_sys;
+                data.morph.target = validated.morph_target;
                 data.scalar_field = validated.scalar_field;
                 data.field = validated.field;
                 data.em = validated.em;
+                [data.bounds_x, data.bounds_y, data.bounds_z] = validated.bounds;
+                data.line_spacing = validated.line_spacing;
+                data.atlas.charts = validated.charts;
                 data.apply_counter += 1;
             }
             Err(error) => *error_popup = Some(error),
@@ -236,20 +260,20 @@ impl ControlApp {
     }
 
     /// Renders the min and max editors for one axis bound pair.
-    fn bounds_row(ui: &mut egui::Ui, label: &str, bounds: &mut (f64, f64)) {
+    fn bounds_row(ui: &mut egui::Ui, label: &str, bounds: &mut (GridBound, GridBound)) {
         ui.horizontal(|ui| {
             ui.label(egui::RichText::new(label).color(TEXT));
-            ui.add(
-                egui::DragValue::new(&mut bounds.0)
-                    .speed(0.1)
-                    .range(-100.0..=100.0),
-            );
-            ui.label("to");
-            ui.add(
-                egui::DragValue::new(&mut bounds.1)
-                    .speed(0.1)
-                    .range(-100.0..=100.0),
-            );
+            for (index, bound) in [&mut bounds.0, &mut bounds.1].into_iter().enumerate() {
+                if index == 1 {
+                    ui.label("to");
+                }
+                ui.add(
+                    egui::TextEdit::singleline(&mut bound.text)
+                        .desired_width(64.0)
+                        .hint_text("e.g. pi/2")
+                        .text_color(TEXT),
+                );
+            }
         });
     }
 
@@ -279,7 +303,7 @@ impl eframe::App for ControlApp {
         show_legend_window(ctx, legend);
     }
 
-    /// Renders the central control panel and any active error popup.
+    /// Renders the central control panel, the point inspector, and any active error popup.
     fn ui(&mut self, ui: &mut egui::Ui, _frame: &mut eframe::Frame) {
         egui::Frame::central_panel(ui.style()).show(ui, |ui| {
             egui::ScrollArea::vertical()
@@ -289,6 +313,8 @@ impl eframe::App for ControlApp {
                 });
         });
         self.show_error_popup(ui.ctx());
+        let mut data = self.state.lock().expect("UI state poisoned");
+        Self::show_inspector_window(ui.ctx(), &mut data);
     }
 
     /// Requests periodic repainting so the control window stays responsive.
@@ -0,0 +1,156 @@
+//! Grid-tab editor of the overlay charts, their transition maps and overlaps.
+
+use super::ControlApp;
+use crate::app::ui::state::{ChartOverlap, ChartPreset, GridUiState, PRIMARY_CHART_NAME};
+use crate::app::ui::theme::{self, MUTED, TEXT};
+use eframe::egui;
+
+impl ControlApp {
+    /// Lists the extra charts drawn over the primary grid, one editor per chart.
+    pub(super) fn render_atlas_section(ui: &mut egui::Ui, data: &mut GridUiState) {
+        let heading = if data.atlas.charts.is_empty() {
+            "Atlas".to_string()
+        } else {
+            format!("Atlas ({} charts)", data.atlas.charts.len() + 1)
+        };
+        egui::CollapsingHeader::new(theme::section_heading(&heading))
+            .id_salt("atlas_section")
+            .default_open(false)
+            .show(ui, |ui| {
+                ui.checkbox(
+                    &mut data.atlas.show_charts,
+                    egui::RichText::new("Show overlay charts").color(TEXT),
+                );
+                ui.label(
+                    egui::RichText::new(
+                        "Each chart has its own embedding, bounds and line counts and is drawn \
+                         in one color over the primary grid, in world view only. Updates on Apply.",
+                    )
+                    .color(MUTED),
+                );
+                Self::preset_buttons(
+                    ui,
+                    ChartPreset::ALL,
+                    |preset, data| data.atlas.add_chart(preset),
+                    data,
+                );
+
+                let mut removed = None;
+                for (index, chart) in data.atlas.charts.iter_mut().enumerate() {
+                    ui.separator();
+                    ui.horizontal(|ui| {
+                        ui.color_edit_button_rgb(&mut chart.color);
+                        ui.add(
+                            egui::TextEdit::singleline(&mut chart.name)
+                                .desired_width(140.0)
+                                .text_color(TEXT),
+                        );
+                        if ui.small_button("Remove").clicked() {
+                            removed = Some(index);
+                        }
+                    });
+                    Self::eq_row(ui, "x =", &mut chart.coords_sys.x.eq_str);
+                    Self::eq_row(ui, "y =", &mut chart.coords_sys.y.eq_str);
+                    Self::eq_row(ui, "z =", &mut chart.coords_sys.z.eq_str);
+                    for (axis, (bounds, count)) in ["x", "y", "z"]
+                        .into_iter()
+                        .zip(chart.bounds.iter_mut().zip(&mut chart.counts))
+                    {
+                        ui.horizontal(|ui| {
+                            Self::bounds_row(ui, &format!("Bounds ({axis}):"), bounds);
+                            ui.add(
+                                egui::DragValue::new(count)
+                                    .speed(0.1)
+                                    .range(1.0..=40.0)
+                                    .suffix(" lines"),
+                            );
+                        });
+                    }
+                }
+                if let Some(index) = removed {
+                    data.atlas.remove_chart(index);
+                }
+
+                if !data.atlas.charts.is_empty() {
+                    ui.separator();
+                    Self::push_field_selector(ui, data);
+                }
+                Self::render_chart_overlaps(ui, &data.chart_overlaps);
+            });
+    }
+
+    /// Picks the chart the Field-tab vector field is carried onto by the transition map.
+    fn push_field_selector(ui: &mut egui::Ui, data: &mut GridUiState) {
+        let selected = data
+            .atlas
+            .push_field_to
+            .and_then(|index| data.atlas.charts.get(index))
+            .map_or("Nowhere", |chart| chart.name.as_str())
+            .to_string();
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new("Push field onto").color(TEXT));
+            egui::ComboBox::from_id_salt("push_field_to")
+                .selected_text(selected)
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut data.atlas.push_field_to, None, "Nowhere");
+                    for (index, chart) in data.atlas.charts.iter().enumerate() {
+                        ui.selectable_value(
+                            &mut data.atlas.push_field_to,
+                            Some(index),
+                            &chart.name,
+                        );
+                    }
+                });
+        });
+        ui.label(
+            egui::RichText::new(
+                "Arrows at the chart's lattice points show the primary vector field carried \
+                 over by the transition map; samples outside the primary bounds are skipped.",
+            )
+            .color(MUTED),
+        );
+    }
+
+    fn render_chart_overlaps(ui: &mut egui::Ui, overlaps: &[ChartOverlap]) {
+        if overlaps.is_empty() {
+            return;
+        }
+        ui.separator();
+        ui.label(egui::RichText::new("Overlaps").color(MUTED));
+        for overlap in overlaps {
+            let [first, second] = &overlap.charts;
+            let summary = match overlap.world_box {
+                None => "disjoint within their bounds".to_string(),
+                Some((min, max)) => format!(
+                    "{}/{} and {}/{} lattice points shared, within x {:.2}..{:.2}, \
+                     y {:.2}..{:.2}, z {:.2}..{:.2}",
+                    overlap.shared[0],
+                    overlap.totals[0],
+                    overlap.shared[1],
+                    overlap.totals[1],
+                    min[0],
+                    max[0],
+                    min[1],
+                    max[1],
+                    min[2],
+                    max[2],
+                ),
+            };
+            ui.horizontal_wrapped(|ui| {
+                ui.label(
+                    egui::RichText::new(format!("{first} ∩ {second}"))
+                        .color(TEXT)
+                        .strong(),
+                );
+                ui.label(egui::RichText::new(summary).color(TEXT));
+            });
+        }
+        ui.label(
+            egui::RichText::new(format!(
+                "{PRIMARY_CHART_NAME} is the Grid-tab coordinate system."
+            ))
+            .color(MUTED)
+            .small(),
+        );
+    }
+}
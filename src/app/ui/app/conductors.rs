@@ -0,0 +1,54 @@
+//! Conductor regions and the source-domain mask of the EM tab.
+
+use super::ControlApp;
+use crate::app::ui::state::GridUiState;
+use crate::app::ui::theme::{MUTED, TEXT};
+use eframe::egui;
+
+impl ControlApp {
+    pub(super) fn conductor_controls(ui: &mut egui::Ui, data: &mut GridUiState) {
+        ui.label(
+            egui::RichText::new(
+                "Regions f(x, y, z) <= 0 in world coordinates. Source cells inside a conductor or \
+                 the mask are dropped from the inverse curl. Affine conductors (plates) place \
+                 image sources so that tangential E and normal B vanish on their surface. \
+                 Sphere quadrics such as x^2 + y^2 + z^2 - 1 enforce normal B only; other \
+                 shapes only mask.",
+            )
+            .color(MUTED),
+        );
+
+        let mut removed = None;
+        for (index, conductor) in data.em.conductors.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                Self::eq_row(
+                    ui,
+                    &format!("Conductor {}:  f =", index + 1),
+                    &mut conductor.eq_str,
+                );
+                if ui.small_button("Remove").clicked() {
+                    removed = Some(index);
+                }
+            });
+        }
+        if let Some(index) = removed {
+            data.em.conductors.remove(index);
+        }
+        ui.horizontal(|ui| {
+            if Self::compact_button(ui, "Add plate") {
+                data.em.add_conductor("z + 1");
+            }
+            if Self::compact_button(ui, "Add sphere") {
+                data.em.add_conductor("x^2 + y^2 + z^2 - 1");
+            }
+        });
+
+        ui.add_space(4.0);
+        Self::eq_row(ui, "Domain mask:  f =", &mut data.em.domain_mask.eq_str);
+        ui.label(
+            egui::RichText::new("The default mask, 1, excludes nothing.")
+                .color(TEXT)
+                .small(),
+        );
+    }
+}
@@ -0,0 +1,111 @@
+//! Read-only EM check reports shown in the EM tab.
+
+use super::ControlApp;
+use crate::app::ui::state::{CovariantReport, GaugeReport};
+use crate::app::ui::theme::{MUTED, TEXT};
+use eframe::egui;
+
+impl ControlApp {
+    /// Shows original and transformed potentials side by side with the invariance check.
+    pub(super) fn gauge_report_grid(ui: &mut egui::Ui, report: &GaugeReport) {
+        egui::Grid::new("gauge_report_grid")
+            .num_columns(3)
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label(egui::RichText::new("").color(TEXT));
+                ui.label(egui::RichText::new("Original").color(TEXT).strong());
+                ui.label(egui::RichText::new("Transformed").color(TEXT).strong());
+                ui.end_row();
+
+                let rows = [
+                    ("V", &report.original_phi, &report.transformed_phi),
+                    (
+                        "Ax",
+                        &report.original_vector_potential[0],
+                        &report.transformed_vector_potential[0],
+                    ),
+                    (
+                        "Ay",
+                        &report.original_vector_potential[1],
+                        &report.transformed_vector_potential[1],
+                    ),
+                    (
+                        "Az",
+                        &report.original_vector_potential[2],
+                        &report.transformed_vector_potential[2],
+                    ),
+                ];
+                for (name, original, transformed) in rows {
+                    ui.label(egui::RichText::new(name).color(TEXT));
+                    ui.label(egui::RichText::new(original.as_str()).color(MUTED));
+                    ui.label(egui::RichText::new(transformed.as_str()).color(MUTED));
+                    ui.end_row();
+                }
+            });
+        let deviation = |value: Option<f64>| match value {
+            Some(value) => format!("{value:.3e}"),
+            None => "layer hidden".to_string(),
+        };
+        ui.label(
+            egui::RichText::new(format!(
+                "max |dE| = {}   max |dB| = {}",
+                deviation(report.max_electric_deviation),
+                deviation(report.max_magnetic_deviation)
+            ))
+            .color(TEXT),
+        );
+        let times = report
+            .sample_times
+            .iter()
+            .map(|time| format!("{time:.3}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        ui.label(
+            egui::RichText::new(format!(
+                "Over {} samples at t = {times}",
+                report.sample_count
+            ))
+            .color(MUTED),
+        );
+    }
+
+    /// Shows the symbolic and sampled residuals of both covariant Maxwell equations.
+    pub(super) fn covariant_report_grid(ui: &mut egui::Ui, report: &CovariantReport) {
+        egui::Grid::new("covariant_report_grid")
+            .num_columns(3)
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label(egui::RichText::new("").color(TEXT));
+                ui.label(egui::RichText::new("Symbolic").color(TEXT).strong());
+                ui.label(egui::RichText::new("max |residual|").color(TEXT).strong());
+                ui.end_row();
+
+                let rows = [
+                    (
+                        "dF",
+                        report.bianchi_symbolic_zero,
+                        report.max_bianchi_residual,
+                    ),
+                    (
+                        "d*F - mu_0 *J",
+                        report.source_symbolic_zero,
+                        report.max_source_residual,
+                    ),
+                ];
+                for (name, symbolic_zero, residual) in rows {
+                    let verdict = if symbolic_zero { "0" } else { "non-zero" };
+                    ui.label(egui::RichText::new(name).color(TEXT));
+                    ui.label(egui::RichText::new(verdict).color(MUTED));
+                    ui.label(egui::RichText::new(format!("{residual:.3e}")).color(MUTED));
+                    ui.end_row();
+                }
+            });
+        ui.label(
+            egui::RichText::new(format!(
+                "Over {} samples at t = {:.3}",
+                report.sample_count, report.sample_time
+            ))
+            .color(MUTED),
+        );
+    }
+}
@@ -0,0 +1,499 @@
+//! EM tab of the control window.
+
+use super::ControlApp;
+use crate::app::em_runtime::{unsupported_media_note, LorentzBoost, MAX_OBSERVER_SPEED};
+use crate::app::ui::presets::EmPreset;
+use crate::app::ui::state::{EmGauge, EmMode, GridUiState, InverseCurlBackend};
+use crate::app::ui::theme::{self, GOLDEN_GLOW, MUTED, TEXT};
+use eframe::egui;
+
+impl ControlApp {
+    pub(super) fn render_em_tab(ui: &mut egui::Ui, data: &mut GridUiState) {
+        egui::CollapsingHeader::new(theme::section_heading("Standard parameters"))
+            .default_open(true)
+            .show(ui, |ui| {
+                Self::preset_buttons(ui, EmPreset::ALL, |preset, data| preset.apply(data), data);
+            });
+
+        ui.add_space(8.0);
+        egui::CollapsingHeader::new(theme::section_heading("Waveguide & cavity modes"))
+            .default_open(false)
+            .show(ui, |ui| {
+                Self::guided_mode_controls(ui, data);
+            });
+
+        ui.add_space(8.0);
+        egui::CollapsingHeader::new(theme::section_heading("Electromagnetism"))
+            .default_open(true)
+            .show(ui, |ui| {
+                if ui
+                    .add_sized(
+                        egui::vec2(160.0, 32.0),
+                        egui::Button::new(egui::RichText::new("Enable EM").color(TEXT).strong())
+                            .selected(data.em.enabled),
+                    )
+                    .on_hover_text("Enable electromagnetism rendering")
+                    .clicked()
+                {
+                    data.em.enabled = !data.em.enabled;
+                }
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    ui.label(egui::RichText::new("Mode").color(TEXT));
+                    if Self::tab_button(ui, data.em.mode == EmMode::Potentials, "Potentials") {
+                        data.em.mode = EmMode::Potentials;
+                    }
+                    if Self::tab_button(ui, data.em.mode == EmMode::Electric, "E") {
+                        data.em.mode = EmMode::Electric;
+                    }
+                    if Self::tab_button(ui, data.em.mode == EmMode::Magnetic, "B") {
+                        data.em.mode = EmMode::Magnetic;
+                    }
+                    if Self::tab_button(ui, data.em.mode == EmMode::Phasor, "Phasor") {
+                        data.em.mode = EmMode::Phasor;
+                    }
+                    if Self::tab_button(ui, data.em.mode == EmMode::ClosedForm, "Closed form") {
+                        data.em.mode = EmMode::ClosedForm;
+                    }
+                });
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.label(egui::RichText::new("Gauge").color(TEXT));
+                    if Self::tab_button(ui, data.em.gauge == EmGauge::Coulomb, "Coulomb") {
+                        data.em.gauge = EmGauge::Coulomb;
+                    }
+                    // if Self::tab_button(ui, data.em.gauge == EmGauge::Lorenz, "Lorenz", ) {
+                    //     data.em.gauge = EmGauge::Lorenz;
+                    // }
+                });
+                ui.label(
+                    egui::RichText::new(
+                        "Named gauge selector for potential display/reconstruction. Coulomb-like \
+                         is the current reconstruction",
+                    )
+                    .color(MUTED),
+                );
+                ui.separator();
+                Self::em_source_group(
+                    ui,
+                    matches!(data.em.mode, EmMode::Potentials | EmMode::ClosedForm),
+                    |ui| {
+                        Self::eq_row(ui, "Scalar:  V =", &mut data.em.phi.eq_str);
+                        Self::eq_row(
+                            ui,
+                            "Vector x:  Ax =",
+                            &mut data.em.vector_potential.x.eq_str,
+                        );
+                        Self::eq_row(
+                            ui,
+                            "Vector y:  Ay =",
+                            &mut data.em.vector_potential.y.eq_str,
+                        );
+                        Self::eq_row(
+                            ui,
+                            "Vector z:  Az =",
+                            &mut data.em.vector_potential.z.eq_str,
+                        );
+                    },
+                );
+                ui.separator();
+                Self::em_source_group(
+                    ui,
+                    matches!(data.em.mode, EmMode::Electric | EmMode::ClosedForm),
+                    |ui| {
+                        Self::eq_row(
+                            ui,
+                            "Electric x:  Ex =",
+                            &mut data.em.electric_field.x.eq_str,
+                        );
+                        Self::eq_row(
+                            ui,
+                            "Electric y:  Ey =",
+                            &mut data.em.electric_field.y.eq_str,
+                        );
+                        Self::eq_row(
+                            ui,
+                            "Electric z:  Ez =",
+                            &mut data.em.electric_field.z.eq_str,
+                        );
+                    },
+                );
+                ui.separator();
+                Self::em_source_group(
+                    ui,
+                    matches!(data.em.mode, EmMode::Magnetic | EmMode::ClosedForm),
+                    |ui| {
+                        Self::eq_row(
+                            ui,
+                            "Magnetic x:  Bx =",
+                            &mut data.em.magnetic_field.x.eq_str,
+                        );
+                        Self::eq_row(
+                            ui,
+                            "Magnetic y:  By =",
+                            &mut data.em.magnetic_field.y.eq_str,
+                        );
+                        Self::eq_row(
+                            ui,
+                            "Magnetic z:  Bz =",
+                            &mut data.em.magnetic_field.z.eq_str,
+                        );
+                    },
+                );
+                ui.separator();
+                Self::em_source_group(ui, data.em.mode == EmMode::Phasor, |ui| {
+                    ui.label(
+                        egui::RichText::new(
+                            "Complex amplitude over x, y, z with i as the imaginary unit. The \
+                             rendered field is Re(E exp(i omega t)).",
+                        )
+                        .color(MUTED),
+                    );
+                    ui.add(
+                        egui::Slider::new(&mut data.em.angular_frequency, 0.1..=20.0)
+                            .logarithmic(true)
+                            .text("omega")
+                            .trailing_fill(true),
+                    );
+                    Self::eq_row(
+                        ui,
+                        "Phasor x:  Ex =",
+                        &mut data.em.phasor_electric_field.x.eq_str,
+                    );
+                    Self::eq_row(
+                        ui,
+                        "Phasor y:  Ey =",
+                        &mut data.em.phasor_electric_field.y.eq_str,
+                    );
+                    Self::eq_row(
+                        ui,
+                        "Phasor z:  Ez =",
+                        &mut data.em.phasor_electric_field.z.eq_str,
+                    );
+                });
+            });
+
+        ui.add_space(8.0);
+        egui::CollapsingHeader::new(theme::section_heading("Media"))
+            .default_open(false)
+            .show(ui, |ui| {
+                ui.label(
+                    egui::RichText::new(
+                        "Relative material parameters over x, y, z. In E source mode they enter \
+                         Ampere's law point by point; in both source modes a uniform lossless \
+                         medium sets the plane-wave speed c / sqrt(eps * mu).",
+                    )
+                    .color(MUTED),
+                );
+                Self::em_source_group(
+                    ui,
+                    matches!(data.em.mode, EmMode::Electric | EmMode::Magnetic),
+                    |ui| {
+                        Self::eq_row(ui, "Permittivity:  eps =", &mut data.em.permittivity.eq_str);
+                        Self::eq_row(ui, "Permeability:  mu =", &mut data.em.permeability.eq_str);
+                        Self::eq_row(
+                            ui,
+                            "Conductivity:  sigma =",
+                            &mut data.em.conductivity.eq_str,
+                        );
+                    },
+                );
+                // Reads the applied media, since `eq` only changes on Apply.
+                if let Some(note) = unsupported_media_note(&data.em) {
+                    ui.label(egui::RichText::new(note).color(GOLDEN_GLOW));
+                }
+            });
+
+        ui.add_space(8.0);
+        egui::CollapsingHeader::new(theme::section_heading("Solver"))
+            .default_open(false)
+            .show(ui, |ui| {
+                ui.label(
+                    egui::RichText::new(
+                        "Inverse-curl method used to derive the companion field in the E and B \
+                         source modes. Resolution is source cells per axis for the tree code and \
+                         lattice cells per axis for multigrid.",
+                    )
+                    .color(MUTED),
+                );
+                Self::em_source_group(
+                    ui,
+                    matches!(data.em.mode, EmMode::Electric | EmMode::Magnetic),
+                    |ui| {
+                        ui.horizontal(|ui| {
+                            ui.label(egui::RichText::new("Backend").color(TEXT));
+                            if Self::tab_button(
+                                ui,
+                                data.em.inverse_curl_backend == InverseCurlBackend::DirectSum,
+                                "Direct sum",
+                            ) {
+                                data.em.inverse_curl_backend = InverseCurlBackend::DirectSum;
+                            }
+                            if Self::tab_button(
+                                ui,
+                                data.em.inverse_curl_backend == InverseCurlBackend::TreeCode,
+                                "Tree code",
+                            ) {
+                                data.em.inverse_curl_backend = InverseCurlBackend::TreeCode;
+                            }
+                            if Self::tab_button(
+                                ui,
+                                data.em.inverse_curl_backend == InverseCurlBackend::Multigrid,
+                                "Multigrid",
+                            ) {
+                                data.em.inverse_curl_backend = InverseCurlBackend::Multigrid;
+                            }
+                        });
+                        ui.add_enabled_ui(
+                            data.em.inverse_curl_backend != InverseCurlBackend::DirectSum,
+                            |ui| {
+                                ui.horizontal(|ui| {
+                                    ui.label(egui::RichText::new("Resolution").color(TEXT));
+                                    for resolution in [16, 32, 64] {
+                                        if Self::tab_button(
+                                            ui,
+                                            data.em.solver_resolution == resolution,
+                                            &resolution.to_string(),
+                                        ) {
+                                            data.em.solver_resolution = resolution;
+                                        }
+                                    }
+                                });
+                            },
+                        );
+                        ui.add_enabled_ui(
+                            data.em.inverse_curl_backend == InverseCurlBackend::TreeCode,
+                            |ui| {
+                                ui.add(
+                                    egui::Slider::new(&mut data.em.tree_opening_angle, 0.0..=1.0)
+                                        .text("opening angle")
+                                        .trailing_fill(true),
+                                )
+                                .on_hover_text(
+                                    "Barnes-Hut accuracy knob: 0 is the exact sum, larger values \
+                                     approximate more distant cell groups.",
+                                );
+                            },
+                        );
+                    },
+                );
+            });
+
+        ui.add_space(8.0);
+        egui::CollapsingHeader::new(theme::section_heading("Conductors & domain mask"))
+            .default_open(false)
+            .show(ui, |ui| {
+                Self::em_source_group(
+                    ui,
+                    matches!(data.em.mode, EmMode::Electric | EmMode::Magnetic),
+                    |ui| Self::conductor_controls(ui, data),
+                );
+            });
+
+        ui.add_space(8.0);
+        egui::CollapsingHeader::new(theme::section_heading("Gauge transformation"))
+            .default_open(false)
+            .show(ui, |ui| {
+                ui.label(
+                    egui::RichText::new(
+                        "Gauge function over x, y, z, t. Applies V -> V - d(lambda)/dt and \
+                         A -> A + grad(lambda); E and B must stay unchanged.",
+                    )
+                    .color(MUTED),
+                );
+                Self::em_source_group(ui, data.em.mode == EmMode::Potentials, |ui| {
+                    Self::eq_row(ui, "Gauge:  lambda =", &mut data.em.gauge_function.eq_str);
+                });
+                if let Some(report) = &data.gauge_report {
+                    ui.separator();
+                    Self::gauge_report_grid(ui, report);
+                }
+            });
+
+        ui.add_space(8.0);
+        egui::CollapsingHeader::new(theme::section_heading("Covariant check"))
+            .default_open(false)
+            .show(ui, |ui| {
+                ui.label(
+                    egui::RichText::new(
+                        "F = dA with A = -V dt + A_i dx^i. Checks dF = 0 and d*F = mu_0 *J \
+                         against the charge density and current below, over x, y, z, t.",
+                    )
+                    .color(MUTED),
+                );
+                Self::em_source_group(ui, data.em.mode == EmMode::Potentials, |ui| {
+                    Self::eq_row(ui, "Charge:  rho =", &mut data.em.charge_density.eq_str);
+                    Self::eq_row(
+                        ui,
+                        "Current x:  Jx =",
+                        &mut data.em.current_density.x.eq_str,
+                    );
+                    Self::eq_row(
+                        ui,
+                        "Current y:  Jy =",
+                        &mut data.em.current_density.y.eq_str,
+                    );
+                    Self::eq_row(
+                        ui,
+                        "Current z:  Jz =",
+                        &mut data.em.current_density.z.eq_str,
+                    );
+                });
+                ui.separator();
+                match &data.covariant_report {
+                    Some(report) => Self::covariant_report_grid(ui, report),
+                    None => {
+                        ui.label(egui::RichText::new("Available in potentials mode.").color(MUTED));
+                    }
+                }
+            });
+
+        ui.add_space(8.0);
+        egui::CollapsingHeader::new(theme::section_heading("Constants"))
+            .default_open(true)
+            .show(ui, |ui| {
+                ui.add(
+                    egui::Slider::new(&mut data.em.light_speed, 0.1..=100.0)
+                        .logarithmic(true)
+                        .text("c")
+                        .trailing_fill(true),
+                );
+                ui.add(
+                    egui::Slider::new(&mut data.em.magnetic_vector_scale, 0.1..=100.0)
+                        .logarithmic(true)
+                        .text("B vector scale")
+                        .trailing_fill(true),
+                );
+                ui.checkbox(
+                    &mut data.em.normalize_vectors,
+                    egui::RichText::new("Normalize EM vectors").color(TEXT),
+                );
+            });
+
+        ui.add_space(8.0);
+        egui::CollapsingHeader::new(theme::section_heading("Observer frame"))
+            .default_open(false)
+            .show(ui, |ui| {
+                ui.label(
+                    egui::RichText::new(
+                        "View the fields from a frame moving at velocity beta * c along world \
+                         axes. Each sample event (x, t) is boosted to (x', t') and E, B, V, A are \
+                         Lorentz transformed; the grid itself stays in the lab frame.",
+                    )
+                    .color(MUTED),
+                );
+                Self::em_source_group(ui, data.em.mode != EmMode::Phasor, |ui| {
+                    Self::observer_controls(ui, data);
+                });
+            });
+
+        ui.add_space(8.0);
+        egui::CollapsingHeader::new(theme::section_heading("Time"))
+            .default_open(true)
+            .show(ui, |ui| {
+                Self::time_controls(ui, data);
+            });
+
+        ui.add_space(8.0);
+        egui::CollapsingHeader::new(theme::section_heading("Probes"))
+            .default_open(false)
+            .show(ui, |ui| {
+                Self::probe_panel(ui, data);
+            });
+
+        ui.add_space(8.0);
+        egui::CollapsingHeader::new(theme::section_heading("Layers"))
+            .default_open(true)
+            .show(ui, |ui| {
+                ui.checkbox(
+                    &mut data.em.layers.electric,
+                    egui::RichText::new("E").color(TEXT),
+                );
+                ui.checkbox(
+                    &mut data.em.layers.magnetic,
+                    egui::RichText::new("B").color(TEXT),
+                );
+                ui.checkbox(
+                    &mut data.em.layers.scalar_potential,
+                    egui::RichText::new("V").color(TEXT),
+                )
+                .on_hover_text(
+                    "Shows the scalar potential. The built-in wave presets use the V = 0 gauge, \
+                     so this layer is intentionally uniform until V is edited or reconstructed \
+                     from E/B source mode.",
+                );
+                ui.checkbox(
+                    &mut data.em.layers.vector_potential,
+                    egui::RichText::new("A").color(TEXT),
+                );
+                ui.add_enabled_ui(data.em.mode == EmMode::Phasor, |ui| {
+                    ui.checkbox(
+                        &mut data.em.layers.amplitude,
+                        egui::RichText::new("|E| amplitude").color(TEXT),
+                    )
+                    .on_hover_text("Only one scalar layer is drawn at a time; V takes precedence.");
+                    ui.checkbox(
+                        &mut data.em.layers.phase,
+                        egui::RichText::new("arg(E) phase").color(TEXT),
+                    );
+                    ui.checkbox(
+                        &mut data.em.layers.polarization,
+                        egui::RichText::new("Polarization ellipses").color(TEXT),
+                    );
+                });
+            });
+    }
+
+    fn observer_controls(ui: &mut egui::Ui, data: &mut GridUiState) {
+        let mut changed = false;
+        for (label, beta) in ["beta x", "beta y", "beta z"]
+            .into_iter()
+            .zip(data.em.observer_beta.iter_mut())
+        {
+            changed |= ui
+                .add(
+                    egui::Slider::new(beta, -MAX_OBSERVER_SPEED..=MAX_OBSERVER_SPEED)
+                        .text(label)
+                        .trailing_fill(true),
+                )
+                .changed();
+        }
+        // Each slider is bounded on its own, so rescale the whole vector back below `c`.
+        let speed = data
+            .em
+            .observer_beta
+            .iter()
+            .map(|beta| beta * beta)
+            .sum::<f64>()
+            .sqrt();
+        if changed && speed > MAX_OBSERVER_SPEED {
+            let scale = MAX_OBSERVER_SPEED / speed;
+            data.em.observer_beta = data.em.observer_beta.map(|beta| beta * scale);
+        }
+
+        ui.horizontal(|ui| {
+            let gamma = LorentzBoost::new(data.em.observer_beta.into(), data.em.light_speed)
+                .map_or(1.0, |boost| boost.gamma());
+            ui.label(egui::RichText::new(format!("gamma = {gamma:.4}")).color(TEXT));
+            if ui.button("Lab frame").clicked() {
+                data.em.observer_beta = [0.0; 3];
+            }
+        });
+        if let Some((earliest, latest)) = data.observer_time_range {
+            ui.label(
+                egui::RichText::new(format!(
+                    "Observer time t' spans {earliest:.3} .. {latest:.3} over the samples"
+                ))
+                .color(MUTED),
+            );
+        }
+    }
+
+    fn em_source_group(
+        ui: &mut egui::Ui,
+        editable: bool,
+        add_contents: impl FnOnce(&mut egui::Ui),
+    ) {
+        ui.add_enabled_ui(editable, add_contents);
+    }
+}
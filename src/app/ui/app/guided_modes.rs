@@ -0,0 +1,153 @@
+//! Waveguide and cavity mode generator and conducting-wall toggles of the EM tab.
+
+use super::ControlApp;
+use crate::app::ui::mode_presets::{guided_mode, mode_label};
+use crate::app::ui::state::{GridUiState, GuideGeometry, ModeFamily};
+use crate::app::ui::theme::{MUTED, RASPBERRY, TEXT};
+use eframe::egui::{self, Color32};
+use eframe::epaint::CornerRadius;
+
+const WALL_LABELS: [&str; 6] = ["x min", "x max", "y min", "y max", "z min", "z max"];
+
+impl ControlApp {
+    pub(super) fn guided_mode_controls(ui: &mut egui::Ui, data: &mut GridUiState) {
+        ui.label(
+            egui::RichText::new(
+                "Closed-form TE/TM modes of a perfectly conducting guide. Rectangular guides span \
+                 [0, a] x [0, b]; circular guides use cylindrical (r, theta, z) over the grid. \
+                 Loading a mode replaces the grid, the closed-form fields and the walls.",
+            )
+            .color(MUTED),
+        );
+        let spec = &mut data.em.guided_mode;
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new("Guide").color(TEXT));
+            if Self::tab_button(
+                ui,
+                spec.geometry == GuideGeometry::Rectangular,
+                "Rectangular",
+            ) {
+                spec.geometry = GuideGeometry::Rectangular;
+            }
+            if Self::tab_button(ui, spec.geometry == GuideGeometry::Circular, "Circular") {
+                spec.geometry = GuideGeometry::Circular;
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new("Family").color(TEXT));
+            if Self::tab_button(ui, spec.family == ModeFamily::Te, "TE") {
+                spec.family = ModeFamily::Te;
+            }
+            if Self::tab_button(ui, spec.family == ModeFamily::Tm, "TM") {
+                spec.family = ModeFamily::Tm;
+            }
+            ui.separator();
+            if Self::tab_button(ui, !spec.cavity, "Waveguide") {
+                spec.cavity = false;
+            }
+            if Self::tab_button(ui, spec.cavity, "Cavity") {
+                spec.cavity = true;
+            }
+        });
+
+        let (first, second) = match spec.geometry {
+            GuideGeometry::Rectangular => ("m", "n"),
+            GuideGeometry::Circular => ("radial m", "azimuthal n"),
+        };
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new(first).color(TEXT));
+            ui.add(egui::DragValue::new(&mut spec.m).range(0..=6));
+            ui.label(egui::RichText::new(second).color(TEXT));
+            ui.add(egui::DragValue::new(&mut spec.n).range(0..=6));
+            ui.add_enabled_ui(spec.cavity, |ui| {
+                ui.label(egui::RichText::new("p").color(TEXT));
+                ui.add(egui::DragValue::new(&mut spec.p).range(0..=6));
+            });
+        });
+        ui.horizontal(|ui| {
+            match spec.geometry {
+                GuideGeometry::Rectangular => {
+                    ui.label(egui::RichText::new("a").color(TEXT));
+                    ui.add(
+                        egui::DragValue::new(&mut spec.width)
+                            .speed(0.05)
+                            .range(0.1..=10.0),
+                    );
+                    ui.label(egui::RichText::new("b").color(TEXT));
+                    ui.add(
+                        egui::DragValue::new(&mut spec.height)
+                            .speed(0.05)
+                            .range(0.1..=10.0),
+                    );
+                }
+                GuideGeometry::Circular => {
+                    ui.label(egui::RichText::new("radius").color(TEXT));
+                    ui.add(
+                        egui::DragValue::new(&mut spec.radius)
+                            .speed(0.05)
+                            .range(0.1..=10.0),
+                    );
+                }
+            }
+            ui.label(egui::RichText::new("length").color(TEXT));
+            ui.add(
+                egui::DragValue::new(&mut spec.length)
+                    .speed(0.05)
+                    .range(0.1..=20.0),
+            );
+        })
+        .response
+        .on_hover_text("Dimensions become the grid bounds of the guide.");
+        ui.add_enabled_ui(!spec.cavity, |ui| {
+            ui.add(
+                egui::Slider::new(&mut data.em.angular_frequency, 0.1..=20.0)
+                    .logarithmic(true)
+                    .text("omega")
+                    .trailing_fill(true),
+            );
+        });
+
+        let spec = &data.em.guided_mode;
+        let label = mode_label(spec);
+        match guided_mode(spec, data.em.light_speed, data.em.angular_frequency) {
+            Ok(mode) => {
+                let frequency = if spec.cavity { "resonance" } else { "omega" };
+                ui.label(
+                    egui::RichText::new(format!(
+                        "{label}: cutoff {:.3}, {frequency} {:.3}",
+                        mode.cutoff_angular_frequency, mode.angular_frequency
+                    ))
+                    .color(TEXT),
+                );
+                if ui
+                    .add(
+                        egui::Button::new(egui::RichText::new("Load mode").color(Color32::WHITE))
+                            .fill(RASPBERRY)
+                            .min_size(egui::vec2(120.0, 30.0))
+                            .corner_radius(CornerRadius::same(6)),
+                    )
+                    .on_hover_text("Fill the editors with this mode; press Apply to render it.")
+                    .clicked()
+                {
+                    mode.apply(data);
+                }
+            }
+            Err(error) => {
+                ui.label(egui::RichText::new(format!("{label}: {error}")).color(RASPBERRY));
+            }
+        }
+
+        ui.separator();
+        ui.label(egui::RichText::new("Conducting walls").color(TEXT));
+        egui::Grid::new("pec_wall_grid")
+            .num_columns(2)
+            .show(ui, |ui| {
+                for (pair, labels) in data.em.pec_walls.chunks_mut(2).zip(WALL_LABELS.chunks(2)) {
+                    for (wall, label) in pair.iter_mut().zip(labels) {
+                        ui.checkbox(wall, egui::RichText::new(*label).color(TEXT));
+                    }
+                    ui.end_row();
+                }
+            });
+    }
+}
@@ -0,0 +1,124 @@
+//! Floating point-inspector window over the control panel.
+
+use super::ControlApp;
+use crate::app::ui::state::{format_vector, GridUiState, InspectedField, InspectorReport};
+use crate::app::ui::theme::{BORDER, MUTED, PANEL, TEXT};
+use eframe::egui::{self, Stroke};
+use eframe::epaint::Margin;
+
+impl ControlApp {
+    /// Shows the inspector while it is enabled; closing the window disables it again.
+    pub(super) fn show_inspector_window(ctx: &egui::Context, data: &mut GridUiState) {
+        if !data.inspector_open {
+            return;
+        }
+        let report = data.inspector.as_ref();
+        egui::Window::new("Point inspector")
+            .resizable(false)
+            .default_width(360.0)
+            .frame(
+                egui::Frame::window(&ctx.global_style())
+                    .fill(PANEL)
+                    .stroke(Stroke::new(1.5, BORDER))
+                    .inner_margin(Margin::same(12)),
+            )
+            .open(&mut data.inspector_open)
+            .show(ctx, |ui| {
+                let Some(report) = report else {
+                    ui.label(
+                        egui::RichText::new(
+                            "Hover a grid sample in the render view; right-click pins it.",
+                        )
+                        .color(MUTED),
+                    );
+                    return;
+                };
+                ui.horizontal(|ui| {
+                    let status = if report.pinned {
+                        "Pinned sample (right-click empty space to unpin)"
+                    } else {
+                        "Hovered sample (right-click to pin)"
+                    };
+                    ui.label(egui::RichText::new(status).color(MUTED));
+                    if ui.button("Copy").clicked() {
+                        ui.ctx().copy_text(report.to_text());
+                    }
+                });
+                if let Some(reason) = &report.snapped_because {
+                    ui.label(
+                        egui::RichText::new(format!(
+                            "Snapped to the nearest grid sample: {reason}."
+                        ))
+                        .color(MUTED),
+                    );
+                }
+                ui.add_space(6.0);
+                Self::inspector_grid(ui, report);
+            });
+    }
+
+    fn inspector_grid(ui: &mut egui::Ui, report: &InspectorReport) {
+        egui::Grid::new("inspector_grid")
+            .num_columns(2)
+            .striped(true)
+            .show(ui, |ui| {
+                let mut row = |name: &str, value: String| {
+                    ui.label(egui::RichText::new(name).color(TEXT));
+                    ui.label(egui::RichText::new(value).color(MUTED).monospace());
+                    ui.end_row();
+                };
+                let optional = |value: Option<f64>| {
+                    value.map_or_else(|| "singular".to_string(), |value| format!("{value:.6}"))
+                };
+                let matrix = |matrix: Option<[[f64; 3]; 3]>| {
+                    matrix.map_or_else(
+                        || "singular".to_string(),
+                        |matrix| {
+                            matrix
+                                .iter()
+                                .map(format_vector)
+                                .collect::<Vec<_>>()
+                                .join("\n")
+                        },
+                    )
+                };
+
+                row("abstract", format_vector(&report.abstract_position));
+                row("world", format_vector(&report.world_position));
+                row("g_ij", matrix(report.metric));
+                row("det g", optional(report.metric_determinant));
+                row(
+                    "h_i",
+                    format!(
+                        "({}, {}, {})",
+                        optional(report.scale_factors[0]),
+                        optional(report.scale_factors[1]),
+                        optional(report.scale_factors[2])
+                    ),
+                );
+                row("vielbein", matrix(report.vielbein));
+                match &report.field {
+                    Some(InspectedField::Scalar(value)) => row("f", format!("{value:.6}")),
+                    Some(InspectedField::Vector {
+                        natural,
+                        dual,
+                        otn,
+                        jacobian,
+                    }) => {
+                        row("F natural", format_vector(natural));
+                        row("F dual", format_vector(dual));
+                        row("F OTN", format_vector(otn));
+                        row("dF_a/dx^j", matrix(Some(*jacobian)));
+                    }
+                    None => {}
+                }
+                if let Some(em) = &report.em {
+                    row("EM time", format!("{:.6}", em.time));
+                    row("V", format!("{:.6}", em.phi));
+                    row("A", format_vector(&em.vector_potential));
+                    row("E", format_vector(&em.electric));
+                    row("B", format_vector(&em.magnetic));
+                }
+            });
+    }
+}
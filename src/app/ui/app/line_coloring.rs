@@ -0,0 +1,47 @@
+//! Grid-tab controls that color the grid lines by a geometric quantity.
+
+use super::ControlApp;
+use crate::app::ui::legend::render_ramp;
+use crate::app::ui::state::{Colormap, GridUiState, LineQuantity};
+use crate::app::ui::theme::{self, MUTED, TEXT};
+use eframe::egui;
+
+impl ControlApp {
+    /// Picks the line quantity and colormap, and shows the range of the applied quantity.
+    pub(super) fn render_line_coloring_section(ui: &mut egui::Ui, data: &mut GridUiState) {
+        egui::CollapsingHeader::new(theme::section_heading("Line coloring"))
+            .default_open(false)
+            .show(ui, |ui| {
+                let coloring = &mut data.line_coloring;
+                ui.horizontal_wrapped(|ui| {
+                    ui.label(egui::RichText::new("Color by").color(TEXT));
+                    for quantity in LineQuantity::ALL {
+                        ui.selectable_value(&mut coloring.quantity, quantity, quantity.label());
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label(egui::RichText::new("Colormap").color(TEXT));
+                    for colormap in Colormap::ALL {
+                        ui.selectable_value(&mut coloring.colormap, colormap, colormap.label());
+                    }
+                });
+
+                if let Some(legend) = data.line_legend {
+                    let descriptor = legend.kind.descriptor();
+                    ui.separator();
+                    ui.label(egui::RichText::new(descriptor.title).color(TEXT).strong());
+                    ui.label(egui::RichText::new(descriptor.subtitle).color(MUTED));
+                    render_ramp(ui, legend, data.line_coloring.colormap);
+                    ui.label(egui::RichText::new(descriptor.footer).color(MUTED));
+                }
+                ui.label(
+                    egui::RichText::new(
+                        "The shader evaluates the quantity at every vertex. The range leaves out \
+                         the lowest and highest 2% of the samples, so values near a singularity \
+                         saturate. Updates on Apply.",
+                    )
+                    .color(MUTED),
+                );
+            });
+    }
+}
@@ -0,0 +1,62 @@
+//! Grid-tab controls of the morph toward a second embedding.
+
+use super::ControlApp;
+use crate::app::ui::state::{GridUiState, MorphHomotopy};
+use crate::app::ui::theme::{self, MUTED, TEXT};
+use eframe::egui;
+
+impl ControlApp {
+    /// Edits the target embedding and homotopy, and drives `s` by hand or on a loop.
+    pub(super) fn render_morph_section(ui: &mut egui::Ui, data: &mut GridUiState) {
+        egui::CollapsingHeader::new(theme::section_heading("Morph"))
+            .default_open(false)
+            .show(ui, |ui| {
+                let morph = &mut data.morph;
+                ui.checkbox(
+                    &mut morph.enabled,
+                    egui::RichText::new("Morph toward a target embedding").color(TEXT),
+                );
+                Self::eq_row(ui, "Target x =", &mut morph.target.x.eq_str);
+                Self::eq_row(ui, "Target y =", &mut morph.target.y.eq_str);
+                Self::eq_row(ui, "Target z =", &mut morph.target.z.eq_str);
+                ui.horizontal(|ui| {
+                    ui.label(egui::RichText::new("Homotopy").color(TEXT));
+                    for homotopy in MorphHomotopy::ALL {
+                        ui.selectable_value(&mut morph.homotopy, homotopy, homotopy.label());
+                    }
+                });
+
+                ui.separator();
+                let dragged = ui
+                    .add(
+                        egui::Slider::new(&mut morph.s, 0.0..=1.0)
+                            .text("s")
+                            .trailing_fill(true),
+                    )
+                    .changed();
+                if dragged {
+                    morph.animate = false;
+                }
+                ui.horizontal(|ui| {
+                    ui.checkbox(
+                        &mut morph.animate,
+                        egui::RichText::new("Animate").color(TEXT),
+                    );
+                    ui.add(
+                        egui::DragValue::new(&mut morph.period)
+                            .speed(0.1)
+                            .range(0.5..=60.0)
+                            .suffix(" s per sweep"),
+                    );
+                });
+                ui.label(
+                    egui::RichText::new(
+                        "s = 0 is the coordinate system above and s = 1 the target. The grid \
+                         follows s every frame; the metric, field samples and arrows follow in \
+                         steps of 1/32. Target and homotopy update on Apply; s updates live.",
+                    )
+                    .color(MUTED),
+                );
+            });
+    }
+}
@@ -0,0 +1,277 @@
+//! Probe list, live plots, spectra and CSV export of the EM tab.
+
+use super::ControlApp;
+use crate::app::probes::probe_csv;
+use crate::app::ui::state::{ExportStatus, GridUiState, ProbeChannel, ProbeTrace, ProbeView};
+use crate::app::ui::theme::{BORDER, JET_BLACK, MUTED, TEXT};
+use crate::maths::spectrum::{wrap_phase, Spectrum};
+use eframe::egui::{self, Color32, Stroke};
+use std::path::PathBuf;
+
+const PLOT_HEIGHT: f32 = 150.0;
+
+/// One plotted probe: its color and `(x, y)` points.
+type Series = (Color32, Vec<egui::Pos2>);
+
+impl ControlApp {
+    pub(super) fn probe_panel(ui: &mut egui::Ui, data: &mut GridUiState) {
+        ui.label(
+            egui::RichText::new(
+                "Hover a grid sample and press P to drop a probe there, or add one at abstract \
+                 coordinates. Probes record lab-frame values over EM time, filling in the series \
+                 as time runs either way, and restart when the fields are re-applied.",
+            )
+            .color(MUTED),
+        );
+        Self::probe_list(ui, data);
+
+        ui.separator();
+        let probes = &mut data.probes;
+        ui.horizontal_wrapped(|ui| {
+            ui.label(egui::RichText::new("Channel").color(TEXT));
+            for channel in ProbeChannel::ALL {
+                ui.selectable_value(&mut probes.channel, channel, channel.label());
+            }
+        });
+        ui.horizontal(|ui| {
+            if Self::tab_button(ui, probes.view == ProbeView::TimeSeries, "Time series") {
+                probes.view = ProbeView::TimeSeries;
+            }
+            if Self::tab_button(ui, probes.view == ProbeView::Spectrum, "Spectrum") {
+                probes.view = ProbeView::Spectrum;
+            }
+        });
+
+        let channel = probes.channel;
+        match probes.view {
+            ProbeView::TimeSeries => Self::time_series_plot(ui, &data.probe_traces, channel),
+            ProbeView::Spectrum => Self::spectrum_plot(ui, &data.probe_traces, channel),
+        }
+
+        ui.horizontal(|ui| {
+            if Self::compact_button(ui, "Export CSV") {
+                export_csv(&data.probe_traces, &data.probes.export_status);
+            }
+            if let Some(status) = data.probes.export_status.get() {
+                ui.label(egui::RichText::new(status).color(MUTED));
+            }
+        });
+    }
+
+    fn probe_list(ui: &mut egui::Ui, data: &mut GridUiState) {
+        let probes = &mut data.probes;
+        ui.horizontal(|ui| {
+            for (label, value) in ["x", "y", "z"]
+                .into_iter()
+                .zip(probes.draft_position.iter_mut())
+            {
+                ui.label(egui::RichText::new(label).color(TEXT));
+                ui.add(egui::DragValue::new(value).speed(0.05));
+            }
+            if ui.button("Add probe").clicked() {
+                probes.add(probes.draft_position);
+            }
+        });
+
+        let mut removed = None;
+        for marker in &probes.markers {
+            ui.horizontal(|ui| {
+                swatch(ui, probe_color(marker.color()));
+                let [x, y, z] = marker.position;
+                ui.label(
+                    egui::RichText::new(format!("P{}  ({x:.3}, {y:.3}, {z:.3})", marker.id))
+                        .color(TEXT),
+                );
+                if ui.small_button("Remove").clicked() {
+                    removed = Some(marker.id);
+                }
+            });
+        }
+        if let Some(id) = removed {
+            probes.markers.retain(|marker| marker.id != id);
+        }
+        if !probes.markers.is_empty() && ui.button("Remove all").clicked() {
+            probes.markers.clear();
+        }
+    }
+
+    fn time_series_plot(ui: &mut egui::Ui, traces: &[ProbeTrace], channel: ProbeChannel) {
+        let series = traces
+            .iter()
+            .map(|trace| {
+                let points = trace
+                    .times
+                    .iter()
+                    .zip(trace.channel(channel))
+                    .map(|(&time, value)| egui::pos2(time as f32, value as f32))
+                    .collect();
+                (probe_color(trace.marker().color()), points)
+            })
+            .collect::<Vec<Series>>();
+        line_plot(ui, &series, "t");
+    }
+
+    /// Plots amplitude spectra and reads off each probe's frequency and its lag behind the first.
+    fn spectrum_plot(ui: &mut egui::Ui, traces: &[ProbeTrace], channel: ProbeChannel) {
+        let spectra = traces
+            .iter()
+            .map(|trace| Spectrum::from_samples(&trace.times, &trace.channel(channel)))
+            .collect::<Vec<_>>();
+        let series = traces
+            .iter()
+            .zip(&spectra)
+            .filter_map(|(trace, spectrum)| {
+                let spectrum = spectrum.as_ref()?;
+                let points = spectrum
+                    .angular_frequencies
+                    .iter()
+                    .zip(&spectrum.amplitudes)
+                    .map(|(&omega, &amplitude)| egui::pos2(omega as f32, amplitude as f32))
+                    .collect();
+                Some((probe_color(trace.marker().color()), points))
+            })
+            .collect::<Vec<Series>>();
+        line_plot(ui, &series, "omega");
+
+        let reference = spectra
+            .first()
+            .and_then(Option::as_ref)
+            .and_then(|spectrum| Some((spectrum, spectrum.dominant()?)));
+        for (trace, spectrum) in traces.iter().zip(&spectra) {
+            let Some(peak) = spectrum.as_ref().and_then(Spectrum::dominant) else {
+                continue;
+            };
+            let mut line = format!(
+                "P{}: omega {:.3}, amplitude {:.3e}, phase {:.1} deg",
+                trace.id,
+                peak.angular_frequency,
+                peak.amplitude,
+                peak.phase.to_degrees()
+            );
+            if let (Some((reference, reference_peak)), Some(spectrum)) = (reference, spectrum) {
+                // Compare both probes at the reference peak so the lag refers to one frequency.
+                let bin = reference_peak.bin;
+                let lag = wrap_phase(reference.phases[bin] - spectrum.phases[bin]);
+                line.push_str(&format!(", lag {:.1} deg", lag.to_degrees()));
+            }
+            ui.label(egui::RichText::new(line).color(probe_color(trace.marker().color())));
+        }
+    }
+}
+
+fn line_plot(ui: &mut egui::Ui, series: &[Series], x_label: &str) {
+    let width = ui.available_width().max(120.0);
+    let (rect, _) = ui.allocate_exact_size(egui::vec2(width, PLOT_HEIGHT), egui::Sense::hover());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 4.0, JET_BLACK);
+    painter.rect_stroke(
+        rect,
+        4.0,
+        Stroke::new(1.0, BORDER),
+        egui::StrokeKind::Inside,
+    );
+
+    let points = series.iter().flat_map(|(_, points)| points);
+    let bounds = egui::Rect::from_points(
+        &points
+            .filter(|point| point.x.is_finite() && point.y.is_finite())
+            .copied()
+            .collect::<Vec<_>>(),
+    );
+    if !bounds.width().is_finite() || bounds.width() <= 0.0 {
+        painter.text(
+            rect.center(),
+            egui::Align2::CENTER_CENTER,
+            "No samples yet",
+            egui::FontId::proportional(12.0),
+            MUTED,
+        );
+        return;
+    }
+
+    // Pad flat series so a constant value still draws as a centred line.
+    let y_span = bounds.height().max(1.0e-9);
+    let (y_min, y_max) = (bounds.min.y - 0.05 * y_span, bounds.max.y + 0.05 * y_span);
+    let inner = rect.shrink(6.0);
+    let to_screen = |point: egui::Pos2| {
+        egui::pos2(
+            inner.left() + (point.x - bounds.min.x) / bounds.width().max(1.0e-9) * inner.width(),
+            inner.bottom() - (point.y - y_min) / (y_max - y_min) * inner.height(),
+        )
+    };
+    for (color, points) in series {
+        let screen = points
+            .iter()
+            .filter(|point| point.x.is_finite() && point.y.is_finite())
+            .map(|point| to_screen(*point))
+            .collect::<Vec<_>>();
+        painter.add(egui::Shape::line(screen, Stroke::new(1.5, *color)));
+    }
+
+    let font = egui::FontId::monospace(10.0);
+    painter.text(
+        inner.left_top(),
+        egui::Align2::LEFT_TOP,
+        format!("{y_max:.3e}"),
+        font.clone(),
+        MUTED,
+    );
+    painter.text(
+        inner.left_bottom(),
+        egui::Align2::LEFT_BOTTOM,
+        format!("{y_min:.3e}"),
+        font.clone(),
+        MUTED,
+    );
+    painter.text(
+        inner.right_bottom(),
+        egui::Align2::RIGHT_BOTTOM,
+        format!("{x_label} {:.2} .. {:.2}", bounds.min.x, bounds.max.x),
+        font,
+        MUTED,
+    );
+}
+
+fn swatch(ui: &mut egui::Ui, color: Color32) {
+    let (rect, _) = ui.allocate_exact_size(egui::vec2(12.0, 12.0), egui::Sense::hover());
+    ui.painter().rect_filled(rect, 2.0, color);
+}
+
+fn probe_color([red, green, blue]: [f32; 3]) -> Color32 {
+    Color32::from_rgb(
+        (red * 255.0) as u8,
+        (green * 255.0) as u8,
+        (blue * 255.0) as u8,
+    )
+}
+
+/// Writes every probe history to a timestamped file in `export_directory`.
+///
+/// The file is written on its own thread so a slow disk never stalls the control window;
+/// `status` names the full path once it is saved.
+fn export_csv(traces: &[ProbeTrace], status: &ExportStatus) {
+    if traces.iter().all(|trace| trace.times.is_empty()) {
+        status.set("Nothing recorded yet".to_string());
+        return;
+    }
+    let directory = export_directory();
+    let path = directory.join(format!(
+        "probes_{}.csv",
+        chrono::Local::now().format("%Y%m%d_%H%M%S")
+    ));
+    let csv = probe_csv(traces);
+    status.set(format!("Saving {}", path.display()));
+    let status = status.clone();
+    std::thread::spawn(move || {
+        let written = std::fs::create_dir_all(&directory).and_then(|()| std::fs::write(&path, csv));
+        status.set(match written {
+            Ok(()) => format!("Saved {}", path.display()),
+            Err(error) => format!("Could not write {}: {error}", path.display()),
+        });
+    });
+}
+
+/// Folder probe exports go to, independent of the directory the app was started from.
+fn export_directory() -> PathBuf {
+    std::env::temp_dir().join("render_engine_probes")
+}
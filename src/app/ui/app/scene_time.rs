@@ -0,0 +1,31 @@
+//! The scene time `t` read by moving embeddings and fields, shown in the Grid and Field tabs.
+
+use super::ControlApp;
+use crate::app::moving_frame::TIME_STEP;
+use crate::app::ui::state::GridUiState;
+use crate::app::ui::theme::{MUTED, TEXT};
+use eframe::egui;
+
+impl ControlApp {
+    /// Shows the `t` the grid is drawn at, with the timeline controls of the EM tab.
+    pub(super) fn scene_time_controls(ui: &mut egui::Ui, data: &mut GridUiState) {
+        ui.label(
+            egui::RichText::new(format!(
+                "Coordinate and field equations may use the scene time t, the time of the \
+                 timeline below, which the EM tab shares. The grid follows t every frame; the \
+                 metric, field samples and arrows follow in steps of {TIME_STEP}, or slower when \
+                 a rebuild takes longer than that. Field values of a visited step are reused \
+                 while the field and the grid stay the same."
+            ))
+            .color(MUTED),
+        );
+        egui::CollapsingHeader::new(
+            egui::RichText::new(format!("Time  t = {:.2}", data.em_time)).color(TEXT),
+        )
+        .id_salt("scene_time")
+        .default_open(false)
+        .show(ui, |ui| {
+            Self::time_controls(ui, data);
+        });
+    }
+}
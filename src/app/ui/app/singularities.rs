@@ -0,0 +1,54 @@
+//! Grid-tab listing of the singular sets of the applied coordinates.
+
+use super::ControlApp;
+use crate::app::ui::state::{GridUiState, SingularKind};
+use crate::app::ui::theme::{self, MUTED, TEXT};
+use eframe::egui::{self, Color32};
+
+/// Matches the red the render view draws the sets in.
+const SINGULAR_RED: Color32 = Color32::from_rgb(242, 26, 26);
+
+impl ControlApp {
+    /// Lists where the embedding degenerates, with a live toggle for the red highlight.
+    pub(super) fn render_singularities_section(ui: &mut egui::Ui, data: &mut GridUiState) {
+        let heading = if data.singularities.is_empty() {
+            "Singularities".to_string()
+        } else {
+            format!("Singularities ({})", data.singularities.len())
+        };
+        // The count is part of the heading, so the id is pinned to keep the open state.
+        egui::CollapsingHeader::new(theme::section_heading(&heading))
+            .id_salt("singularities_section")
+            .default_open(false)
+            .show(ui, |ui| {
+                ui.checkbox(
+                    &mut data.show_singularities,
+                    egui::RichText::new("Highlight in red").color(TEXT),
+                );
+                ui.label(
+                    egui::RichText::new(
+                        "Arrows and field samples are skipped where det J = 0, since the \
+                         coordinate tangents span no frame there. Updates on Apply.",
+                    )
+                    .color(MUTED),
+                );
+                if data.singularities.is_empty() {
+                    ui.label(egui::RichText::new("None found within the grid bounds.").color(TEXT));
+                    return;
+                }
+                ui.separator();
+                for set in &data.singularities {
+                    ui.horizontal_wrapped(|ui| {
+                        let source = if set.symbolic { "factored" } else { "sampled" };
+                        let color = match set.kind {
+                            SingularKind::Degenerate => SINGULAR_RED,
+                            SingularKind::NonInjective => TEXT,
+                        };
+                        ui.label(egui::RichText::new(set.kind.label()).color(color).strong());
+                        ui.label(egui::RichText::new(format!("({source})")).color(MUTED));
+                        ui.label(egui::RichText::new(&set.description).color(TEXT));
+                    });
+                }
+            });
+    }
+}
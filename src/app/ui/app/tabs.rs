@@ -1,9 +1,9 @@
 use super::{ControlApp, PresetLabel};
-use crate::app::ui::presets::{EmPreset, FieldPreset, GridPreset};
-use crate::app::ui::state::{ControlTab, EmGauge, EmMode, FieldKind, GridUiState};
-use crate::app::ui::theme::{self, MUTED, RASPBERRY, TEXT};
-use eframe::egui::{self, Color32};
-use eframe::epaint::CornerRadius;
+use crate::app::grid::MAX_CUSTOM_LINES;
+use crate::app::ui::presets::{FieldPreset, GridPreset};
+use crate::app::ui::state::{AxisSpacing, ControlTab, FieldKind, GridUiState, SpacingMode};
+use crate::app::ui::theme::{self, MUTED, TEXT};
+use eframe::egui;
 
 impl ControlApp {
     /// Dispatches to the active tab renderer.
@@ -39,16 +39,34 @@ impl ControlApp {
                 Self::eq_row(ui, "Equation x:  x =", &mut data.coords_sys.x.eq_str);
                 Self::eq_row(ui, "Equation y:  y =", &mut data.coords_sys.y.eq_str);
                 Self::eq_row(ui, "Equation z:  z =", &mut data.coords_sys.z.eq_str);
+                ui.separator();
+                Self::scene_time_controls(ui, data);
             });
 
+        ui.add_space(8.0);
+        Self::render_morph_section(ui, data);
+
         ui.add_space(8.0);
         egui::CollapsingHeader::new(theme::section_heading("Grid settings"))
             .default_open(true)
             .show(ui, |ui| {
                 ui.label(egui::RichText::new("Line per coordinate").color(MUTED));
-                Self::density_slider(ui, &mut data.nb_x, "x");
-                Self::density_slider(ui, &mut data.nb_y, "y");
-                Self::density_slider(ui, &mut data.nb_z, "z");
+                ui.label(
+                    egui::RichText::new(
+                        "Custom lines take constants such as 0, pi/4, 1 or an expression in i.",
+                    )
+                    .color(TEXT)
+                    .small(),
+                );
+                Self::lines_row(ui, &mut data.nb_x, &mut data.line_spacing[0], "x");
+                Self::lines_row(ui, &mut data.nb_y, &mut data.line_spacing[1], "y");
+                Self::lines_row(ui, &mut data.nb_z, &mut data.line_spacing[2], "z");
+                ui.horizontal(|ui| {
+                    ui.label(egui::RichText::new("Segment length").color(TEXT));
+                    for length in &mut data.segment_lengths {
+                        ui.add(egui::DragValue::new(length).speed(0.01).range(0.01..=100.0));
+                    }
+                });
 
                 ui.separator();
                 ui.label(egui::RichText::new("Bounds").color(MUTED));
@@ -61,8 +79,51 @@ impl ControlApp {
                 Self::bounds_row(ui, "Bounds (x):", &mut data.bounds_x);
                 Self::bounds_row(ui, "Bounds (y):", &mut data.bounds_y);
                 Self::bounds_row(ui, "Bounds (z):", &mut data.bounds_z);
+                ui.label(
+                    egui::RichText::new("Bounds accept constants such as pi/2 or -1.5.")
+                        .color(TEXT)
+                        .small(),
+                );
             });
 
+        ui.add_space(8.0);
+        Self::render_line_coloring_section(ui, data);
+
+        ui.add_space(8.0);
+        egui::CollapsingHeader::new(theme::section_heading("Coordinate surfaces"))
+            .default_open(false)
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    for (shown, label) in data.surface_axes.iter_mut().zip(["x", "y", "z"]) {
+                        ui.checkbox(
+                            shown,
+                            egui::RichText::new(format!("{label} = const")).color(TEXT),
+                        );
+                    }
+                });
+                ui.add(
+                    egui::Slider::new(&mut data.surface_opacity, 0.05..=1.0)
+                        .text("opacity")
+                        .trailing_fill(true),
+                );
+                ui.label(
+                    egui::RichText::new(
+                        "One shaded surface per grid line. Surfaces update on Apply; opacity \
+                         updates live.",
+                    )
+                    .color(MUTED),
+                );
+            });
+
+        ui.add_space(8.0);
+        Self::render_singularities_section(ui, data);
+
+        ui.add_space(8.0);
+        Self::render_tissot_section(ui, data);
+
+        ui.add_space(8.0);
+        Self::render_atlas_section(ui, data);
+
         ui.add_space(8.0);
         egui::CollapsingHeader::new(theme::section_heading("Tangent view"))
             .default_open(true)
@@ -84,9 +145,66 @@ impl ControlApp {
                     )
                     .color(MUTED),
                 );
+                ui.add_space(4.0);
+                ui.checkbox(&mut data.inspector_open, "Point inspector");
+                ui.label(
+                    egui::RichText::new("Hover a sample to inspect it; right-click pins it.")
+                        .color(MUTED),
+                );
             });
     }
 
+    /// Renders one axis's spacing mode with the count or parameter that mode uses.
+    fn lines_row(ui: &mut egui::Ui, count: &mut f64, spacing: &mut AxisSpacing, label: &str) {
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_id_salt(("line_spacing", label))
+                .width(90.0)
+                .selected_text(spacing.mode.label())
+                .show_ui(ui, |ui| {
+                    for mode in SpacingMode::ALL {
+                        ui.selectable_value(&mut spacing.mode, mode, mode.label());
+                    }
+                });
+            match spacing.mode {
+                SpacingMode::Uniform | SpacingMode::Chebyshev => {
+                    Self::density_slider(ui, count, label);
+                }
+                SpacingMode::Logarithmic => {
+                    ui.add(
+                        egui::DragValue::new(&mut spacing.ratio)
+                            .speed(0.1)
+                            .range(0.01..=1000.0)
+                            .prefix("ratio "),
+                    );
+                    Self::density_slider(ui, count, label);
+                }
+                SpacingMode::Step => {
+                    ui.add(
+                        egui::DragValue::new(&mut spacing.step)
+                            .speed(0.01)
+                            .range(0.001..=100.0),
+                    );
+                    ui.label(egui::RichText::new(label).color(TEXT));
+                }
+                SpacingMode::Custom => {
+                    ui.add(
+                        egui::TextEdit::singleline(&mut spacing.custom)
+                            .desired_width(140.0)
+                            .hint_text("0, 0.5, 2  or  i^2")
+                            .text_color(TEXT),
+                    );
+                    ui.add(
+                        egui::DragValue::new(count)
+                            .speed(0.1)
+                            .range(1.0..=MAX_CUSTOM_LINES as f64)
+                            .prefix("i < "),
+                    );
+                    ui.label(egui::RichText::new(label).color(TEXT));
+                }
+            }
+        });
+    }
+
     /// Renders the field-equation and tangent-arrow controls.
     fn render_field_tab(ui: &mut egui::Ui, data: &mut GridUiState) {
         egui::CollapsingHeader::new(theme::section_heading("Standard parameters"))
@@ -124,13 +242,7 @@ impl ControlApp {
                         );
                     }
                     FieldKind::Vector => {
-                        ui.label(
-                            egui::RichText::new("Vector components in the active coordinates")
-                                .color(MUTED),
-                        );
-                        Self::eq_row(ui, "Equation x:  Fx =", &mut data.field.x.eq_str);
-                        Self::eq_row(ui, "Equation y:  Fy =", &mut data.field.y.eq_str);
-                        Self::eq_row(ui, "Equation z:  Fz =", &mut data.field.z.eq_str);
+                        Self::render_vector_equations(ui, data);
                         ui.label(
                             egui::RichText::new(
                                 "Base render uses arrows. Enabling d renders the associated curl field.",
@@ -139,6 +251,8 @@ impl ControlApp {
                         );
                     }
                 }
+                ui.separator();
+                Self::scene_time_controls(ui, data);
 
                 ui.add_space(8.0);
                 ui.add_enabled_ui(data.renders_vector_field(), |ui| {
@@ -215,197 +329,7 @@ impl ControlApp {
         });
     }
 
-    fn render_em_tab(ui: &mut egui::Ui, data: &mut GridUiState) {
-        egui::CollapsingHeader::new(theme::section_heading("Standard parameters"))
-            .default_open(true)
-            .show(ui, |ui| {
-                Self::preset_buttons(ui, EmPreset::ALL, |preset, data| preset.apply(data), data);
-            });
-
-        ui.add_space(8.0);
-        egui::CollapsingHeader::new(theme::section_heading("Electromagnetism"))
-            .default_open(true)
-            .show(ui, |ui| {
-                if ui
-                    .add_sized(
-                        egui::vec2(160.0, 32.0),
-                        egui::Button::new(egui::RichText::new("Enable EM").color(TEXT).strong())
-                            .selected(data.em.enabled),
-                    )
-                    .on_hover_text("Enable electromagnetism rendering")
-                    .clicked()
-                {
-                    data.em.enabled = !data.em.enabled;
-                }
-                ui.add_space(8.0);
-                ui.horizontal(|ui| {
-                    ui.label(egui::RichText::new("Mode").color(TEXT));
-                    if Self::tab_button(ui, data.em.mode == EmMode::Potentials, "Potentials") {
-                        data.em.mode = EmMode::Potentials;
-                    }
-                    if Self::tab_button(ui, data.em.mode == EmMode::Electric, "E") {
-                        data.em.mode = EmMode::Electric;
-                    }
-                    if Self::tab_button(ui, data.em.mode == EmMode::Magnetic, "B") {
-                        data.em.mode = EmMode::Magnetic;
-                    }
-                });
-                ui.separator();
-                ui.horizontal(|ui| {
-                    ui.label(egui::RichText::new("Gauge").color(TEXT));
-                    if Self::tab_button(ui, data.em.gauge == EmGauge::Coulomb, "Coulomb") {
-                        data.em.gauge = EmGauge::Coulomb;
-                    }
-                    // if Self::tab_button(ui, data.em.gauge == EmGauge::Lorenz, "Lorenz", ) {
-                    //     data.em.gauge = EmGauge::Lorenz;
-                    // }
-                });
-                ui.label(
-                    egui::RichText::new(
-                        "Named gauge selector for potential display/reconstruction. Coulomb-like \
-                         is the current reconstruction",
-                    )
-                    .color(MUTED),
-                );
-                ui.separator();
-                Self::em_source_group(ui, data.em.mode == EmMode::Potentials, |ui| {
-                    Self::eq_row(ui, "Scalar:  V =", &mut data.em.phi.eq_str);
-                    Self::eq_row(
-                        ui,
-                        "Vector x:  Ax =",
-                        &mut data.em.vector_potential.x.eq_str,
-                    );
-                    Self::eq_row(
-                        ui,
-                        "Vector y:  Ay =",
-                        &mut data.em.vector_potential.y.eq_str,
-                    );
-                    Self::eq_row(
-                        ui,
-                        "Vector z:  Az =",
-                        &mut data.em.vector_potential.z.eq_str,
-                    );
-                });
-                ui.separator();
-                Self::em_source_group(ui, data.em.mode == EmMode::Electric, |ui| {
-                    Self::eq_row(
-                        ui,
-                        "Electric x:  Ex =",
-                        &mut data.em.electric_field.x.eq_str,
-                    );
-                    Self::eq_row(
-                        ui,
-                        "Electric y:  Ey =",
-                        &mut data.em.electric_field.y.eq_str,
-                    );
-                    Self::eq_row(
-                        ui,
-                        "Electric z:  Ez =",
-                        &mut data.em.electric_field.z.eq_str,
-                    );
-                });
-                ui.separator();
-                Self::em_source_group(ui, data.em.mode == EmMode::Magnetic, |ui| {
-                    Self::eq_row(
-                        ui,
-                        "Magnetic x:  Bx =",
-                        &mut data.em.magnetic_field.x.eq_str,
-                    );
-                    Self::eq_row(
-                        ui,
-                        "Magnetic y:  By =",
-                        &mut data.em.magnetic_field.y.eq_str,
-                    );
-                    Self::eq_row(
-                        ui,
-                        "Magnetic z:  Bz =",
-                        &mut data.em.magnetic_field.z.eq_str,
-                    );
-                });
-            });
-
-        ui.add_space(8.0);
-        egui::CollapsingHeader::new(theme::section_heading("Constants"))
-            .default_open(true)
-            .show(ui, |ui| {
-                ui.add(
-                    egui::Slider::new(&mut data.em.light_speed, 0.1..=100.0)
-                        .logarithmic(true)
-                        .text("c")
-                        .trailing_fill(true),
-                );
-                ui.add(
-                    egui::Slider::new(&mut data.em.magnetic_vector_scale, 0.1..=100.0)
-                        .logarithmic(true)
-                        .text("B vector scale")
-                        .trailing_fill(true),
-                );
-                ui.checkbox(
-                    &mut data.em.normalize_vectors,
-                    egui::RichText::new("Normalize EM vectors").color(TEXT),
-                );
-            });
-
-        ui.add_space(8.0);
-        egui::CollapsingHeader::new(theme::section_heading("Time"))
-            .default_open(true)
-            .show(ui, |ui| {
-                ui.checkbox(&mut data.em.running, egui::RichText::new("Run").color(TEXT));
-                ui.add(
-                    egui::Slider::new(&mut data.em.time_scale, -5.0..=5.0)
-                        .text("time scale")
-                        .trailing_fill(true),
-                );
-                if ui
-                    .add(
-                        egui::Button::new(egui::RichText::new("Reset time").color(Color32::WHITE))
-                            .fill(RASPBERRY)
-                            .min_size(egui::vec2(120.0, 30.0))
-                            .corner_radius(CornerRadius::same(6)),
-                    )
-                    .clicked()
-                {
-                    data.em.reset_counter += 1;
-                }
-            });
-
-        ui.add_space(8.0);
-        egui::CollapsingHeader::new(theme::section_heading("Layers"))
-            .default_open(true)
-            .show(ui, |ui| {
-                ui.checkbox(
-                    &mut data.em.layers.electric,
-                    egui::RichText::new("E").color(TEXT),
-                );
-                ui.checkbox(
-                    &mut data.em.layers.magnetic,
-                    egui::RichText::new("B").color(TEXT),
-                );
-                ui.checkbox(
-                    &mut data.em.layers.scalar_potential,
-                    egui::RichText::new("V").color(TEXT),
-                )
-                .on_hover_text(
-                    "Shows the scalar potential. The built-in wave presets use the V = 0 gauge, \
-                     so this layer is intentionally uniform until V is edited or reconstructed \
-                     from E/B source mode.",
-                );
-                ui.checkbox(
-                    &mut data.em.layers.vector_potential,
-                    egui::RichText::new("A").color(TEXT),
-                );
-            });
-    }
-
-    fn em_source_group(
-        ui: &mut egui::Ui,
-        editable: bool,
-        add_contents: impl FnOnce(&mut egui::Ui),
-    ) {
-        ui.add_enabled_ui(editable, add_contents);
-    }
-
-    fn preset_buttons<T: Copy>(
+    pub(super) fn preset_buttons<T: Copy>(
         ui: &mut egui::Ui,
         presets: impl IntoIterator<Item = T>,
         apply: impl Fn(T, &mut GridUiState),
@@ -0,0 +1,128 @@
+//! Timeline controls: playback, scrubber, loop range, single steps, direction and rate
+//! keyframes. The timeline time drives the EM fields and the scene time `t` alike.
+
+use super::ControlApp;
+use crate::app::ui::state::{GridUiState, TimeScaleKeyframe};
+use crate::app::ui::theme::{MUTED, RASPBERRY, TEXT};
+use eframe::egui::{self, Color32};
+use eframe::epaint::CornerRadius;
+
+impl ControlApp {
+    /// Runs, scales and resets the timeline, followed by the timeline panel.
+    pub(super) fn time_controls(ui: &mut egui::Ui, data: &mut GridUiState) {
+        ui.checkbox(&mut data.em.running, egui::RichText::new("Run").color(TEXT));
+        ui.add(
+            egui::Slider::new(&mut data.em.time_scale, -5.0..=5.0)
+                .text("time scale")
+                .trailing_fill(true),
+        );
+        if ui
+            .add(
+                egui::Button::new(egui::RichText::new("Reset time").color(Color32::WHITE))
+                    .fill(RASPBERRY)
+                    .min_size(egui::vec2(120.0, 30.0))
+                    .corner_radius(CornerRadius::same(6)),
+            )
+            .clicked()
+        {
+            data.em.reset_counter += 1;
+        }
+        ui.separator();
+        Self::timeline_panel(ui, data);
+    }
+
+    fn timeline_panel(ui: &mut egui::Ui, data: &mut GridUiState) {
+        let em_time = data.em_time;
+        let timeline = &mut data.em.timeline;
+
+        ui.label(
+            egui::RichText::new(format!("t = {em_time:.3}"))
+                .color(TEXT)
+                .monospace(),
+        );
+        let (start, end) = (
+            timeline.loop_start,
+            timeline.loop_end.max(timeline.loop_start),
+        );
+        let mut scrub = em_time.clamp(start, end);
+        let scrubber = ui.add(
+            egui::Slider::new(&mut scrub, start..=end)
+                .text("scrub")
+                .trailing_fill(true),
+        );
+        if scrubber.changed() {
+            data.em.running = false;
+            timeline.seek(scrub);
+        }
+
+        ui.horizontal(|ui| {
+            if Self::compact_button(ui, "Step back") {
+                data.em.running = false;
+                timeline.seek(em_time - timeline.step);
+            }
+            if Self::compact_button(ui, "Step forward") {
+                data.em.running = false;
+                timeline.seek(em_time + timeline.step);
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.checkbox(
+                &mut timeline.reverse,
+                egui::RichText::new("Reverse").color(TEXT),
+            );
+            ui.label(egui::RichText::new("step").color(TEXT));
+            ui.add(
+                egui::DragValue::new(&mut timeline.step)
+                    .speed(0.005)
+                    .range(0.001..=10.0),
+            );
+        });
+        ui.horizontal(|ui| {
+            ui.checkbox(
+                &mut timeline.loop_enabled,
+                egui::RichText::new("Loop").color(TEXT),
+            );
+            ui.label(egui::RichText::new("from").color(TEXT));
+            ui.add(egui::DragValue::new(&mut timeline.loop_start).speed(0.05));
+            ui.label(egui::RichText::new("to").color(TEXT));
+            ui.add(egui::DragValue::new(&mut timeline.loop_end).speed(0.05));
+        });
+        if timeline.loop_end <= timeline.loop_start {
+            timeline.loop_end = timeline.loop_start + timeline.step;
+        }
+
+        ui.add_space(4.0);
+        ui.label(
+            egui::RichText::new(
+                "Keyframes multiply the time scale at the given time; the rate is interpolated \
+                 linearly between them and held beyond the first and last.",
+            )
+            .color(MUTED),
+        );
+        let mut removed = None;
+        for (index, keyframe) in timeline.keyframes.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new("t").color(TEXT));
+                ui.add(egui::DragValue::new(&mut keyframe.time).speed(0.05));
+                ui.label(egui::RichText::new("scale").color(TEXT));
+                ui.add(
+                    egui::DragValue::new(&mut keyframe.scale)
+                        .speed(0.02)
+                        .range(-10.0..=10.0),
+                );
+                if ui.small_button("Remove").clicked() {
+                    removed = Some(index);
+                }
+            });
+        }
+        if let Some(index) = removed {
+            timeline.keyframes.remove(index);
+        }
+        if ui.button("Add keyframe at current time").clicked() {
+            timeline.keyframes.push(TimeScaleKeyframe {
+                time: em_time,
+                scale: 1.0,
+            });
+        }
+    }
+}
@@ -0,0 +1,54 @@
+//! Grid-tab toggle and distortion summary of the Tissot indicatrices.
+
+use super::ControlApp;
+use crate::app::ui::state::{format_vector, GridUiState};
+use crate::app::ui::theme::{self, MUTED, TEXT};
+use eframe::egui;
+
+impl ControlApp {
+    /// Shows or sizes the strain ellipsoids and reports how far the embedding is from conformal.
+    pub(super) fn render_tissot_section(ui: &mut egui::Ui, data: &mut GridUiState) {
+        egui::CollapsingHeader::new(theme::section_heading("Tissot indicatrices"))
+            .default_open(false)
+            .show(ui, |ui| {
+                ui.checkbox(
+                    &mut data.tissot.show,
+                    egui::RichText::new("Draw strain ellipsoids").color(TEXT),
+                );
+                ui.add(
+                    egui::Slider::new(&mut data.tissot.radius, 0.01..=1.0)
+                        .logarithmic(true)
+                        .text("abstract radius")
+                        .trailing_fill(true),
+                );
+                ui.label(
+                    egui::RichText::new(
+                        "Each ellipsoid is the image of a small coordinate ball: its axes are the \
+                         principal stretch directions and its radii the singular values of J. \
+                         Blue shrinks volumes, red grows them. Shown in world space only.",
+                    )
+                    .color(MUTED),
+                );
+
+                let Some(report) = &data.tissot_report else {
+                    ui.label(egui::RichText::new("No field samples.").color(TEXT));
+                    return;
+                };
+                ui.separator();
+                for line in [
+                    format!("conformal: {} of {}", report.conformal, report.samples),
+                    format!(
+                        "volume-preserving: {} of {}",
+                        report.volume_preserving, report.samples
+                    ),
+                    format!(
+                        "max σ_max/σ_min = {:.4} at {}",
+                        report.max_anisotropy,
+                        format_vector(&report.max_anisotropy_at)
+                    ),
+                ] {
+                    ui.label(egui::RichText::new(line).color(TEXT));
+                }
+            });
+    }
+}
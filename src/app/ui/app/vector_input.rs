@@ -0,0 +1,74 @@
+//! Field-tab vector components, in the active coordinates or in world Cartesian form.
+
+use super::ControlApp;
+use crate::app::ui::state::{GridUiState, VectorInput};
+use crate::app::ui::theme::{MUTED, TEXT};
+use eframe::egui;
+
+impl ControlApp {
+    /// Picks the input basis, edits the three components and shows the pulled-back expressions
+    /// of a world Cartesian field.
+    pub(super) fn render_vector_equations(ui: &mut egui::Ui, data: &mut GridUiState) {
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new("Components in").color(TEXT));
+            for input in [VectorInput::Orthonormal, VectorInput::WorldCartesian] {
+                ui.selectable_value(&mut data.vector_input, input, input.label());
+            }
+        });
+        let hint = match data.vector_input {
+            VectorInput::Orthonormal => "Orthonormal components over x, y, z",
+            VectorInput::WorldCartesian => {
+                "Cartesian components over the world position X, Y, Z, pulled back through the \
+                 embedding on Apply"
+            }
+        };
+        ui.label(egui::RichText::new(hint).color(MUTED));
+        let [x, y, z] = data.vector_input.variables();
+        Self::eq_row(
+            ui,
+            &format!("Equation {x}:  F{x} ="),
+            &mut data.field.x.eq_str,
+        );
+        Self::eq_row(
+            ui,
+            &format!("Equation {y}:  F{y} ="),
+            &mut data.field.y.eq_str,
+        );
+        Self::eq_row(
+            ui,
+            &format!("Equation {z}:  F{z} ="),
+            &mut data.field.z.eq_str,
+        );
+
+        if data.vector_input != VectorInput::WorldCartesian {
+            return;
+        }
+        let Some(pulled_back) = &data.pulled_back_field else {
+            return;
+        };
+        ui.add_space(4.0);
+        egui::Grid::new("pulled_back_field")
+            .num_columns(2)
+            .striped(true)
+            .show(ui, |ui| {
+                let coords = ["x", "y", "z"];
+                for (coord, natural) in coords.iter().zip(&pulled_back.natural) {
+                    ui.label(egui::RichText::new(format!("F_{coord} (natural)")).color(TEXT));
+                    ui.label(egui::RichText::new(natural).color(MUTED).monospace());
+                    ui.end_row();
+                }
+                for (coord, orthonormal) in coords.iter().zip(&pulled_back.orthonormal) {
+                    ui.label(egui::RichText::new(format!("F_{coord} (orthonormal)")).color(TEXT));
+                    ui.label(egui::RichText::new(orthonormal).color(MUTED).monospace());
+                    ui.end_row();
+                }
+            });
+        ui.label(
+            egui::RichText::new(
+                "Natural components are Jᵀ F with X, Y, Z replaced by the embedding; the \
+                 orthonormal ones are what the active-coordinates input would take.",
+            )
+            .color(MUTED),
+        );
+    }
+}
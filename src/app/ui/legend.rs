@@ -1,10 +1,10 @@
 //! Detached legend window and shared color ramp for sampled scalar or dual-form values.
 
-use crate::app::ui::state::LegendState;
+use crate::app::ui::state::{Colormap, LegendState};
 use crate::app::ui::theme::{self, BORDER, MUTED, SHADOW_GREY, TEXT};
 use eframe::egui::{self, Color32, Stroke, ViewportBuilder, ViewportCommand, ViewportId};
 use eframe::epaint::{CornerRadius, Margin};
-use nalgebra::{vector, Vector4};
+use nalgebra::{Vector3, Vector4};
 
 /// Shows or closes the detached legend viewport for sampled value rendering.
 pub(crate) fn show_legend_window(ctx: &egui::Context, legend: Option<LegendState>) {
@@ -46,30 +46,7 @@ fn render_legend(ui: &mut egui::Ui, legend: LegendState) {
             ui.label(egui::RichText::new(descriptor.subtitle).color(MUTED));
             ui.add_space(8.0);
 
-            let desired_size = egui::vec2(ui.available_width(), 26.0);
-            let (rect, _) = ui.allocate_exact_size(desired_size, egui::Sense::hover());
-            let painter = ui.painter();
-            let steps = 96;
-            for step in 0..steps {
-                let t0 = step as f32 / steps as f32;
-                let t1 = (step + 1) as f32 / steps as f32;
-                let x0 = rect.left() + rect.width() * t0;
-                let x1 = rect.left() + rect.width() * t1;
-                let band = egui::Rect::from_min_max(
-                    egui::pos2(x0, rect.top()),
-                    egui::pos2(x1, rect.bottom()),
-                );
-                painter.rect_filled(band, CornerRadius::ZERO, legend_color(t0 as f64));
-            }
-            painter.rect_stroke(
-                rect,
-                CornerRadius::same(4),
-                Stroke::new(1.0, BORDER),
-                egui::StrokeKind::Outside,
-            );
-
-            ui.add_space(6.0);
-            render_scale_labels(ui, legend);
+            render_ramp(ui, legend, Colormap::Diverging);
 
             ui.label(
                 egui::RichText::new("Blue is most negative, red is most positive.").color(MUTED),
@@ -78,13 +55,43 @@ fn render_legend(ui: &mut egui::Ui, legend: LegendState) {
         });
 }
 
+/// Draws the `colormap` ramp with the range markers of `legend` underneath.
+pub(crate) fn render_ramp(ui: &mut egui::Ui, legend: LegendState, colormap: Colormap) {
+    let desired_size = egui::vec2(ui.available_width(), 26.0);
+    let (rect, _) = ui.allocate_exact_size(desired_size, egui::Sense::hover());
+    let painter = ui.painter();
+    let steps = 96;
+    for step in 0..steps {
+        let t0 = step as f32 / steps as f32;
+        let t1 = (step + 1) as f32 / steps as f32;
+        let x0 = rect.left() + rect.width() * t0;
+        let x1 = rect.left() + rect.width() * t1;
+        let band =
+            egui::Rect::from_min_max(egui::pos2(x0, rect.top()), egui::pos2(x1, rect.bottom()));
+        painter.rect_filled(
+            band,
+            CornerRadius::ZERO,
+            colormap_color(colormap, t0 as f64),
+        );
+    }
+    painter.rect_stroke(
+        rect,
+        CornerRadius::same(4),
+        Stroke::new(1.0, BORDER),
+        egui::StrokeKind::Outside,
+    );
+
+    ui.add_space(6.0);
+    render_scale_labels(ui, legend, colormap);
+}
+
 /// Renders scale markers underneath the legend ramp using the actual value range.
-fn render_scale_labels(ui: &mut egui::Ui, legend: LegendState) {
+fn render_scale_labels(ui: &mut egui::Ui, legend: LegendState, colormap: Colormap) {
     let desired_size = egui::vec2(ui.available_width(), 20.0);
     let (rect, _) = ui.allocate_exact_size(desired_size, egui::Sense::hover());
     let painter = ui.painter();
 
-    for marker in legend_markers(legend) {
+    for marker in legend_markers(legend, colormap) {
         let x = rect.left() + rect.width() * marker.mix as f32;
         painter.text(
             egui::pos2(x, rect.center().y),
@@ -96,19 +103,29 @@ fn render_scale_labels(ui: &mut egui::Ui, legend: LegendState) {
     }
 }
 
-/// Maps a normalized legend position onto the dual tangent color ramp.
-fn legend_color(t: f64) -> Color32 {
-    let clamped = t.clamp(0.0, 1.0);
-    let cold = vector![0.08, 0.22, 1.0];
-    let neutral = vector![0.95, 0.95, 1.0];
-    let warm = vector![1.0, 0.18, 0.08];
-    let color = if clamped < 0.5 {
-        let local_mix = clamped * 2.0;
-        cold * (1.0 - local_mix) + neutral * local_mix
-    } else {
-        let local_mix = (clamped - 0.5) * 2.0;
-        neutral * (1.0 - local_mix) + warm * local_mix
+/// Stops of the blue, white and red ramp shared by the scalar and dual-form legends.
+const DIVERGING_STOPS: [[f64; 3]; 3] = [[0.08, 0.22, 1.0], [0.95, 0.95, 1.0], [1.0, 0.18, 0.08]];
+const VIRIDIS_STOPS: [[f64; 3]; 5] = [
+    [0.267, 0.005, 0.329],
+    [0.229, 0.322, 0.546],
+    [0.128, 0.567, 0.551],
+    [0.369, 0.789, 0.383],
+    [0.993, 0.906, 0.144],
+];
+const GRAYSCALE_STOPS: [[f64; 3]; 2] = [[0.1, 0.1, 0.1], [0.95, 0.95, 0.95]];
+
+/// Maps a normalized legend position onto `colormap`; `grid_edit.vert` mirrors these ramps.
+pub(crate) fn colormap_color(colormap: Colormap, t: f64) -> Color32 {
+    let stops: &[[f64; 3]] = match colormap {
+        Colormap::Diverging => &DIVERGING_STOPS,
+        Colormap::Viridis => &VIRIDIS_STOPS,
+        Colormap::Grayscale => &GRAYSCALE_STOPS,
     };
+    let position = t.clamp(0.0, 1.0) * (stops.len() - 1) as f64;
+    let index = (position.floor() as usize).min(stops.len() - 2);
+    let local_mix = position - index as f64;
+    let color = Vector3::from(stops[index]) * (1.0 - local_mix)
+        + Vector3::from(stops[index + 1]) * local_mix;
 
     Color32::from_rgb(
         (color.x * 255.0).round() as u8,
@@ -136,7 +153,7 @@ fn legend_mix(value: f64, min_value: f64, max_value: f64) -> f64 {
 pub(crate) fn sampled_value_color(value: f64, min_value: f64, max_value: f64) -> Vector4<f64> {
     let mix = legend_mix(value, min_value, max_value);
 
-    let color = legend_color(mix);
+    let color = colormap_color(Colormap::Diverging, mix);
     Vector4::new(
         f64::from(color.r()) / 255.0,
         f64::from(color.g()) / 255.0,
@@ -152,18 +169,18 @@ struct LegendMarker {
     anchor: egui::Align2,
 }
 
-fn legend_markers(legend: LegendState) -> Vec<LegendMarker> {
+fn legend_markers(legend: LegendState, colormap: Colormap) -> Vec<LegendMarker> {
     let mut markers = vec![
         LegendMarker {
             mix: 0.0,
             label: format!("{:.3}", legend.min_value),
-            color: legend_color(0.0),
+            color: colormap_color(colormap, 0.0),
             anchor: egui::Align2::LEFT_CENTER,
         },
         LegendMarker {
             mix: 1.0,
             label: format!("{:.3}", legend.max_value),
-            color: legend_color(1.0),
+            color: colormap_color(colormap, 1.0),
             anchor: egui::Align2::RIGHT_CENTER,
         },
     ];
@@ -178,7 +195,7 @@ fn legend_markers(legend: LegendState) -> Vec<LegendMarker> {
     markers.push(LegendMarker {
         mix: middle_mix,
         label: format!("{:.3}", middle_value),
-        color: legend_color(middle_mix),
+        color: colormap_color(colormap, middle_mix),
         anchor: egui::Align2::CENTER_CENTER,
     });
 
@@ -187,8 +204,8 @@ fn legend_markers(legend: LegendState) -> Vec<LegendMarker> {
 
 #[cfg(test)]
 mod tests {
-    use super::{legend_markers, legend_mix};
-    use crate::app::ui::{LegendKind, LegendState};
+    use super::{colormap_color, legend_markers, legend_mix};
+    use crate::app::ui::{Colormap, LegendKind, LegendState};
 
     #[test]
     fn legend_mix_places_zero_at_start_for_nonnegative_range() {
@@ -197,17 +214,36 @@ mod tests {
 
     #[test]
     fn legend_markers_use_midpoint_when_zero_is_not_inside_range() {
-        let markers = legend_markers(LegendState {
-            kind: LegendKind::ScalarField,
-            min_value: 0.0,
-            max_value: 10.0,
-        });
+        let markers = legend_markers(
+            LegendState {
+                kind: LegendKind::ScalarField,
+                min_value: 0.0,
+                max_value: 10.0,
+            },
+            Colormap::Diverging,
+        );
 
         assert_eq!(markers[0].label, "0.000");
         assert_eq!(markers[1].label, "10.000");
         assert_eq!(markers[2].label, "5.000");
         assert_eq!(markers[2].mix, 0.5);
     }
+
+    #[test]
+    fn colormaps_interpolate_between_their_stops() {
+        assert_eq!(
+            colormap_color(Colormap::Diverging, 0.5),
+            eframe::egui::Color32::from_rgb(242, 242, 255)
+        );
+        assert_eq!(
+            colormap_color(Colormap::Grayscale, 0.5),
+            eframe::egui::Color32::from_rgb(134, 134, 134)
+        );
+        assert_eq!(
+            colormap_color(Colormap::Viridis, 2.0),
+            colormap_color(Colormap::Viridis, 1.0)
+        );
+    }
 }
 
 /// Returns the viewport id reserved for the detached legend window.
@@ -2,6 +2,7 @@
 
 mod app;
 pub(crate) mod legend;
+mod mode_presets;
 mod presets;
 mod state;
 mod theme;
@@ -9,8 +10,13 @@ mod validation;
 
 #[allow(unused_imports)]
 pub use state::{
-    EmGauge, EmLayerVisibility, EmMode, EmUiState, EqRender, FieldKind, GridUiState, LegendKind,
-    LegendState, SpacialEqs,
+    AtlasUiState, AxisSpacing, ChartOverlap, ChartPreset, ChartUiState, Colormap, CovariantReport,
+    EmGauge, EmLayerVisibility, EmMode, EmTimelineState, EmUiState, EqRender, FieldKind,
+    GaugeReport, GridBound, GridUiState, InspectedEm, InspectedField, InspectorReport,
+    InverseCurlBackend, LegendKind, LegendState, LineColoringUiState, LineQuantity, MorphHomotopy,
+    MorphUiState, ProbeChannel, ProbeMarker, ProbeTrace, ProbeUiState, PulledBackField,
+    SingularKind, SingularSet, SpacialEqs, SpacingMode, TimeScaleKeyframe, TissotReport,
+    TissotUiState, VectorInput, PRIMARY_CHART_NAME, PROBE_CHANNEL_COUNT,
 };
 
 use crate::app::ui::app::ControlApp;
@@ -0,0 +1,611 @@
+//! Parameterized waveguide and cavity mode presets.
+//!
+//! Modes are generated in closed form with `V = 0` and `A = -int E dt`, and are loaded in the
+//! closed-form EM mode so the generated `E` and `B` are rendered exactly as written. Units follow
+//! the runtime: `eps_0 = 1` and `mu_0 = 1 / c^2`. Rectangular guides span `[0, a] x [0, b]` in
+//! Cartesian coordinates; circular guides use cylindrical `(r, theta, z) = (x, y, z)`, so the
+//! generated components are the orthonormal `r`, `theta` and `z` components.
+
+use crate::app::ui::state::{
+    EmMode, GridUiState, GuideGeometry, GuidedModeState, ModeFamily, SpacialEqs,
+};
+use crate::maths::special::{bessel_j_prime_zero, bessel_j_zero, BESSEL_J};
+use std::f64::consts::PI;
+
+const CARTESIAN: [&str; 3] = ["x", "y", "z"];
+const CYLINDRICAL: [&str; 3] = ["x*cos(y)", "x*sin(y)", "z"];
+/// Smallest guide width, height, radius or length a mode is built for.
+const MIN_GUIDE_DIMENSION: f64 = 0.1;
+
+/// Fully generated mode, ready to be written into the UI state.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct GuidedMode {
+    pub(crate) vector_potential: [String; 3],
+    pub(crate) electric_field: [String; 3],
+    pub(crate) magnetic_field: [String; 3],
+    /// Driving frequency for waveguides, resonant frequency for cavities.
+    pub(crate) angular_frequency: f64,
+    pub(crate) cutoff_angular_frequency: f64,
+    coords: [&'static str; 3],
+    bounds: [(String, String); 3],
+    counts: [f64; 3],
+    /// Conducting faces of the grid box, ordered `x_min, x_max, y_min, y_max, z_min, z_max`.
+    walls: [bool; 6],
+}
+
+impl GuidedMode {
+    /// Loads the mode, its guide geometry and its walls into the editable UI state.
+    pub(crate) fn apply(&self, state: &mut GridUiState) {
+        state.render_3d = true;
+        set_spacial_eqs(&mut state.coords_sys, &self.coords.map(str::to_string));
+        state.line_spacing = Default::default();
+        state.set_bound_texts(self.bounds.clone());
+        state.nb_x = self.counts[0];
+        state.nb_y = self.counts[1];
+        state.nb_z = self.counts[2];
+
+        state.em.mode = EmMode::ClosedForm;
+        state.em.phi.eq_str = "0".to_string();
+        set_spacial_eqs(&mut state.em.vector_potential, &self.vector_potential);
+        set_spacial_eqs(&mut state.em.electric_field, &self.electric_field);
+        set_spacial_eqs(&mut state.em.magnetic_field, &self.magnetic_field);
+        state.em.angular_frequency = self.angular_frequency;
+        state.em.pec_walls = self.walls;
+        state.em.layers.scalar_potential = false;
+        state.em.layers.vector_potential = false;
+        state.em.layers.electric = true;
+        state.em.layers.magnetic = true;
+    }
+}
+
+/// Returns the cutoff angular frequency `c k_c` of the selected transverse mode.
+pub(crate) fn cutoff_angular_frequency(
+    spec: &GuidedModeState,
+    light_speed: f64,
+) -> Result<f64, String> {
+    Ok(light_speed * cutoff_wavenumber(spec)?)
+}
+
+/// Builds the closed-form fields of the selected mode.
+///
+/// Waveguides are driven at `angular_frequency`, which must exceed the cutoff. Cavities close
+/// the guide at `z = 0` and `z = L` and oscillate at their resonant frequency instead.
+pub(crate) fn guided_mode(
+    spec: &GuidedModeState,
+    light_speed: f64,
+    angular_frequency: f64,
+) -> Result<GuidedMode, String> {
+    let c = light_speed;
+    let cutoff = cutoff_wavenumber(spec)?;
+    let length = guide_dimension(spec.length);
+    let (omega, beta) = if spec.cavity {
+        if spec.family == ModeFamily::Te && spec.p == 0 {
+            return Err("TE cavity modes need p >= 1".to_string());
+        }
+        let beta = spec.p as f64 * PI / length;
+        (c * (cutoff * cutoff + beta * beta).sqrt(), beta)
+    } else {
+        let k = angular_frequency / c;
+        if k <= cutoff {
+            return Err(format!(
+                "omega = {angular_frequency:.3} is at or below the {} cutoff {:.3}",
+                mode_label(spec),
+                c * cutoff
+            ));
+        }
+        (angular_frequency, (k * k - cutoff * cutoff).sqrt())
+    };
+
+    let shape = ModeShape {
+        omega,
+        beta,
+        cutoff,
+        c,
+    };
+    let (electric, magnetic) = match spec.geometry {
+        GuideGeometry::Rectangular => shape.rectangular(spec),
+        GuideGeometry::Circular => shape.circular(spec),
+    };
+    let wave = Wave {
+        omega,
+        beta,
+        cavity: spec.cavity,
+    };
+
+    let (coords, bounds, counts) = match spec.geometry {
+        GuideGeometry::Rectangular => (
+            CARTESIAN,
+            [
+                bound_texts(0.0, guide_dimension(spec.width)),
+                bound_texts(0.0, guide_dimension(spec.height)),
+                bound_texts(0.0, length),
+            ],
+            [5.0, 5.0, 7.0],
+        ),
+        GuideGeometry::Circular => (
+            CYLINDRICAL,
+            [
+                bound_texts(0.0, guide_dimension(spec.radius)),
+                ("0".to_string(), "2*pi".to_string()),
+                bound_texts(0.0, length),
+            ],
+            [4.0, 12.0, 7.0],
+        ),
+    };
+    let walls = match spec.geometry {
+        GuideGeometry::Rectangular => [true, true, true, true, spec.cavity, spec.cavity],
+        GuideGeometry::Circular => [false, true, false, false, spec.cavity, spec.cavity],
+    };
+
+    Ok(GuidedMode {
+        vector_potential: [0, 1, 2].map(|axis| wave.potential(&electric[axis], axis)),
+        electric_field: [0, 1, 2].map(|axis| wave.field(&electric[axis], axis, Parity::Polar)),
+        magnetic_field: [0, 1, 2].map(|axis| wave.field(&magnetic[axis], axis, Parity::Axial)),
+        angular_frequency: omega,
+        cutoff_angular_frequency: c * cutoff,
+        coords,
+        bounds,
+        counts,
+        walls,
+    })
+}
+
+/// Returns a label such as `TE_10` or `TM_011`.
+pub(crate) fn mode_label(spec: &GuidedModeState) -> String {
+    let family = match spec.family {
+        ModeFamily::Te => "TE",
+        ModeFamily::Tm => "TM",
+    };
+    let (first, second) = match spec.geometry {
+        GuideGeometry::Rectangular => (spec.m, spec.n),
+        GuideGeometry::Circular => (spec.n, spec.m),
+    };
+    if spec.cavity {
+        format!("{family}_{first}{second}{}", spec.p)
+    } else {
+        format!("{family}_{first}{second}")
+    }
+}
+
+fn cutoff_wavenumber(spec: &GuidedModeState) -> Result<f64, String> {
+    match (spec.geometry, spec.family) {
+        (GuideGeometry::Rectangular, ModeFamily::Te) if spec.m + spec.n == 0 => {
+            Err("rectangular TE modes need m + n >= 1".to_string())
+        }
+        (GuideGeometry::Rectangular, ModeFamily::Tm) if spec.m == 0 || spec.n == 0 => {
+            Err("rectangular TM modes need m, n >= 1".to_string())
+        }
+        (GuideGeometry::Rectangular, _) => {
+            let (kx, ky) = rectangular_wavenumbers(spec);
+            Ok((kx * kx + ky * ky).sqrt())
+        }
+        (GuideGeometry::Circular, _) if spec.m == 0 => {
+            Err("circular modes need a radial index m >= 1".to_string())
+        }
+        (GuideGeometry::Circular, ModeFamily::Te) => {
+            Ok(bessel_j_prime_zero(spec.n as i32, spec.m as usize)? / guide_dimension(spec.radius))
+        }
+        (GuideGeometry::Circular, ModeFamily::Tm) => {
+            Ok(bessel_j_zero(spec.n as i32, spec.m as usize)? / guide_dimension(spec.radius))
+        }
+    }
+}
+
+fn rectangular_wavenumbers(spec: &GuidedModeState) -> (f64, f64) {
+    (
+        spec.m as f64 * PI / guide_dimension(spec.width),
+        spec.n as f64 * PI / guide_dimension(spec.height),
+    )
+}
+
+/// Guide dimensions become the grid bounds as typed.
+fn guide_dimension(value: f64) -> f64 {
+    value.max(MIN_GUIDE_DIMENSION)
+}
+
+fn bound_texts(min: f64, max: f64) -> (String, String) {
+    (min.to_string(), max.to_string())
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Trig {
+    Sin,
+    Cos,
+}
+
+impl Trig {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Sin => "sin",
+            Self::Cos => "cos",
+        }
+    }
+}
+
+/// How a component flips under the mirror `z -> -z`: polar `E` keeps its transverse part,
+/// axial `B` keeps its longitudinal part.
+#[derive(Clone, Copy)]
+enum Parity {
+    Polar,
+    Axial,
+}
+
+/// One component of a `+z` travelling mode: `coefficient * profile * trig(omega t - beta z)`.
+///
+/// `None` stands for an identically zero component.
+#[derive(Debug, Clone)]
+struct Travelling {
+    coefficient: f64,
+    profile: Vec<String>,
+    phase: Trig,
+}
+
+type Components = [Option<Travelling>; 3];
+
+struct ModeShape {
+    omega: f64,
+    beta: f64,
+    cutoff: f64,
+    c: f64,
+}
+
+impl ModeShape {
+    /// Transverse fields of the rectangular modes with `B_z` or `E_z` of unit amplitude.
+    fn rectangular(&self, spec: &GuidedModeState) -> (Components, Components) {
+        let (kx, ky) = rectangular_wavenumbers(spec);
+        let kc2 = self.cutoff * self.cutoff;
+        let (omega, beta, c2) = (self.omega, self.beta, self.c * self.c);
+        let term = |coefficient: f64, x: Trig, y: Trig, phase: Trig| {
+            travelling(
+                coefficient,
+                [trig_factor(x, kx, "x"), trig_factor(y, ky, "y")],
+                phase,
+            )
+        };
+        use Trig::{Cos, Sin};
+
+        match spec.family {
+            ModeFamily::Te => (
+                [
+                    term(-omega * ky / kc2, Cos, Sin, Sin),
+                    term(omega * kx / kc2, Sin, Cos, Sin),
+                    None,
+                ],
+                [
+                    term(-beta * kx / kc2, Sin, Cos, Sin),
+                    term(-beta * ky / kc2, Cos, Sin, Sin),
+                    term(1.0, Cos, Cos, Cos),
+                ],
+            ),
+            ModeFamily::Tm => (
+                [
+                    term(beta * kx / kc2, Cos, Sin, Sin),
+                    term(beta * ky / kc2, Sin, Cos, Sin),
+                    term(1.0, Sin, Sin, Cos),
+                ],
+                [
+                    term(-omega * ky / (c2 * kc2), Sin, Cos, Sin),
+                    term(omega * kx / (c2 * kc2), Cos, Sin, Sin),
+                    None,
+                ],
+            ),
+        }
+    }
+
+    /// Fields of the circular modes in `(r, theta, z)` components, with the `cos(n theta)`
+    /// polarization.
+    ///
+    /// `J_n'` and `n J_n(u) / u` are written through `J_(n-1)` and `J_(n+1)`, so nothing is
+    /// singular on the axis.
+    fn circular(&self, spec: &GuidedModeState) -> (Components, Components) {
+        let order = spec.n as i32;
+        let kc = self.cutoff;
+        let (omega, beta, c2) = (self.omega, self.beta, self.c * self.c);
+        let argument = format!("{kc}*x");
+        let bessel = format!("{BESSEL_J}({order}, {argument})");
+        let derivative = format!(
+            "0.5*({BESSEL_J}({}, {argument}) - {BESSEL_J}({}, {argument}))",
+            order - 1,
+            order + 1
+        );
+        let quotient = format!(
+            "0.5*({BESSEL_J}({}, {argument}) + {BESSEL_J}({}, {argument}))",
+            order - 1,
+            order + 1
+        );
+        let angular = |trig: Trig| trig_factor(trig, order as f64, "y");
+        let term = |coefficient: f64, radial: &str, theta: Trig, phase: Trig| {
+            travelling(
+                coefficient,
+                [Some(radial.to_string()), angular(theta)],
+                phase,
+            )
+        };
+        use Trig::{Cos, Sin};
+
+        match spec.family {
+            ModeFamily::Te => (
+                [
+                    term(-omega / kc, &quotient, Sin, Sin),
+                    term(-omega / kc, &derivative, Cos, Sin),
+                    None,
+                ],
+                [
+                    term(beta / kc, &derivative, Cos, Sin),
+                    term(-beta / kc, &quotient, Sin, Sin),
+                    term(1.0, &bessel, Cos, Cos),
+                ],
+            ),
+            ModeFamily::Tm => (
+                [
+                    term(beta / kc, &derivative, Cos, Sin),
+                    term(-beta / kc, &quotient, Sin, Sin),
+                    term(1.0, &bessel, Cos, Cos),
+                ],
+                [
+                    term(omega / (c2 * kc), &quotient, Sin, Sin),
+                    term(omega / (c2 * kc), &derivative, Cos, Sin),
+                    None,
+                ],
+            ),
+        }
+    }
+}
+
+/// Returns `trig(k * variable)`; `None` marks a vanishing `sin(0)` and an empty string stands for
+/// `cos(0) = 1`.
+fn trig_factor(trig: Trig, wavenumber: f64, variable: &str) -> Option<String> {
+    match (trig, wavenumber == 0.0) {
+        (Trig::Sin, true) => None,
+        (Trig::Cos, true) => Some(String::new()),
+        (_, false) => Some(format!("{}({wavenumber}*{variable})", trig.name())),
+    }
+}
+
+fn travelling<const N: usize>(
+    coefficient: f64,
+    factors: [Option<String>; N],
+    phase: Trig,
+) -> Option<Travelling> {
+    let mut profile = Vec::with_capacity(N);
+    for factor in factors {
+        let factor = factor?;
+        if !factor.is_empty() {
+            profile.push(factor);
+        }
+    }
+    (coefficient != 0.0).then_some(Travelling {
+        coefficient,
+        profile,
+        phase,
+    })
+}
+
+/// A rendered component, `coefficient * profile * time(omega t [- beta z])`.
+struct Term {
+    coefficient: f64,
+    profile: Vec<String>,
+    time: Trig,
+}
+
+/// Renders travelling components either as a `+z` wave or as the cavity standing wave.
+struct Wave {
+    omega: f64,
+    beta: f64,
+    cavity: bool,
+}
+
+impl Wave {
+    fn field(&self, component: &Option<Travelling>, axis: usize, parity: Parity) -> String {
+        self.render(self.term(component, axis, parity))
+    }
+
+    /// Renders the temporal-gauge potential `A = -int E dt` for one electric component.
+    ///
+    /// `-int sin(wt + phi) dt = cos(wt + phi) / w` and `-int cos(wt + phi) dt = -sin(wt + phi) / w`,
+    /// so the same rule covers travelling and standing phases.
+    fn potential(&self, component: &Option<Travelling>, axis: usize) -> String {
+        self.render(self.term(component, axis, Parity::Polar).map(|term| {
+            let (sign, time) = match term.time {
+                Trig::Sin => (1.0, Trig::Cos),
+                Trig::Cos => (-1.0, Trig::Sin),
+            };
+            Term {
+                coefficient: sign * term.coefficient / self.omega,
+                profile: term.profile,
+                time,
+            }
+        }))
+    }
+
+    /// Builds the rendered term of one component.
+    ///
+    /// Cavity modes superpose the wave with its `z -> -z` mirror image, `(F(z) - s F(-z)) / 2`,
+    /// where `s` is the mirror sign of the component; this zeroes tangential `E` on `z = 0`, and
+    /// `beta = p pi / L` zeroes it again on `z = L`.
+    fn term(&self, component: &Option<Travelling>, axis: usize, parity: Parity) -> Option<Term> {
+        let component = component.as_ref()?;
+        if !self.cavity {
+            return Some(Term {
+                coefficient: component.coefficient,
+                profile: component.profile.clone(),
+                time: component.phase,
+            });
+        }
+
+        let transverse = axis < 2;
+        let keeps_sign = matches!(
+            (parity, transverse),
+            (Parity::Polar, true) | (Parity::Axial, false)
+        );
+        // sin(wt - bz) - sin(wt + bz) = -2 cos(wt) sin(bz), cos(..) - cos(..) = 2 sin(wt) sin(bz),
+        // and the sums give 2 sin(wt) cos(bz) and 2 cos(wt) cos(bz).
+        let (sign, axial, time) = match (keeps_sign, component.phase) {
+            (true, Trig::Sin) => (-1.0, Trig::Sin, Trig::Cos),
+            (true, Trig::Cos) => (1.0, Trig::Sin, Trig::Sin),
+            (false, Trig::Sin) => (1.0, Trig::Cos, Trig::Sin),
+            (false, Trig::Cos) => (1.0, Trig::Cos, Trig::Cos),
+        };
+        let axial = trig_factor(axial, self.beta, "z")?;
+        let mut profile = component.profile.clone();
+        if !axial.is_empty() {
+            profile.push(axial);
+        }
+        Some(Term {
+            coefficient: sign * component.coefficient,
+            profile,
+            time,
+        })
+    }
+
+    fn render(&self, term: Option<Term>) -> String {
+        let Some(term) = term else {
+            return "0".to_string();
+        };
+        let phase = if self.cavity {
+            format!("{}({}*t)", term.time.name(), self.omega)
+        } else {
+            format!("{}({}*t - {}*z)", term.time.name(), self.omega, self.beta)
+        };
+        let product = term.profile.into_iter().chain([phase]).collect::<Vec<_>>();
+        let product = product.join(" * ");
+        if term.coefficient == 1.0 {
+            product
+        } else if term.coefficient == -1.0 {
+            format!("-{product}")
+        } else {
+            format!("{} * {product}", term.coefficient)
+        }
+    }
+}
+
+fn set_spacial_eqs(eqs: &mut SpacialEqs, values: &[String; 3]) {
+    eqs.x.eq_str = values[0].clone();
+    eqs.y.eq_str = values[1].clone();
+    eqs.z.eq_str = values[2].clone();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{cutoff_angular_frequency, guided_mode};
+    use crate::app::ui::state::{
+        EmMode, GridUiState, GuideGeometry, GuidedModeState, ModeFamily, SpacialEqs,
+    };
+    use crate::app::ui::validation::validate_ui_state;
+    use crate::maths::{expr_to_fastexpr4d, FastExpr4d};
+
+    const LIGHT_SPEED: f64 = 2.0;
+
+    fn spec(geometry: GuideGeometry, family: ModeFamily, cavity: bool) -> GuidedModeState {
+        GuidedModeState {
+            geometry,
+            family,
+            cavity,
+            m: 1,
+            n: 1,
+            p: 1,
+            ..GuidedModeState::default()
+        }
+    }
+
+    fn all_specs() -> Vec<GuidedModeState> {
+        let mut specs = Vec::new();
+        for geometry in [GuideGeometry::Rectangular, GuideGeometry::Circular] {
+            for family in [ModeFamily::Te, ModeFamily::Tm] {
+                for cavity in [false, true] {
+                    specs.push(spec(geometry, family, cavity));
+                }
+            }
+        }
+        specs
+    }
+
+    fn loaded_state(spec: &GuidedModeState) -> GridUiState {
+        let cutoff = cutoff_angular_frequency(spec, LIGHT_SPEED).unwrap();
+        let mode = guided_mode(spec, LIGHT_SPEED, 1.5 * cutoff).unwrap();
+        let mut state = GridUiState::default();
+        state.em.enabled = true;
+        state.em.light_speed = LIGHT_SPEED;
+        mode.apply(&mut state);
+        state
+    }
+
+    fn compiled(eqs: &SpacialEqs) -> [FastExpr4d; 3] {
+        [&eqs.x, &eqs.y, &eqs.z].map(|eq| expr_to_fastexpr4d(eq.eq.clone()))
+    }
+
+    #[test]
+    fn guided_modes_are_valid_after_apply() {
+        for spec in all_specs() {
+            let state = loaded_state(&spec);
+
+            let result = validate_ui_state(&state);
+
+            assert!(
+                result.is_ok(),
+                "{spec:?} should validate: {:?}",
+                result.err()
+            );
+            assert_eq!(state.em.mode, EmMode::ClosedForm);
+            assert!(state.em.pec_walls[1]);
+            assert_eq!(state.em.pec_walls[4], spec.cavity);
+        }
+    }
+
+    #[test]
+    fn rectangular_modes_have_no_tangential_electric_field_on_the_walls() {
+        for spec in all_specs()
+            .into_iter()
+            .filter(|spec| spec.geometry == GuideGeometry::Rectangular)
+        {
+            let validated = validate_ui_state(&loaded_state(&spec)).unwrap();
+            let electric = compiled(&validated.em.electric_field);
+            let (a, b, length) = (spec.width, spec.height, spec.length);
+            let mut faces = vec![(0, 0.0), (0, a), (1, 0.0), (1, b)];
+            if spec.cavity {
+                faces.extend([(2, 0.0), (2, length)]);
+            }
+
+            for (axis, value) in faces {
+                for (s, t, time) in [(0.3, 0.7, 0.2), (0.55, 0.1, 1.3), (0.9, 0.45, 2.1)] {
+                    let mut point = [s * a, t * b, s * length];
+                    point[axis] = value;
+                    for tangent in (0..3).filter(|&component| component != axis) {
+                        let field = electric[tangent](point[0], point[1], point[2], time);
+                        assert!(
+                            field.abs() < 1.0e-9,
+                            "{spec:?}: E[{tangent}] = {field} on face {axis} = {value}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn circular_te_mode_has_no_azimuthal_electric_field_on_the_wall() {
+        let spec = spec(GuideGeometry::Circular, ModeFamily::Te, false);
+        let validated = validate_ui_state(&loaded_state(&spec)).unwrap();
+        let electric = compiled(&validated.em.electric_field);
+
+        for (theta, z, time) in [(0.2, 0.5, 0.1), (1.4, 2.5, 0.8), (4.0, 5.0, 1.7)] {
+            let field = electric[1](spec.radius, theta, z, time);
+            assert!(field.abs() < 1.0e-9, "E_theta = {field}");
+        }
+    }
+
+    #[test]
+    fn guided_mode_rejects_frequencies_below_cutoff_and_empty_modes() {
+        let waveguide = GuidedModeState::default();
+        let cutoff = cutoff_angular_frequency(&waveguide, LIGHT_SPEED).unwrap();
+        assert!(guided_mode(&waveguide, LIGHT_SPEED, 0.9 * cutoff).is_err());
+
+        let mut empty = waveguide;
+        empty.m = 0;
+        assert!(cutoff_angular_frequency(&empty, LIGHT_SPEED).is_err());
+
+        let mut flat_cavity = spec(GuideGeometry::Rectangular, ModeFamily::Te, true);
+        flat_cavity.p = 0;
+        assert!(guided_mode(&flat_cavity, LIGHT_SPEED, 1.0).is_err());
+    }
+}
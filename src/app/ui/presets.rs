@@ -1,6 +1,6 @@
 //! Built-in UI presets for common coordinate systems and field configurations.
 
-use crate::app::ui::state::{EmGauge, EmMode, FieldKind, GridUiState, SpacialEqs};
+use crate::app::ui::state::{EmGauge, EmMode, FieldKind, GridUiState, SpacialEqs, VectorInput};
 
 #[derive(Debug, Clone, Copy)]
 pub(crate) struct GridPreset {
@@ -8,38 +8,45 @@ pub(crate) struct GridPreset {
     render_3d: bool,
     equations: [&'static str; 3],
     counts: [f64; 3],
-    bounds: [(f64, f64); 3],
+    bounds: [(&'static str, &'static str); 3],
 }
 
 impl GridPreset {
-    pub(crate) const ALL: [Self; 4] = [
+    pub(crate) const ALL: [Self; 5] = [
         Self {
             label: "Cartesian",
             render_3d: true,
             equations: ["x", "y", "z"],
             counts: [7.0, 7.0, 7.0],
-            bounds: [(-4.0, 4.0), (-4.0, 4.0), (-4.0, 4.0)],
+            bounds: [("-4", "4"), ("-4", "4"), ("-4", "4")],
         },
         Self {
             label: "Spherical",
             render_3d: true,
             equations: ["x*cos(y) * sin(z)", "x*sin(y) * sin(z)", "x * cos(z)"],
             counts: [5.0, 12.0, 8.0],
-            bounds: [(0.0, 6.0), (0.0, 6.28), (0.0, 3.14)],
+            bounds: [("0", "6"), ("0", "2*pi"), ("0", "pi")],
         },
         Self {
             label: "Cylindrical",
             render_3d: true,
             equations: ["x*cos(y)", "x*sin(y)", "z"],
             counts: [5.0, 12.0, 7.0],
-            bounds: [(0.0, 6.0), (0.0, 6.28), (-4.0, 4.0)],
+            bounds: [("0", "6"), ("0", "2*pi"), ("-4", "4")],
         },
         Self {
             label: "Polar",
             render_3d: false,
             equations: ["x*cos(y)", "x*sin(y)", "0"],
             counts: [6.0, 16.0, 2.0],
-            bounds: [(0.0, 6.0), (0.0, 6.28), (0.0, 1.0)],
+            bounds: [("0", "6"), ("0", "2*pi"), ("0", "1")],
+        },
+        Self {
+            label: "Rotating frame",
+            render_3d: true,
+            equations: ["x*cos(t) - y*sin(t)", "x*sin(t) + y*cos(t)", "z"],
+            counts: [7.0, 7.0, 3.0],
+            bounds: [("-4", "4"), ("-4", "4"), ("-1", "1")],
         },
     ];
 
@@ -51,9 +58,8 @@ impl GridPreset {
         state.nb_x = self.counts[0];
         state.nb_y = self.counts[1];
         state.nb_z = self.counts[2];
-        state.bounds_x = self.bounds[0];
-        state.bounds_y = self.bounds[1];
-        state.bounds_z = self.bounds[2];
+        state.line_spacing = Default::default();
+        state.set_bound_texts(self.bounds);
     }
 }
 
@@ -63,17 +69,19 @@ pub(crate) struct FieldPreset {
     kind: FieldKind,
     scalar: &'static str,
     vector: [&'static str; 3],
+    vector_input: VectorInput,
     render_d: bool,
     normalize: bool,
 }
 
 impl FieldPreset {
-    pub(crate) const ALL: [Self; 4] = [
+    pub(crate) const ALL: [Self; 8] = [
         Self {
             label: "Constant scalar",
             kind: FieldKind::Scalar,
             scalar: "1",
             vector: ["1", "0", "0"],
+            vector_input: VectorInput::Orthonormal,
             render_d: false,
             normalize: false,
         },
@@ -82,6 +90,7 @@ impl FieldPreset {
             kind: FieldKind::Scalar,
             scalar: "x*y + z",
             vector: ["1", "0", "0"],
+            vector_input: VectorInput::Orthonormal,
             render_d: false,
             normalize: false,
         },
@@ -90,6 +99,7 @@ impl FieldPreset {
             kind: FieldKind::Vector,
             scalar: "x",
             vector: ["1", "0", "0"],
+            vector_input: VectorInput::Orthonormal,
             render_d: false,
             normalize: false,
         },
@@ -98,6 +108,45 @@ impl FieldPreset {
             kind: FieldKind::Vector,
             scalar: "x",
             vector: ["-y", "x", "0.5*z"],
+            vector_input: VectorInput::Orthonormal,
+            render_d: false,
+            normalize: false,
+        },
+        // The centrifugal acceleration of a frame turning at unit rate, in its own coordinates.
+        Self {
+            label: "Centrifugal",
+            kind: FieldKind::Vector,
+            scalar: "x",
+            vector: ["x", "y", "0"],
+            vector_input: VectorInput::Orthonormal,
+            render_d: false,
+            normalize: false,
+        },
+        Self {
+            label: "Cartesian rotation",
+            kind: FieldKind::Vector,
+            scalar: "x",
+            vector: ["-Y", "X", "0"],
+            vector_input: VectorInput::WorldCartesian,
+            render_d: false,
+            normalize: false,
+        },
+        Self {
+            label: "Travelling wave",
+            kind: FieldKind::Scalar,
+            scalar: "sin(x - t)",
+            vector: ["1", "0", "0"],
+            vector_input: VectorInput::Orthonormal,
+            render_d: false,
+            normalize: false,
+        },
+        // A rigid rotation plus a uniform drift that turns with the clock.
+        Self {
+            label: "Unsteady vortex",
+            kind: FieldKind::Vector,
+            scalar: "x",
+            vector: ["-Y + 0.5*cos(t)", "X + 0.5*sin(t)", "0"],
+            vector_input: VectorInput::WorldCartesian,
             render_d: false,
             normalize: false,
         },
@@ -109,11 +158,15 @@ impl FieldPreset {
         state.field.x.eq_str = self.vector[0].to_string();
         state.field.y.eq_str = self.vector[1].to_string();
         state.field.z.eq_str = self.vector[2].to_string();
+        state.vector_input = self.vector_input;
         state.render_d = self.render_d;
         state.normalize_field = self.normalize;
     }
 }
 
+/// Relative permittivity, permeability and conductivity of empty space.
+const VACUUM_MEDIA: [&str; 3] = ["1", "1", "0"];
+
 #[derive(Debug, Clone, Copy)]
 pub(crate) struct EmPreset {
     pub(crate) label: &'static str,
@@ -121,6 +174,7 @@ pub(crate) struct EmPreset {
     vector_potential: [&'static str; 3],
     electric_field: [&'static str; 3],
     magnetic_field: [&'static str; 3],
+    media: [&'static str; 3],
 }
 
 impl EmPreset {
@@ -131,6 +185,7 @@ impl EmPreset {
             vector_potential: ["0", "sin(z - t)", "0"],
             electric_field: ["0", "cos(z - t)", "0"],
             magnetic_field: ["-cos(z - t)", "0", "0"],
+            media: VACUUM_MEDIA,
         },
         Self {
             label: "Standing wave",
@@ -138,6 +193,7 @@ impl EmPreset {
             vector_potential: ["0", "sin(z - t) + sin(z + t)", "0"],
             electric_field: ["0", "cos(z - t) - cos(z + t)", "0"],
             magnetic_field: ["-cos(z - t) - cos(z + t)", "0", "0"],
+            media: VACUUM_MEDIA,
         },
         Self {
             label: "Damped wave",
@@ -145,6 +201,7 @@ impl EmPreset {
             vector_potential: ["0", "exp(-0.25*z) * sin(z - t)", "0"],
             electric_field: ["0", "exp(-0.25*z) * cos(z - t)", "0"],
             magnetic_field: ["exp(-0.25*z) * (0.25*sin(z - t) - cos(z - t))", "0", "0"],
+            media: VACUUM_MEDIA,
         },
     ];
 
@@ -155,6 +212,9 @@ impl EmPreset {
         set_spacial_eqs(&mut state.em.vector_potential, self.vector_potential);
         set_spacial_eqs(&mut state.em.electric_field, self.electric_field);
         set_spacial_eqs(&mut state.em.magnetic_field, self.magnetic_field);
+        state.em.permittivity.eq_str = self.media[0].to_string();
+        state.em.permeability.eq_str = self.media[1].to_string();
+        state.em.conductivity.eq_str = self.media[2].to_string();
         state.em.layers.scalar_potential = false;
         state.em.layers.vector_potential = false;
         state.em.layers.electric = true;
@@ -5,6 +5,31 @@ use crate::maths::Expr;
 use mathhook_core::Parser;
 use std::f64::consts::PI;
 
+mod atlas;
+mod grid_bounds;
+mod inspector;
+mod line_coloring;
+mod line_spacing;
+mod morph;
+mod probes;
+mod singularities;
+mod timeline;
+mod tissot;
+
+pub use atlas::{AtlasUiState, ChartOverlap, ChartPreset, ChartUiState, PRIMARY_CHART_NAME};
+pub use grid_bounds::GridBound;
+pub use inspector::{format_vector, InspectedEm, InspectedField, InspectorReport};
+pub use line_coloring::{Colormap, LineColoringUiState, LineQuantity};
+pub use line_spacing::{AxisSpacing, SpacingMode};
+pub use morph::{MorphHomotopy, MorphUiState};
+pub use probes::{
+    ExportStatus, ProbeChannel, ProbeMarker, ProbeTrace, ProbeUiState, ProbeView,
+    PROBE_CHANNEL_COUNT,
+};
+pub use singularities::{SingularKind, SingularSet};
+pub use timeline::{EmTimelineState, TimeScaleKeyframe};
+pub use tissot::{TissotReport, TissotUiState};
+
 #[derive(Debug, Clone)]
 pub struct EqRender {
     pub eq: Expr,
@@ -55,11 +80,49 @@ pub enum FieldKind {
     Vector,
 }
 
+/// Basis and variables the Field-tab vector components are entered in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VectorInput {
+    /// Components on the orthonormal frame drawn by the arrows, over `x, y, z`.
+    Orthonormal,
+    /// World Cartesian components over `X, Y, Z`, pulled back through the embedding.
+    WorldCartesian,
+}
+
+impl VectorInput {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Orthonormal => "Active coordinates",
+            Self::WorldCartesian => "World Cartesian",
+        }
+    }
+
+    /// Variables the components may read.
+    pub fn variables(self) -> [&'static str; 3] {
+        match self {
+            Self::Orthonormal => ["x", "y", "z"],
+            Self::WorldCartesian => ["X", "Y", "Z"],
+        }
+    }
+}
+
+/// Curvilinear form of a world-Cartesian field, published after it is pulled back.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PulledBackField {
+    /// Natural components `F_i = Σ_a F_a ∂X^a/∂x^i` of the 1-form.
+    pub natural: [String; 3],
+    /// Components on the orthonormal frame, as the active-coordinates input would take them.
+    pub orthonormal: [String; 3],
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum EmMode {
     Potentials,
     Electric,
     Magnetic,
+    Phasor,
+    /// `V`, `A`, `E` and `B` are all given in closed form and used as entered.
+    ClosedForm,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -68,17 +131,86 @@ pub enum EmGauge {
     // Lorenz,
 }
 
+/// Numerical method used to reconstruct the complementary field in E and B source modes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InverseCurlBackend {
+    /// Direct Biot-Savart sum over a coarse set of source cells.
+    DirectSum,
+    /// Barnes-Hut approximation of the Biot-Savart sum over a fine set of source cells.
+    TreeCode,
+    /// Poisson solve on a fine world-space lattice by geometric multigrid.
+    Multigrid,
+}
+
+/// Cross-section of the guide used by the mode presets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GuideGeometry {
+    Rectangular,
+    Circular,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModeFamily {
+    Te,
+    Tm,
+}
+
+/// Editable parameters of the waveguide and cavity mode presets.
+///
+/// For rectangular guides `m` and `n` count half-wavelengths along `x` and `y`. For circular
+/// guides `n` is the azimuthal order and `m` the radial root index. `p` counts half-wavelengths
+/// along the cavity length and is ignored for open waveguides.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GuidedModeState {
+    pub geometry: GuideGeometry,
+    pub family: ModeFamily,
+    pub cavity: bool,
+    pub m: u32,
+    pub n: u32,
+    pub p: u32,
+    pub width: f64,
+    pub height: f64,
+    pub radius: f64,
+    pub length: f64,
+}
+
+impl Default for GuidedModeState {
+    fn default() -> Self {
+        Self {
+            geometry: GuideGeometry::Rectangular,
+            family: ModeFamily::Te,
+            cavity: false,
+            m: 1,
+            n: 0,
+            p: 1,
+            width: 2.0,
+            height: 1.0,
+            radius: 1.0,
+            length: 6.0,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct EmLayerVisibility {
     pub electric: bool,
     pub magnetic: bool,
     pub scalar_potential: bool,
     pub vector_potential: bool,
+    pub amplitude: bool,
+    pub phase: bool,
+    pub polarization: bool,
 }
 
 impl EmLayerVisibility {
     pub fn any_visible(&self) -> bool {
-        self.electric || self.magnetic || self.scalar_potential || self.vector_potential
+        self.electric
+            || self.magnetic
+            || self.scalar_potential
+            || self.vector_potential
+            || self.amplitude
+            || self.phase
+            || self.polarization
     }
 }
 
@@ -89,6 +221,9 @@ impl Default for EmLayerVisibility {
             magnetic: true,
             scalar_potential: false,
             vector_potential: false,
+            amplitude: false,
+            phase: false,
+            polarization: false,
         }
     }
 }
@@ -101,16 +236,49 @@ pub struct EmUiState {
     pub magnetic_vector_scale: f64,
     pub normalize_vectors: bool,
     pub gauge: EmGauge,
+    pub gauge_function: EqRender,
     pub phi: EqRender,
     pub vector_potential: SpacialEqs,
+    /// Charge density the covariant check expects the potentials to be sourced by.
+    pub charge_density: EqRender,
+    /// Current the covariant check expects, in orthonormal components.
+    pub current_density: SpacialEqs,
     pub electric_field: SpacialEqs,
     pub magnetic_field: SpacialEqs,
+    pub permittivity: EqRender,
+    pub permeability: EqRender,
+    pub conductivity: EqRender,
+    pub angular_frequency: f64,
+    pub phasor_electric_field: SpacialEqs,
+    pub inverse_curl_backend: InverseCurlBackend,
+    pub solver_resolution: usize,
+    pub tree_opening_angle: f64,
+    /// Observer velocity as a fraction of `c`, in world axes; zero views from the lab frame.
+    pub observer_beta: [f64; 3],
+    pub guided_mode: GuidedModeState,
+    /// Perfectly conducting faces of the grid box, ordered `x_min, x_max, y_min, y_max, z_min,
+    /// z_max` in abstract coordinates. They are drawn as translucent walls.
+    pub pec_walls: [bool; 6],
+    /// Perfect conductors `f(x, y, z) <= 0` in world coordinates, applied by the inverse curl.
+    pub conductors: Vec<EqRender>,
+    /// Region `f(x, y, z) <= 0` excluded from the inverse-curl source domain.
+    pub domain_mask: EqRender,
+    /// Playback of the timeline, whose time is also the scene time `t` of moving embeddings and
+    /// Field-tab fields, so it runs with EM enabled or not.
     pub running: bool,
     pub time_scale: f64,
     pub reset_counter: u64,
+    pub timeline: EmTimelineState,
     pub layers: EmLayerVisibility,
 }
 
+impl EmUiState {
+    /// Appends a conductor region, starting from the half-space `f <= 0` given by `expr`.
+    pub fn add_conductor(&mut self, expr: &str) {
+        self.conductors.push(default_eq(expr));
+    }
+}
+
 impl Default for EmUiState {
     fn default() -> Self {
         Self {
@@ -120,13 +288,30 @@ impl Default for EmUiState {
             magnetic_vector_scale: 1.0,
             normalize_vectors: false,
             gauge: EmGauge::Coulomb,
+            gauge_function: default_eq("0"),
             phi: default_eq("0"),
             vector_potential: SpacialEqs::from_defaults("0", "sin(z - t)", "0"),
+            charge_density: default_eq("0"),
+            current_density: SpacialEqs::from_defaults("0", "0", "0"),
             electric_field: SpacialEqs::from_defaults("0", "cos(z - t)", "0"),
             magnetic_field: SpacialEqs::from_defaults("-cos(z - t)", "0", "0"),
+            permittivity: default_eq("1"),
+            permeability: default_eq("1"),
+            conductivity: default_eq("0"),
+            angular_frequency: 1.0,
+            phasor_electric_field: SpacialEqs::from_defaults("exp(-i*z)", "-i*exp(-i*z)", "0"),
+            inverse_curl_backend: InverseCurlBackend::DirectSum,
+            solver_resolution: 32,
+            tree_opening_angle: 0.5,
+            observer_beta: [0.0; 3],
+            guided_mode: GuidedModeState::default(),
+            pec_walls: [false; 6],
+            conductors: Vec::new(),
+            domain_mask: default_eq("1"),
             running: true,
             time_scale: 1.0,
             reset_counter: 0,
+            timeline: EmTimelineState::default(),
             layers: EmLayerVisibility::default(),
         }
     }
@@ -136,29 +321,80 @@ impl Default for EmUiState {
 pub struct GridUiState {
     pub render_3d: bool,
     pub coords_sys: SpacialEqs,
+    /// Morph from `coords_sys` toward a second embedding.
+    pub morph: MorphUiState,
     pub field_kind: FieldKind,
     pub scalar_field: EqRender,
     pub field: SpacialEqs,
+    /// How the components of `field` are read.
+    pub vector_input: VectorInput,
     pub render_d: bool,
     pub normalize_field: bool,
     pub em: EmUiState,
+    pub probes: ProbeUiState,
     pub tangent_scale: f64,
     pub geometric_arrow_scale: f64,
     pub nb_x: f64,
     pub nb_y: f64,
     pub nb_z: f64,
-    pub bounds_x: (f64, f64),
-    pub bounds_y: (f64, f64),
-    pub bounds_z: (f64, f64),
+    pub bounds_x: (GridBound, GridBound),
+    pub bounds_y: (GridBound, GridBound),
+    pub bounds_z: (GridBound, GridBound),
+    /// Line placement per axis; uniform, logarithmic and Chebyshev modes place `nb_*` lines.
+    pub line_spacing: [AxisSpacing; 3],
+    /// Longest straight grid segment per axis; longer gaps between lines are subdivided.
+    pub segment_lengths: [f64; 3],
+    /// Quantity and colormap of the primary grid lines.
+    pub line_coloring: LineColoringUiState,
+    /// Axes whose constant-coordinate surfaces are drawn, one through each of their grid lines.
+    pub surface_axes: [bool; 3],
+    /// Opacity of the coordinate surfaces; live, like `tangent_scale`.
+    pub surface_opacity: f64,
+    /// Whether singular sets are drawn in red; live, like `surface_opacity`.
+    pub show_singularities: bool,
+    /// Strain ellipsoids at the field samples.
+    pub tissot: TissotUiState,
+    /// Whether the point inspector window is shown and evaluated each frame.
+    pub inspector_open: bool,
+    /// Extra charts overlaid on the primary grid.
+    pub atlas: AtlasUiState,
     pub apply_counter: u64,
     pub legend: Option<LegendState>,
+    /// Range of the quantity the grid lines are colored by; `None` for axis colors.
+    pub line_legend: Option<LegendState>,
+    pub gauge_report: Option<GaugeReport>,
+    pub covariant_report: Option<CovariantReport>,
+    /// Spread of observer times `t'` over the samples shown at the current lab time.
+    pub observer_time_range: Option<(f64, f64)>,
+    pub probe_traces: Vec<ProbeTrace>,
+    /// Inspected sample, pinned or hovered; `None` while the inspector is closed.
+    pub inspector: Option<InspectorReport>,
+    /// Singular sets of the applied coordinates within the applied bounds.
+    pub singularities: Vec<SingularSet>,
+    /// Pulled-back vector field while its components are entered in world Cartesian form.
+    pub pulled_back_field: Option<PulledBackField>,
+    /// Distortion summary of the Tissot indicatrices; `None` without field samples.
+    pub tissot_report: Option<TissotReport>,
+    /// Overlap of every applied chart pair, the primary chart included.
+    pub chart_overlaps: Vec<ChartOverlap>,
+    /// Timeline time of the last rendered frame, shown on the scrubber.
+    pub em_time: f64,
+    /// Scene time `t` that moving embeddings and fields are built at; the timeline time of the
+    /// last rendered frame, kept apart so an Apply snapshots it.
+    pub scene_time: f64,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum LegendKind {
     ScalarField,
     ScalarPotential,
+    PhasorAmplitude,
+    PhasorPhase,
     DualTangent,
+    LineScaleFactor,
+    LineJacobian,
+    LineCurvature,
+    LineScalarField,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -185,12 +421,48 @@ impl LegendKind {
                 subtitle: "Sampled EM scalar potential over the current grid",
                 footer: "A uniform range means the active gauge has constant V.",
             },
+            Self::PhasorAmplitude => LegendDescriptor {
+                window_title: "Phasor Amplitude Legend",
+                title: "Phasor Amplitude |E| Legend",
+                subtitle: "Magnitude of the complex electric amplitude",
+                footer: "Time independent: the animated field is Re(E exp(i omega t)).",
+            },
+            Self::PhasorPhase => LegendDescriptor {
+                window_title: "Phasor Phase Legend",
+                title: "Phasor Phase arg(E) Legend",
+                subtitle: "Phase of the dominant electric component, in radians",
+                footer: "Values wrap at -pi and pi.",
+            },
             Self::DualTangent => LegendDescriptor {
                 window_title: "Dual Tangent Legend",
                 title: "Dual Tangent Legend",
                 subtitle: "alpha(v) over the sampled dual-space lattice",
                 footer: "Visible only in dual tangent mode: Ctrl+T.",
             },
+            Self::LineScaleFactor => LegendDescriptor {
+                window_title: "Scale Factor Legend",
+                title: "Scale factor h_i",
+                subtitle: "Length of the coordinate tangent along each line",
+                footer: "Large ratios between lines mean strong metric distortion.",
+            },
+            Self::LineJacobian => LegendDescriptor {
+                window_title: "Jacobian Legend",
+                title: "det J",
+                subtitle: "Volume of the coordinate cell per unit abstract volume",
+                footer: "Zero marks a singularity; negative values are left-handed.",
+            },
+            Self::LineCurvature => LegendDescriptor {
+                window_title: "Line Curvature Legend",
+                title: "Line curvature",
+                subtitle: "Inverse radius of each grid line in world space",
+                footer: "Straight lines have zero curvature.",
+            },
+            Self::LineScalarField => LegendDescriptor {
+                window_title: "Line Scalar Field Legend",
+                title: "Scalar field",
+                subtitle: "Field-tab scalar field along the grid lines",
+                footer: "Uses the scalar field even while a vector field is shown.",
+            },
         }
     }
 }
@@ -202,40 +474,80 @@ pub struct LegendState {
     pub max_value: f64,
 }
 
+/// Potentials before and after the EM gauge transformation, with the numerical invariance check.
+///
+/// Published by the render thread whenever a non-zero gauge function is applied in potentials
+/// mode. Deviations compare the rendered `E` and `B` samples of both gauges at each of
+/// `sample_times`, and are `None` for a layer that is not shown.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GaugeReport {
+    pub original_phi: String,
+    pub original_vector_potential: [String; 3],
+    pub transformed_phi: String,
+    pub transformed_vector_potential: [String; 3],
+    pub max_electric_deviation: Option<f64>,
+    pub max_magnetic_deviation: Option<f64>,
+    pub sample_count: usize,
+    pub sample_times: Vec<f64>,
+}
+
+/// Residuals of `dF = 0` and `d*F = mu_0 *J` for the potentials-mode Faraday 2-form.
+///
+/// `*_symbolic_zero` records whether the simplified residual form vanished outright; the maxima
+/// are the largest absolute residual component over the grid samples at `sample_time`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CovariantReport {
+    pub bianchi_symbolic_zero: bool,
+    pub max_bianchi_residual: f64,
+    pub source_symbolic_zero: bool,
+    pub max_source_residual: f64,
+    pub sample_count: usize,
+    pub sample_time: f64,
+}
+
 impl GridUiState {
-    /// Rounds and normalizes the editable UI bounds into a runtime `GridConfig`.
+    /// Converts the current UI state into the grid configuration used by the runtime.
     ///
-    /// The control panel stores editable floating-point values because sliders and text fields
-    /// operate in that domain. The runtime grid builder, however, expects stable snapped values
-    /// so cache keys and segment generation do not drift across frames. The special handling for
-    /// `3.14` and `6.28` preserves the common `PI` and `2*PI` intent from the UI.
-    #[allow(clippy::approx_constant)]
-    pub fn approximate_grid_config(&self) -> GridConfig {
-        let normalize_bound = |value: f64| match value {
-            value if value == 3.14 => PI,
-            value if value == 6.28 => 2.0 * PI,
-            _ => value.round(),
-        };
+    /// Bounds use the values evaluated at the last Apply, so a half-typed bound field never
+    /// reaches the grid builder. This is the configuration snapshot compared by `World` when
+    /// deciding whether geometry and lookup caches must be rebuilt.
+    pub fn to_grid_config(&self) -> GridConfig {
+        self.grid_config_with_bounds(self.bound_values())
+    }
 
+    /// Builds the grid configuration these settings give for explicit `bounds`.
+    pub fn grid_config_with_bounds(&self, bounds: [(f64, f64); 3]) -> GridConfig {
         GridConfig::new(
-            normalize_bound(self.bounds_x.0),
-            normalize_bound(self.bounds_x.1),
+            bounds[0].0,
+            bounds[0].1,
             self.nb_x.round(),
-            normalize_bound(self.bounds_y.0),
-            normalize_bound(self.bounds_y.1),
+            bounds[1].0,
+            bounds[1].1,
             self.nb_y.round(),
-            normalize_bound(self.bounds_z.0),
-            normalize_bound(self.bounds_z.1),
+            bounds[2].0,
+            bounds[2].1,
             self.nb_z.round(),
         )
+        .with_line_spacing(
+            self.line_spacing
+                .each_ref()
+                .map(AxisSpacing::to_line_spacing),
+        )
+        .with_segment_lengths(self.segment_lengths)
     }
 
-    /// Converts the current UI state into the grid configuration used by the runtime.
-    ///
-    /// This is the configuration snapshot compared by `World` when deciding whether geometry and
-    /// lookup caches must be rebuilt.
-    pub fn to_grid_config(&self) -> GridConfig {
-        self.approximate_grid_config()
+    /// Returns the applied `(min, max)` value of every axis.
+    pub fn bound_values(&self) -> [(f64, f64); 3] {
+        [&self.bounds_x, &self.bounds_y, &self.bounds_z].map(|(min, max)| (min.value, max.value))
+    }
+
+    /// Replaces the text of every bound field, e.g. with `("0", "2*pi")`; Apply evaluates them.
+    pub(crate) fn set_bound_texts<S: Into<String>>(&mut self, bounds: [(S, S); 3]) {
+        let fields = [&mut self.bounds_x, &mut self.bounds_y, &mut self.bounds_z];
+        for (field, (min, max)) in fields.into_iter().zip(bounds) {
+            field.0.text = min.into();
+            field.1.text = max.into();
+        }
     }
 
     /// Returns whether the active field render path should draw arrows.
@@ -256,27 +568,50 @@ impl Default for GridUiState {
     /// field without any UI interaction. `apply_counter` starts at zero and is only bumped after
     /// validation succeeds, which lets the render thread cheaply detect committed edits without
     /// reacting to every intermediate keystroke.
-    #[allow(clippy::approx_constant)]
     fn default() -> Self {
         Self {
             render_3d: true,
             coords_sys: SpacialEqs::default_sys(),
+            morph: MorphUiState::default(),
             field_kind: FieldKind::Vector,
             scalar_field: default_eq("x"),
             field: SpacialEqs::default_field(),
+            vector_input: VectorInput::Orthonormal,
             render_d: false,
             normalize_field: false,
             em: EmUiState::default(),
+            probes: ProbeUiState::default(),
             tangent_scale: 0.12,
             geometric_arrow_scale: 0.55,
             nb_x: 5.0,
             nb_y: 5.0,
             nb_z: 5.0,
-            bounds_x: (-0.1, 15.0),
-            bounds_y: (0.0, 6.28),
-            bounds_z: (0.0, 3.14),
+            bounds_x: (GridBound::from_value(0.0), GridBound::from_value(15.0)),
+            bounds_y: (GridBound::from_value(0.0), GridBound::new(2.0 * PI, "2*pi")),
+            bounds_z: (GridBound::from_value(0.0), GridBound::new(PI, "pi")),
+            line_spacing: Default::default(),
+            segment_lengths: [1.0; 3],
+            line_coloring: LineColoringUiState::default(),
+            surface_axes: [false; 3],
+            surface_opacity: 0.4,
+            show_singularities: true,
+            tissot: TissotUiState::default(),
+            inspector_open: false,
+            atlas: AtlasUiState::default(),
             apply_counter: 0,
             legend: None,
+            line_legend: None,
+            gauge_report: None,
+            covariant_report: None,
+            observer_time_range: None,
+            probe_traces: Vec::new(),
+            inspector: None,
+            singularities: Vec::new(),
+            pulled_back_field: None,
+            tissot_report: None,
+            chart_overlaps: Vec::new(),
+            em_time: 0.0,
+            scene_time: 0.0,
         }
     }
 }
@@ -298,15 +633,21 @@ fn default_eq(expr: &str) -> EqRender {
 
 #[cfg(test)]
 mod tests {
-    use super::{ControlTab, EmGauge, EmMode, FieldKind, GridUiState};
+    use super::{
+        AtlasUiState, AxisSpacing, ChartPreset, Colormap, ControlTab, EmGauge, EmMode, FieldKind,
+        GridUiState, GuideGeometry, GuidedModeState, InverseCurlBackend, LineQuantity, ModeFamily,
+        MorphHomotopy, ProbeChannel,
+    };
+    use std::f64::consts::PI;
 
     #[test]
-    #[allow(clippy::approx_constant)]
     fn grid_ui_state_defaults_match_expected_values() {
         let state = GridUiState::default();
 
         assert!(state.render_3d);
         assert_eq!(state.field_kind, FieldKind::Vector);
+        assert_eq!(state.vector_input, VectorInput::Orthonormal);
+        assert_eq!(state.pulled_back_field, None);
         assert!(!state.render_d);
         assert!(!state.normalize_field);
         assert!(!state.em.enabled);
@@ -315,21 +656,103 @@ mod tests {
         assert_eq!(state.em.magnetic_vector_scale, 1.0);
         assert!(!state.em.normalize_vectors);
         assert_eq!(state.em.gauge, EmGauge::Coulomb);
+        assert_eq!(state.em.gauge_function.eq_str, "0");
         assert_eq!(state.em.electric_field.y.eq_str, "cos(z - t)");
         assert_eq!(state.em.magnetic_field.x.eq_str, "-cos(z - t)");
+        assert_eq!(state.em.permittivity.eq_str, "1");
+        assert_eq!(state.em.permeability.eq_str, "1");
+        assert_eq!(state.em.conductivity.eq_str, "0");
         assert!(state.em.layers.electric);
         assert!(state.em.layers.magnetic);
         assert!(!state.em.layers.scalar_potential);
         assert!(!state.em.layers.vector_potential);
+        assert!(!state.em.layers.amplitude);
+        assert!(!state.em.layers.phase);
+        assert!(!state.em.layers.polarization);
+        assert_eq!(state.em.angular_frequency, 1.0);
+        assert_eq!(state.em.inverse_curl_backend, InverseCurlBackend::DirectSum);
+        assert_eq!(state.em.solver_resolution, 32);
+        assert_eq!(state.em.tree_opening_angle, 0.5);
+        assert_eq!(state.em.observer_beta, [0.0; 3]);
+        assert_eq!(state.em.guided_mode, GuidedModeState::default());
+        assert_eq!(state.em.guided_mode.geometry, GuideGeometry::Rectangular);
+        assert_eq!(state.em.guided_mode.family, ModeFamily::Te);
+        assert!(!state.em.guided_mode.cavity);
+        assert_eq!(
+            (
+                state.em.guided_mode.m,
+                state.em.guided_mode.n,
+                state.em.guided_mode.p
+            ),
+            (1, 0, 1)
+        );
+        assert_eq!(state.em.pec_walls, [false; 6]);
+        assert!(state.em.conductors.is_empty());
+        assert_eq!(state.em.domain_mask.eq_str, "1");
+        assert!(state.probes.markers.is_empty());
+        assert_eq!(state.probes.channel, ProbeChannel::ElectricMagnitude);
+        assert!(state.probe_traces.is_empty());
+        assert!(!state.em.timeline.loop_enabled);
+        assert!(state.em.timeline.keyframes.is_empty());
+        assert_eq!(state.em_time, 0.0);
         assert_eq!(state.tangent_scale, 0.12);
         assert_eq!(state.geometric_arrow_scale, 0.55);
         assert_eq!(state.nb_x, 5.0);
         assert_eq!(state.nb_y, 5.0);
         assert_eq!(state.nb_z, 5.0);
-        assert_eq!(state.bounds_x, (-0.1, 15.0));
-        assert_eq!(state.bounds_y, (0.0, 6.28));
-        assert_eq!(state.bounds_z, (0.0, 3.14));
+        assert_eq!(
+            state.bound_values(),
+            [(0.0, 15.0), (0.0, 2.0 * PI), (0.0, PI)]
+        );
+        assert_eq!(state.bounds_y.1.text, "2*pi");
+        assert_eq!(state.line_spacing, <[AxisSpacing; 3]>::default());
+        assert_eq!(state.segment_lengths, [1.0; 3]);
+        assert_eq!(state.line_coloring.quantity, LineQuantity::Axis);
+        assert_eq!(state.line_coloring.colormap, Colormap::Viridis);
+        assert_eq!(state.line_legend, None);
+        assert_eq!(state.surface_axes, [false; 3]);
+        assert_eq!(state.surface_opacity, 0.4);
+        assert!(state.show_singularities);
+        assert!(state.singularities.is_empty());
+        assert!(!state.tissot.show);
+        assert_eq!(state.tissot.radius, 0.15);
+        assert_eq!(state.tissot_report, None);
+        assert!(!state.inspector_open);
+        assert!(state.atlas.charts.is_empty());
+        assert!(state.atlas.show_charts);
+        assert_eq!(state.atlas.push_field_to, None);
+        assert!(state.chart_overlaps.is_empty());
+        assert!(!state.morph.enabled);
+        assert!(!state.morph.animate);
+        assert_eq!(state.morph.s, 0.0);
+        assert_eq!(state.morph.homotopy, MorphHomotopy::Linear);
+        assert_eq!(state.scene_time, 0.0);
+        assert_eq!(state.inspector, None);
         assert_eq!(state.legend, None);
+        assert_eq!(state.gauge_report, None);
+        assert_eq!(state.covariant_report, None);
+        assert_eq!(state.observer_time_range, None);
+    }
+
+    #[test]
+    fn removing_a_chart_keeps_the_push_target_on_its_chart() {
+        let mut atlas = AtlasUiState::default();
+        for preset in [
+            ChartPreset::Cartesian,
+            ChartPreset::Spherical,
+            ChartPreset::Cylindrical,
+        ] {
+            atlas.add_chart(preset);
+        }
+        atlas.push_field_to = Some(2);
+
+        atlas.remove_chart(0);
+
+        assert_eq!(atlas.push_field_to, Some(1));
+        assert_eq!(atlas.charts[1].name, "Cylindrical");
+        assert_ne!(atlas.charts[0].color, atlas.charts[1].color);
+        atlas.remove_chart(1);
+        assert_eq!(atlas.push_field_to, None);
     }
 
     #[test]
@@ -0,0 +1,179 @@
+//! Extra coordinate charts drawn over the primary grid, and the overlaps found between them.
+
+use super::{GridBound, SpacialEqs};
+use crate::app::grid::GridConfig;
+use std::f64::consts::PI;
+
+/// Name the Grid-tab coordinates go by in the atlas section and its overlap reports.
+pub const PRIMARY_CHART_NAME: &str = "Primary";
+
+/// Colors handed to new charts in turn; the primary grid keeps its per-axis colors.
+const CHART_PALETTE: [[f32; 3]; 5] = [
+    [1.0, 0.62, 0.0],
+    [0.0, 0.85, 0.85],
+    [0.85, 0.35, 1.0],
+    [1.0, 1.0, 1.0],
+    [0.55, 1.0, 0.25],
+];
+
+/// Ready-made charts offered by the atlas section.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChartPreset {
+    Cartesian,
+    Cylindrical,
+    Spherical,
+    /// Spherical shells `(u, v, r)` centered on the north pole; never reaches the south pole.
+    StereographicNorth,
+    /// Spherical shells `(u, v, r)` centered on the south pole; never reaches the north pole.
+    StereographicSouth,
+}
+
+impl ChartPreset {
+    pub const ALL: [Self; 5] = [
+        Self::Cartesian,
+        Self::Cylindrical,
+        Self::Spherical,
+        Self::StereographicNorth,
+        Self::StereographicSouth,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Cartesian => "Cartesian",
+            Self::Cylindrical => "Cylindrical",
+            Self::Spherical => "Spherical",
+            Self::StereographicNorth => "Stereographic N",
+            Self::StereographicSouth => "Stereographic S",
+        }
+    }
+
+    /// Builds the chart this preset stands for, colored with `color`.
+    fn chart(self, color: [f32; 3]) -> ChartUiState {
+        let stereographic = |sign: &str| {
+            SpacialEqs::from_defaults(
+                "2*x*z/(1 + x^2 + y^2)",
+                "2*y*z/(1 + x^2 + y^2)",
+                &format!("{sign}z*(1 - x^2 - y^2)/(1 + x^2 + y^2)"),
+            )
+        };
+        let (coords_sys, bounds) = match self {
+            Self::Cartesian => (
+                SpacialEqs::from_defaults("x", "y", "z"),
+                [(-2.0, "-2", 2.0, "2"); 3],
+            ),
+            Self::Cylindrical => (
+                SpacialEqs::from_defaults("x*cos(y)", "x*sin(y)", "z"),
+                [
+                    (0.0, "0", 2.0, "2"),
+                    (0.0, "0", 2.0 * PI, "2*pi"),
+                    (-2.0, "-2", 2.0, "2"),
+                ],
+            ),
+            Self::Spherical => (
+                SpacialEqs::default_sys(),
+                [
+                    (0.0, "0", 2.0, "2"),
+                    (0.0, "0", 2.0 * PI, "2*pi"),
+                    (0.0, "0", PI, "pi"),
+                ],
+            ),
+            Self::StereographicNorth => (
+                stereographic(""),
+                [
+                    (-1.5, "-1.5", 1.5, "1.5"),
+                    (-1.5, "-1.5", 1.5, "1.5"),
+                    (1.0, "1", 2.0, "2"),
+                ],
+            ),
+            Self::StereographicSouth => (
+                stereographic("-"),
+                [
+                    (-1.5, "-1.5", 1.5, "1.5"),
+                    (-1.5, "-1.5", 1.5, "1.5"),
+                    (1.0, "1", 2.0, "2"),
+                ],
+            ),
+        };
+        ChartUiState {
+            name: self.label().to_string(),
+            coords_sys,
+            bounds: bounds.map(|(min, min_text, max, max_text)| {
+                (GridBound::new(min, min_text), GridBound::new(max, max_text))
+            }),
+            counts: [5.0; 3],
+            color,
+        }
+    }
+}
+
+/// One extra chart: its own embedding, bounds and uniformly spaced line counts.
+#[derive(Debug, Clone)]
+pub struct ChartUiState {
+    pub name: String,
+    pub coords_sys: SpacialEqs,
+    pub bounds: [(GridBound, GridBound); 3],
+    pub counts: [f64; 3],
+    /// Line color of the whole chart, as linear RGB.
+    pub color: [f32; 3],
+}
+
+impl ChartUiState {
+    /// Converts the applied bounds and counts into the grid configuration of this chart.
+    pub fn grid_config(&self) -> GridConfig {
+        let [x, y, z] = self
+            .bounds
+            .each_ref()
+            .map(|(min, max)| (min.value, max.value));
+        let [nb_x, nb_y, nb_z] = self.counts.map(f64::round);
+        GridConfig::new(x.0, x.1, nb_x, y.0, y.1, nb_y, z.0, z.1, nb_z)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AtlasUiState {
+    /// Charts drawn on top of the primary grid; edits take effect on Apply.
+    pub charts: Vec<ChartUiState>,
+    /// Whether the extra charts are drawn; live, like `show_singularities`.
+    pub show_charts: bool,
+    /// Chart the Field-tab vector field is pushed onto through the transition map.
+    pub push_field_to: Option<usize>,
+}
+
+impl AtlasUiState {
+    /// Appends the chart of `preset`, taking the next palette color.
+    pub fn add_chart(&mut self, preset: ChartPreset) {
+        let color = CHART_PALETTE[self.charts.len() % CHART_PALETTE.len()];
+        self.charts.push(preset.chart(color));
+    }
+
+    /// Removes one chart, keeping the push target on the same chart when it survives.
+    pub fn remove_chart(&mut self, index: usize) {
+        self.charts.remove(index);
+        self.push_field_to = match self.push_field_to {
+            Some(target) if target == index => None,
+            Some(target) if target > index => Some(target - 1),
+            target => target,
+        };
+    }
+}
+
+impl Default for AtlasUiState {
+    fn default() -> Self {
+        Self {
+            charts: Vec::new(),
+            show_charts: true,
+            push_field_to: None,
+        }
+    }
+}
+
+/// How much of two charts' lattices the transition maps carry into each other.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChartOverlap {
+    pub charts: [String; 2],
+    /// Lattice samples of each chart that land inside the other chart's bounds.
+    pub shared: [usize; 2],
+    pub totals: [usize; 2],
+    /// World-space box around the shared samples; `None` when the charts are disjoint.
+    pub world_box: Option<([f64; 3], [f64; 3])>,
+}
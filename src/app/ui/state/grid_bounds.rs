@@ -0,0 +1,26 @@
+//! Editable grid bounds, typed as constant expressions such as `pi/2`.
+
+/// One grid bound: the text in its field and the value it evaluated to at the last Apply.
+///
+/// Presets and the bound fields only write `text`; validation re-evaluates it on Apply, the same
+/// way equation strings are reparsed into `EqRender::eq`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GridBound {
+    pub value: f64,
+    pub text: String,
+}
+
+impl GridBound {
+    /// Creates a bound whose `text` is already known to evaluate to `value`.
+    pub fn new(value: f64, text: impl Into<String>) -> Self {
+        Self {
+            value,
+            text: text.into(),
+        }
+    }
+
+    /// Creates a bound typed as the plain number `value`.
+    pub fn from_value(value: f64) -> Self {
+        Self::new(value, value.to_string())
+    }
+}
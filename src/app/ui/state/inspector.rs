@@ -0,0 +1,126 @@
+//! Local geometry and field values at the inspected grid sample.
+
+use std::fmt::Write;
+
+/// Field values at the inspected point, in the basis conventions of `VectorField`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InspectedField {
+    Scalar(f64),
+    Vector {
+        /// Contravariant components on the coordinate tangents `∂_i`.
+        natural: [f64; 3],
+        /// Covariant components on the coordinate differentials `dx^i`.
+        dual: [f64; 3],
+        /// Components on the orthonormal frame drawn by the arrows.
+        otn: [f64; 3],
+        /// `∂F_a / ∂x^j` of the orthonormal components, row `a`, column `j`.
+        jacobian: [[f64; 3]; 3],
+    },
+}
+
+/// Lab-frame EM fields at the inspected point and the current EM time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InspectedEm {
+    pub time: f64,
+    pub phi: f64,
+    pub vector_potential: [f64; 3],
+    pub electric: [f64; 3],
+    pub magnetic: [f64; 3],
+}
+
+/// Everything the inspector window shows for one sample; published by the render thread.
+///
+/// Geometry entries are `None` where the embedding is singular, e.g. on a polar axis.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InspectorReport {
+    /// Whether the sample was pinned by a right click rather than merely hovered.
+    pub pinned: bool,
+    /// Why the exact preimage of the picked point was not found, so that the nearest grid
+    /// sample is shown instead; `None` for exact coordinates.
+    pub snapped_because: Option<String>,
+    pub abstract_position: [f64; 3],
+    pub world_position: [f64; 3],
+    pub metric: Option<[[f64; 3]; 3]>,
+    pub metric_determinant: Option<f64>,
+    pub scale_factors: [Option<f64>; 3],
+    /// Lower-triangular `e` with `g = e eᵀ`, the vielbein used by the basis conversions.
+    pub vielbein: Option<[[f64; 3]; 3]>,
+    pub field: Option<InspectedField>,
+    pub em: Option<InspectedEm>,
+}
+
+impl InspectorReport {
+    /// Formats the report as plain text for the clipboard.
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        if let Some(reason) = &self.snapped_because {
+            let _ = writeln!(text, "snapped to the nearest grid sample: {reason}");
+        }
+        let _ = writeln!(
+            text,
+            "abstract (x, y, z) = {}",
+            format_vector(&self.abstract_position)
+        );
+        let _ = writeln!(
+            text,
+            "world (X, Y, Z) = {}",
+            format_vector(&self.world_position)
+        );
+        match &self.metric {
+            Some(metric) => write_matrix(&mut text, "metric g_ij", metric),
+            None => text.push_str("metric g_ij = singular\n"),
+        }
+        let _ = writeln!(text, "det g = {}", format_optional(self.metric_determinant));
+        let _ = writeln!(
+            text,
+            "scale factors h_i = ({}, {}, {})",
+            format_optional(self.scale_factors[0]),
+            format_optional(self.scale_factors[1]),
+            format_optional(self.scale_factors[2])
+        );
+        match &self.vielbein {
+            Some(vielbein) => write_matrix(&mut text, "vielbein e", vielbein),
+            None => text.push_str("vielbein e = singular\n"),
+        }
+        match &self.field {
+            Some(InspectedField::Scalar(value)) => {
+                let _ = writeln!(text, "f = {value:.6}");
+            }
+            Some(InspectedField::Vector {
+                natural,
+                dual,
+                otn,
+                jacobian,
+            }) => {
+                let _ = writeln!(text, "F natural = {}", format_vector(natural));
+                let _ = writeln!(text, "F dual = {}", format_vector(dual));
+                let _ = writeln!(text, "F OTN = {}", format_vector(otn));
+                write_matrix(&mut text, "OTN Jacobian dF_a/dx^j", jacobian);
+            }
+            None => {}
+        }
+        if let Some(em) = &self.em {
+            let _ = writeln!(text, "EM at t = {:.6}", em.time);
+            let _ = writeln!(text, "V = {:.6}", em.phi);
+            let _ = writeln!(text, "A = {}", format_vector(&em.vector_potential));
+            let _ = writeln!(text, "E = {}", format_vector(&em.electric));
+            let _ = writeln!(text, "B = {}", format_vector(&em.magnetic));
+        }
+        text
+    }
+}
+
+pub fn format_vector(vector: &[f64; 3]) -> String {
+    format!("({:.6}, {:.6}, {:.6})", vector[0], vector[1], vector[2])
+}
+
+fn format_optional(value: Option<f64>) -> String {
+    value.map_or_else(|| "singular".to_string(), |value| format!("{value:.6}"))
+}
+
+fn write_matrix(text: &mut String, label: &str, matrix: &[[f64; 3]; 3]) {
+    let _ = writeln!(text, "{label} =");
+    for row in matrix {
+        let _ = writeln!(text, "  {}", format_vector(row));
+    }
+}
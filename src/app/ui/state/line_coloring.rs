@@ -0,0 +1,85 @@
+//! Coloring of the primary grid lines by a geometric quantity.
+
+use super::LegendKind;
+
+/// Value each grid vertex is colored by; `Axis` keeps the fixed red, blue and green lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineQuantity {
+    Axis,
+    /// `h_i = |∂_i Φ|` of the axis the line runs along.
+    ScaleFactor,
+    /// `det J` of the embedding.
+    JacobianDeterminant,
+    /// Curvature `|Φ' × Φ''| / |Φ'|³` of the line in world space.
+    Curvature,
+    /// The Field-tab scalar field.
+    ScalarField,
+}
+
+impl LineQuantity {
+    pub const ALL: [Self; 5] = [
+        Self::Axis,
+        Self::ScaleFactor,
+        Self::JacobianDeterminant,
+        Self::Curvature,
+        Self::ScalarField,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Axis => "Axis",
+            Self::ScaleFactor => "h_i",
+            Self::JacobianDeterminant => "det J",
+            Self::Curvature => "Curvature",
+            Self::ScalarField => "Scalar field",
+        }
+    }
+
+    /// Returns the legend of the quantity, or `None` for the fixed axis colors.
+    pub fn legend_kind(self) -> Option<LegendKind> {
+        match self {
+            Self::Axis => None,
+            Self::ScaleFactor => Some(LegendKind::LineScaleFactor),
+            Self::JacobianDeterminant => Some(LegendKind::LineJacobian),
+            Self::Curvature => Some(LegendKind::LineCurvature),
+            Self::ScalarField => Some(LegendKind::LineScalarField),
+        }
+    }
+}
+
+/// Ramp from the low to the high end of a legend range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Colormap {
+    /// Blue through white to red, the ramp of the scalar-field legend.
+    Diverging,
+    Viridis,
+    Grayscale,
+}
+
+impl Colormap {
+    pub const ALL: [Self; 3] = [Self::Diverging, Self::Viridis, Self::Grayscale];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Diverging => "Diverging",
+            Self::Viridis => "Viridis",
+            Self::Grayscale => "Grayscale",
+        }
+    }
+}
+
+/// Both settings take effect on Apply, since they recompile the grid shader.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LineColoringUiState {
+    pub quantity: LineQuantity,
+    pub colormap: Colormap,
+}
+
+impl Default for LineColoringUiState {
+    fn default() -> Self {
+        Self {
+            quantity: LineQuantity::Axis,
+            colormap: Colormap::Viridis,
+        }
+    }
+}
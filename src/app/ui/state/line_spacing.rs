@@ -0,0 +1,74 @@
+//! Editable line placement for one grid axis.
+
+use crate::app::grid::{CustomLines, LineSpacing};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SpacingMode {
+    #[default]
+    Uniform,
+    Step,
+    Logarithmic,
+    Chebyshev,
+    Custom,
+}
+
+impl SpacingMode {
+    pub const ALL: [Self; 5] = [
+        Self::Uniform,
+        Self::Step,
+        Self::Logarithmic,
+        Self::Chebyshev,
+        Self::Custom,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Uniform => "uniform",
+            Self::Step => "step",
+            Self::Logarithmic => "log",
+            Self::Chebyshev => "Chebyshev",
+            Self::Custom => "custom",
+        }
+    }
+}
+
+/// Spacing controls of one axis. Every mode keeps its own parameter, so switching modes back and
+/// forth does not lose what was typed.
+///
+/// `custom` is either a comma-separated list of constants such as `0, pi/4, 1` or an expression
+/// `f(i)` sampled at `i = 0, 1, …` once per requested line. Apply evaluates it into
+/// `custom_lines`, the same way bound texts are evaluated into `GridBound::value`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AxisSpacing {
+    pub mode: SpacingMode,
+    pub step: f64,
+    /// Last gap over the first one in logarithmic mode.
+    pub ratio: f64,
+    pub custom: String,
+    pub custom_lines: Vec<f64>,
+}
+
+impl AxisSpacing {
+    /// Converts these controls into the spacing the grid configuration uses.
+    pub fn to_line_spacing(&self) -> LineSpacing {
+        match self.mode {
+            SpacingMode::Uniform => LineSpacing::Uniform,
+            SpacingMode::Step => LineSpacing::Step(self.step),
+            SpacingMode::Logarithmic => LineSpacing::Logarithmic { ratio: self.ratio },
+            SpacingMode::Chebyshev => LineSpacing::Chebyshev,
+            SpacingMode::Custom => LineSpacing::Custom(CustomLines::new(&self.custom_lines)),
+        }
+    }
+}
+
+impl Default for AxisSpacing {
+    fn default() -> Self {
+        Self {
+            mode: SpacingMode::Uniform,
+            step: 1.0,
+            ratio: 10.0,
+            custom: "i^2".to_string(),
+            custom_lines: Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,55 @@
+//! Morph of the primary grid toward a second embedding.
+
+use super::SpacialEqs;
+
+/// Path taken from the source embedding `Φ₀` to the target `Φ₁` as `s` goes from 0 to 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MorphHomotopy {
+    /// `(1 − s)·Φ₀ + s·Φ₁`.
+    Linear,
+    /// The linear path with `s` eased by `3s² − 2s³`, so it starts and stops at rest.
+    Eased,
+    /// The linear path rescaled so the distance from the origin also moves linearly, which
+    /// keeps curved grids from collapsing inward halfway.
+    Radial,
+}
+
+impl MorphHomotopy {
+    pub const ALL: [Self; 3] = [Self::Linear, Self::Eased, Self::Radial];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Linear => "Linear",
+            Self::Eased => "Eased",
+            Self::Radial => "Radial",
+        }
+    }
+}
+
+/// Source is the Grid-tab coordinate system; the target and homotopy take effect on Apply.
+///
+/// `s`, `animate` and `period` are live settings. While animating, the render thread sweeps
+/// `s` back and forth and writes it back here for the slider.
+#[derive(Debug, Clone)]
+pub struct MorphUiState {
+    pub enabled: bool,
+    pub target: SpacialEqs,
+    pub homotopy: MorphHomotopy,
+    pub s: f64,
+    pub animate: bool,
+    /// Seconds for one sweep from `s = 0` to `s = 1`.
+    pub period: f64,
+}
+
+impl Default for MorphUiState {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            target: SpacialEqs::from_defaults("x", "y", "z"),
+            homotopy: MorphHomotopy::Linear,
+            s: 0.0,
+            animate: false,
+            period: 4.0,
+        }
+    }
+}
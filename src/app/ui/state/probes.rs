@@ -0,0 +1,169 @@
+//! Probe markers edited in the EM tab and the histories recorded at them.
+
+use std::sync::{Arc, Mutex};
+
+/// Number of quantities recorded per probe sample.
+pub const PROBE_CHANNEL_COUNT: usize = 9;
+
+/// One recorded quantity; components are in the orthonormal basis of the abstract coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProbeChannel {
+    ScalarPotential,
+    ElectricMagnitude,
+    MagneticMagnitude,
+    ElectricX,
+    ElectricY,
+    ElectricZ,
+    MagneticX,
+    MagneticY,
+    MagneticZ,
+}
+
+impl ProbeChannel {
+    pub const ALL: [Self; PROBE_CHANNEL_COUNT] = [
+        Self::ScalarPotential,
+        Self::ElectricMagnitude,
+        Self::MagneticMagnitude,
+        Self::ElectricX,
+        Self::ElectricY,
+        Self::ElectricZ,
+        Self::MagneticX,
+        Self::MagneticY,
+        Self::MagneticZ,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::ScalarPotential => "V",
+            Self::ElectricMagnitude => "|E|",
+            Self::MagneticMagnitude => "|B|",
+            Self::ElectricX => "Ex",
+            Self::ElectricY => "Ey",
+            Self::ElectricZ => "Ez",
+            Self::MagneticX => "Bx",
+            Self::MagneticY => "By",
+            Self::MagneticZ => "Bz",
+        }
+    }
+
+    /// Position of this channel in `ProbeTrace::values` rows.
+    pub fn index(self) -> usize {
+        self as usize
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProbeView {
+    TimeSeries,
+    Spectrum,
+}
+
+/// A probe placed at abstract coordinates; `id` stays stable while other probes come and go.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProbeMarker {
+    pub id: u64,
+    pub position: [f64; 3],
+}
+
+/// Colors cycled through by probe id, shared by the 3D markers and the plot lines.
+const PROBE_PALETTE: [[f32; 3]; 6] = [
+    [0.96, 0.55, 0.16],
+    [0.30, 0.80, 0.95],
+    [0.60, 0.90, 0.35],
+    [0.95, 0.35, 0.60],
+    [0.80, 0.70, 0.98],
+    [0.98, 0.88, 0.30],
+];
+
+impl ProbeMarker {
+    /// RGB color of this probe.
+    pub fn color(&self) -> [f32; 3] {
+        PROBE_PALETTE[(self.id as usize).saturating_sub(1) % PROBE_PALETTE.len()]
+    }
+}
+
+/// Editable probe list and plot settings.
+///
+/// Probes take effect immediately, without Apply: the render thread reads `markers` every frame
+/// and appends to it when a hovered grid sample is picked with `P`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProbeUiState {
+    pub markers: Vec<ProbeMarker>,
+    pub next_id: u64,
+    pub draft_position: [f64; 3],
+    pub channel: ProbeChannel,
+    pub view: ProbeView,
+    /// Outcome of the last CSV export, shown under the export button.
+    pub export_status: ExportStatus,
+}
+
+/// Status line of a CSV export, filled in by the thread that writes the file.
+///
+/// Clones share the line, and two statuses are equal when they are the same line.
+#[derive(Debug, Clone, Default)]
+pub struct ExportStatus(Arc<Mutex<Option<String>>>);
+
+impl ExportStatus {
+    pub fn get(&self) -> Option<String> {
+        self.0.lock().unwrap().clone()
+    }
+
+    pub fn set(&self, status: String) {
+        *self.0.lock().unwrap() = Some(status);
+    }
+}
+
+impl PartialEq for ExportStatus {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl ProbeUiState {
+    /// Adds a probe at `position` and returns its id.
+    pub fn add(&mut self, position: [f64; 3]) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.markers.push(ProbeMarker { id, position });
+        id
+    }
+}
+
+impl Default for ProbeUiState {
+    fn default() -> Self {
+        Self {
+            markers: Vec::new(),
+            next_id: 1,
+            draft_position: [1.0, 1.0, 1.0],
+            channel: ProbeChannel::ElectricMagnitude,
+            view: ProbeView::TimeSeries,
+            export_status: ExportStatus::default(),
+        }
+    }
+}
+
+/// Recorded history of one probe, oldest sample first.
+///
+/// Published by the render thread while EM is enabled. Each row of `values` holds every
+/// `ProbeChannel` at the matching entry of `times`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProbeTrace {
+    pub id: u64,
+    pub position: [f64; 3],
+    pub times: Vec<f64>,
+    pub values: Vec<[f64; PROBE_CHANNEL_COUNT]>,
+}
+
+impl ProbeTrace {
+    pub fn marker(&self) -> ProbeMarker {
+        ProbeMarker {
+            id: self.id,
+            position: self.position,
+        }
+    }
+
+    /// Returns the history of one channel.
+    pub fn channel(&self, channel: ProbeChannel) -> Vec<f64> {
+        self.values.iter().map(|row| row[channel.index()]).collect()
+    }
+}
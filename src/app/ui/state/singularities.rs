@@ -0,0 +1,27 @@
+//! Degenerate sets of the coordinate embedding, listed in the Grid tab.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SingularKind {
+    /// `det J` vanishes or is undefined, so the coordinate tangents span no frame.
+    Degenerate,
+    /// Distinct abstract points share one world position.
+    NonInjective,
+}
+
+impl SingularKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Degenerate => "degenerate",
+            Self::NonInjective => "non-injective",
+        }
+    }
+}
+
+/// One set found by the singularity analysis after the last geometry change.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SingularSet {
+    pub kind: SingularKind,
+    /// Whether the set comes from factoring `det J` rather than from sampling.
+    pub symbolic: bool,
+    pub description: String,
+}
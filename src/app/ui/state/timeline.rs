@@ -0,0 +1,54 @@
+//! EM playback timeline: loop range, stepping, direction and keyframed time scale.
+
+use std::f64::consts::PI;
+
+/// Time-scale multiplier reached at EM time `time`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimeScaleKeyframe {
+    pub time: f64,
+    pub scale: f64,
+}
+
+/// Timeline controls layered on top of `EmUiState::running` and `EmUiState::time_scale`.
+///
+/// Like the run flag, these are live settings read by the render thread every frame. Seeks are
+/// requested by writing `seek_time` and bumping `seek_counter`; the render thread snaps the
+/// target onto multiples of `step`, so scrubbing back to a time already shown reuses the
+/// sources prepared for it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmTimelineState {
+    pub loop_enabled: bool,
+    pub loop_start: f64,
+    pub loop_end: f64,
+    /// Spacing of single steps and of the grid scrubbed times are snapped to.
+    pub step: f64,
+    pub reverse: bool,
+    /// Multipliers of `time_scale`, linearly interpolated between keyframes and held constant
+    /// before the first and after the last. No keyframes means a constant multiplier of one.
+    pub keyframes: Vec<TimeScaleKeyframe>,
+    pub seek_time: f64,
+    pub seek_counter: u64,
+}
+
+impl EmTimelineState {
+    /// Requests a jump to `time` on the next rendered frame.
+    pub fn seek(&mut self, time: f64) {
+        self.seek_time = time;
+        self.seek_counter += 1;
+    }
+}
+
+impl Default for EmTimelineState {
+    fn default() -> Self {
+        Self {
+            loop_enabled: false,
+            loop_start: 0.0,
+            loop_end: 2.0 * PI,
+            step: 0.05,
+            reverse: false,
+            keyframes: Vec::new(),
+            seek_time: 0.0,
+            seek_counter: 0,
+        }
+    }
+}
@@ -0,0 +1,31 @@
+//! Tissot indicatrices: strain ellipsoids drawn at the field samples.
+
+/// Visibility and size of the glyphs; both live, like `show_singularities`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TissotUiState {
+    pub show: bool,
+    /// Radius of the abstract ball each ellipsoid is the image of.
+    pub radius: f64,
+}
+
+impl Default for TissotUiState {
+    fn default() -> Self {
+        Self {
+            show: false,
+            radius: 0.15,
+        }
+    }
+}
+
+/// How the embedding distorts small balls over the field samples of the last geometry change.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TissotReport {
+    pub samples: usize,
+    /// Samples whose principal stretches agree, so that balls stay balls.
+    pub conformal: usize,
+    /// Samples where `σ₁σ₂σ₃ = |det J|` is one.
+    pub volume_preserving: usize,
+    /// Largest ratio `σ_max / σ_min` and the abstract point it was found at.
+    pub max_anisotropy: f64,
+    pub max_anisotropy_at: [f64; 3],
+}
@@ -1,14 +1,25 @@
 //! Parsing and validation helpers for equations entered in the control window.
 
-use crate::app::ui::state::{EmMode, EmUiState, EqRender, FieldKind, GridUiState, SpacialEqs};
+use crate::app::grid::{MAX_AXIS_LINES, MAX_CUSTOM_LINES, MAX_GRID_SEGMENTS};
+use crate::app::ui::state::{
+    AtlasUiState, AxisSpacing, ChartUiState, EmMode, EmUiState, EqRender, FieldKind, GridBound,
+    GridUiState, LineQuantity, SpacialEqs, SpacingMode,
+};
+use crate::maths::glsl::expr_to_glsl;
+use crate::maths::{expr_to_fastexpr3d, num, IMAGINARY_UNIT};
 use mathhook_core::Parser;
+use std::collections::HashMap;
 
 #[derive(Debug)]
 pub(crate) struct ValidatedUiState {
     pub coords_sys: SpacialEqs,
+    pub morph_target: SpacialEqs,
     pub scalar_field: EqRender,
     pub field: SpacialEqs,
     pub em: EmUiState,
+    pub bounds: [(GridBound, GridBound); 3],
+    pub line_spacing: [AxisSpacing; 3],
+    pub charts: Vec<ChartUiState>,
 }
 
 /// Validates and reparses every editable equation in the UI state before apply.
@@ -17,33 +28,49 @@ pub(crate) struct ValidatedUiState {
 /// fields keep their previous parsed expression so the user can switch modes without losing a
 /// temporarily invalid draft in the hidden section.
 pub(crate) fn validate_ui_state(state: &GridUiState) -> Result<ValidatedUiState, String> {
-    let coord_x = validate_xyz_equation("Coordinate x", &state.coords_sys.x.eq_str);
-    let coord_y = validate_xyz_equation("Coordinate y", &state.coords_sys.y.eq_str);
-    let coord_z = validate_xyz_equation("Coordinate z", &state.coords_sys.z.eq_str);
+    let coord_x = validate_moving_coordinate_equation("Coordinate x", &state.coords_sys.x.eq_str);
+    let coord_y = validate_moving_coordinate_equation("Coordinate y", &state.coords_sys.y.eq_str);
+    let coord_z = validate_moving_coordinate_equation("Coordinate z", &state.coords_sys.z.eq_str);
+    let morph_target = validate_morph_target(state);
+    // Grid lines colored by the scalar field evaluate it in the grid shader, whatever is shown.
     let scalar_field = match (state.em.enabled, state.field_kind) {
+        _ if state.line_coloring.quantity == LineQuantity::ScalarField => {
+            validate_coordinate_equation("Scalar field", &state.scalar_field.eq_str)
+        }
         (true, _) | (false, FieldKind::Vector) => Ok(state.scalar_field.clone()),
         (false, FieldKind::Scalar) => {
-            validate_xyz_equation("Scalar field", &state.scalar_field.eq_str)
+            validate_xyzt_equation("Scalar field", &state.scalar_field.eq_str)
         }
     };
     let field_x = match (state.em.enabled, state.field_kind) {
         (true, _) | (false, FieldKind::Scalar) => Ok(state.field.x.clone()),
-        (false, FieldKind::Vector) => validate_xyz_equation("Field Fx", &state.field.x.eq_str),
+        (false, FieldKind::Vector) => {
+            validate_field_component(state, "Field Fx", &state.field.x.eq_str)
+        }
     };
     let field_y = match (state.em.enabled, state.field_kind) {
         (true, _) | (false, FieldKind::Scalar) => Ok(state.field.y.clone()),
-        (false, FieldKind::Vector) => validate_xyz_equation("Field Fy", &state.field.y.eq_str),
+        (false, FieldKind::Vector) => {
+            validate_field_component(state, "Field Fy", &state.field.y.eq_str)
+        }
     };
     let field_z = match (state.em.enabled, state.field_kind) {
         (true, _) | (false, FieldKind::Scalar) => Ok(state.field.z.clone()),
-        (false, FieldKind::Vector) => validate_xyz_equation("Field Fz", &state.field.z.eq_str),
+        (false, FieldKind::Vector) => {
+            validate_field_component(state, "Field Fz", &state.field.z.eq_str)
+        }
     };
     let em = validate_em_state(&state.em);
+    let grid = validate_grid_bounds(state);
+    let charts = validate_atlas(&state.atlas);
 
     let mut errors = Vec::new();
     collect_error(&coord_x, &mut errors);
     collect_error(&coord_y, &mut errors);
     collect_error(&coord_z, &mut errors);
+    if let Err(error) = &morph_target {
+        errors.push(error.clone());
+    }
     collect_error(&scalar_field, &mut errors);
     collect_error(&field_x, &mut errors);
     collect_error(&field_y, &mut errors);
@@ -51,17 +78,25 @@ pub(crate) fn validate_ui_state(state: &GridUiState) -> Result<ValidatedUiState,
     if let Err(error) = &em {
         errors.push(error.clone());
     }
+    if let Err(error) = &grid {
+        errors.push(error.clone());
+    }
+    if let Err(error) = &charts {
+        errors.push(error.clone());
+    }
 
     if !errors.is_empty() {
         return Err(format_error_summary(&errors));
     }
 
+    let (bounds, line_spacing) = grid?;
     Ok(ValidatedUiState {
         coords_sys: SpacialEqs {
             x: coord_x?,
             y: coord_y?,
             z: coord_z?,
         },
+        morph_target: morph_target?,
         scalar_field: scalar_field?,
         field: SpacialEqs {
             x: field_x?,
@@ -69,6 +104,9 @@ pub(crate) fn validate_ui_state(state: &GridUiState) -> Result<ValidatedUiState,
             z: field_z?,
         },
         em: em?,
+        bounds,
+        line_spacing,
+        charts: charts?,
     })
 }
 
@@ -77,10 +115,61 @@ fn validate_xyz_equation(label: &str, eq: &str) -> Result<EqRender, String> {
     validate_equation(label, eq, &["x", "y", "z"])
 }
 
+/// Parses one Field-tab vector component over the variables of the selected input basis and the
+/// scene time `t`.
+fn validate_field_component(
+    state: &GridUiState,
+    label: &str,
+    eq: &str,
+) -> Result<EqRender, String> {
+    let [x, y, z] = state.vector_input.variables();
+    validate_equation(label, eq, &[x, y, z, "t"])
+}
+
+/// Parses one equation over `x`, `y` and `z` and checks that the grid shader can evaluate it.
+fn validate_coordinate_equation(label: &str, eq: &str) -> Result<EqRender, String> {
+    let eq = validate_xyz_equation(label, eq)?;
+    expr_to_glsl(&eq.eq).map_err(|error| format!("{label}: {error}"))?;
+    Ok(eq)
+}
+
+/// Like `validate_coordinate_equation`, but the embedding may also move with the scene time `t`.
+fn validate_moving_coordinate_equation(label: &str, eq: &str) -> Result<EqRender, String> {
+    let eq = validate_xyzt_equation(label, eq)?;
+    expr_to_glsl(&eq.eq).map_err(|error| format!("{label}: {error}"))?;
+    Ok(eq)
+}
+
+/// Checks the morph target like the coordinate system; a disabled morph keeps its draft.
+fn validate_morph_target(state: &GridUiState) -> Result<SpacialEqs, String> {
+    let target = &state.morph.target;
+    if !state.morph.enabled {
+        return Ok(target.clone());
+    }
+    let x = validate_moving_coordinate_equation("Morph target x", &target.x.eq_str);
+    let y = validate_moving_coordinate_equation("Morph target y", &target.y.eq_str);
+    let z = validate_moving_coordinate_equation("Morph target z", &target.z.eq_str);
+    match (x, y, z) {
+        (Ok(x), Ok(y), Ok(z)) => Ok(SpacialEqs { x, y, z }),
+        (x, y, z) => {
+            let mut errors = Vec::new();
+            collect_error(&x, &mut errors);
+            collect_error(&y, &mut errors);
+            collect_error(&z, &mut errors);
+            Err(errors.join("\n"))
+        }
+    }
+}
+
 fn validate_xyzt_equation(label: &str, eq: &str) -> Result<EqRender, String> {
     validate_equation(label, eq, &["x", "y", "z", "t"])
 }
 
+/// Parses one complex amplitude where `i` is the imaginary unit.
+fn validate_complex_xyz_equation(label: &str, eq: &str) -> Result<EqRender, String> {
+    validate_equation(label, eq, &["x", "y", "z", IMAGINARY_UNIT])
+}
+
 fn validate_equation(
     label: &str,
     eq: &str,
@@ -107,63 +196,390 @@ fn validate_equation(
     Ok(EqRender::new(formal_eq, eq.to_string()))
 }
 
+/// Evaluates every bound field and custom line list, then checks the lines and segments the
+/// grid would need.
+fn validate_grid_bounds(
+    state: &GridUiState,
+) -> Result<([(GridBound, GridBound); 3], [AxisSpacing; 3]), String> {
+    let fields = [&state.bounds_x, &state.bounds_y, &state.bounds_z];
+    let counts = [state.nb_x, state.nb_y, state.nb_z];
+    let mut bounds = Vec::with_capacity(3);
+    let mut spacings = state.line_spacing.clone();
+    let mut errors = Vec::new();
+    for (axis, (min, max)) in ["x", "y", "z"].into_iter().zip(fields) {
+        let min = validate_bound(&format!("Bounds ({axis}) min"), &min.text);
+        let max = validate_bound(&format!("Bounds ({axis}) max"), &max.text);
+        match (min, max) {
+            (Ok(min), Ok(max)) => bounds.push((min, max)),
+            (min, max) => errors.extend(min.err().into_iter().chain(max.err())),
+        }
+    }
+    for ((axis, spacing), count) in ["x", "y", "z"].into_iter().zip(&mut spacings).zip(counts) {
+        let label = format!("Grid ({axis})");
+        match spacing.mode {
+            SpacingMode::Step if !(spacing.step > 0.0 && spacing.step.is_finite()) => {
+                errors.push(format!("{label}: Line spacing must be positive"));
+            }
+            SpacingMode::Logarithmic if !(spacing.ratio > 0.0 && spacing.ratio.is_finite()) => {
+                errors.push(format!("{label}: Spacing ratio must be positive"));
+            }
+            SpacingMode::Custom => {
+                let count = count.round().max(1.0) as usize;
+                match validate_custom_lines(&label, &spacing.custom, count) {
+                    Ok(lines) => spacing.custom_lines = lines,
+                    Err(error) => errors.push(error),
+                }
+            }
+            _ => {}
+        }
+    }
+    for (axis, length) in ["x", "y", "z"].into_iter().zip(state.segment_lengths) {
+        if !(length > 0.0 && length.is_finite()) {
+            errors.push(format!("Grid ({axis}): Segment length must be positive"));
+        }
+    }
+    if !errors.is_empty() {
+        return Err(errors.join("\n"));
+    }
+
+    let bounds: [(GridBound, GridBound); 3] = bounds
+        .try_into()
+        .expect("one bound pair was validated per axis");
+    let config = state
+        .grid_config_with_bounds(bounds.each_ref().map(|(min, max)| (min.value, max.value)))
+        .with_line_spacing(spacings.each_ref().map(AxisSpacing::to_line_spacing));
+    for (axis, name) in ["x", "y", "z"].into_iter().enumerate() {
+        let count = config.line_count(axis);
+        if count > MAX_AXIS_LINES {
+            return Err(format!(
+                "Grid ({name}): Line spacing gives {count} lines; at most {MAX_AXIS_LINES} are allowed"
+            ));
+        }
+    }
+    let segments = config.segment_count();
+    if segments > MAX_GRID_SEGMENTS {
+        return Err(format!(
+            "Grid: {segments} segments exceed the limit of {MAX_GRID_SEGMENTS}; increase the segment lengths or line spacing"
+        ));
+    }
+    Ok((bounds, spacings))
+}
+
+/// Reparses the embedding and evaluates the bounds of every extra chart.
+fn validate_atlas(atlas: &AtlasUiState) -> Result<Vec<ChartUiState>, String> {
+    let mut charts = Vec::with_capacity(atlas.charts.len());
+    let mut errors = Vec::new();
+    for (index, chart) in atlas.charts.iter().enumerate() {
+        let label = format!("Chart {} ({})", index + 1, chart.name);
+        let eqs = [
+            &chart.coords_sys.x,
+            &chart.coords_sys.y,
+            &chart.coords_sys.z,
+        ];
+        let coords = ["x", "y", "z"]
+            .into_iter()
+            .zip(eqs)
+            .map(|(axis, eq)| validate_coordinate_equation(&format!("{label} {axis}"), &eq.eq_str))
+            .collect::<Vec<_>>();
+        let bounds = ["x", "y", "z"]
+            .into_iter()
+            .zip(&chart.bounds)
+            .map(|(axis, (min, max))| {
+                let min = validate_bound(&format!("{label} bounds ({axis}) min"), &min.text);
+                let max = validate_bound(&format!("{label} bounds ({axis}) max"), &max.text);
+                min.and_then(|min| max.map(|max| (min, max)))
+            })
+            .collect::<Vec<_>>();
+        coords.iter().for_each(|eq| collect_error(eq, &mut errors));
+        errors.extend(
+            bounds
+                .iter()
+                .filter_map(|bound| bound.as_ref().err().cloned()),
+        );
+        if chart.name.trim().is_empty() {
+            errors.push(format!("{label}: Chart name cannot be empty"));
+        }
+        let (Ok(coords), Ok(bounds)) = (
+            coords.into_iter().collect::<Result<Vec<_>, _>>(),
+            bounds.into_iter().collect::<Result<Vec<_>, _>>(),
+        ) else {
+            continue;
+        };
+        let [x, y, z] = coords.try_into().expect("three chart equations");
+        let validated = ChartUiState {
+            coords_sys: SpacialEqs { x, y, z },
+            bounds: bounds.try_into().expect("three chart bound pairs"),
+            ..chart.clone()
+        };
+        let segments = validated.grid_config().segment_count();
+        if segments > MAX_GRID_SEGMENTS {
+            errors.push(format!(
+                "{label}: {segments} segments exceed the limit of {MAX_GRID_SEGMENTS}; lower the line counts"
+            ));
+        }
+        charts.push(validated);
+    }
+    if !errors.is_empty() {
+        return Err(errors.join("\n"));
+    }
+    Ok(charts)
+}
+
+/// Evaluates a custom line list: comma-separated constants, or an expression in `i` sampled at
+/// `i = 0..count`.
+fn validate_custom_lines(label: &str, text: &str, count: usize) -> Result<Vec<f64>, String> {
+    let lines = if text.contains(',') {
+        text.split(',')
+            .map(|entry| validate_bound(label, entry.trim()).map(|bound| bound.value))
+            .collect::<Result<Vec<_>, _>>()?
+    } else {
+        if text.trim().is_empty() {
+            return Err(format!("{label}: Custom lines cannot be empty"));
+        }
+        let expr = Parser::default()
+            .parse(text)
+            .map_err(|error| format!("{label}: Invalid custom lines: {error}"))?;
+        if let Some(variable) = expr
+            .find_variables()
+            .iter()
+            .find(|variable| variable.name() != "i")
+        {
+            return Err(format!(
+                "{label}: Custom lines may only use the index 'i', found '{}'",
+                variable.name()
+            ));
+        }
+        (0..count)
+            .map(|index| {
+                let vars = HashMap::from([("i".to_string(), num(index as f64))]);
+                expr_to_fastexpr3d(expr.substitute(&vars))(0.0, 0.0, 0.0)
+            })
+            .collect()
+    };
+    if let Some(index) = lines.iter().position(|value| !value.is_finite()) {
+        return Err(format!(
+            "{label}: Custom line {index} does not evaluate to a finite number"
+        ));
+    }
+    if lines.len() > MAX_CUSTOM_LINES {
+        return Err(format!(
+            "{label}: {} custom lines given; at most {MAX_CUSTOM_LINES} are allowed",
+            lines.len()
+        ));
+    }
+    Ok(lines)
+}
+
+/// Parses one bound as a constant expression such as `-1.5` or `pi/2`.
+fn validate_bound(label: &str, text: &str) -> Result<GridBound, String> {
+    if text.trim().is_empty() {
+        return Err(format!("{label}: Bound cannot be empty"));
+    }
+    let expr = Parser::default()
+        .parse(text)
+        .map_err(|error| format!("{label}: Invalid bound: {error}"))?;
+    if let Some(variable) = expr.find_variables().first() {
+        return Err(format!(
+            "{label}: Bounds must be constants, found variable '{}'",
+            variable.name()
+        ));
+    }
+    let value = expr_to_fastexpr3d(expr)(0.0, 0.0, 0.0);
+    if !value.is_finite() {
+        return Err(format!(
+            "{label}: Bound does not evaluate to a finite number"
+        ));
+    }
+    Ok(GridBound::new(value, text))
+}
+
 fn validate_em_state(state: &EmUiState) -> Result<EmUiState, String> {
     if !state.enabled {
         return Ok(state.clone());
     }
 
     let phi = match state.mode {
-        EmMode::Potentials => validate_xyzt_equation("EM phi", &state.phi.eq_str),
-        EmMode::Electric | EmMode::Magnetic => Ok(state.phi.clone()),
+        EmMode::Potentials | EmMode::ClosedForm => {
+            validate_xyzt_equation("EM phi", &state.phi.eq_str)
+        }
+        EmMode::Electric | EmMode::Magnetic | EmMode::Phasor => Ok(state.phi.clone()),
+    };
+    let gauge_function = match state.mode {
+        EmMode::Potentials => validate_xyzt_equation("EM lambda", &state.gauge_function.eq_str),
+        EmMode::Electric | EmMode::Magnetic | EmMode::Phasor | EmMode::ClosedForm => {
+            Ok(state.gauge_function.clone())
+        }
     };
     let ax = match state.mode {
-        EmMode::Potentials => validate_xyzt_equation("EM Ax", &state.vector_potential.x.eq_str),
-        EmMode::Electric | EmMode::Magnetic => Ok(state.vector_potential.x.clone()),
+        EmMode::Potentials | EmMode::ClosedForm => {
+            validate_xyzt_equation("EM Ax", &state.vector_potential.x.eq_str)
+        }
+        EmMode::Electric | EmMode::Magnetic | EmMode::Phasor => {
+            Ok(state.vector_potential.x.clone())
+        }
     };
     let ay = match state.mode {
-        EmMode::Potentials => validate_xyzt_equation("EM Ay", &state.vector_potential.y.eq_str),
-        EmMode::Electric | EmMode::Magnetic => Ok(state.vector_potential.y.clone()),
+        EmMode::Potentials | EmMode::ClosedForm => {
+            validate_xyzt_equation("EM Ay", &state.vector_potential.y.eq_str)
+        }
+        EmMode::Electric | EmMode::Magnetic | EmMode::Phasor => {
+            Ok(state.vector_potential.y.clone())
+        }
     };
     let az = match state.mode {
-        EmMode::Potentials => validate_xyzt_equation("EM Az", &state.vector_potential.z.eq_str),
-        EmMode::Electric | EmMode::Magnetic => Ok(state.vector_potential.z.clone()),
+        EmMode::Potentials | EmMode::ClosedForm => {
+            validate_xyzt_equation("EM Az", &state.vector_potential.z.eq_str)
+        }
+        EmMode::Electric | EmMode::Magnetic | EmMode::Phasor => {
+            Ok(state.vector_potential.z.clone())
+        }
     };
+    let [rho, jx, jy, jz] = [
+        ("EM rho", &state.charge_density),
+        ("EM Jx", &state.current_density.x),
+        ("EM Jy", &state.current_density.y),
+        ("EM Jz", &state.current_density.z),
+    ]
+    .map(|(name, eq)| match state.mode {
+        EmMode::Potentials => validate_xyzt_equation(name, &eq.eq_str),
+        EmMode::Electric | EmMode::Magnetic | EmMode::Phasor | EmMode::ClosedForm => Ok(eq.clone()),
+    });
     let ex = match state.mode {
-        EmMode::Electric => validate_xyzt_equation("EM Ex", &state.electric_field.x.eq_str),
-        EmMode::Potentials | EmMode::Magnetic => Ok(state.electric_field.x.clone()),
+        EmMode::Electric | EmMode::ClosedForm => {
+            validate_xyzt_equation("EM Ex", &state.electric_field.x.eq_str)
+        }
+        EmMode::Potentials | EmMode::Magnetic | EmMode::Phasor => {
+            Ok(state.electric_field.x.clone())
+        }
     };
     let ey = match state.mode {
-        EmMode::Electric => validate_xyzt_equation("EM Ey", &state.electric_field.y.eq_str),
-        EmMode::Potentials | EmMode::Magnetic => Ok(state.electric_field.y.clone()),
+        EmMode::Electric | EmMode::ClosedForm => {
+            validate_xyzt_equation("EM Ey", &state.electric_field.y.eq_str)
+        }
+        EmMode::Potentials | EmMode::Magnetic | EmMode::Phasor => {
+            Ok(state.electric_field.y.clone())
+        }
     };
     let ez = match state.mode {
-        EmMode::Electric => validate_xyzt_equation("EM Ez", &state.electric_field.z.eq_str),
-        EmMode::Potentials | EmMode::Magnetic => Ok(state.electric_field.z.clone()),
+        EmMode::Electric | EmMode::ClosedForm => {
+            validate_xyzt_equation("EM Ez", &state.electric_field.z.eq_str)
+        }
+        EmMode::Potentials | EmMode::Magnetic | EmMode::Phasor => {
+            Ok(state.electric_field.z.clone())
+        }
     };
     let bx = match state.mode {
-        EmMode::Magnetic => validate_xyzt_equation("EM Bx", &state.magnetic_field.x.eq_str),
-        EmMode::Potentials | EmMode::Electric => Ok(state.magnetic_field.x.clone()),
+        EmMode::Magnetic | EmMode::ClosedForm => {
+            validate_xyzt_equation("EM Bx", &state.magnetic_field.x.eq_str)
+        }
+        EmMode::Potentials | EmMode::Electric | EmMode::Phasor => {
+            Ok(state.magnetic_field.x.clone())
+        }
     };
     let by = match state.mode {
-        EmMode::Magnetic => validate_xyzt_equation("EM By", &state.magnetic_field.y.eq_str),
-        EmMode::Potentials | EmMode::Electric => Ok(state.magnetic_field.y.clone()),
+        EmMode::Magnetic | EmMode::ClosedForm => {
+            validate_xyzt_equation("EM By", &state.magnetic_field.y.eq_str)
+        }
+        EmMode::Potentials | EmMode::Electric | EmMode::Phasor => {
+            Ok(state.magnetic_field.y.clone())
+        }
     };
     let bz = match state.mode {
-        EmMode::Magnetic => validate_xyzt_equation("EM Bz", &state.magnetic_field.z.eq_str),
-        EmMode::Potentials | EmMode::Electric => Ok(state.magnetic_field.z.clone()),
+        EmMode::Magnetic | EmMode::ClosedForm => {
+            validate_xyzt_equation("EM Bz", &state.magnetic_field.z.eq_str)
+        }
+        EmMode::Potentials | EmMode::Electric | EmMode::Phasor => {
+            Ok(state.magnetic_field.z.clone())
+        }
+    };
+    let phasor_x = match state.mode {
+        EmMode::Phasor => {
+            validate_complex_xyz_equation("EM phasor Ex", &state.phasor_electric_field.x.eq_str)
+        }
+        EmMode::Potentials | EmMode::Electric | EmMode::Magnetic | EmMode::ClosedForm => {
+            Ok(state.phasor_electric_field.x.clone())
+        }
+    };
+    let phasor_y = match state.mode {
+        EmMode::Phasor => {
+            validate_complex_xyz_equation("EM phasor Ey", &state.phasor_electric_field.y.eq_str)
+        }
+        EmMode::Potentials | EmMode::Electric | EmMode::Magnetic | EmMode::ClosedForm => {
+            Ok(state.phasor_electric_field.y.clone())
+        }
+    };
+    let phasor_z = match state.mode {
+        EmMode::Phasor => {
+            validate_complex_xyz_equation("EM phasor Ez", &state.phasor_electric_field.z.eq_str)
+        }
+        EmMode::Potentials | EmMode::Electric | EmMode::Magnetic | EmMode::ClosedForm => {
+            Ok(state.phasor_electric_field.z.clone())
+        }
+    };
+    // Media only enter the source-mode solve; potentials already determine E and B directly.
+    let permittivity = match state.mode {
+        EmMode::Electric | EmMode::Magnetic => {
+            validate_xyz_equation("EM epsilon", &state.permittivity.eq_str)
+        }
+        EmMode::Potentials | EmMode::Phasor | EmMode::ClosedForm => Ok(state.permittivity.clone()),
+    };
+    let permeability = match state.mode {
+        EmMode::Electric | EmMode::Magnetic => {
+            validate_xyz_equation("EM mu", &state.permeability.eq_str)
+        }
+        EmMode::Potentials | EmMode::Phasor | EmMode::ClosedForm => Ok(state.permeability.clone()),
+    };
+    let conductivity = match state.mode {
+        EmMode::Electric | EmMode::Magnetic => {
+            validate_xyz_equation("EM sigma", &state.conductivity.eq_str)
+        }
+        EmMode::Potentials | EmMode::Phasor | EmMode::ClosedForm => Ok(state.conductivity.clone()),
+    };
+    // Conductors and the mask shape the inverse-curl domain, so they share the media's modes.
+    let conductors = state
+        .conductors
+        .iter()
+        .enumerate()
+        .map(|(index, conductor)| match state.mode {
+            EmMode::Electric | EmMode::Magnetic => {
+                validate_xyz_equation(&format!("EM conductor {}", index + 1), &conductor.eq_str)
+            }
+            EmMode::Potentials | EmMode::Phasor | EmMode::ClosedForm => Ok(conductor.clone()),
+        })
+        .collect::<Vec<_>>();
+    let domain_mask = match state.mode {
+        EmMode::Electric | EmMode::Magnetic => {
+            validate_xyz_equation("EM domain mask", &state.domain_mask.eq_str)
+        }
+        EmMode::Potentials | EmMode::Phasor | EmMode::ClosedForm => Ok(state.domain_mask.clone()),
     };
 
     let mut errors = Vec::new();
     collect_error(&phi, &mut errors);
+    collect_error(&gauge_function, &mut errors);
     collect_error(&ax, &mut errors);
     collect_error(&ay, &mut errors);
     collect_error(&az, &mut errors);
+    collect_error(&rho, &mut errors);
+    collect_error(&jx, &mut errors);
+    collect_error(&jy, &mut errors);
+    collect_error(&jz, &mut errors);
     collect_error(&ex, &mut errors);
     collect_error(&ey, &mut errors);
     collect_error(&ez, &mut errors);
     collect_error(&bx, &mut errors);
     collect_error(&by, &mut errors);
     collect_error(&bz, &mut errors);
+    collect_error(&phasor_x, &mut errors);
+    collect_error(&phasor_y, &mut errors);
+    collect_error(&phasor_z, &mut errors);
+    collect_error(&permittivity, &mut errors);
+    collect_error(&permeability, &mut errors);
+    collect_error(&conductivity, &mut errors);
+    conductors
+        .iter()
+        .for_each(|conductor| collect_error(conductor, &mut errors));
+    collect_error(&domain_mask, &mut errors);
 
     if !errors.is_empty() {
         return Err(errors.join("\n"));
@@ -171,11 +587,18 @@ fn validate_em_state(state: &EmUiState) -> Result<EmUiState, String> {
 
     let mut validated = state.clone();
     validated.phi = phi?;
+    validated.gauge_function = gauge_function?;
     validated.vector_potential = SpacialEqs {
         x: ax?,
         y: ay?,
         z: az?,
     };
+    validated.charge_density = rho?;
+    validated.current_density = SpacialEqs {
+        x: jx?,
+        y: jy?,
+        z: jz?,
+    };
     validated.electric_field = SpacialEqs {
         x: ex?,
         y: ey?,
@@ -186,6 +609,16 @@ fn validate_em_state(state: &EmUiState) -> Result<EmUiState, String> {
         y: by?,
         z: bz?,
     };
+    validated.phasor_electric_field = SpacialEqs {
+        x: phasor_x?,
+        y: phasor_y?,
+        z: phasor_z?,
+    };
+    validated.permittivity = permittivity?;
+    validated.permeability = permeability?;
+    validated.conductivity = conductivity?;
+    validated.conductors = conductors.into_iter().collect::<Result<_, _>>()?;
+    validated.domain_mask = domain_mask?;
     Ok(validated)
 }
 
@@ -193,6 +626,7 @@ fn format_allowed_variables(allowed_variables: &[&str]) -> String {
     match allowed_variables {
         ["x", "y", "z"] => "'x', 'y', and 'z'".to_string(),
         ["x", "y", "z", "t"] => "'x', 'y', 'z', and 't'".to_string(),
+        ["x", "y", "z", IMAGINARY_UNIT] => "'x', 'y', 'z', and the imaginary unit 'i'".to_string(),
         _ => allowed_variables
             .iter()
             .map(|variable| format!("'{variable}'"))
@@ -216,7 +650,10 @@ fn format_error_summary(errors: &[String]) -> String {
 #[cfg(test)]
 mod tests {
     use super::{format_error_summary, validate_ui_state};
-    use crate::app::ui::{EmMode, FieldKind, GridUiState};
+    use crate::app::ui::{
+        ChartPreset, EmMode, FieldKind, GridUiState, LineQuantity, SpacingMode, VectorInput,
+    };
+    use std::f64::consts::{FRAC_PI_2, PI};
 
     #[test]
     fn validate_ui_state_accepts_polynomial_expression() {
@@ -244,6 +681,134 @@ mod tests {
         assert!(error.contains("Coordinate x: Equation cannot be empty"));
     }
 
+    #[test]
+    fn validate_ui_state_rejects_coordinates_the_grid_shader_cannot_draw() {
+        let mut state = GridUiState::default();
+        state.coords_sys.y.eq_str = "besselj(0, x)".to_string();
+
+        let error = validate_ui_state(&state).unwrap_err();
+
+        assert!(error.contains("Coordinate y: function 'besselj' is not supported on the GPU"));
+    }
+
+    #[test]
+    fn validate_ui_state_evaluates_symbolic_grid_bounds() {
+        let mut state = GridUiState::default();
+        state.set_bound_texts([("0.5", "2.25"), ("-pi/2", "pi/2"), ("0", "1/3")]);
+
+        let validated = validate_ui_state(&state).unwrap();
+
+        let [(x_min, _), (y_min, y_max), (_, z_max)] = &validated.bounds;
+        assert_eq!(x_min.value, 0.5);
+        assert!((y_min.value + FRAC_PI_2).abs() < 1.0e-12);
+        assert!((y_max.value - FRAC_PI_2).abs() < 1.0e-12);
+        assert!((z_max.value - 1.0 / 3.0).abs() < 1.0e-12);
+        assert_eq!(y_max.text, "pi/2");
+    }
+
+    #[test]
+    fn validate_ui_state_rejects_variable_bounds_and_dense_spacing() {
+        let mut state = GridUiState::default();
+        state.bounds_x.1.text = "x + 1".to_string();
+        state.line_spacing[2].mode = SpacingMode::Step;
+        state.line_spacing[2].step = 1.0e-4;
+
+        let error = validate_ui_state(&state).unwrap_err();
+
+        assert!(error.contains("Bounds (x) max: Bounds must be constants"));
+
+        state.bounds_x.1.text = "15".to_string();
+        let error = validate_ui_state(&state).unwrap_err();
+
+        assert!(error.contains("Grid (z): Line spacing gives"));
+    }
+
+    #[test]
+    fn validate_ui_state_evaluates_custom_line_lists_and_index_expressions() {
+        let mut state = GridUiState::default();
+        state.nb_y = 4.0;
+        state.line_spacing[0].mode = SpacingMode::Custom;
+        state.line_spacing[0].custom = "0, pi/4, 2".to_string();
+        state.line_spacing[1].mode = SpacingMode::Custom;
+        state.line_spacing[1].custom = "i^2 / 2".to_string();
+
+        let validated = validate_ui_state(&state).unwrap();
+
+        let [x, y, _] = &validated.line_spacing;
+        assert_eq!(x.custom_lines.len(), 3);
+        assert!((x.custom_lines[1] - FRAC_PI_2 / 2.0).abs() < 1.0e-12);
+        assert_eq!(y.custom_lines, vec![0.0, 0.5, 2.0, 4.5]);
+
+        state.line_spacing[1].custom = "i + x".to_string();
+        let error = validate_ui_state(&state).unwrap_err();
+
+        assert!(error.contains("Grid (y): Custom lines may only use the index 'i', found 'x'"));
+    }
+
+    #[test]
+    fn validate_ui_state_checks_every_chart_of_the_atlas() {
+        let mut state = GridUiState::default();
+        state.atlas.add_chart(ChartPreset::Cylindrical);
+        state.atlas.add_chart(ChartPreset::Cartesian);
+        state.atlas.charts[0].bounds[1].1.text = "pi".to_string();
+
+        let validated = validate_ui_state(&state).unwrap();
+        assert!((validated.charts[0].bounds[1].1.value - PI).abs() < 1.0e-12);
+
+        state.atlas.charts[1].coords_sys.z.eq_str = "z + t".to_string();
+        state.atlas.charts[1].bounds[0].0.text = "y".to_string();
+        let error = validate_ui_state(&state).unwrap_err();
+
+        assert!(error.contains("Chart 2 (Cartesian) z: Invalid variable 't'"));
+        assert!(error.contains("Chart 2 (Cartesian) bounds (x) min: Bounds must be constants"));
+    }
+
+    #[test]
+    fn validate_ui_state_checks_the_scalar_field_for_the_shader_while_lines_show_it() {
+        let mut state = GridUiState::default();
+        state.field_kind = FieldKind::Vector;
+        state.scalar_field.eq_str = "besselj(0, x)".to_string();
+        assert!(validate_ui_state(&state).is_ok());
+
+        state.line_coloring.quantity = LineQuantity::ScalarField;
+        let error = validate_ui_state(&state).unwrap_err();
+        assert!(error.contains("Scalar field: "));
+
+        state.scalar_field.eq_str = "x*y".to_string();
+        let validated = validate_ui_state(&state).unwrap();
+        assert_eq!(validated.scalar_field.eq_str, "x*y");
+    }
+
+    #[test]
+    fn validate_ui_state_lets_the_embedding_move_with_scene_time() {
+        let mut state = GridUiState::default();
+        state.coords_sys.x.eq_str = "x*cos(t) - y*sin(t)".to_string();
+        state.morph.enabled = true;
+        state.morph.target.z.eq_str = "z*(1 + t)".to_string();
+
+        let validated = validate_ui_state(&state).unwrap();
+        assert_eq!(validated.coords_sys.x.eq_str, "x*cos(t) - y*sin(t)");
+
+        state.coords_sys.y.eq_str = "y + s".to_string();
+        let error = validate_ui_state(&state).unwrap_err();
+
+        assert!(error.contains("Coordinate y: Invalid variable 's'"));
+    }
+
+    #[test]
+    fn validate_ui_state_checks_the_morph_target_only_while_enabled() {
+        let mut state = GridUiState::default();
+        state.morph.target.y.eq_str = "y + w".to_string();
+
+        let validated = validate_ui_state(&state).unwrap();
+        assert_eq!(validated.morph_target.y.eq_str, "y + w");
+
+        state.morph.enabled = true;
+        let error = validate_ui_state(&state).unwrap_err();
+
+        assert!(error.contains("Morph target y: Invalid variable 'w'"));
+    }
+
     #[test]
     fn validate_ui_state_uses_scalar_equation_in_scalar_mode() {
         let mut state = GridUiState::default();
@@ -257,15 +822,32 @@ mod tests {
     #[test]
     fn validate_ui_state_rejects_unknown_variable() {
         let mut state = GridUiState::default();
-        state.field.y.eq_str = "x + t".to_string();
+        state.field.y.eq_str = "x + w".to_string();
 
         let error = validate_ui_state(&state).unwrap_err();
 
-        assert!(error.contains("Field Fy: Invalid variable 't'"));
+        assert!(error.contains("Field Fy: Invalid variable 'w'"));
+    }
+
+    #[test]
+    fn validate_ui_state_lets_field_tab_equations_read_scene_time() {
+        let mut state = GridUiState::default();
+        state.field_kind = FieldKind::Vector;
+        state.field.x.eq_str = "-y * cos(t)".to_string();
+        assert!(validate_ui_state(&state).is_ok());
+
+        state.field_kind = FieldKind::Scalar;
+        state.scalar_field.eq_str = "sin(x - t)".to_string();
+        assert!(validate_ui_state(&state).is_ok());
+
+        // Lines colored by the scalar field are ranged once per Apply, so they stay static.
+        state.line_coloring.quantity = LineQuantity::ScalarField;
+        let error = validate_ui_state(&state).unwrap_err();
+        assert!(error.contains("Scalar field: Invalid variable 't'"));
     }
 
     #[test]
-    fn validate_ui_state_accepts_time_only_for_em_equations() {
+    fn validate_ui_state_accepts_time_in_em_equations() {
         let mut state = GridUiState::default();
         state.em.enabled = true;
         state.em.mode = EmMode::Electric;
@@ -297,6 +879,65 @@ mod tests {
         assert!(validate_ui_state(&state).is_ok());
     }
 
+    #[test]
+    fn validate_ui_state_reads_cartesian_field_components_over_world_variables() {
+        let mut state = GridUiState::default();
+        state.field_kind = FieldKind::Vector;
+        state.vector_input = VectorInput::WorldCartesian;
+        state.field.x.eq_str = "-Y".to_string();
+        state.field.y.eq_str = "X".to_string();
+        state.field.z.eq_str = "0".to_string();
+
+        assert!(validate_ui_state(&state).is_ok());
+
+        state.field.z.eq_str = "x".to_string();
+        let error = validate_ui_state(&state).unwrap_err();
+
+        assert!(error.contains("Field Fz: Invalid variable 'x'"));
+    }
+
+    #[test]
+    fn validate_ui_state_rejects_time_dependent_media_in_source_mode() {
+        let mut state = GridUiState::default();
+        state.em.enabled = true;
+        state.em.mode = EmMode::Electric;
+        state.em.permittivity.eq_str = "1 + t".to_string();
+
+        let error = validate_ui_state(&state).unwrap_err();
+
+        assert!(error.contains("EM epsilon: Invalid variable 't'"));
+    }
+
+    #[test]
+    fn validate_ui_state_checks_conductor_regions_only_in_source_modes() {
+        let mut state = GridUiState::default();
+        state.em.enabled = true;
+        state.em.add_conductor("z + 1");
+        state.em.conductors[0].eq_str = "z + t".to_string();
+
+        assert!(validate_ui_state(&state).is_ok());
+
+        state.em.mode = EmMode::Magnetic;
+        let error = validate_ui_state(&state).unwrap_err();
+
+        assert!(error.contains("EM conductor 1: Invalid variable 't'"));
+    }
+
+    #[test]
+    fn validate_ui_state_accepts_imaginary_unit_only_in_phasor_mode() {
+        let mut state = GridUiState::default();
+        state.em.enabled = true;
+        state.em.mode = EmMode::Phasor;
+        state.em.phasor_electric_field.y.eq_str = "i * exp(-i*z)".to_string();
+
+        assert!(validate_ui_state(&state).is_ok());
+
+        state.em.phasor_electric_field.y.eq_str = "i * exp(-i*z) * t".to_string();
+        let error = validate_ui_state(&state).unwrap_err();
+
+        assert!(error.contains("EM phasor Ey: Invalid variable 't'"));
+    }
+
     #[test]
     fn format_error_summary_joins_multiple_lines() {
         let message = format_error_summary(&[
@@ -1,37 +1,57 @@
 //! Runtime world state that bridges UI changes, cached field data, and rendering.
 
 mod apply;
+mod atlas;
+mod covariant_check;
 mod field_rendering;
 mod frame;
+mod gauge_check;
 mod grid_cache;
+mod inspector;
+mod probes;
+mod step;
 
 use crate::app::applied_config::AppliedConfig;
-use crate::app::coords_sys::CoordsSys;
+use crate::app::coords_sys::{CoordsSys, InverseFailure};
 use crate::app::em_runtime::EmRuntime;
-use crate::app::field_render::{EmRenderCache, FieldRenderCache, FieldSample};
-use crate::app::field_runtime::RuntimeField;
+use crate::app::field_render::{EmRenderCache, FieldSample, FieldTimeCache};
+use crate::app::field_runtime::{pulled_back_field, RuntimeField};
 use crate::app::grid::Grid;
 use crate::app::grid_world::{GridSample, GridWorld};
+use crate::app::morph::{self, MorphClock};
+use crate::app::moving_frame::{self, StepThrottle};
+use crate::app::probes::ProbeRecorder;
 use crate::app::tangent_space::TangentSpace;
-use crate::app::ui::{GridUiState, LegendState};
+use crate::app::tissot::Indicatrix;
+use crate::app::ui::{
+    ChartOverlap, CovariantReport, GaugeReport, GridUiState, InspectorReport, LegendState,
+    PulledBackField, SingularSet, TissotReport, TissotUiState,
+};
 use crate::graphics::model::{RenderVField, Sphere};
 use crate::render::master_render::MasterRenderer;
 use crate::toolbox::opengl::display_manager::DisplayManager;
-use nalgebra::Matrix4;
+use atlas::OverlayChart;
+use inspector::InspectorKey;
+use nalgebra::{Matrix4, Vector3};
 use std::sync::{Arc, Mutex};
 
 const SPHERE_SIZE: f64 = 0.1;
 
 pub struct World {
     field: RuntimeField,
+    /// Curvilinear form of a world Cartesian field input, published for the Field tab.
+    pulled_back_field: Option<PulledBackField>,
     em_runtime: Option<EmRuntime>,
     render_field: Vec<RenderVField>,
     render_form_samples: Vec<Sphere>,
     field_samples: Vec<FieldSample>,
-    field_cache: FieldRenderCache,
+    /// Field values at the samples for each scene time visited since the field last changed.
+    field_cache: FieldTimeCache,
     em_cache: Option<EmRenderCache>,
+    /// Timeline time, the scene time `t` of the EM fields and of moving embeddings and fields.
     em_time: f64,
     last_em_reset_counter: u64,
+    last_em_seek_counter: u64,
     normalize_field: bool,
     em_normalize_vectors: bool,
     renderer: MasterRenderer,
@@ -43,7 +63,39 @@ pub struct World {
     sphere: Option<Sphere>,
     tangent_space: TangentSpace,
     applied_config: AppliedConfig,
+    /// Last applied UI state, stepped to a new `s` or `t` whenever the embedding or field reaches
+    /// another step.
+    applied_state: GridUiState,
+    morph_clock: MorphClock,
+    step_throttle: StepThrottle,
     legend: Option<LegendState>,
+    /// Range of the quantity the grid lines are colored by, published for the Grid tab.
+    line_legend: Option<LegendState>,
+    gauge_report: Option<GaugeReport>,
+    covariant_report: Option<CovariantReport>,
+    probe_recorders: Vec<ProbeRecorder>,
+    /// Marker id and revision of each history last published to the UI.
+    published_probes: Vec<(u64, u64)>,
+    probe_markers: Vec<Sphere>,
+    /// Abstract position pinned by a right click, with the reason it is snapped to a sample
+    /// rather than exact; the inspector follows the hover otherwise.
+    inspected: Option<(Vector3<f64>, Option<InverseFailure>)>,
+    inspector_report: Option<InspectorReport>,
+    /// What `inspector_report` was evaluated from, so an unchanged point is not re-evaluated.
+    inspector_key: Option<InspectorKey>,
+    singularities: Vec<SingularSet>,
+    singular_markers: Vec<Sphere>,
+    show_singularities: bool,
+    /// Principal stretches at the field samples, rebuilt with the geometry.
+    indicatrices: Vec<Indicatrix>,
+    tissot_glyphs: Vec<Sphere>,
+    tissot: TissotUiState,
+    tissot_report: Option<TissotReport>,
+    charts: Vec<OverlayChart>,
+    chart_overlaps: Vec<ChartOverlap>,
+    /// Field-tab field pushed onto one overlay chart, drawn in that chart's color.
+    pushed_field: Vec<RenderVField>,
+    show_charts: bool,
 }
 
 impl World {
@@ -61,15 +113,17 @@ impl World {
             EmRuntime::from_ui_with_config(&initial_state.em, &grid, applied_config.grid_config)
         });
         let mut world = Self {
+            pulled_back_field: pulled_back_field(&initial_state, &grid),
             field,
             em_runtime,
             render_field: Vec::new(),
             render_form_samples: Vec::new(),
             field_samples,
-            field_cache: FieldRenderCache::Scalar(Vec::new()),
+            field_cache: FieldTimeCache::default(),
             em_cache: None,
             em_time: 0.0,
             last_em_reset_counter: initial_state.em.reset_counter,
+            last_em_seek_counter: initial_state.em.timeline.seek_counter,
             normalize_field: initial_state.normalize_field,
             em_normalize_vectors: initial_state.em.normalize_vectors,
             renderer: MasterRenderer::new(
@@ -84,7 +138,30 @@ impl World {
             sphere: None,
             tangent_space: TangentSpace::new(),
             applied_config,
+            applied_state: initial_state.clone(),
+            morph_clock: MorphClock::new(&initial_state.morph),
+            step_throttle: StepThrottle::default(),
             legend: None,
+            line_legend: None,
+            gauge_report: None,
+            covariant_report: None,
+            probe_recorders: Vec::new(),
+            published_probes: Vec::new(),
+            probe_markers: Vec::new(),
+            inspected: None,
+            inspector_report: None,
+            inspector_key: None,
+            singularities: Vec::new(),
+            singular_markers: Vec::new(),
+            show_singularities: initial_state.show_singularities,
+            indicatrices: Vec::new(),
+            tissot_glyphs: Vec::new(),
+            tissot: initial_state.tissot,
+            tissot_report: None,
+            charts: Vec::new(),
+            chart_overlaps: Vec::new(),
+            pushed_field: Vec::new(),
+            show_charts: initial_state.atlas.show_charts,
         };
         world
             .tangent_space
@@ -92,8 +169,31 @@ impl World {
         world
             .tangent_space
             .set_geometric_arrow_scale(initial_state.geometric_arrow_scale);
-        world.recompute_cached_field_data();
+        world.recompute_cached_field_data(world.applied_config.field_time());
         world.recompute_cached_em_data();
+        world.refresh_gauge_report(&initial_state.em, world.applied_config.grid_config);
+        world.refresh_covariant_report(&initial_state.em);
+        world.rebuild_line_range(&initial_state, world.applied_config.line_coloring.quantity);
+        world.rebuild_coordinate_surfaces(
+            world.applied_config.surface_axes,
+            world.applied_config.grid_config,
+        );
+        world
+            .renderer
+            .surface_renderer
+            .set_opacity(initial_state.surface_opacity);
+        world.rebuild_singularities(world.applied_config.grid_config);
+        world.rebuild_indicatrices();
+        let charts = world.applied_config.charts.clone();
+        world.rebuild_charts(&initial_state.atlas.charts, &charts, &[]);
+        world.refresh_chart_overlaps(world.applied_config.grid_config, &charts);
+        world.rebuild_pushed_field(
+            world.applied_config.grid_config,
+            &charts,
+            world.applied_config.push_field_to,
+        );
+        world.rebuild_pec_walls(&initial_state.em, world.applied_config.grid_config);
+        world.rebuild_region_surfaces(&initial_state.em, world.applied_config.grid_config);
         world.rebuild_render_field();
         world
     }
@@ -104,17 +204,30 @@ impl World {
     /// updates take over.
     fn init(initial_state: GridUiState) -> (Grid, RuntimeField, Vec<FieldSample>, Vec<GridSample>) {
         let config = initial_state.to_grid_config();
-        let x_eq = initial_state.coords_sys.x.eq.clone();
-        let y_eq = initial_state.coords_sys.y.eq.clone();
-        let z_eq = initial_state.coords_sys.z.eq.clone();
-        let sys_coord = CoordsSys::new(x_eq, y_eq, z_eq);
-        let mut grid = Grid::new(sys_coord);
+        let mut grid = Grid::new(Self::embedding(&initial_state));
         grid.update_config(&config);
         let (field_samples, grid_samples) = Self::build_grid_cache(&grid);
         let field = RuntimeField::from_ui(&initial_state, &grid);
         (grid, field, field_samples, grid_samples)
     }
 
+    /// Builds the coordinate system of the Grid tab at the snapped `t`, morphed to the snapped
+    /// `s` when enabled.
+    fn embedding(state: &GridUiState) -> CoordsSys {
+        let time = moving_frame::snap_time(state.scene_time);
+        let source = moving_frame::freeze(&state.coords_sys, time);
+        if !state.morph.enabled {
+            let [x_eq, y_eq, z_eq] = source;
+            return CoordsSys::new(x_eq, y_eq, z_eq);
+        }
+        CoordsSys::morph(
+            &source,
+            &moving_frame::freeze(&state.morph.target, time),
+            state.morph.homotopy,
+            morph::snap(state.morph.s),
+        )
+    }
+
     #[allow(dead_code)]
     /// Returns the projection matrix currently owned by the master renderer.
     ///
@@ -127,9 +240,12 @@ impl World {
 
 #[cfg(test)]
 mod tests {
+    use super::step::step_em_runtime;
     use super::World;
     use crate::app::applied_config::AppliedConfig;
-    use crate::app::ui::{EmMode, FieldKind, GridUiState};
+    use crate::app::em_runtime::EmRuntime;
+    use crate::app::grid::Grid;
+    use crate::app::ui::{EmMode, FieldKind, GridUiState, LineQuantity, MorphHomotopy, SpacialEqs};
     use crate::maths::differential::Form;
     use crate::maths::field::VectorField;
     use crate::maths::space::Space;
@@ -252,6 +368,183 @@ mod tests {
         assert!(diff.em_render_changed());
     }
 
+    #[test]
+    fn apply_diff_treats_em_observer_velocity_as_render_only() {
+        let current = AppliedConfig::from_ui(&GridUiState::default());
+        let mut next_state = GridUiState::default();
+        next_state.em.observer_beta = [0.5, 0.0, 0.0];
+        let next = AppliedConfig::from_ui(&next_state);
+
+        let diff = current.diff(&next);
+
+        assert!(diff.em_observer_changed);
+        assert!(!diff.em_runtime_changed());
+        assert!(diff.em_render_changed());
+    }
+
+    #[test]
+    fn apply_diff_treats_pec_walls_as_a_separate_layer() {
+        let current = AppliedConfig::from_ui(&GridUiState::default());
+        let mut next_state = GridUiState::default();
+        next_state.em.pec_walls[1] = true;
+        let next = AppliedConfig::from_ui(&next_state);
+
+        let diff = current.diff(&next);
+
+        assert!(diff.em_walls_changed);
+        assert!(diff.pec_walls_changed());
+        assert!(!diff.em_runtime_changed());
+        assert!(!diff.em_render_changed());
+    }
+
+    #[test]
+    fn apply_diff_retessellates_coordinate_surfaces_without_touching_fields() {
+        let current = AppliedConfig::from_ui(&GridUiState::default());
+        let mut next_state = GridUiState::default();
+        next_state.surface_axes[0] = true;
+        next_state.surface_opacity = 0.9;
+        let next = AppliedConfig::from_ui(&next_state);
+
+        let diff = current.diff(&next);
+
+        assert!(diff.coordinate_surfaces_changed());
+        assert!(!diff.geometry_changed());
+        assert!(!diff.field_cache_changed());
+        assert!(!diff.em_runtime_changed());
+    }
+
+    #[test]
+    fn apply_diff_rebuilds_the_runtime_and_surfaces_for_conductor_edits() {
+        let current = AppliedConfig::from_ui(&GridUiState::default());
+        let mut next_state = GridUiState::default();
+        next_state.em.add_conductor("z + 1");
+        let next = AppliedConfig::from_ui(&next_state);
+
+        let diff = current.diff(&next);
+
+        assert!(diff.em_regions_changed);
+        assert!(diff.em_runtime_changed());
+        assert!(diff.region_surfaces_changed());
+        assert!(!diff.pec_walls_changed());
+    }
+
+    #[test]
+    fn apply_diff_rebuilds_geometry_but_not_the_shader_for_morph_steps() {
+        let mut state = GridUiState::default();
+        state.morph.enabled = true;
+        let current = AppliedConfig::from_ui(&state);
+
+        state.morph.s = 0.01;
+        let within_step = current.diff(&AppliedConfig::from_ui(&state));
+        state.morph.s = 0.2;
+        let step = current.diff(&AppliedConfig::from_ui(&state));
+        state.morph.homotopy = MorphHomotopy::Radial;
+        let homotopy = current.diff(&AppliedConfig::from_ui(&state));
+
+        assert_eq!(within_step, Default::default());
+        assert!(step.morph_step_changed);
+        assert!(!step.coords_changed);
+        assert!(step.geometry_changed());
+        assert!(step.runtime_field_changed());
+        assert!(homotopy.coords_changed);
+    }
+
+    #[test]
+    fn morph_steps_rebuild_the_em_runtime_on_the_morphed_embedding() {
+        let parse = |expr: &str| Parser::default().parse(expr).unwrap();
+        let mut state = GridUiState::default();
+        state.morph.enabled = true;
+        state.morph.target = SpacialEqs::from_defaults("2*x", "y", "z");
+        state.em.enabled = true;
+        state.em.mode = EmMode::Potentials;
+        state.em.phi.eq = parse("x");
+        let config = state.to_grid_config();
+        let grid = Grid::new(World::embedding(&state));
+        let runtime = Some(EmRuntime::from_ui_with_config(&state.em, &grid, config));
+        let applied = AppliedConfig::from_ui(&state);
+
+        state.morph.s = 1.0;
+        let diff = applied.diff(&AppliedConfig::from_ui(&state));
+        let morphed = Grid::new(World::embedding(&state));
+        let stepped = step_em_runtime(runtime, &state, &morphed, config, diff)
+            .expect("a present runtime stays present");
+
+        // V = x over x' = 2x: E = -dV/dx' = -1/2 in orthonormal components.
+        let point = Point {
+            x: 0.5,
+            y: 0.0,
+            z: 0.0,
+        };
+        let electric = stepped.electric_at(point, 0.0);
+        assert!(diff.morph_step_changed);
+        assert!((electric - vector![-0.5, 0.0, 0.0]).norm() < 1.0e-9);
+    }
+
+    #[test]
+    fn apply_diff_rebuilds_geometry_on_time_steps_of_moving_embeddings_only() {
+        let mut state = GridUiState::default();
+        state.scene_time = 1.0;
+        let still = AppliedConfig::from_ui(&state);
+        state.scene_time = 2.0;
+        assert_eq!(
+            still.diff(&AppliedConfig::from_ui(&state)),
+            Default::default()
+        );
+
+        state.coords_sys =
+            SpacialEqs::from_defaults("x*cos(t) - y*sin(t)", "x*sin(t) + y*cos(t)", "z");
+        let moving = AppliedConfig::from_ui(&state);
+        state.scene_time = 2.01;
+        let within_step = moving.diff(&AppliedConfig::from_ui(&state));
+        state.scene_time = 2.5;
+        let step = moving.diff(&AppliedConfig::from_ui(&state));
+
+        assert!(moving.embedding_uses_time);
+        assert_eq!(within_step, Default::default());
+        assert!(step.time_step_changed);
+        assert!(!step.coords_changed);
+        assert!(step.geometry_changed());
+    }
+
+    #[test]
+    fn apply_diff_rereads_only_the_field_on_time_steps_of_moving_fields() {
+        let mut state = GridUiState::default();
+        state.field = SpacialEqs::from_defaults("-y * cos(t)", "x * cos(t)", "0");
+        let moving = AppliedConfig::from_ui(&state);
+        state.scene_time = 0.5;
+        let step = moving.diff(&AppliedConfig::from_ui(&state));
+
+        assert!(moving.field_uses_time && !moving.embedding_uses_time);
+        assert!(step.field_time_step_changed);
+        assert!(step.field_samples_changed());
+        assert!(!step.field_cache_changed());
+        assert!(!step.geometry_changed());
+        assert_eq!(AppliedConfig::from_ui(&state).field_time(), 0.5);
+
+        state.em.enabled = true;
+        assert!(!AppliedConfig::from_ui(&state).field_uses_time);
+    }
+
+    #[test]
+    fn apply_diff_recompiles_the_grid_shader_for_line_coloring() {
+        let mut state = GridUiState::default();
+        let current = AppliedConfig::from_ui(&state);
+        state.line_coloring.quantity = LineQuantity::Curvature;
+        let curvature = current.diff(&AppliedConfig::from_ui(&state));
+
+        state.line_coloring.quantity = LineQuantity::ScalarField;
+        let by_scalar = AppliedConfig::from_ui(&state);
+        state.scalar_field.eq = Parser::default().parse("x*y").unwrap();
+        let scalar_edit = by_scalar.diff(&AppliedConfig::from_ui(&state));
+
+        assert!(curvature.line_coloring_changed);
+        assert!(curvature.grid_shader_changed());
+        assert!(curvature.line_range_changed());
+        assert!(!curvature.geometry_changed());
+        assert!(!curvature.field_cache_changed());
+        assert!(scalar_edit.grid_shader_changed());
+    }
+
     // #[test]
     // fn apply_diff_tracks_em_gauge_selection() {
     //     let current = AppliedConfig::from_ui(&GridUiState::default());
@@ -2,10 +2,21 @@
 
 use super::World;
 use crate::app::applied_config::{AppliedConfig, ApplyDiff};
-use crate::app::coords_sys::CoordsSys;
+use crate::app::coordinate_surfaces::build_coordinate_surfaces;
 use crate::app::em_runtime::EmRuntime;
-use crate::app::field_runtime::RuntimeField;
-use crate::app::ui::GridUiState;
+use crate::app::field_runtime::{pulled_back_field, RuntimeField};
+use crate::app::grid::GridConfig;
+use crate::app::line_coloring::line_range;
+use crate::app::pec_walls::build_wall_mesh;
+use crate::app::region_surfaces::build_region_mesh;
+use crate::app::singularities::analyze_singularities;
+use crate::app::tissot::{build_indicatrices, tissot_glyphs, tissot_report};
+use crate::app::ui::{EmMode, EmUiState, GridUiState, LegendState, LineQuantity, MorphHomotopy};
+use crate::graphics::model::Sphere;
+use crate::render::renderer::SINGULAR_COLOR;
+
+/// Radius of the markers of singular sets that collapse to a point.
+const SINGULAR_MARKER_SIZE: f64 = 0.08;
 
 impl World {
     /// Applies validated UI state to the world and refreshes whichever caches changed.
@@ -22,8 +33,10 @@ impl World {
     ) {
         self.apply_coordinate_changes(&state, &next_config, diff);
         self.apply_field_changes(&state, &next_config, diff);
+        self.apply_atlas_changes(&state, &next_config, diff);
         self.applied_config = next_config;
         self.last_counter = state.apply_counter;
+        self.applied_state = state;
     }
 
     fn apply_coordinate_changes(
@@ -32,13 +45,20 @@ impl World {
         next_config: &AppliedConfig,
         diff: ApplyDiff,
     ) {
-        if diff.coords_changed {
-            let coords = state.coords_sys.clone();
-            let coord_sys = CoordsSys::new(coords.x.eq, coords.y.eq, coords.z.eq);
-            self.grid.set_coordinates(coord_sys);
-            self.renderer
-                .grid_renderer
-                .update_shader_eqs(&next_config.coord_eqs);
+        if diff.embedding_changed() {
+            self.grid.set_coordinates(Self::embedding(state));
+        }
+        if diff.grid_shader_changed() {
+            let (target_glsl, homotopy) = match &next_config.morph {
+                Some(morph) => (&morph.target_glsl, morph.homotopy),
+                None => (&next_config.coord_glsl, MorphHomotopy::Linear),
+            };
+            self.renderer.grid_renderer.update_morph_shader_eqs(
+                &next_config.coord_glsl,
+                target_glsl,
+                homotopy,
+                &next_config.line_coloring,
+            );
         }
 
         if diff.geometry_changed() {
@@ -49,6 +69,16 @@ impl World {
             if self.em_runtime.is_some() && !diff.em_runtime_changed() {
                 self.recompute_cached_em_data();
             }
+            self.rebuild_probe_markers();
+            self.rebuild_singularities(next_config.grid_config);
+            self.rebuild_indicatrices();
+        }
+
+        if diff.line_range_changed() {
+            self.rebuild_line_range(state, next_config.line_coloring.quantity);
+        }
+        if diff.coordinate_surfaces_changed() {
+            self.rebuild_coordinate_surfaces(next_config.surface_axes, next_config.grid_config);
         }
     }
 
@@ -60,25 +90,138 @@ impl World {
     ) {
         if diff.runtime_field_changed() {
             self.field = RuntimeField::from_ui(state, &self.grid);
+            self.pulled_back_field = pulled_back_field(state, &self.grid);
         }
 
         self.normalize_field = next_config.normalize_field;
         self.em_normalize_vectors = state.em.normalize_vectors;
 
         if diff.field_cache_changed() {
-            self.recompute_cached_field_data();
+            self.field_cache.clear();
+        }
+        if diff.field_samples_changed() {
+            self.recompute_cached_field_data(next_config.field_time());
         }
 
         if diff.em_runtime_changed() {
             self.em_runtime = state.em.enabled.then(|| {
                 EmRuntime::from_ui_with_config(&state.em, &self.grid, next_config.grid_config)
             });
-        } else if let Some(runtime) = &mut self.em_runtime {
-            runtime.update_render_controls(&state.em);
+            self.refresh_covariant_report(&state.em);
+            self.clear_probe_histories();
+        } else {
+            if let Some(runtime) = &mut self.em_runtime {
+                runtime.update_render_controls(&state.em);
+            }
+            if diff.em_sources_changed {
+                self.refresh_covariant_report(&state.em);
+            }
         }
 
         if diff.em_render_changed() {
             self.recompute_cached_em_data();
+            self.refresh_gauge_report(&state.em, next_config.grid_config);
         }
+
+        if diff.pec_walls_changed() {
+            self.rebuild_pec_walls(&state.em, next_config.grid_config);
+        }
+        if diff.region_surfaces_changed() {
+            self.rebuild_region_surfaces(&state.em, next_config.grid_config);
+        }
+    }
+
+    /// Runs after the field changes, so a pushed field sees the new runtime field and EM state.
+    fn apply_atlas_changes(
+        &mut self,
+        state: &GridUiState,
+        next_config: &AppliedConfig,
+        diff: ApplyDiff,
+    ) {
+        if diff.charts_changed {
+            let previous = self.applied_config.charts.clone();
+            self.rebuild_charts(&state.atlas.charts, &next_config.charts, &previous);
+        }
+        if diff.chart_overlaps_changed() {
+            self.refresh_chart_overlaps(next_config.grid_config, &next_config.charts);
+        }
+        if diff.pushed_field_changed() {
+            self.rebuild_pushed_field(
+                next_config.grid_config,
+                &next_config.charts,
+                next_config.push_field_to,
+            );
+        }
+    }
+
+    /// Resamples the range the grid lines are colored over and the legend that shows it.
+    pub(super) fn rebuild_line_range(&mut self, state: &GridUiState, quantity: LineQuantity) {
+        let range = line_range(&self.grid, quantity, &state.scalar_field.eq);
+        self.renderer
+            .grid_renderer
+            .set_line_range(range.unwrap_or((0.0, 1.0)));
+        self.line_legend =
+            quantity
+                .legend_kind()
+                .zip(range)
+                .map(|(kind, (min_value, max_value))| LegendState {
+                    kind,
+                    min_value,
+                    max_value,
+                });
+    }
+
+    /// Re-tessellates the constant-coordinate surfaces of the selected axes.
+    pub(super) fn rebuild_coordinate_surfaces(&mut self, axes: [bool; 3], config: GridConfig) {
+        let mesh = build_coordinate_surfaces(self.grid.get_coords(), &config, axes);
+        self.renderer.surface_renderer.update_mesh(mesh);
+    }
+
+    /// Re-runs the singularity analysis of the current coordinates within `config`.
+    pub(super) fn rebuild_singularities(&mut self, config: GridConfig) {
+        let analysis = analyze_singularities(self.grid.get_coords(), &config);
+        self.renderer.renderer.update_singular_lines(&analysis.mesh);
+        self.singular_markers = analysis
+            .mesh
+            .points
+            .iter()
+            .map(|&point| Sphere::from_rgba(point, SINGULAR_COLOR, SINGULAR_MARKER_SIZE))
+            .collect();
+        self.singularities = analysis.sets;
+    }
+
+    /// Decomposes the metric at the field samples and rebuilds the Tissot glyphs.
+    pub(super) fn rebuild_indicatrices(&mut self) {
+        let geometry = self.grid.get_coords().sample_geometry();
+        self.indicatrices = build_indicatrices(&geometry, &self.field_samples);
+        self.tissot_report = tissot_report(&self.indicatrices);
+        self.tissot_glyphs = tissot_glyphs(&self.indicatrices, self.tissot.radius);
+    }
+
+    /// Re-tessellates the conducting walls; they are only shown while EM is enabled.
+    pub(super) fn rebuild_pec_walls(&mut self, em: &EmUiState, config: GridConfig) {
+        let walls = if em.enabled { em.pec_walls } else { [false; 6] };
+        let mesh = build_wall_mesh(self.grid.get_coords(), &config, walls);
+        self.renderer.renderer.update_walls(mesh);
+    }
+
+    /// Re-extracts conductor and mask surfaces; they are only shown in the modes that use them.
+    pub(super) fn rebuild_region_surfaces(&mut self, em: &EmUiState, config: GridConfig) {
+        let shown = em.enabled && matches!(em.mode, EmMode::Electric | EmMode::Magnetic);
+        let (conductors, mask) = if shown {
+            let conductors = em
+                .conductors
+                .iter()
+                .map(|conductor| conductor.eq.clone())
+                .collect::<Vec<_>>();
+            (conductors, vec![em.domain_mask.eq.clone()])
+        } else {
+            (Vec::new(), Vec::new())
+        };
+        let coords = self.grid.get_coords();
+        self.renderer.renderer.update_regions(
+            build_region_mesh(coords, &config, &conductors),
+            build_region_mesh(coords, &config, &mask),
+        );
     }
 }
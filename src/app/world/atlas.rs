@@ -0,0 +1,119 @@
+//! Overlay charts of the atlas: their grids, pairwise overlaps and the field pushed onto one.
+
+use super::World;
+use crate::app::applied_config::AppliedChart;
+use crate::app::atlas::{chart_overlap, push_field};
+use crate::app::coords_sys::CoordsSys;
+use crate::app::grid::{Grid, GridConfig};
+use crate::app::ui::{ChartUiState, PRIMARY_CHART_NAME};
+use crate::graphics::model::RenderVField;
+use nalgebra::{Vector3, Vector4};
+
+/// One overlay chart: its own grid, drawn in a single color.
+pub(super) struct OverlayChart {
+    pub(super) grid: Grid,
+    pub(super) color: Vector3<f64>,
+}
+
+impl World {
+    /// Brings the overlay grids in line with `applied`, recompiling only the embeddings that
+    /// differ from `previous`.
+    pub(super) fn rebuild_charts(
+        &mut self,
+        charts: &[ChartUiState],
+        applied: &[AppliedChart],
+        previous: &[AppliedChart],
+    ) {
+        self.charts.truncate(applied.len());
+        self.renderer.resize_chart_renderers(applied.len());
+        for (index, (chart, next)) in charts.iter().zip(applied).enumerate() {
+            let color = Vector3::from(next.color.map(f64::from));
+            let embedding_changed = previous
+                .get(index)
+                .is_none_or(|previous| previous.coord_glsl != next.coord_glsl);
+            if index >= self.charts.len() || embedding_changed {
+                let eqs = &chart.coords_sys;
+                let coords = CoordsSys::new(eqs.x.eq.clone(), eqs.y.eq.clone(), eqs.z.eq.clone());
+                self.renderer.update_chart_eqs(index, &next.coord_glsl);
+                if index < self.charts.len() {
+                    self.charts[index].grid.set_coordinates(coords);
+                } else {
+                    self.charts.push(OverlayChart {
+                        grid: Grid::new(coords),
+                        color,
+                    });
+                }
+            }
+            let overlay = &mut self.charts[index];
+            overlay.grid.update_config(&next.grid_config);
+            overlay.color = color;
+        }
+    }
+
+    /// Recounts the overlap of every pair of charts, the primary grid included.
+    pub(super) fn refresh_chart_overlaps(
+        &mut self,
+        primary_config: GridConfig,
+        applied: &[AppliedChart],
+    ) {
+        let mut entries = vec![(PRIMARY_CHART_NAME, self.grid.get_coords(), primary_config)];
+        entries.extend(self.charts.iter().zip(applied).map(|(overlay, chart)| {
+            (
+                chart.name.as_str(),
+                overlay.grid.get_coords(),
+                chart.grid_config,
+            )
+        }));
+        let mut overlaps = Vec::new();
+        for (index, first) in entries.iter().enumerate() {
+            for second in &entries[index + 1..] {
+                overlaps.push(chart_overlap(
+                    [first.0, second.0],
+                    (first.1, &first.2),
+                    (second.1, &second.2),
+                ));
+            }
+        }
+        self.chart_overlaps = overlaps;
+    }
+
+    /// Pushes the Field-tab vector field onto chart `target` through the transition map.
+    ///
+    /// Nothing is pushed while EM is enabled or when the active field is a scalar.
+    pub(super) fn rebuild_pushed_field(
+        &mut self,
+        primary_config: GridConfig,
+        applied: &[AppliedChart],
+        target: Option<usize>,
+    ) {
+        self.pushed_field.clear();
+        if self.em_runtime.is_some() {
+            return;
+        }
+        let (Some(field), Some(index)) = (self.field.as_vector(), target) else {
+            return;
+        };
+        let (Some(overlay), Some(chart)) = (self.charts.get(index), applied.get(index)) else {
+            return;
+        };
+        let color = Vector4::new(overlay.color.x, overlay.color.y, overlay.color.z, 1.0);
+        self.pushed_field = push_field(
+            field,
+            (self.grid.get_coords(), &primary_config),
+            (overlay.grid.get_coords(), &chart.grid_config),
+        )
+        .into_iter()
+        .map(|sample| {
+            let vector = if self.normalize_field {
+                sample
+                    .world_vector
+                    .try_normalize(1.0e-6)
+                    .unwrap_or(sample.world_vector)
+            } else {
+                sample.world_vector
+            };
+            RenderVField::new(sample.world_pos, vector, color)
+        })
+        .collect();
+    }
+}
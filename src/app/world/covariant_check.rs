@@ -0,0 +1,58 @@
+//! Covariant Maxwell check behind the EM tab's Faraday 2-form report.
+
+use super::World;
+use crate::app::em_runtime::{CovariantMaxwellCheck, MaxwellSources};
+use crate::app::field_render::FieldSample;
+use crate::app::ui::{CovariantReport, EmUiState};
+use crate::maths::differential::Form;
+use crate::maths::expr_to_fastexpr4d;
+
+impl World {
+    /// Recomputes the covariant report published to the EM tab.
+    ///
+    /// Runs when the EM runtime is rebuilt or the expected sources change; outside potentials
+    /// mode there is no `F` and the report is cleared.
+    pub(super) fn refresh_covariant_report(&mut self, em: &EmUiState) {
+        let sources = MaxwellSources::from_state(em);
+        let check = self.em_runtime.as_ref().and_then(|runtime| {
+            runtime.covariant_maxwell_check(self.grid.get_coords().get_space(), &sources)
+        });
+        self.covariant_report =
+            check.map(|check| build_covariant_report(&check, &self.field_samples, self.em_time));
+    }
+}
+
+fn build_covariant_report(
+    check: &CovariantMaxwellCheck,
+    samples: &[FieldSample],
+    time: f64,
+) -> CovariantReport {
+    CovariantReport {
+        bianchi_symbolic_zero: check.bianchi.is_zero(),
+        max_bianchi_residual: max_residual(&check.bianchi, samples, time),
+        source_symbolic_zero: check.source.is_zero(),
+        max_source_residual: max_residual(&check.source, samples, time),
+        sample_count: samples.len(),
+        sample_time: time,
+    }
+}
+
+/// Largest absolute component of `form` over the samples, ignoring non-finite evaluations.
+fn max_residual(form: &Form, samples: &[FieldSample], time: f64) -> f64 {
+    let components = form
+        .exprs
+        .iter()
+        .cloned()
+        .map(expr_to_fastexpr4d)
+        .collect::<Vec<_>>();
+    samples
+        .iter()
+        .flat_map(|sample| {
+            let position = sample.abstract_pos;
+            components
+                .iter()
+                .map(move |component| component(position.x, position.y, position.z, time).abs())
+        })
+        .filter(|value| value.is_finite())
+        .fold(0.0, f64::max)
+}
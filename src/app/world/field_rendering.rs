@@ -2,9 +2,9 @@
 
 use super::{World, SPHERE_SIZE};
 use crate::app::field_render::{
-    build_scalar_render, build_scalar_render_with_kind, build_vector_render,
-    build_vector_render_with_color, EmRenderCache, FieldRenderCache, VectorNormalization,
-    VectorRenderConfig,
+    build_polarization_render, build_scalar_render, build_scalar_render_with_kind,
+    build_vector_render, build_vector_render_with_color, EmRenderCache, FieldRenderCache,
+    VectorNormalization, VectorRenderConfig,
 };
 use crate::app::field_runtime::RuntimeField;
 use crate::app::ui::LegendKind;
@@ -16,16 +16,21 @@ use nalgebra::Vector4;
 const ELECTRIC_COLOR: Vector4<f64> = Vector4::new(0.0, 0.78, 1.0, 1.0);
 const MAGNETIC_COLOR: Vector4<f64> = Vector4::new(1.0, 0.0, 0.78, 1.0);
 const VECTOR_POTENTIAL_COLOR: Vector4<f64> = Vector4::new(1.0, 0.86, 0.0, 1.0);
+const POLARIZATION_COLOR: Vector4<f64> = Vector4::new(0.55, 0.9, 1.0, 1.0);
 
 impl World {
-    /// Recomputes cached scalar values or vector components for every sampled point.
+    /// Recomputes cached scalar values or vector components for every sampled point at `time`.
     ///
     /// This is one of the more expensive CPU-side rebuild steps: the field is evaluated in
     /// abstract coordinates, then expanded through the cached tangent basis stored in each
     /// `FieldSample`. The result is retained so tangent/view-only changes can rebuild renderables
-    /// without reevaluating the field function itself.
-    pub(super) fn recompute_cached_field_data(&mut self) {
-        self.field_cache = FieldRenderCache::from_field(&self.field, &self.field_samples);
+    /// without reevaluating the field function itself, and kept per time so that a moving field
+    /// revisiting a step reuses it. Callers clear `field_cache` first when the field or the
+    /// samples changed.
+    pub(super) fn recompute_cached_field_data(&mut self, time: f64) {
+        self.field.set_time(time);
+        self.field_cache
+            .select(&self.field, &self.field_samples, time);
     }
 
     pub(super) fn recompute_cached_em_data(&mut self) {
@@ -53,8 +58,8 @@ impl World {
             return;
         }
 
-        match (&self.field, &self.field_cache) {
-            (RuntimeField::Scalar(_), FieldRenderCache::Scalar(values)) => {
+        match (&self.field, self.field_cache.current()) {
+            (RuntimeField::Scalar(_), Some(FieldRenderCache::Scalar(values))) => {
                 let render = build_scalar_render(
                     &self.field_samples,
                     values,
@@ -66,10 +71,10 @@ impl World {
             }
             (
                 RuntimeField::Vector(_field),
-                FieldRenderCache::Vector {
+                Some(FieldRenderCache::Vector {
                     components,
                     world_vectors,
-                },
+                }),
             ) => {
                 self.render_field = build_vector_render(
                     &self.field_samples,
@@ -101,28 +106,50 @@ impl World {
             return;
         };
         let layers = runtime.active_layers();
+        // A boosted observer sees each sampled event at its primed position.
+        let samples = cache
+            .observer_frame
+            .as_ref()
+            .map_or(self.field_samples.as_slice(), |frame| {
+                frame.samples.as_slice()
+            });
 
-        if layers.scalar_potential {
-            let Some(phi) = &cache.phi else {
-                return;
-            };
+        // Scalar layers share the sample spheres and the legend, so only the first visible one
+        // is drawn: V, then |E|, then arg(E).
+        let scalar_layer = [
+            (LegendKind::ScalarPotential, &cache.phi),
+            (LegendKind::PhasorAmplitude, &cache.amplitude),
+            (LegendKind::PhasorPhase, &cache.phase),
+        ]
+        .into_iter()
+        .find_map(|(kind, values)| values.as_ref().map(|values| (kind, values)));
+        if let Some((legend_kind, values)) = scalar_layer {
             let scalar_render = build_scalar_render_with_kind(
-                &self.field_samples,
-                phi,
+                samples,
+                values,
                 &self.tangent_space,
                 SPHERE_SIZE,
-                LegendKind::ScalarPotential,
+                legend_kind,
             );
             self.render_form_samples = scalar_render.samples;
             self.legend = scalar_render.legend;
         }
+        if let Some(polarization) = &cache.polarization {
+            self.render_form_samples.extend(build_polarization_render(
+                samples,
+                polarization,
+                &self.tangent_space,
+                SPHERE_SIZE * 0.25,
+                POLARIZATION_COLOR,
+            ));
+        }
 
         if layers.electric {
             let Some(electric) = &cache.electric else {
                 return;
             };
             self.render_field.extend(build_vector_render_with_color(
-                &self.field_samples,
+                samples,
                 &electric.components,
                 &electric.world_vectors,
                 &self.tangent_space,
@@ -137,7 +164,7 @@ impl World {
                 return;
             };
             self.render_field.extend(build_vector_render_with_color(
-                &self.field_samples,
+                samples,
                 &magnetic.components,
                 &magnetic.world_vectors,
                 &self.tangent_space,
@@ -152,7 +179,7 @@ impl World {
                 return;
             };
             self.render_field.extend(build_vector_render_with_color(
-                &self.field_samples,
+                samples,
                 &vector_potential.components,
                 &vector_potential.world_vectors,
                 &self.tangent_space,
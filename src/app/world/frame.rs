@@ -2,10 +2,16 @@
 
 use super::{World, SPHERE_SIZE};
 use crate::app::applied_config::AppliedConfig;
+use crate::app::em_timeline;
+use crate::app::morph;
+use crate::app::moving_frame;
+use crate::app::tissot::tissot_glyphs;
+use crate::app::ui::GridUiState;
 use crate::toolbox::camera::Camera;
 use crate::toolbox::color::WHITE;
 use crate::toolbox::input::Input;
 use crate::toolbox::opengl::display_manager::DisplayManager;
+use std::time::Instant;
 
 impl World {
     /// Advances the world by one frame.
@@ -29,14 +35,26 @@ impl World {
         let render_state_before = self.tangent_space.render_state();
         let mut needs_render_rebuild = false;
 
-        if let Some(state) = self.take_pending_apply_state(camera) {
+        let pending_state = self.take_pending_apply_state(camera);
+        self.morph_clock.advance(dt);
+        let mut em_samples_moved = self.advance_em_time(dt);
+        self.renderer.grid_renderer.set_morph_s(self.morph_clock.s);
+        self.renderer.grid_renderer.set_time(self.em_time);
+        if let Some(state) = pending_state {
             let next_config = AppliedConfig::from_ui(&state);
             let diff = self.applied_config.diff(&next_config);
             self.apply_state(state, next_config, diff);
             needs_render_rebuild = true;
+        } else if let Some(state) = self.take_embedding_step_state() {
+            let started = Instant::now();
+            let next_config = AppliedConfig::from_ui(&state);
+            let diff = self.applied_config.diff(&next_config);
+            em_samples_moved |= self.apply_step(state, next_config, diff);
+            self.step_throttle.record(started);
+            needs_render_rebuild = true;
         }
 
-        if self.advance_em_time(dt) {
+        if em_samples_moved && self.refresh_em_samples() {
             needs_render_rebuild = true;
         }
 
@@ -47,8 +65,11 @@ impl World {
             display_manager,
             &self.grid_world,
             self.grid.get_coords(),
+            &self.applied_config.grid_config.bounds(),
             self.renderer.projection,
         );
+        self.update_probes(input);
+        self.update_inspector(input);
         //self.renderer.set_zoom_mix(self.tangent_space.scene_mix()); comment for now do not remove it!!
         if needs_render_rebuild || self.tangent_space.render_state() != render_state_before {
             self.rebuild_render_field();
@@ -71,13 +92,32 @@ impl World {
             camera,
             &self.sphere,
             &self.tangent_space.scene_transform(),
-        )
+        );
+        if self.tangent_space.show_grid() {
+            self.renderer.render_markers(&self.probe_markers, camera);
+            if self.show_singularities {
+                self.renderer
+                    .render_singularities(&self.singular_markers, camera);
+            }
+            // The glyphs are built at world positions, so like the charts below they only show
+            // in world space.
+            if self.tissot.show && self.tangent_space.scene_mix() == 0.0 {
+                self.renderer.render_markers(&self.tissot_glyphs, camera);
+            }
+            // Overlay charts have no tangent view of their own, so they only show in world space.
+            if self.show_charts && self.tangent_space.scene_mix() == 0.0 {
+                self.renderer.render_charts(
+                    self.charts
+                        .iter()
+                        .map(|overlay| (&overlay.grid, overlay.color)),
+                    &self.pushed_field,
+                    camera,
+                );
+            }
+        }
     }
 
-    fn take_pending_apply_state(
-        &mut self,
-        camera: &mut Camera,
-    ) -> Option<crate::app::ui::GridUiState> {
+    fn take_pending_apply_state(&mut self, camera: &mut Camera) -> Option<GridUiState> {
         let mut pending_state = self.deferred_apply_state.take();
         {
             let shared = self.shared_ui_state.lock().unwrap();
@@ -85,6 +125,16 @@ impl World {
                 .set_geometric_local_scale(shared.tangent_scale);
             self.tangent_space
                 .set_geometric_arrow_scale(shared.geometric_arrow_scale);
+            self.renderer
+                .surface_renderer
+                .set_opacity(shared.surface_opacity);
+            self.show_singularities = shared.show_singularities;
+            if shared.tissot.radius != self.tissot.radius {
+                self.tissot_glyphs = tissot_glyphs(&self.indicatrices, shared.tissot.radius);
+            }
+            self.tissot = shared.tissot;
+            self.show_charts = shared.atlas.show_charts;
+            self.morph_clock.read_live(&shared.morph);
             if pending_state.is_none() && self.last_counter != shared.apply_counter {
                 pending_state = Some(shared.clone());
             }
@@ -99,39 +149,75 @@ impl World {
         pending_state
     }
 
-    fn advance_em_time(&mut self, dt: f64) -> bool {
-        if self.em_runtime.is_none() {
-            return false;
+    /// Returns the last applied state at the new `s` or `t` once the morph, or a moving
+    /// embedding or Field-tab field, reaches another step.
+    ///
+    /// Like an Apply, a step waits while tangent mode is active; the grid shader keeps following
+    /// the live `s` and `t` in the meantime. Steps also wait for the step throttle, so a slow
+    /// rebuild skips steps rather than stalling every frame.
+    fn take_embedding_step_state(&self) -> Option<GridUiState> {
+        if self.tangent_space.should_defer_apply() || !self.step_throttle.ready() {
+            return None;
+        }
+        let applied = &self.applied_state;
+        let morph_step = applied.morph.enabled
+            && morph::snap(self.morph_clock.s) != morph::snap(applied.morph.s);
+        let time = self.em_time;
+        let time_step = self.applied_config.uses_time()
+            && moving_frame::snap_time(time) != moving_frame::snap_time(applied.scene_time);
+        if !morph_step && !time_step {
+            return None;
+        }
+        let mut state = applied.clone();
+        state.morph.s = self.morph_clock.s;
+        if time_step {
+            state.scene_time = time;
         }
+        Some(state)
+    }
 
-        let (running, time_scale, reset_counter) = {
+    /// Moves the timeline time by one frame of playback, or jumps to a reset or seek request,
+    /// and returns whether it changed.
+    ///
+    /// The timeline runs whether EM is enabled or not, since its time is also the `t` of moving
+    /// embeddings and fields. Seeks only move the clock: the runtime is kept and the render cache
+    /// is rebuilt at the new time, so revisiting a snapped time reuses its prepared Maxwell
+    /// sources.
+    fn advance_em_time(&mut self, dt: f64) -> bool {
+        let (running, time_scale, reset_counter, timeline) = {
             let shared = self.shared_ui_state.lock().unwrap();
             (
                 shared.em.running,
                 shared.em.time_scale,
                 shared.em.reset_counter,
+                shared.em.timeline.clone(),
             )
         };
 
-        let mut changed = false;
+        let previous_time = self.em_time;
         if reset_counter != self.last_em_reset_counter {
-            self.em_time = 0.0;
+            self.em_time = em_timeline::reset_time(&timeline);
             self.last_em_reset_counter = reset_counter;
-            changed = true;
         }
-        if running && time_scale != 0.0 {
-            self.em_time += dt * time_scale;
-            changed = true;
+        if timeline.seek_counter != self.last_em_seek_counter {
+            self.em_time = em_timeline::seek_target(&timeline);
+            self.last_em_seek_counter = timeline.seek_counter;
+        } else if running && time_scale != 0.0 {
+            self.em_time = em_timeline::advance_time(&timeline, time_scale, self.em_time, dt);
         }
+        self.em_time.to_bits() != previous_time.to_bits()
+    }
 
+    /// Resamples the EM render cache at the current time and samples, if any layer is shown.
+    fn refresh_em_samples(&mut self) -> bool {
         let has_visible_layers = self
             .em_runtime
             .as_ref()
             .is_some_and(|runtime| runtime.active_layers().any_visible());
-        if changed && has_visible_layers {
+        if has_visible_layers {
             self.recompute_cached_em_data();
         }
-        changed && has_visible_layers
+        has_visible_layers
     }
 
     /// Refreshes the marker sphere that highlights the hovered or anchored sample.
@@ -152,11 +238,58 @@ impl World {
 
     /// Publishes overlay metadata back to the shared UI state.
     ///
-    /// The shared lock is taken only for the scalar legend payload; renderables themselves remain
-    /// owned by the main thread. This keeps the UI thread informed without turning the mutex into
-    /// a transport for large scene structures.
-    fn sync_overlay_state(&self) {
+    /// The shared lock is taken only for the scalar and line legends, EM reports, EM time,
+    /// observer time span, probe histories, singular sets, the pulled-back field, the Tissot
+    /// report, chart overlaps, the swept morph `s`, the scene time, and the inspector report;
+    /// renderables remain owned by the main thread. Probe histories are only copied when one of
+    /// them gained or lost samples since the last publish.
+    /// This keeps the UI thread informed without turning the mutex into a transport for large
+    /// scene structures.
+    fn sync_overlay_state(&mut self) {
         let mut shared = self.shared_ui_state.lock().unwrap();
         shared.legend = self.legend;
+        shared.line_legend = self.line_legend;
+        if shared.gauge_report != self.gauge_report {
+            shared.gauge_report = self.gauge_report.clone();
+        }
+        if shared.covariant_report != self.covariant_report {
+            shared.covariant_report = self.covariant_report.clone();
+        }
+        shared.observer_time_range = self
+            .em_cache
+            .as_ref()
+            .and_then(|cache| cache.observer_frame.as_ref())
+            .map(|frame| frame.time_range);
+        shared.em_time = self.em_time;
+        let probes = self
+            .probe_recorders
+            .iter()
+            .map(|recorder| (recorder.marker().id, recorder.revision()))
+            .collect::<Vec<_>>();
+        if probes != self.published_probes {
+            shared.probe_traces = self
+                .probe_recorders
+                .iter()
+                .map(|recorder| recorder.trace())
+                .collect();
+            self.published_probes = probes;
+        }
+        if shared.singularities != self.singularities {
+            shared.singularities = self.singularities.clone();
+        }
+        if shared.pulled_back_field != self.pulled_back_field {
+            shared.pulled_back_field = self.pulled_back_field.clone();
+        }
+        if shared.tissot_report != self.tissot_report {
+            shared.tissot_report = self.tissot_report.clone();
+        }
+        if shared.chart_overlaps != self.chart_overlaps {
+            shared.chart_overlaps = self.chart_overlaps.clone();
+        }
+        self.morph_clock.publish(&mut shared.morph);
+        shared.scene_time = self.em_time;
+        if shared.inspector != self.inspector_report {
+            shared.inspector = self.inspector_report.clone();
+        }
     }
 }
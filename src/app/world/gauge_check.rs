@@ -0,0 +1,116 @@
+//! Numerical gauge-invariance check behind the EM gauge-transformation panel.
+
+use super::World;
+use crate::app::em_runtime::EmRuntime;
+use crate::app::field_render::{EmRenderCache, FieldSample};
+use crate::app::grid::GridConfig;
+use crate::app::ui::{EmMode, EmUiState, EqRender, GaugeReport};
+use crate::maths::Expr;
+use mathhook_core::formatter::simple::SimpleContext;
+use mathhook_core::{SimpleFormatter, Simplify};
+
+/// Offsets from the scene time at which both gauges are compared.
+///
+/// Uneven, so a field periodic in `t` does not line the later checks up with the first one.
+const GAUGE_CHECK_TIME_OFFSETS: [f64; 3] = [0.0, 0.37, 1.3];
+
+impl World {
+    /// Recomputes the gauge report published to the EM tab.
+    ///
+    /// The live runtime already carries the gauge function, so only the untransformed runtime is
+    /// built here. Both are compared through the render cache, so the report covers the layers,
+    /// observer boost and normalization on screen; call it after `recompute_cached_em_data`.
+    pub(super) fn refresh_gauge_report(&mut self, em: &EmUiState, grid_config: GridConfig) {
+        self.gauge_report = self.em_runtime.as_ref().and_then(|runtime| {
+            build_gauge_report(
+                em,
+                runtime,
+                self.em_cache.as_ref(),
+                &self.field_samples,
+                self.em_time,
+                |original| EmRuntime::from_ui_with_config(original, &self.grid, grid_config),
+            )
+        });
+    }
+}
+
+/// Samples `E` and `B` with and without the gauge function and compares them.
+///
+/// Returns `None` outside potentials mode or when the gauge function is identically zero.
+fn build_gauge_report(
+    em: &EmUiState,
+    transformed: &EmRuntime,
+    live_cache: Option<&EmRenderCache>,
+    samples: &[FieldSample],
+    time: f64,
+    build_runtime: impl FnOnce(&EmUiState) -> EmRuntime,
+) -> Option<GaugeReport> {
+    if !em.enabled || em.mode != EmMode::Potentials || em.gauge_function.eq.simplify().is_zero() {
+        return None;
+    }
+
+    let mut original_state = em.clone();
+    original_state.gauge_function = EqRender::new(Expr::number(0.0), "0".to_string());
+    let original = build_runtime(&original_state);
+
+    let sample_times = GAUGE_CHECK_TIME_OFFSETS.map(|offset| time + offset);
+    let (electric_deviation, magnetic_deviation) = sample_times
+        .iter()
+        .map(|&sample_time| {
+            let rebuilt;
+            let transformed_cache = match live_cache {
+                Some(cache) if sample_time == time => cache,
+                _ => {
+                    rebuilt = EmRenderCache::from_runtime(
+                        transformed,
+                        samples,
+                        sample_time,
+                        em.normalize_vectors,
+                    );
+                    &rebuilt
+                }
+            };
+            EmRenderCache::from_runtime(&original, samples, sample_time, em.normalize_vectors)
+                .max_field_deviation(transformed_cache)
+        })
+        .fold(
+            (None, None),
+            |(electric, magnetic), (next_electric, next_magnetic)| {
+                (
+                    max_deviation(electric, next_electric),
+                    max_deviation(magnetic, next_magnetic),
+                )
+            },
+        );
+    let (phi, vector_potential) = transformed.gauge_transformed_potentials()?;
+    let context = SimpleContext::default();
+
+    Some(GaugeReport {
+        original_phi: em.phi.eq_str.clone(),
+        original_vector_potential: [
+            em.vector_potential.x.eq_str.clone(),
+            em.vector_potential.y.eq_str.clone(),
+            em.vector_potential.z.eq_str.clone(),
+        ],
+        transformed_phi: format_expr(phi, &context),
+        transformed_vector_potential: vector_potential
+            .each_ref()
+            .map(|expr| format_expr(expr, &context)),
+        max_electric_deviation: electric_deviation,
+        max_magnetic_deviation: magnetic_deviation,
+        sample_count: samples.len(),
+        sample_times: sample_times.to_vec(),
+    })
+}
+
+fn max_deviation(lhs: Option<f64>, rhs: Option<f64>) -> Option<f64> {
+    match (lhs, rhs) {
+        (Some(lhs), Some(rhs)) => Some(lhs.max(rhs)),
+        (lhs, rhs) => lhs.or(rhs),
+    }
+}
+
+fn format_expr(expr: &Expr, context: &SimpleContext) -> String {
+    expr.to_simple(context)
+        .unwrap_or_else(|_| "<unprintable>".to_string())
+}
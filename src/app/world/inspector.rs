@@ -0,0 +1,79 @@
+//! Point-inspector picking and evaluation for `World`.
+
+use super::World;
+use crate::app::coords_sys::InverseFailure;
+use crate::app::inspector::inspect_point;
+use crate::app::ui::InspectorReport;
+use crate::toolbox::input::Input;
+use glfw::MouseButton;
+use nalgebra::Vector3;
+
+/// Everything an inspector report is evaluated from: the point, the EM time, and the Apply or
+/// step that built the coordinates, field and EM runtime.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(super) struct InspectorKey {
+    position: [u64; 3],
+    em_time: u64,
+    apply_counter: u64,
+    morph_s: u64,
+    scene_time: u64,
+}
+
+impl World {
+    /// Pins the hovered sample on a right click, or unpins on a right click over empty space, and
+    /// evaluates the inspector report while the window is open.
+    ///
+    /// The pinned sample wins over the hovered one, so the window keeps its values while the
+    /// mouse moves on. Nothing is evaluated while the inspector is closed, and a report is only
+    /// re-evaluated once its `InspectorKey` changes.
+    pub(super) fn update_inspector(&mut self, input: &Input) {
+        if input.is_mouse_button_just_pressed(MouseButton::Right) {
+            self.inspected = self.hovered_pick();
+        }
+        let open = self.shared_ui_state.lock().unwrap().inspector_open;
+        let picked = open
+            .then(|| self.inspected.or_else(|| self.hovered_pick()))
+            .flatten();
+        let Some((position, failure)) = picked else {
+            self.inspector_report = None;
+            self.inspector_key = None;
+            return;
+        };
+
+        let pinned = self.inspected.is_some();
+        let key = self.inspector_key(position);
+        if self.inspector_key != Some(key) || self.inspector_report.is_none() {
+            self.inspector_report = Some(inspect_point(
+                self.grid.get_coords(),
+                &self.field,
+                self.em_runtime
+                    .as_ref()
+                    .map(|runtime| (runtime, self.em_time)),
+                position,
+                pinned,
+            ));
+            self.inspector_key = Some(key);
+        }
+        if let Some(report) = &mut self.inspector_report {
+            report.pinned = pinned;
+            report.snapped_because = failure.map(|failure| failure.describe());
+        }
+    }
+
+    fn inspector_key(&self, position: Vector3<f64>) -> InspectorKey {
+        InspectorKey {
+            position: [position.x, position.y, position.z].map(f64::to_bits),
+            em_time: self.em_time.to_bits(),
+            apply_counter: self.last_counter,
+            morph_s: self.applied_state.morph.s.to_bits(),
+            scene_time: self.applied_state.scene_time.to_bits(),
+        }
+    }
+
+    /// The hovered abstract position, exact unless the inverse failed and left it snapped.
+    fn hovered_pick(&self) -> Option<(Vector3<f64>, Option<InverseFailure>)> {
+        self.tangent_space
+            .hovered_abstract_position()
+            .map(|position| (position, self.tangent_space.hover_failure()))
+    }
+}
@@ -0,0 +1,90 @@
+//! Probe picking, recording, and marker spheres for `World`.
+
+use super::World;
+use crate::app::probes::{probe_values, ProbeRecorder};
+use crate::app::ui::ProbeMarker;
+use crate::graphics::model::Sphere;
+use crate::toolbox::color::Color;
+use crate::toolbox::input::Input;
+use glfw::Key;
+use nalgebra::Vector3;
+
+const PROBE_MARKER_SIZE: f64 = 0.14;
+
+impl World {
+    /// Picks the hovered grid sample on `P`, follows the shared probe list, and records one
+    /// sample per probe at the current EM time.
+    ///
+    /// Probes are live state rather than applied configuration, so the list is read under the
+    /// same short lock every frame instead of waiting for the apply counter.
+    pub(super) fn update_probes(&mut self, input: &Input) {
+        let markers = {
+            let mut shared = self.shared_ui_state.lock().unwrap();
+            if input.is_key_just_pressed(Key::P) {
+                if let Some(position) = self.tangent_space.hovered_abstract_position() {
+                    shared.probes.add(position.into());
+                }
+            }
+            shared.probes.markers.clone()
+        };
+        self.sync_probe_recorders(markers);
+
+        if let Some(runtime) = &self.em_runtime {
+            for recorder in &mut self.probe_recorders {
+                let values = probe_values(runtime, recorder.marker().position, self.em_time);
+                recorder.push(self.em_time, values);
+            }
+        }
+    }
+
+    /// Drops every recorded history, e.g. after the EM fields were rebuilt.
+    pub(super) fn clear_probe_histories(&mut self) {
+        self.probe_recorders
+            .iter_mut()
+            .for_each(ProbeRecorder::clear);
+    }
+
+    /// Places the marker spheres through the current coordinate embedding.
+    pub(super) fn rebuild_probe_markers(&mut self) {
+        let coords = self.grid.get_coords();
+        self.probe_markers = self
+            .probe_recorders
+            .iter()
+            .map(|recorder| {
+                let marker = recorder.marker();
+                let [red, green, blue] = marker.color();
+                Sphere::new(
+                    coords.eval_position(Vector3::from(marker.position)),
+                    Color::new(red, green, blue, 1.0),
+                    PROBE_MARKER_SIZE,
+                )
+            })
+            .collect();
+    }
+
+    /// Keeps recorders of markers that are still listed and starts empty ones for new markers.
+    fn sync_probe_recorders(&mut self, markers: Vec<ProbeMarker>) {
+        let unchanged = self.probe_recorders.len() == markers.len()
+            && self
+                .probe_recorders
+                .iter()
+                .zip(&markers)
+                .all(|(recorder, marker)| recorder.marker() == *marker);
+        if unchanged {
+            return;
+        }
+
+        let mut previous = std::mem::take(&mut self.probe_recorders);
+        self.probe_recorders = markers
+            .into_iter()
+            .map(|marker| {
+                previous
+                    .iter()
+                    .position(|recorder| recorder.marker() == marker)
+                    .map(|index| previous.swap_remove(index))
+                    .unwrap_or_else(|| ProbeRecorder::new(marker))
+            })
+            .collect();
+        self.rebuild_probe_markers();
+    }
+}
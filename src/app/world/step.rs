@@ -0,0 +1,77 @@
+//! Morph and time steps: the CPU-side refresh between two Applies.
+
+use super::World;
+use crate::app::applied_config::{AppliedConfig, ApplyDiff};
+use crate::app::em_runtime::EmRuntime;
+use crate::app::field_runtime::RuntimeField;
+use crate::app::grid::{Grid, GridConfig};
+use crate::app::ui::GridUiState;
+
+impl World {
+    /// Moves the world to the `s` and `t` of `state`, which differs from the applied state in
+    /// nothing else, and returns whether the EM samples moved with the embedding.
+    ///
+    /// Only what the step moves is rebuilt: the CPU-side embedding with its samples, the field
+    /// read at them, and the EM runtime, whose space and solver cells follow the embedding on
+    /// morph and time steps alike. Its rebuild counts towards the step throttle, so a slow solve
+    /// skips steps. Probe histories carry on. Gauge and covariant reports, singular
+    /// sets, Tissot glyphs, coordinate surfaces, chart overlaps and the pulled-back field are
+    /// Apply-only and stay as the last Apply left them.
+    pub(super) fn apply_step(
+        &mut self,
+        state: GridUiState,
+        next_config: AppliedConfig,
+        diff: ApplyDiff,
+    ) -> bool {
+        if diff.embedding_changed() {
+            self.grid.set_coordinates(Self::embedding(&state));
+            self.grid.update_config(&next_config.grid_config);
+            let (field_samples, grid_samples) = Self::build_grid_cache(&self.grid);
+            self.field_samples = field_samples;
+            self.grid_world.replace_samples(grid_samples);
+            self.field = RuntimeField::from_ui(&state, &self.grid);
+            self.field_cache.clear();
+            self.rebuild_probe_markers();
+            self.rebuild_line_range(&state, next_config.line_coloring.quantity);
+        }
+        self.em_runtime = step_em_runtime(
+            self.em_runtime.take(),
+            &state,
+            &self.grid,
+            next_config.grid_config,
+            diff,
+        );
+
+        if diff.field_samples_changed() {
+            self.recompute_cached_field_data(next_config.field_time());
+        }
+        if diff.field_time_step_changed {
+            self.rebuild_pushed_field(
+                next_config.grid_config,
+                &next_config.charts,
+                next_config.push_field_to,
+            );
+        }
+
+        self.applied_config = next_config;
+        self.applied_state = state;
+        diff.embedding_changed() && self.em_runtime.is_some()
+    }
+}
+
+/// Rebuilds a present EM runtime on the stepped `grid` once the embedding moved, and keeps it
+/// otherwise.
+pub(super) fn step_em_runtime(
+    runtime: Option<EmRuntime>,
+    state: &GridUiState,
+    grid: &Grid,
+    grid_config: GridConfig,
+    diff: ApplyDiff,
+) -> Option<EmRuntime> {
+    match runtime {
+        Some(_) if diff.embedding_changed() => {
+            Some(EmRuntime::from_ui_with_config(&state.em, grid, grid_config))
+        }
+        runtime => runtime,
+    }
+}
@@ -2,7 +2,7 @@
 
 use crate::toolbox::color::Color;
 use crate::toolbox::opengl::vao::VAO;
-use nalgebra::{Matrix4, Rotation3, Translation3, UnitQuaternion, Vector3, Vector4};
+use nalgebra::{Matrix3, Matrix4, Rotation3, Translation3, UnitQuaternion, Vector3, Vector4};
 use std::f64::consts::PI;
 
 #[derive(PartialEq)]
@@ -97,6 +97,16 @@ impl Sphere {
         )
     }
 
+    /// Creates an ellipsoid centered on `position` whose semi-axes are the columns of `axes`.
+    pub fn from_axes(position: Vector3<f64>, axes: Matrix3<f64>, rgba: Vector4<f64>) -> Self {
+        let translation = Translation3::from(position);
+        Self {
+            position,
+            color: Color::new(rgba.x as f32, rgba.y as f32, rgba.z as f32, rgba.w as f32),
+            transformation: translation.to_homogeneous() * axes.to_homogeneous(),
+        }
+    }
+
     /// Returns the current transformation matrix.
     pub fn get_transformation_matrix(&self) -> &Matrix4<f64> {
         &self.transformation
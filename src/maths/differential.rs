@@ -1,15 +1,19 @@
 #![allow(unused)]
 //! Differential-form primitives used by scalar, vector, curl, and dual tangent rendering.
 //!
-//! Component ordering is fixed throughout this module:
-//! - 0-forms and 3-forms store one scalar expression.
-//! - 1-forms store `[dx, dy, dz]` components.
-//! - 2-forms store `[dx^dy, dy^dz, dz^dx]` components.
+//! A form lives over an ordered list of coordinate `Variables` (`x, y, z` unless stated
+//! otherwise) and stores one component per basis element of its degree:
+//! - 0-forms and top-degree forms store one scalar expression.
+//! - 1-forms store `[dx^0, dx^1, ...]` components in variable order.
+//! - Other degrees store lexicographically ordered wedges, e.g. `[dt^dx, dt^dy, dt^dz, dx^dy,
+//!   dx^dz, dy^dz]` for 2-forms over `(t, x, y, z)`.
+//! - 3D 2-forms keep the cyclic `[dx^dy, dy^dz, dz^dx]` ordering, so the Hodge star maps them
+//!   to 1-forms without sign changes.
 //!
 //! Forms also carry the basis they are expressed in. `Natural` means the coordinate coframe,
 //! while `Orthonormal` means the local orthonormal tangent coframe derived from `Space`.
 
-use crate::maths::space::{Metric, Space};
+use crate::maths::space::{Metric, Space, Variables};
 use crate::maths::{derivate, Expr, ExternalDerivative, Hodge};
 use crate::toolbox::logging::LOGGER;
 use mathhook::prelude::expr;
@@ -28,6 +32,7 @@ pub struct Form {
     pub exprs: Vec<Expr>,
     n_forms: usize,
     basis: FormBasis,
+    variables: Variables,
 }
 
 impl Form {
@@ -43,15 +48,41 @@ impl Form {
         Self::new_in_basis(exprs, n_forms, FormBasis::Orthonormal)
     }
 
-    /// Builds a differential form in the supplied basis.
+    /// Builds a differential form over `x, y, z` in the supplied basis.
     pub fn new_in_basis(exprs: Vec<Expr>, n_forms: usize, basis: FormBasis) -> Self {
+        Self::new_over(Variables::xyz(), exprs, n_forms, basis)
+    }
+
+    /// Builds a differential form over arbitrary coordinate variables.
+    ///
+    /// `exprs` must hold one component per basis element, in the order returned by
+    /// `basis_elements`.
+    pub fn new_over(
+        variables: Variables,
+        exprs: Vec<Expr>,
+        n_forms: usize,
+        basis: FormBasis,
+    ) -> Self {
+        debug_assert_eq!(
+            exprs.len(),
+            basis_elements(variables.dim(), n_forms).len(),
+            "{n_forms}-form over {} variables has the wrong component count",
+            variables.dim()
+        );
         Self {
             exprs,
             n_forms,
             basis,
+            variables,
         }
     }
 
+    /// Builds the zero form of the given degree.
+    pub fn zero(variables: Variables, n_forms: usize, basis: FormBasis) -> Self {
+        let count = basis_elements(variables.dim(), n_forms).len();
+        Self::new_over(variables, vec![Expr::number(0.0); count], n_forms, basis)
+    }
+
     /// Returns the coframe basis currently attached to this form.
     pub fn basis(&self) -> FormBasis {
         self.basis
@@ -63,6 +94,16 @@ impl Form {
         self
     }
 
+    /// Returns the coordinate variables this form is expressed over.
+    pub fn variables(&self) -> &Variables {
+        &self.variables
+    }
+
+    /// Returns the dimension of the underlying space.
+    pub fn dim(&self) -> usize {
+        self.variables.dim()
+    }
+
     /// Panics if this form is not expressed in the expected coframe.
     fn expect_basis(&self, expected: FormBasis, operation: &str) {
         if self.basis != expected {
@@ -75,82 +116,58 @@ impl Form {
 
     /// Computes the symmetric square terms of a 1-form.
     ///
-    /// The result is ordered for metric construction as
-    /// `[dx^2, 2 dx dy, dy^2, 2 dx dz, 2 dy dz, dz^2]`.
+    /// The result is packed column by column over the upper triangle for metric construction,
+    /// which in 3D reads `[dx^2, 2 dx dy, dy^2, 2 dx dz, 2 dy dz, dz^2]`.
     pub fn square(&self) -> Vec<Expr> {
         if self.n_forms != 1 {
             panic!("Square only works for 1-form")
         }
-        let mut out = Vec::with_capacity(6);
+        let dim = self.dim();
         let two = expr!(2);
-        out.push(Expr::pow(self.exprs[0].clone(), two.clone()));
-        out.push(
-            self.exprs[0]
-                .clone()
-                .mul(self.exprs[1].clone())
-                .mul(two.clone()),
-        );
-        out.push(Expr::pow(self.exprs[1].clone(), two.clone()));
-        out.push(
-            self.exprs[0]
-                .clone()
-                .mul(self.exprs[2].clone())
-                .mul(two.clone()),
-        );
-        out.push(
-            self.exprs[1]
-                .clone()
-                .mul(self.exprs[2].clone())
-                .mul(two.clone()),
-        );
-        out.push(Expr::pow(self.exprs[2].clone(), two.clone()));
+        let mut out = Vec::with_capacity(dim * (dim + 1) / 2);
+        for column in 0..dim {
+            for row in 0..=column {
+                if row == column {
+                    out.push(Expr::pow(self.exprs[row].clone(), two.clone()));
+                } else {
+                    out.push(
+                        self.exprs[row]
+                            .clone()
+                            .mul(self.exprs[column].clone())
+                            .mul(two.clone()),
+                    );
+                }
+            }
+        }
         out
     }
 
-    /// Transforms a 2-form using the supplied one-form basis conversion matrix.
-    /// The matrix is interpreted as mapping old one-form components into the target basis.
-    /// The resulting antisymmetric 2-form is then projected back into the stored
-    /// `[dx^dy, dy^dz, dz^dx]` ordering.
-    fn transform_two_form(&self, transform: &Matrix, target_basis: FormBasis) -> Form {
-        if self.n_forms != 2 {
-            panic!("2-form transform only works for 2-forms");
-        }
+    /// Transforms this form with a one-form basis conversion matrix.
+    ///
+    /// `transform(row, column)` maps old one-form components into the target basis. A k-form
+    /// component then picks up the k x k minor of that matrix for each source element, which
+    /// reduces to the matrix product for 1-forms and the determinant for top-degree forms, so a
+    /// top-degree component scales by the determinant of the frame change.
+    fn transform(&self, transform: impl Fn(usize, usize) -> Expr, target_basis: FormBasis) -> Form {
+        let dim = self.dim();
+        let elements = basis_elements(dim, self.n_forms);
+        let exprs = elements
+            .iter()
+            .map(|target| {
+                let terms = elements
+                    .iter()
+                    .zip(&self.exprs)
+                    .filter(|(_, component)| !component.is_zero())
+                    .filter_map(|(source, component)| {
+                        let minor = minor_determinant(&transform, target, source)?;
+                        Some(component.clone().mul(minor))
+                    })
+                    .collect::<Vec<_>>();
+                sum_terms(terms)
+            })
+            .collect();
 
-        let minor = |row_a: usize, row_b: usize, col_i: usize, col_j: usize| {
-            transform
-                .get_element(row_a, col_i)
-                .mul(transform.get_element(row_b, col_j))
-                .sub(
-                    transform
-                        .get_element(row_b, col_i)
-                        .mul(transform.get_element(row_a, col_j)),
-                )
-        };
-
-        let transformed_xy = self.exprs[0]
-            .clone()
-            .mul(minor(0, 1, 0, 1))
-            .add(self.exprs[1].clone().mul(minor(0, 1, 1, 2)))
-            .add(self.exprs[2].clone().mul(minor(0, 1, 2, 0)))
-            .simplify();
-        let transformed_yz = self.exprs[0]
-            .clone()
-            .mul(minor(1, 2, 0, 1))
-            .add(self.exprs[1].clone().mul(minor(1, 2, 1, 2)))
-            .add(self.exprs[2].clone().mul(minor(1, 2, 2, 0)))
-            .simplify();
-        let transformed_zx = self.exprs[0]
-            .clone()
-            .mul(minor(2, 0, 0, 1))
-            .add(self.exprs[1].clone().mul(minor(2, 0, 1, 2)))
-            .add(self.exprs[2].clone().mul(minor(2, 0, 2, 0)))
-            .simplify();
-
-        Form::new_in_basis(
-            vec![transformed_xy, transformed_yz, transformed_zx],
-            2,
-            target_basis,
-        )
+        Form::new_over(self.variables.clone(), exprs, self.n_forms, target_basis)
     }
 
     /// Transforms this form from the natural basis into the orthonormal tangent basis.
@@ -159,36 +176,15 @@ impl Form {
     /// `Space`.
     pub fn to_otn_base(&self, space: &Space) -> Form {
         self.expect_basis(FormBasis::Natural, "to_otn_base");
-        match self.n_forms {
-            0 => Form::new_otn(vec![self.exprs[0].clone()], 0),
-            1 => {
-                let nat_to_otn = space.natural_to_otn();
-                let mut new_exprs = Vec::with_capacity(3);
-                for row in 0..3 {
-                    let transformed = self.exprs[0]
-                        .clone()
-                        .mul(nat_to_otn.get_element(row, 0))
-                        .add(self.exprs[1].clone().mul(nat_to_otn.get_element(row, 1)))
-                        .add(self.exprs[2].clone().mul(nat_to_otn.get_element(row, 2)));
-                    new_exprs.push(transformed);
-                }
-                Form::new_otn(new_exprs, 1)
-            }
-            2 => {
-                let nat_to_otn = space.natural_to_otn();
-                self.transform_two_form(&nat_to_otn, FormBasis::Orthonormal)
-            }
-            3 => {
-                let nat_to_otn = space.natural_to_otn();
-                let new_expr = self.exprs[0]
-                    .clone()
-                    .mul(nat_to_otn.get_element(0, 0))
-                    .mul(nat_to_otn.get_element(1, 0))
-                    .mul(nat_to_otn.get_element(2, 0));
-                Form::new_otn(vec![new_expr], 3)
-            }
-            _ => panic!("Unknown number of forms {}", self.n_forms),
+        self.expect_dim(space.dim(), "to_otn_base");
+        if self.n_forms == 0 {
+            return self.clone().with_basis(FormBasis::Orthonormal);
         }
+        let nat_to_otn = space.natural_to_otn();
+        self.transform(
+            |row, column| nat_to_otn.get_element(row, column),
+            FormBasis::Orthonormal,
+        )
     }
 
     /// Transforms this form from the orthonormal tangent basis back into the natural dual
@@ -197,35 +193,39 @@ impl Form {
     /// The transformation mirrors `to_otn_base` using the inverse basis conversion.
     pub fn to_dual_base(&self, space: &Space) -> Form {
         self.expect_basis(FormBasis::Orthonormal, "to_dual_base");
-        match self.n_forms {
-            0 => Form::new(vec![self.exprs[0].clone()], 0),
-            1 => {
-                let otn_to_nat = space.otn_to_natural();
-                let mut new_exprs = Vec::with_capacity(3);
-                for row in 0..3 {
-                    let transformed = self.exprs[0]
-                        .clone()
-                        .mul(otn_to_nat.get_element(row, 0))
-                        .add(self.exprs[1].clone().mul(otn_to_nat.get_element(row, 1)))
-                        .add(self.exprs[2].clone().mul(otn_to_nat.get_element(row, 2)));
-                    new_exprs.push(transformed);
-                }
-                Form::new(new_exprs, 1)
-            }
-            2 => {
-                let otn_to_nat = space.otn_to_natural();
-                self.transform_two_form(&otn_to_nat, FormBasis::Natural)
-            }
-            3 => {
-                let otn_to_nat = space.otn_to_natural();
-                let new_expr = self.exprs[0]
-                    .clone()
-                    .mul(otn_to_nat.get_element(0, 0))
-                    .mul(otn_to_nat.get_element(1, 0))
-                    .mul(otn_to_nat.get_element(2, 0));
-                Form::new(vec![new_expr], 3)
-            }
-            _ => panic!("Unknown number of forms {}", self.n_forms),
+        self.expect_dim(space.dim(), "to_dual_base");
+        if self.n_forms == 0 {
+            return self.clone().with_basis(FormBasis::Natural);
+        }
+        let otn_to_nat = space.otn_to_natural();
+        self.transform(
+            |row, column| otn_to_nat.get_element(row, column),
+            FormBasis::Natural,
+        )
+    }
+
+    /// Changes basis with an explicit one-form conversion, for frames `Space` does not model.
+    ///
+    /// `transform(row, column)` maps old one-form components into the target basis, as
+    /// `Space::natural_to_otn` does.
+    pub fn change_basis(
+        &self,
+        transform: impl Fn(usize, usize) -> Expr,
+        target_basis: FormBasis,
+    ) -> Form {
+        if self.n_forms == 0 {
+            return self.clone().with_basis(target_basis);
+        }
+        self.transform(transform, target_basis)
+    }
+
+    /// Panics if this form does not live in a space of the expected dimension.
+    fn expect_dim(&self, expected: usize, operation: &str) {
+        if self.dim() != expected {
+            panic!(
+                "{operation} expects a form over {expected} variables, got {}",
+                self.dim()
+            );
         }
     }
 
@@ -241,8 +241,7 @@ impl Form {
 
     /// Returns the degree of this differential form.
     ///
-    /// The value ranges from 0 through 3 for the three-dimensional spaces modeled by this
-    /// crate.
+    /// The value ranges from 0 through the dimension of the underlying space.
     pub fn n_forms(&self) -> usize {
         self.n_forms
     }
@@ -255,6 +254,56 @@ impl Form {
         &self.exprs[i]
     }
 
+    /// Returns the component multiplying the wedge of `indices`, in that order.
+    ///
+    /// Out-of-order indices pick up the permutation sign, and repeated indices give zero.
+    pub fn component(&self, indices: &[usize]) -> Expr {
+        match locate_element(&basis_elements(self.dim(), self.n_forms), indices) {
+            Some((position, sign)) => signed(self.exprs[position].clone(), sign),
+            None => Expr::number(0.0),
+        }
+    }
+
+    /// Applies the Hodge star in an orthonormal coframe with the given metric signature.
+    ///
+    /// `signature[i]` is `eta_ii`, `1` or `-1`. With orientation `e0^...^e(n-1)` the star maps
+    /// `e^I` to `sign(I, J) * prod(eta_ii for i in I) * e^J`, where `J` lists the remaining
+    /// indices in increasing order.
+    pub fn hodge_star_otn(&self, signature: &[f64]) -> Form {
+        self.expect_basis(FormBasis::Orthonormal, "hodge_star_otn");
+        self.expect_dim(signature.len(), "hodge_star_otn");
+        let dim = self.dim();
+        let target_degree = dim - self.n_forms;
+        let targets = basis_elements(dim, target_degree);
+        let mut exprs = vec![Vec::new(); targets.len()];
+
+        for (element, component) in basis_elements(dim, self.n_forms).iter().zip(&self.exprs) {
+            let complement = (0..dim)
+                .filter(|index| !element.contains(index))
+                .collect::<Vec<_>>();
+            let ordering = element
+                .iter()
+                .chain(&complement)
+                .copied()
+                .collect::<Vec<_>>();
+            let metric_sign = element
+                .iter()
+                .map(|&index| signature[index])
+                .product::<f64>();
+            let (position, stored_sign) = locate_element(&targets, &complement)
+                .expect("complement of a basis element is a basis element");
+            let sign = permutation_sign(&ordering) * metric_sign * stored_sign;
+            exprs[position].push(signed(component.clone(), sign));
+        }
+
+        Form::new_over(
+            self.variables.clone(),
+            exprs.into_iter().map(sum_terms).collect(),
+            target_degree,
+            FormBasis::Orthonormal,
+        )
+    }
+
     /// Applies the 3D Hodge star in a positively oriented orthonormal coframe.
     ///
     /// This follows the local OTN rules:
@@ -263,27 +312,31 @@ impl Form {
     /// `*(e1^e2^e3) = 1`.
     pub fn hodge_star_otn_3d(&self) -> Form {
         self.expect_basis(FormBasis::Orthonormal, "hodge_star_otn_3d");
-        match self.n_forms {
-            0 => Form::new_otn(vec![self.exprs[0].clone()], 3),
-            1 => Form::new_otn(
-                vec![
-                    self.exprs[2].clone(),
-                    self.exprs[0].clone(),
-                    self.exprs[1].clone(),
-                ],
-                2,
-            ),
-            2 => Form::new_otn(
-                vec![
-                    self.exprs[1].clone(),
-                    self.exprs[2].clone(),
-                    self.exprs[0].clone(),
-                ],
-                1,
-            ),
-            3 => Form::new_otn(vec![self.exprs[0].clone()], 0),
-            _ => panic!("Unknown number of forms {}", self.n_forms),
+        self.hodge_star_otn(&[1.0; 3])
+    }
+
+    /// Returns whether every component simplifies to zero.
+    pub fn is_zero(&self) -> bool {
+        self.exprs.iter().all(|expr| expr.simplify().is_zero())
+    }
+}
+
+impl Sub for Form {
+    type Output = Form;
+
+    /// Subtracts two forms of the same degree, basis, and variables component by component.
+    fn sub(self, rhs: Form) -> Form {
+        if self.n_forms != rhs.n_forms || self.basis != rhs.basis || self.variables != rhs.variables
+        {
+            panic!("Cannot subtract forms of different degree, basis, or variables");
         }
+        let exprs = self
+            .exprs
+            .into_iter()
+            .zip(rhs.exprs)
+            .map(|(lhs, rhs)| lhs.sub(rhs).simplify())
+            .collect();
+        Form::new_over(self.variables, exprs, self.n_forms, self.basis)
     }
 }
 
@@ -299,33 +352,158 @@ impl Hodge for Form {
 impl ExternalDerivative for Form {
     /// Computes the exterior derivative of this form.
     ///
-    /// The implementation follows the standard 3D coordinate ordering used by the rest of the
-    /// symbolic math layer.
+    /// Each component `f dx^I` contributes `df/dx^j dx^j ^ dx^I` for every variable `j` not
+    /// already in `I`, and the wedge is reordered into the stored basis element.
     fn d(&mut self) -> Form {
         self.expect_basis(FormBasis::Natural, "d");
-        if self.n_forms == 0 {
-            let dx = derivate(self.exprs[0].clone(), &"x".to_string());
-            let dy = derivate(self.exprs[0].clone(), &"y".to_string());
-            let dz = derivate(self.exprs[0].clone(), &"z".to_string());
-            Form::new(vec![dx, dy, dz], 1)
-        } else if self.n_forms == 1 {
-            let dx_dy = derivate(self.exprs[1].clone(), &"x".to_string())
-                .sub(derivate(self.exprs[0].clone(), &"y".to_string()));
-            let dy_dz = derivate(self.exprs[2].clone(), &"y".to_string())
-                .sub(derivate(self.exprs[1].clone(), &"z".to_string()));
-            let dz_dx = derivate(self.exprs[0].clone(), &"z".to_string())
-                .sub(derivate(self.exprs[2].clone(), &"x".to_string()));
-            Form::new(vec![dx_dy, dy_dz, dz_dx], 2)
-        } else if self.n_forms == 2 {
-            let dz = derivate(self.exprs[0].clone(), &"z".to_string());
-            let dx = derivate(self.exprs[1].clone(), &"x".to_string());
-            let dy = derivate(self.exprs[2].clone(), &"y".to_string());
-            let dx_dy_dz = dx.add(dy).add(dz);
-            Form::new(vec![dx_dy_dz], 3)
-        } else if self.n_forms == 3 {
-            Form::new(vec![Expr::number(0.)], 0) // zero form
-        } else {
-            panic!("Unknown number of forms {}", self.n_forms)
+        let dim = self.dim();
+        if self.n_forms > dim {
+            panic!("Unknown number of forms {}", self.n_forms);
+        }
+        if self.n_forms == dim {
+            return Form::zero(self.variables.clone(), 0, FormBasis::Natural); // zero form
+        }
+
+        let target_degree = self.n_forms + 1;
+        let targets = basis_elements(dim, target_degree);
+        let mut terms = vec![Vec::new(); targets.len()];
+        for (element, component) in basis_elements(dim, self.n_forms).iter().zip(&self.exprs) {
+            for variable in (0..dim).filter(|index| !element.contains(index)) {
+                let wedge = std::iter::once(variable)
+                    .chain(element.iter().copied())
+                    .collect::<Vec<_>>();
+                let (position, sign) = locate_element(&targets, &wedge)
+                    .expect("wedge with a new variable is a basis element");
+                let derivative = derivate(component.clone(), self.variables.name(variable));
+                terms[position].push(signed(derivative, sign));
+            }
         }
+
+        Form::new_over(
+            self.variables.clone(),
+            terms.into_iter().map(sum_terms).collect(),
+            target_degree,
+            FormBasis::Natural,
+        )
+    }
+}
+
+/// Returns the oriented basis elements for k-forms in `dim` dimensions, in storage order.
+pub fn basis_elements(dim: usize, degree: usize) -> Vec<Vec<usize>> {
+    if dim == 3 && degree == 2 {
+        return vec![vec![0, 1], vec![1, 2], vec![2, 0]];
+    }
+    let mut elements = Vec::new();
+    let mut current = Vec::with_capacity(degree);
+    push_combinations(0, dim, degree, &mut current, &mut elements);
+    elements
+}
+
+fn push_combinations(
+    start: usize,
+    dim: usize,
+    degree: usize,
+    current: &mut Vec<usize>,
+    elements: &mut Vec<Vec<usize>>,
+) {
+    if current.len() == degree {
+        elements.push(current.clone());
+        return;
+    }
+    for index in start..dim {
+        current.push(index);
+        push_combinations(index + 1, dim, degree, current, elements);
+        current.pop();
+    }
+}
+
+/// Finds the element of `elements`, as built by `basis_elements`, matching the wedge of
+/// `indices`, and the sign relating them.
+///
+/// Returns `None` when an index repeats, since the wedge then vanishes. Callers build
+/// `elements` once and reuse it across their loop.
+fn locate_element(elements: &[Vec<usize>], indices: &[usize]) -> Option<(usize, f64)> {
+    let mut sorted = indices.to_vec();
+    sorted.sort_unstable();
+    if sorted.windows(2).any(|pair| pair[0] == pair[1]) {
+        return None;
+    }
+    let sign = permutation_sign(indices);
+    elements
+        .iter()
+        .position(|element| {
+            element.len() == sorted.len()
+                && element
+                    .iter()
+                    .all(|index| sorted.binary_search(index).is_ok())
+        })
+        .map(|position| (position, sign * permutation_sign(&elements[position])))
+}
+
+/// Returns the sign of the permutation sorting `indices`, assumed distinct.
+fn permutation_sign(indices: &[usize]) -> f64 {
+    let inversions = (0..indices.len())
+        .flat_map(|i| (i + 1..indices.len()).map(move |j| (i, j)))
+        .filter(|&(i, j)| indices[i] > indices[j])
+        .count();
+    if inversions % 2 == 0 {
+        1.0
+    } else {
+        -1.0
+    }
+}
+
+/// Determinant of the `rows x columns` minor of `matrix`, or `None` when it is structurally zero.
+fn minor_determinant(
+    matrix: &impl Fn(usize, usize) -> Expr,
+    rows: &[usize],
+    columns: &[usize],
+) -> Option<Expr> {
+    if rows.len() == 1 {
+        let element = matrix(rows[0], columns[0]);
+        return (!element.is_zero()).then_some(element);
+    }
+
+    // Laplace expansion along the first row; degrees stay small, so this is cheap enough.
+    let terms = columns
+        .iter()
+        .enumerate()
+        .filter_map(|(position, &column)| {
+            let element = matrix(rows[0], column);
+            if element.is_zero() {
+                return None;
+            }
+            let remaining_columns = columns
+                .iter()
+                .enumerate()
+                .filter(|&(other, _)| other != position)
+                .map(|(_, &column)| column)
+                .collect::<Vec<_>>();
+            let cofactor = minor_determinant(matrix, &rows[1..], &remaining_columns)?;
+            let sign = if position % 2 == 0 { 1.0 } else { -1.0 };
+            Some(signed(element.mul(cofactor), sign))
+        })
+        .collect::<Vec<_>>();
+    (!terms.is_empty()).then(|| sum_terms(terms))
+}
+
+fn signed(expr: Expr, sign: f64) -> Expr {
+    if sign < 0.0 {
+        negated(expr)
+    } else {
+        expr
+    }
+}
+
+fn negated(expr: Expr) -> Expr {
+    Expr::number(-1.0).mul(expr)
+}
+
+/// Adds the collected terms and simplifies, or returns zero when there are none.
+fn sum_terms(terms: Vec<Expr>) -> Expr {
+    let mut terms = terms.into_iter();
+    match terms.next() {
+        Some(first) => terms.fold(first, |sum, term| sum.add(term)).simplify(),
+        None => Expr::number(0.0),
     }
 }
@@ -4,28 +4,41 @@
 //! for per-frame sampling. Vector fields keep both dual-basis and orthonormal-tangent-basis
 //! representations because geometric tangent view, dual tangent view, gradient, and curl render
 //! paths need different bases.
+//!
+//! Expressions may read the scene time `t`. The symbolic derivatives treat it as a constant, and
+//! each field evaluates at the time last set on it.
 
 use crate::maths::differential::{Form, FormBasis};
 use crate::maths::space::Space;
-use crate::maths::{derivate, expr_to_fastexpr3d, Expr, ExternalDerivative, FastExpr3d, Point};
+use crate::maths::{derivate, expr_to_fastexpr4d, Expr, ExternalDerivative, FastExpr4d, Point};
 use crate::toolbox::logging::LOGGER;
 
 #[derive(Clone)]
 pub struct ScalarField {
     expr: Expr,
-    fast_expr: FastExpr3d,
+    fast_expr: FastExpr4d,
+    time: f64,
 }
 
 impl ScalarField {
     /// Builds a scalar field from one symbolic expression.
     pub fn new(expr: Expr) -> Self {
-        let fast_expr = expr_to_fastexpr3d(expr.clone());
-        Self { expr, fast_expr }
+        let fast_expr = expr_to_fastexpr4d(expr.clone());
+        Self {
+            expr,
+            fast_expr,
+            time: 0.0,
+        }
+    }
+
+    /// Sets the scene time later evaluations read.
+    pub fn set_time(&mut self, time: f64) {
+        self.time = time;
     }
 
     /// Evaluates the scalar field at one abstract coordinate.
     pub fn at(&self, point: Point) -> f64 {
-        (self.fast_expr)(point.x, point.y, point.z)
+        (self.fast_expr)(point.x, point.y, point.z, self.time)
     }
 
     /// Returns the stored symbolic expression.
@@ -38,9 +51,10 @@ impl ScalarField {
 pub struct VectorField {
     dual_expr: Form,
     otn_expr: Form,
-    fast_dual_expr: [FastExpr3d; 3],
-    fast_otn_expr: [FastExpr3d; 3],
-    fast_otn_jacobian: [[FastExpr3d; 3]; 3],
+    fast_dual_expr: [FastExpr4d; 3],
+    fast_otn_expr: [FastExpr4d; 3],
+    fast_otn_jacobian: [[FastExpr4d; 3]; 3],
+    time: f64,
 }
 
 impl VectorField {
@@ -66,6 +80,7 @@ impl VectorField {
             fast_dual_expr,
             fast_otn_expr,
             fast_otn_jacobian,
+            time: 0.0,
         }
     }
 
@@ -97,6 +112,7 @@ impl VectorField {
             fast_dual_expr,
             fast_otn_expr,
             fast_otn_jacobian,
+            time: 0.0,
         }
     }
 
@@ -111,8 +127,14 @@ impl VectorField {
         if expr.n_forms() != 1 {
             LOGGER.error("Curl input must be a 1-form");
         }
+        Self::curl_from_dual(expr.to_dual_base(space), space)
+    }
 
-        let mut dual_input = expr.to_dual_base(space);
+    /// Builds the rendered curl field of a natural-basis 1-form.
+    pub fn curl_from_dual(mut dual_input: Form, space: &Space) -> Self {
+        if dual_input.n_forms() != 1 {
+            LOGGER.error("Curl input must be a 1-form");
+        }
         let curl_dual = dual_input.d();
         let curl_otn = curl_dual.to_otn_base(space).hodge_star_otn_3d();
         Self::from_otn(curl_otn, space)
@@ -121,18 +143,18 @@ impl VectorField {
     /// Compiles the three components of a form into numeric closures.
     ///
     /// The components are assumed to follow the axis ordering already enforced by `Form`.
-    fn compile_fast_expr(expr: &Form) -> [FastExpr3d; 3] {
+    fn compile_fast_expr(expr: &Form) -> [FastExpr4d; 3] {
         [
-            expr_to_fastexpr3d(expr.get_expr(0).clone()),
-            expr_to_fastexpr3d(expr.get_expr(1).clone()),
-            expr_to_fastexpr3d(expr.get_expr(2).clone()),
+            expr_to_fastexpr4d(expr.get_expr(0).clone()),
+            expr_to_fastexpr4d(expr.get_expr(1).clone()),
+            expr_to_fastexpr4d(expr.get_expr(2).clone()),
         ]
     }
 
     /// Compiles the orthonormal-tangent components of the field into numeric closures.
     ///
     /// This is a thin wrapper that exists to keep basis-specific call sites explicit.
-    fn compile_fast_otn_expr(otn_expr: &Form) -> [FastExpr3d; 3] {
+    fn compile_fast_otn_expr(otn_expr: &Form) -> [FastExpr4d; 3] {
         Self::compile_fast_expr(otn_expr)
     }
 
@@ -140,38 +162,43 @@ impl VectorField {
     ///
     /// Each entry stores one partial derivative needed by the local linear approximation used
     /// in tangent mode.
-    fn compile_fast_otn_jacobian(otn_expr: &Form) -> [[FastExpr3d; 3]; 3] {
+    fn compile_fast_otn_jacobian(otn_expr: &Form) -> [[FastExpr4d; 3]; 3] {
         let x = "x".to_string();
         let y = "y".to_string();
         let z = "z".to_string();
 
         [
             [
-                expr_to_fastexpr3d(derivate(otn_expr.get_expr(0).clone(), &x)),
-                expr_to_fastexpr3d(derivate(otn_expr.get_expr(0).clone(), &y)),
-                expr_to_fastexpr3d(derivate(otn_expr.get_expr(0).clone(), &z)),
+                expr_to_fastexpr4d(derivate(otn_expr.get_expr(0).clone(), &x)),
+                expr_to_fastexpr4d(derivate(otn_expr.get_expr(0).clone(), &y)),
+                expr_to_fastexpr4d(derivate(otn_expr.get_expr(0).clone(), &z)),
             ],
             [
-                expr_to_fastexpr3d(derivate(otn_expr.get_expr(1).clone(), &x)),
-                expr_to_fastexpr3d(derivate(otn_expr.get_expr(1).clone(), &y)),
-                expr_to_fastexpr3d(derivate(otn_expr.get_expr(1).clone(), &z)),
+                expr_to_fastexpr4d(derivate(otn_expr.get_expr(1).clone(), &x)),
+                expr_to_fastexpr4d(derivate(otn_expr.get_expr(1).clone(), &y)),
+                expr_to_fastexpr4d(derivate(otn_expr.get_expr(1).clone(), &z)),
             ],
             [
-                expr_to_fastexpr3d(derivate(otn_expr.get_expr(2).clone(), &x)),
-                expr_to_fastexpr3d(derivate(otn_expr.get_expr(2).clone(), &y)),
-                expr_to_fastexpr3d(derivate(otn_expr.get_expr(2).clone(), &z)),
+                expr_to_fastexpr4d(derivate(otn_expr.get_expr(2).clone(), &x)),
+                expr_to_fastexpr4d(derivate(otn_expr.get_expr(2).clone(), &y)),
+                expr_to_fastexpr4d(derivate(otn_expr.get_expr(2).clone(), &z)),
             ],
         ]
     }
 
+    /// Sets the scene time later evaluations read.
+    pub fn set_time(&mut self, time: f64) {
+        self.time = time;
+    }
+
     /// Evaluates the field components in the orthonormal tangent basis at one point.
     ///
     /// The returned `Point` contains the x, y, and z components in abstract coordinate order.
     pub fn at(&self, point: Point) -> Point {
         Point {
-            x: self.fast_otn_expr[0](point.x, point.y, point.z),
-            y: self.fast_otn_expr[1](point.x, point.y, point.z),
-            z: self.fast_otn_expr[2](point.x, point.y, point.z),
+            x: self.fast_otn_expr[0](point.x, point.y, point.z, self.time),
+            y: self.fast_otn_expr[1](point.x, point.y, point.z, self.time),
+            z: self.fast_otn_expr[2](point.x, point.y, point.z, self.time),
         }
     }
 
@@ -180,9 +207,9 @@ impl VectorField {
     /// This is used when building dual tangent overlays and other covector-oriented views.
     pub fn dual_at(&self, point: Point) -> Point {
         Point {
-            x: self.fast_dual_expr[0](point.x, point.y, point.z),
-            y: self.fast_dual_expr[1](point.x, point.y, point.z),
-            z: self.fast_dual_expr[2](point.x, point.y, point.z),
+            x: self.fast_dual_expr[0](point.x, point.y, point.z, self.time),
+            y: self.fast_dual_expr[1](point.x, point.y, point.z, self.time),
+            z: self.fast_dual_expr[2](point.x, point.y, point.z, self.time),
         }
     }
 
@@ -191,20 +218,25 @@ impl VectorField {
     /// The `delta` argument is interpreted as an abstract-space offset from `anchor`.
     pub fn linearized_at(&self, anchor: Point, delta: Point) -> Point {
         let anchor_value = self.at(anchor);
+        let jacobian = self.otn_jacobian_at(anchor);
 
-        let dot_row = |row: &[FastExpr3d; 3]| {
-            row[0](anchor.x, anchor.y, anchor.z) * delta.x
-                + row[1](anchor.x, anchor.y, anchor.z) * delta.y
-                + row[2](anchor.x, anchor.y, anchor.z) * delta.z
-        };
+        let dot_row = |row: [f64; 3]| row[0] * delta.x + row[1] * delta.y + row[2] * delta.z;
 
         Point {
-            x: anchor_value.x + dot_row(&self.fast_otn_jacobian[0]),
-            y: anchor_value.y + dot_row(&self.fast_otn_jacobian[1]),
-            z: anchor_value.z + dot_row(&self.fast_otn_jacobian[2]),
+            x: anchor_value.x + dot_row(jacobian[0]),
+            y: anchor_value.y + dot_row(jacobian[1]),
+            z: anchor_value.z + dot_row(jacobian[2]),
         }
     }
 
+    /// Evaluates `∂F_a / ∂x^j` of the orthonormal components at one point, row `a`, column `j`.
+    pub fn otn_jacobian_at(&self, point: Point) -> [[f64; 3]; 3] {
+        self.fast_otn_jacobian.each_ref().map(|row| {
+            row.each_ref()
+                .map(|entry| entry(point.x, point.y, point.z, self.time))
+        })
+    }
+
     /// Returns the symbolic field representation stored in the dual basis.
     ///
     /// Callers can use this for further symbolic manipulation or debugging.
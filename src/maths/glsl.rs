@@ -0,0 +1,297 @@
+//! GLSL code generation for coordinate equations drawn by the grid vertex shader.
+//!
+//! The generator walks the parsed expression tree rather than printing the user's text, so
+//! operator precedence, float literals and function names follow GLSL rules. It accepts the
+//! same nodes as the fast CPU evaluators over `x`, `y` and `z`, so the GPU embedding matches the
+//! one the CPU samples. Integer powers go through the `int_pow` helper declared in
+//! `grid_edit.vert`, because GLSL's `pow` is undefined for negative bases. The scene time `t` of
+//! moving embeddings is a local the shader fills from its `time` uniform.
+
+use super::{number_to_f64, Expr};
+use mathhook::Expression;
+
+/// Functions with a direct GLSL builtin, as `(equation name, GLSL name)`.
+const GLSL_FUNCTIONS: [(&str, &str); 14] = [
+    ("sqrt", "sqrt"),
+    ("sin", "sin"),
+    ("cos", "cos"),
+    ("tan", "tan"),
+    ("asin", "asin"),
+    ("arcsin", "asin"),
+    ("acos", "acos"),
+    ("arccos", "acos"),
+    ("atan", "atan"),
+    ("arctan", "atan"),
+    ("ln", "log"),
+    ("log", "log"),
+    ("exp", "exp"),
+    ("abs", "abs"),
+];
+
+/// Binding strength of a generated GLSL expression, weakest first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Precedence {
+    Sum,
+    Product,
+    Unary,
+    Atom,
+}
+
+struct Glsl {
+    code: String,
+    precedence: Precedence,
+}
+
+impl Glsl {
+    fn new(code: String, precedence: Precedence) -> Self {
+        Self { code, precedence }
+    }
+
+    /// Code usable as an operand that must bind at least as tightly as `minimum`.
+    fn operand(self, minimum: Precedence) -> String {
+        if self.precedence >= minimum {
+            self.code
+        } else {
+            format!("({})", self.code)
+        }
+    }
+}
+
+/// Translates a coordinate equation over `x`, `y`, `z` and `t` into one GLSL float expression.
+///
+/// Returns a message naming the first node that has no GLSL counterpart.
+pub fn expr_to_glsl(expr: &Expr) -> Result<String, String> {
+    emit(expr).map(|glsl| glsl.code)
+}
+
+fn emit(expr: &Expr) -> Result<Glsl, String> {
+    match expr {
+        Expression::Number(number) => {
+            let value = number_to_f64(number)
+                .ok_or_else(|| "number cannot be evaluated on the GPU".to_string())?;
+            literal(value)
+        }
+        Expression::Constant(constant) => literal(constant.to_f64()),
+        Expression::Symbol(symbol) => match symbol.name() {
+            name @ ("x" | "y" | "z" | "t") => Ok(Glsl::new(name.to_string(), Precedence::Atom)),
+            name => Err(format!("variable '{name}' is not available on the GPU")),
+        },
+        Expression::Add(terms) => emit_sum(terms),
+        Expression::Mul(factors) => emit_product(factors),
+        Expression::Pow(base, exponent) => emit_power(base, exponent),
+        Expression::Function { name, args } => {
+            let Some((_, glsl_name)) = GLSL_FUNCTIONS
+                .iter()
+                .find(|(equation_name, _)| *equation_name == name.as_ref())
+            else {
+                return Err(format!("function '{name}' is not supported on the GPU"));
+            };
+            let [arg] = args.as_slice() else {
+                return Err(format!(
+                    "function '{name}' takes one argument, got {}",
+                    args.len()
+                ));
+            };
+            Ok(Glsl::new(
+                format!("{glsl_name}({})", emit(arg)?.code),
+                Precedence::Atom,
+            ))
+        }
+        _ => Err("expression cannot be drawn on the GPU".to_string()),
+    }
+}
+
+/// Joins terms with `+`, folding the leading minus of later negated products into `-`.
+fn emit_sum(terms: &[Expr]) -> Result<Glsl, String> {
+    let mut code = String::new();
+    for (index, term) in terms.iter().enumerate() {
+        let term = emit(term)?;
+        let negated = (term.precedence >= Precedence::Product)
+            .then(|| term.code.strip_prefix('-'))
+            .flatten();
+        match (index, negated) {
+            (0, _) => code.push_str(&term.code),
+            (_, Some(negated)) => {
+                code.push_str(" - ");
+                code.push_str(negated);
+            }
+            (_, None) => {
+                code.push_str(" + ");
+                code.push_str(&term.code);
+            }
+        }
+    }
+    Ok(Glsl::new(code, Precedence::Sum))
+}
+
+/// Joins factors with `*`, moving negative numeric powers below a single `/`.
+///
+/// A leading `-1` factor becomes a unary minus. The result never starts with `--`, which GLSL
+/// would read as a decrement.
+fn emit_product(factors: &[Expr]) -> Result<Glsl, String> {
+    let mut negate = false;
+    let mut numerator = Vec::new();
+    let mut denominator = Vec::new();
+    for factor in factors {
+        match factor {
+            Expression::Number(number) if number_to_f64(number) == Some(-1.0) => {
+                negate = !negate;
+            }
+            Expression::Pow(base, exponent) => match numeric_value(exponent) {
+                Some(value) if value < 0.0 => {
+                    denominator.push(emit_power_value(base, -value)?);
+                }
+                _ => numerator.push(emit(factor)?),
+            },
+            _ => numerator.push(emit(factor)?),
+        }
+    }
+
+    let (mut code, mut precedence) = match numerator.len() {
+        0 => ("1.0".to_string(), Precedence::Atom),
+        1 => {
+            let only = numerator.remove(0);
+            (only.code, only.precedence)
+        }
+        _ => (
+            join_factors(numerator, Precedence::Product),
+            Precedence::Product,
+        ),
+    };
+    if !denominator.is_empty() {
+        let divisor = if denominator.len() == 1 {
+            denominator.remove(0).operand(Precedence::Unary)
+        } else {
+            format!("({})", join_factors(denominator, Precedence::Product))
+        };
+        code = format!(
+            "{} / {divisor}",
+            Glsl::new(code, precedence).operand(Precedence::Product)
+        );
+        precedence = Precedence::Product;
+    }
+    if negate {
+        if precedence >= Precedence::Product && !code.starts_with('-') {
+            code = format!("-{code}");
+            precedence = precedence.min(Precedence::Unary);
+        } else {
+            code = format!("-({code})");
+            precedence = Precedence::Unary;
+        }
+    }
+    Ok(Glsl::new(code, precedence))
+}
+
+fn join_factors(factors: Vec<Glsl>, minimum: Precedence) -> String {
+    factors
+        .into_iter()
+        .map(|factor| factor.operand(minimum))
+        .collect::<Vec<_>>()
+        .join(" * ")
+}
+
+fn emit_power(base: &Expr, exponent: &Expr) -> Result<Glsl, String> {
+    match numeric_value(exponent) {
+        Some(value) => emit_power_value(base, value),
+        None => Ok(Glsl::new(
+            format!("pow({}, {})", emit(base)?.code, emit(exponent)?.code),
+            Precedence::Atom,
+        )),
+    }
+}
+
+/// `base^exponent` for a constant exponent: identities and square roots inline, integers
+/// through `int_pow`, everything else through `pow`.
+fn emit_power_value(base: &Expr, exponent: f64) -> Result<Glsl, String> {
+    let base = emit(base)?;
+    if exponent == 1.0 {
+        return Ok(base);
+    }
+    let code = if exponent == 0.0 {
+        "1.0".to_string()
+    } else if exponent == 0.5 {
+        format!("sqrt({})", base.code)
+    } else if exponent.fract() == 0.0 && exponent.abs() <= i32::MAX as f64 {
+        format!("int_pow({}, {})", base.code, exponent as i32)
+    } else {
+        format!("pow({}, {})", base.code, literal(exponent)?.code)
+    };
+    Ok(Glsl::new(code, Precedence::Atom))
+}
+
+fn numeric_value(expr: &Expr) -> Option<f64> {
+    match expr {
+        Expression::Number(number) => number_to_f64(number),
+        Expression::Constant(constant) => Some(constant.to_f64()),
+        _ => None,
+    }
+}
+
+fn literal(value: f64) -> Result<Glsl, String> {
+    if !value.is_finite() {
+        return Err(format!("constant {value} is not finite"));
+    }
+    let code = float_literal(value);
+    let precedence = if value < 0.0 {
+        Precedence::Unary
+    } else {
+        Precedence::Atom
+    };
+    Ok(Glsl::new(code, precedence))
+}
+
+/// Formats a finite value so GLSL always reads a float, e.g. `2.0` rather than `2`.
+fn float_literal(value: f64) -> String {
+    // `Debug` keeps a fractional part or an exponent, both of which are GLSL float syntax.
+    format!("{value:?}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{expr_to_glsl, float_literal};
+    use mathhook_core::Parser;
+
+    fn glsl(expr: &str) -> Result<String, String> {
+        expr_to_glsl(&Parser::default().parse(expr).unwrap())
+    }
+
+    #[test]
+    fn literals_are_always_floats() {
+        assert_eq!(float_literal(2.0), "2.0");
+        assert_eq!(float_literal(-0.25), "-0.25");
+        assert_eq!(float_literal(1.0e-7), "1e-7");
+        assert_eq!(glsl("3").unwrap(), "3.0");
+    }
+
+    #[test]
+    fn functions_and_powers_use_glsl_names() {
+        assert_eq!(glsl("ln(x)").unwrap(), "log(x)");
+        assert_eq!(glsl("arcsin(y)").unwrap(), "asin(y)");
+        assert_eq!(glsl("x^2").unwrap(), "int_pow(x, 2)");
+        assert_eq!(glsl("x^y").unwrap(), "pow(x, y)");
+    }
+
+    #[test]
+    fn nested_sums_keep_their_parentheses() {
+        let code = glsl("(x + 1) * cos(y + z)").unwrap();
+
+        assert!(
+            code.contains("(x + 1.0)") || code.contains("(1.0 + x)"),
+            "{code}"
+        );
+        assert!(code.contains(" * "), "{code}");
+        assert!(!glsl("x - y").unwrap().contains("--"));
+        assert!(!glsl("x - -2 * y").unwrap().contains("--"));
+    }
+
+    #[test]
+    fn unsupported_nodes_are_rejected() {
+        assert!(glsl("besselj(0, x)").unwrap_err().contains("besselj"));
+        assert!(glsl("x + w").unwrap_err().contains("'w'"));
+    }
+
+    #[test]
+    fn scene_time_is_a_shader_variable() {
+        assert_eq!(glsl("cos(t)").unwrap(), "cos(t)");
+    }
+}
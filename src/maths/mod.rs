@@ -2,12 +2,13 @@
 //! Symbolic math helpers and fast evaluator compilation used by the runtime.
 
 use crate::maths::space::Metric;
+use crate::maths::special::{bessel_j, BESSEL_J};
 use egui::TextBuffer;
 use lazy_static::lazy_static;
 use mathhook::prelude::Simplify;
 use mathhook::Expression;
 use mathhook_core::{Derivative, EvalContext, Symbol};
-use nalgebra::Vector3;
+use nalgebra::{Complex, Vector3};
 use once_cell::sync::Lazy;
 use std::cell::RefCell;
 use std::collections::{BTreeMap, HashMap};
@@ -17,7 +18,11 @@ use typed_floats::NonNaN;
 
 pub mod differential;
 pub mod field;
+pub mod glsl;
 pub mod space;
+pub mod spacetime;
+pub mod special;
+pub mod spectrum;
 
 pub type Expr = Expression;
 pub type FastExpr1d = Arc<dyn Fn(f64) -> f64 + Send + Sync>;
@@ -25,8 +30,11 @@ pub type FastExpr1d = Arc<dyn Fn(f64) -> f64 + Send + Sync>;
 pub type FastExpr2dto1d = Arc<dyn Fn(f64, f64) -> FastExpr1d>;
 pub type FastExpr3d = Arc<dyn Fn(f64, f64, f64) -> f64 + Send + Sync>;
 pub type FastExpr4d = Arc<dyn Fn(f64, f64, f64, f64) -> f64 + Send + Sync>;
+pub type FastComplexExpr3d = Arc<dyn Fn(f64, f64, f64) -> Complex<f64> + Send + Sync>;
 
 pub const COORD: [&str; 3] = ["x", "y", "z"];
+/// Symbol name reserved for the imaginary unit in complex-valued expressions.
+pub const IMAGINARY_UNIT: &str = "i";
 
 #[derive(Clone, Copy)]
 pub struct Point {
@@ -203,6 +211,64 @@ pub fn expr_to_fastexpr4d(expr: Expr) -> FastExpr4d {
     Arc::new(eval)
 }
 
+/// Compiles a complex-valued spatial expression where the symbol `i` is the imaginary unit.
+///
+/// Unlike the real evaluators there is no symbolic fallback: nodes outside the supported
+/// elementary functions evaluate to `NaN`.
+pub fn expr_to_fast_complex_expr3d(expr: Expr) -> FastComplexExpr3d {
+    let eval_expr = Arc::new(expr.simplify());
+    let eval = move |x: f64, y: f64, z: f64| -> Complex<f64> {
+        eval_complex_node(&eval_expr, x, y, z)
+            .filter(|value| value.re.is_finite() && value.im.is_finite())
+            .unwrap_or(Complex::new(f64::NAN, f64::NAN))
+    };
+    Arc::new(eval)
+}
+
+fn eval_complex_node(expr: &Expr, x: f64, y: f64, z: f64) -> Option<Complex<f64>> {
+    match expr {
+        Expression::Number(number) => number_to_f64(number).map(Complex::from),
+        Expression::Constant(constant) => Some(Complex::from(constant.to_f64())),
+        Expression::Symbol(symbol) => match symbol.name() {
+            "x" => Some(Complex::from(x)),
+            "y" => Some(Complex::from(y)),
+            "z" => Some(Complex::from(z)),
+            IMAGINARY_UNIT => Some(Complex::i()),
+            _ => None,
+        },
+        Expression::Add(terms) => terms.iter().try_fold(Complex::from(0.0), |acc, term| {
+            Some(acc + eval_complex_node(term, x, y, z)?)
+        }),
+        Expression::Mul(factors) => factors.iter().try_fold(Complex::from(1.0), |acc, factor| {
+            Some(acc * eval_complex_node(factor, x, y, z)?)
+        }),
+        Expression::Pow(base, exp) => {
+            let base = eval_complex_node(base, x, y, z)?;
+            let exp = eval_complex_node(exp, x, y, z)?;
+            // Integer powers stay exact so `i^2` is exactly `-1`.
+            if exp.im == 0.0 && exp.re.fract() == 0.0 && exp.re.abs() <= i32::MAX as f64 {
+                Some(base.powi(exp.re as i32))
+            } else {
+                Some(base.powc(exp))
+            }
+        }
+        Expression::Function { name, args } => {
+            let arg = |idx: usize| eval_complex_node(&args[idx], x, y, z);
+            match name.as_ref() {
+                "sqrt" => Some(arg(0)?.sqrt()),
+                "sin" => Some(arg(0)?.sin()),
+                "cos" => Some(arg(0)?.cos()),
+                "tan" => Some(arg(0)?.tan()),
+                "ln" | "log" => Some(arg(0)?.ln()),
+                "exp" => Some(arg(0)?.exp()),
+                "abs" => Some(Complex::from(arg(0)?.norm())),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
 fn eval_xyzt_fast_path(expr: &Expr, x: f64, y: f64, z: f64, t: f64) -> Option<f64> {
     eval_xyzt_node(expr, x, y, z, t).filter(|value| value.is_finite())
 }
@@ -240,6 +306,7 @@ fn eval_xyzt_node(expr: &Expr, x: f64, y: f64, z: f64, t: f64) -> Option<f64> {
                 "ln" | "log" => Some(arg(0)?.ln()),
                 "exp" => Some(arg(0)?.exp()),
                 "abs" => Some(arg(0)?.abs()),
+                BESSEL_J if args.len() == 2 => Some(bessel_j(integer_order(arg(0)?)?, arg(1)?)),
                 _ => None,
             }
         }
@@ -247,6 +314,11 @@ fn eval_xyzt_node(expr: &Expr, x: f64, y: f64, z: f64, t: f64) -> Option<f64> {
     }
 }
 
+/// Rounds a Bessel order argument, rejecting non-integer orders.
+fn integer_order(value: f64) -> Option<i32> {
+    (value.fract() == 0.0 && value.abs() <= i32::MAX as f64).then_some(value as i32)
+}
+
 fn number_to_f64(number: &mathhook_core::Number) -> Option<f64> {
     match number {
         mathhook_core::Number::Integer(value) => Some(*value as f64),
@@ -286,6 +358,10 @@ fn eval_numeric_fallback(expr: &Expr) -> Result<f64, mathhook_core::MathError> {
                 "ln" => Ok(arg(0)?.ln()),
                 "log" => Ok(arg(0)?.ln()),
                 "exp" => Ok(arg(0)?.exp()),
+                BESSEL_J if args.len() == 2 => match integer_order(arg(0)?) {
+                    Some(order) => Ok(bessel_j(order, arg(1)?)),
+                    None => expr.evaluate_to_f64(),
+                },
                 _ => expr.evaluate_to_f64(),
             }
         }
@@ -331,7 +407,7 @@ pub trait ExternalDerivative {
 
 #[cfg(test)]
 mod tests {
-    use super::expr_to_fastexpr3d;
+    use super::{expr_to_fast_complex_expr3d, expr_to_fastexpr3d, expr_to_fastexpr4d};
     use mathhook_core::Parser;
 
     #[test]
@@ -343,4 +419,27 @@ mod tests {
 
         assert!(result.is_nan());
     }
+
+    #[test]
+    fn fast_expr_evaluates_integer_order_bessel_functions() {
+        let expr = Parser::default()
+            .parse("besselj(1, 2*x) + besselj(0, z)")
+            .unwrap();
+        let eval = expr_to_fastexpr4d(expr);
+
+        let result = eval(1.25, 0.0, 0.0, 0.0);
+
+        assert!((result - 1.497_094_102_464_274).abs() < 1.0e-12, "{result}");
+    }
+
+    #[test]
+    fn complex_expr_treats_i_as_imaginary_unit() {
+        let expr = Parser::default().parse("exp(i * z) + i^2 * x").unwrap();
+        let eval = expr_to_fast_complex_expr3d(expr);
+
+        let result = eval(2.0, 0.0, std::f64::consts::FRAC_PI_2);
+
+        assert!((result.re + 2.0).abs() < 1.0e-12, "{result}");
+        assert!((result.im - 1.0).abs() < 1.0e-12, "{result}");
+    }
 }
@@ -4,8 +4,8 @@
 //! matrices used to move differential forms between the natural coordinate coframe and the
 //! orthonormal tangent coframe.
 
-use crate::maths::differential::Form;
-use crate::maths::{Expr, ExternalDerivative};
+use crate::maths::differential::{Form, FormBasis};
+use crate::maths::{Expr, ExternalDerivative, COORD};
 use mathhook_core::matrices::{Matrix, MatrixOperations};
 use mathhook_core::{expr, Expression, Simplify};
 use std::ops::{Add, Mul};
@@ -13,9 +13,42 @@ use std::sync::Arc;
 
 pub type Metric = Matrix;
 
+/// Ordered coordinate names shared by a `Space` and the forms living on it.
+///
+/// Cloning is cheap, so every form carries its own copy.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Variables(Arc<[String]>);
+
+impl Variables {
+    pub fn new<S: Into<String>>(names: impl IntoIterator<Item = S>) -> Self {
+        Self(names.into_iter().map(Into::into).collect())
+    }
+
+    /// The spatial `x, y, z` coordinates used by the 3D grid.
+    pub fn xyz() -> Self {
+        Self::new(COORD)
+    }
+
+    pub fn dim(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn name(&self, index: usize) -> &String {
+        &self.0[index]
+    }
+
+    pub fn names(&self) -> &[String] {
+        &self.0
+    }
+
+    /// Returns these variables with `name` prepended, e.g. `t` in front of `x, y, z`.
+    pub fn prepended(&self, name: &str) -> Self {
+        Self::new(std::iter::once(name).chain(self.0.iter().map(String::as_str)))
+    }
+}
+
 pub struct Space {
-    #[allow(dead_code)]
-    dim: u32,
+    variables: Variables,
     metric: Metric,
     vielbein: Expr,
     vielbein_inv: Expr,
@@ -48,36 +81,56 @@ impl Space {
     /// The resulting `Space` stores both the symbolic metric tensor and the Cholesky-based
     /// basis transforms used elsewhere in the math layer.
     pub fn new(x_eq: Expr, y_eq: Expr, z_eq: Expr) -> Space {
-        // J columns: d(X,Y,Z)/d(x), d(X,Y,Z)/d(y), d(X,Y,Z)/d(z)
-        let d_x = Form::new(vec![x_eq], 0).d().square();
-        let d_y = Form::new(vec![y_eq], 0).d().square();
-        let d_z = Form::new(vec![z_eq], 0).d().square();
-
-        // assert!(
-        //     d_x.len() == 3 && d_y.len() == 3 && d_z.len() == 3,
-        //     "Expected 3 derivatives per coordinate expression; got X={}, Y={}, Z={}",
-        //     d_x.len(),
-        //     d_y.len(),
-        //     d_z.len()
-        // );
-
-        let g_xx = sum3(&d_x[0], &d_y[0], &d_z[0]);
-        let g_xy = sum3(&d_x[1], &d_y[1], &d_z[1]);
-        let g_yy = sum3(&d_x[2], &d_y[2], &d_z[2]);
-        let g_xz = sum3(&d_x[3], &d_y[3], &d_z[3]);
-        let g_yz = sum3(&d_x[4], &d_y[4], &d_z[4]);
-        let g_zz = sum3(&d_x[5], &d_y[5], &d_z[5]);
-
-        let metric: Matrix = Matrix::symmetric(3, vec![g_xx, g_xy, g_yy, g_xz, g_yz, g_zz]);
+        Self::from_embedding(Variables::xyz(), vec![x_eq, y_eq, z_eq])
+    }
+
+    /// Builds the metric induced on `variables` by an embedding into flat space.
+    ///
+    /// `embedding` holds one expression per ambient Cartesian axis, so a surface in 3D uses two
+    /// variables and three expressions. The metric is packed with `Form::square`, one 1-form per
+    /// ambient axis, and must be positive definite for the Cholesky vielbein.
+    pub fn from_embedding(variables: Variables, embedding: Vec<Expr>) -> Space {
+        let dim = variables.dim();
+        // Row `a` holds the packed squares of d(X^a); the metric sums them over `a`.
+        let squares = embedding
+            .into_iter()
+            .map(|axis| {
+                Form::new_over(variables.clone(), vec![axis], 0, FormBasis::Natural)
+                    .d()
+                    .square()
+            })
+            .collect::<Vec<_>>();
+        let packed = (0..dim * (dim + 1) / 2)
+            .map(|entry| {
+                squares
+                    .iter()
+                    .map(|axis| axis[entry].clone())
+                    .reduce(|sum, term| sum.add(term))
+                    .unwrap_or_else(|| Expr::number(0.0))
+                    .simplify()
+            })
+            .collect();
+
+        let metric: Matrix = Matrix::symmetric(dim, packed);
         let vielbein = Expression::Matrix(Arc::new(metric.cholesky_decomposition().unwrap().l));
         Space {
-            dim: 3,
+            variables,
             metric,
             vielbein: vielbein.clone(),
             vielbein_inv: vielbein.inverse(),
         }
     }
 
+    /// Returns the coordinate variables of this space.
+    pub fn variables(&self) -> &Variables {
+        &self.variables
+    }
+
+    /// Returns the number of coordinates.
+    pub fn dim(&self) -> usize {
+        self.variables.dim()
+    }
+
     /// Returns the matrix that maps natural basis components into the orthonormal tangent
     /// basis.
     ///
@@ -0,0 +1,81 @@
+//! Static spacetime `-c^2 dt^2 + g` over `(t, x, y, z)`, built on a spatial `Space`.
+//!
+//! The orthonormal coframe is `e^0 = c dt` followed by the spatial vielbein, so basis changes
+//! are block diagonal and the Hodge star only needs the Lorentzian signature.
+
+use crate::maths::differential::{Form, FormBasis};
+use crate::maths::space::{Space, Variables};
+use crate::maths::Expr;
+use mathhook_core::matrices::MatrixOperations;
+
+/// Name of the time coordinate prepended to the spatial variables.
+pub const TIME: &str = "t";
+/// Metric signature of the orthonormal coframe, time first.
+pub const LORENTZIAN_SIGNATURE: [f64; 4] = [-1.0, 1.0, 1.0, 1.0];
+
+pub struct Spacetime<'a> {
+    space: &'a Space,
+    variables: Variables,
+    light_speed: f64,
+}
+
+impl<'a> Spacetime<'a> {
+    /// Builds the spacetime over `space` with light speed `c`.
+    ///
+    /// Only 3D spaces are supported, since the Hodge star uses the 4D Lorentzian signature.
+    pub fn new(space: &'a Space, light_speed: f64) -> Self {
+        assert_eq!(space.dim(), 3, "spacetime needs a 3D spatial slice");
+        Self {
+            space,
+            variables: space.variables().prepended(TIME),
+            light_speed,
+        }
+    }
+
+    /// Returns `(t, x, y, z)`, or the time coordinate followed by the spatial names.
+    pub fn variables(&self) -> &Variables {
+        &self.variables
+    }
+
+    pub fn light_speed(&self) -> f64 {
+        self.light_speed
+    }
+
+    /// Builds a natural-basis form over the spacetime variables.
+    pub fn form(&self, exprs: Vec<Expr>, n_forms: usize) -> Form {
+        Form::new_over(self.variables.clone(), exprs, n_forms, FormBasis::Natural)
+    }
+
+    /// Moves a natural spacetime form into the orthonormal coframe.
+    pub fn to_otn(&self, form: &Form) -> Form {
+        let nat_to_otn = self.space.natural_to_otn();
+        let time_scale = Expr::number(1.0 / self.light_speed);
+        form.change_basis(
+            |row, column| match (row, column) {
+                (0, 0) => time_scale.clone(),
+                (0, _) | (_, 0) => Expr::number(0.0),
+                _ => nat_to_otn.get_element(row - 1, column - 1),
+            },
+            FormBasis::Orthonormal,
+        )
+    }
+
+    /// Moves an orthonormal spacetime form back into the natural coframe.
+    pub fn to_natural(&self, form: &Form) -> Form {
+        let otn_to_nat = self.space.otn_to_natural();
+        let time_scale = Expr::number(self.light_speed);
+        form.change_basis(
+            |row, column| match (row, column) {
+                (0, 0) => time_scale.clone(),
+                (0, _) | (_, 0) => Expr::number(0.0),
+                _ => otn_to_nat.get_element(row - 1, column - 1),
+            },
+            FormBasis::Natural,
+        )
+    }
+
+    /// Applies the Lorentzian Hodge star to a natural form and returns a natural form.
+    pub fn hodge_star(&self, form: &Form) -> Form {
+        self.to_natural(&self.to_otn(form).hodge_star_otn(&LORENTZIAN_SIGNATURE))
+    }
+}
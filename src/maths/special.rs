@@ -0,0 +1,194 @@
+//! Special functions used by the numeric evaluators.
+//!
+//! Only integer-order Bessel functions of the first kind are provided, which is what the
+//! circular waveguide and cavity modes need.
+
+use std::f64::consts::PI;
+
+/// Name of the Bessel function recognized in equations, called as `besselj(n, x)`.
+pub const BESSEL_J: &str = "besselj";
+
+/// Step used when bracketing Bessel zeros; consecutive zeros are roughly `pi` apart.
+const ZERO_SCAN_STEP: f64 = 0.05;
+
+/// Bessel zeros are only searched below this argument.
+const ZERO_SCAN_LIMIT: f64 = 1000.0;
+
+/// Largest `|n|` evaluated; higher orders give `NaN`.
+const MAX_ORDER: u32 = 64;
+
+/// Smallest `|x|` handed to the asymptotic expansion, which also needs `|x| >= n^2`.
+const ASYMPTOTIC_MIN_X: f64 = 1000.0;
+
+/// Trapezoidal samples at the largest `|x| + |n|` left to the integral.
+const MAX_SAMPLES: usize = 2 * (MAX_ORDER * MAX_ORDER + MAX_ORDER) as usize + 32;
+
+/// Evaluates the Bessel function `J_n(x)` for integer order `n`.
+///
+/// Uses the trapezoidal rule on Bessel's integral `J_n(x) = 1/(2 pi) int cos(n tau - x sin tau)`
+/// over a full period. The integrand is smooth and periodic, so the error falls off like
+/// `J_(N - n)(x)` and vanishes to machine precision once `N` exceeds `|x| + |n|` comfortably.
+/// Beyond `max(1000, n^2)` the Hankel expansion takes over, so the sample count stays bounded.
+pub fn bessel_j(order: i32, x: f64) -> f64 {
+    if !x.is_finite() || order.unsigned_abs() > MAX_ORDER {
+        return f64::NAN;
+    }
+    let order_f64 = order as f64;
+    if x.abs() >= ASYMPTOTIC_MIN_X.max(order_f64 * order_f64) {
+        // J_n(-x) = (-1)^n J_n(x) and J_-n(x) = (-1)^n J_n(x).
+        let flipped = (x < 0.0) != (order < 0);
+        let sign = if flipped && order % 2 != 0 { -1.0 } else { 1.0 };
+        return sign * bessel_j_asymptotic(order.unsigned_abs(), x.abs());
+    }
+    let samples = (2 * (x.abs() + order_f64.abs()).ceil() as usize + 32).min(MAX_SAMPLES);
+    let step = 2.0 * PI / samples as f64;
+    let sum = (0..samples)
+        .map(|index| {
+            let tau = index as f64 * step;
+            (order_f64 * tau - x * tau.sin()).cos()
+        })
+        .sum::<f64>();
+    sum / samples as f64
+}
+
+/// Hankel's expansion `J_n(x) ~ sqrt(2 / (pi x)) (P cos chi - Q sin chi)` with
+/// `chi = x - (n / 2 + 1 / 4) pi`, summed until the terms reach rounding or start to grow.
+fn bessel_j_asymptotic(order: u32, x: f64) -> f64 {
+    let mu = 4.0 * (order as f64).powi(2);
+    let (mut p, mut q) = (0.0, 0.0);
+    let mut term = 1.0_f64;
+    let mut previous = f64::INFINITY;
+    for k in 0..64 {
+        if term.abs() > previous {
+            break;
+        }
+        match k % 4 {
+            0 => p += term,
+            1 => q += term,
+            2 => p -= term,
+            _ => q -= term,
+        }
+        if term.abs() <= f64::EPSILON * p.abs().max(q.abs()) {
+            break;
+        }
+        previous = term.abs();
+        let next = (k + 1) as f64;
+        term *= (mu - (2.0 * next - 1.0).powi(2)) / (8.0 * next * x);
+    }
+    let chi = x - (0.5 * order as f64 + 0.25) * PI;
+    (2.0 / (PI * x)).sqrt() * (p * chi.cos() - q * chi.sin())
+}
+
+/// Evaluates `J_n'(x)` through the recurrence `2 J_n' = J_(n-1) - J_(n+1)`.
+pub fn bessel_j_prime(order: i32, x: f64) -> f64 {
+    0.5 * (bessel_j(order - 1, x) - bessel_j(order + 1, x))
+}
+
+/// Returns the `index`-th positive zero of `J_n`, counting from one.
+pub fn bessel_j_zero(order: i32, index: usize) -> Result<f64, String> {
+    nth_positive_root(|x| bessel_j(order, x), index)
+        .ok_or_else(|| format!("zero {index} of J_{order} lies beyond x = {ZERO_SCAN_LIMIT}"))
+}
+
+/// Returns the `index`-th positive zero of `J_n'`, counting from one.
+///
+/// The root at `x = 0` that `J_n'` has for `n >= 2` is not counted, matching the usual
+/// `p'_nm` tables for TE modes.
+pub fn bessel_j_prime_zero(order: i32, index: usize) -> Result<f64, String> {
+    nth_positive_root(|x| bessel_j_prime(order, x), index)
+        .ok_or_else(|| format!("zero {index} of J_{order}' lies beyond x = {ZERO_SCAN_LIMIT}"))
+}
+
+/// Scans `(0, ZERO_SCAN_LIMIT)` for sign changes and refines the `index`-th one by bisection.
+///
+/// Returns `None` when the scan ends, or meets a non-finite value, before that root.
+fn nth_positive_root(function: impl Fn(f64) -> f64, index: usize) -> Option<f64> {
+    assert!(index >= 1, "root indices start at one");
+    let mut found = 0;
+    let mut left = ZERO_SCAN_STEP;
+    let mut left_value = function(left);
+    while left < ZERO_SCAN_LIMIT && left_value.is_finite() {
+        let right = left + ZERO_SCAN_STEP;
+        let right_value = function(right);
+        if !right_value.is_finite() {
+            return None;
+        }
+        if left_value == 0.0 || left_value.signum() != right_value.signum() {
+            found += 1;
+            if found == index {
+                return Some(bisect(&function, left, right));
+            }
+        }
+        left = right;
+        left_value = right_value;
+    }
+    None
+}
+
+fn bisect(function: &impl Fn(f64) -> f64, mut low: f64, mut high: f64) -> f64 {
+    let low_sign = function(low).signum();
+    for _ in 0..64 {
+        let mid = 0.5 * (low + high);
+        if function(mid).signum() == low_sign {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    0.5 * (low + high)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{bessel_j, bessel_j_prime, bessel_j_prime_zero, bessel_j_zero};
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1.0e-12,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn bessel_j_matches_reference_values() {
+        assert_close(bessel_j(0, 0.0), 1.0);
+        assert_close(bessel_j(3, 0.0), 0.0);
+        assert_close(bessel_j(0, 1.0), 0.765_197_686_557_966_6);
+        assert_close(bessel_j(1, 2.5), 0.497_094_102_464_274_4);
+        assert_close(bessel_j(2, 10.0), 0.254_630_313_685_120_9);
+        assert_close(bessel_j(-1, 2.5), -bessel_j(1, 2.5));
+    }
+
+    #[test]
+    fn bessel_j_switches_to_the_asymptotic_expansion_for_large_arguments() {
+        assert_close(bessel_j(0, 1000.5), 0.019_486_559_987_130_14);
+        assert_close(bessel_j(3, 2500.0), 0.015_907_426_871_279_75);
+        assert_close(bessel_j(-2, -1234.5), 0.013_579_893_604_811_57);
+        assert_close(bessel_j(-3, 2500.0), -bessel_j(3, 2500.0));
+        assert_close(bessel_j(0, 1000.0), 0.024_786_686_152_420_176);
+        assert!(bessel_j(0, 1.0e300).is_finite());
+        assert!(bessel_j(1000, 2.0).is_nan());
+    }
+
+    #[test]
+    fn bessel_j_prime_follows_the_order_zero_identity() {
+        for x in [0.3, 1.7, 6.2] {
+            assert_close(bessel_j_prime(0, x), -bessel_j(1, x));
+        }
+    }
+
+    #[test]
+    fn bessel_zeros_match_waveguide_tables() {
+        assert_close(bessel_j_zero(0, 1).unwrap(), 2.404_825_557_695_773);
+        assert_close(bessel_j_zero(1, 2).unwrap(), 7.015_586_669_815_619);
+        assert_close(bessel_j_prime_zero(1, 1).unwrap(), 1.841_183_781_340_659_3);
+        assert_close(bessel_j_prime_zero(0, 1).unwrap(), 3.831_705_970_207_512_3);
+        assert_close(bessel_j_prime_zero(2, 1).unwrap(), 3.054_236_928_227_140_3);
+    }
+
+    #[test]
+    fn bessel_zeros_beyond_the_scan_limit_are_errors() {
+        assert!(bessel_j_zero(0, 1000).is_err());
+        assert!(bessel_j_prime_zero(1000, 1).is_err());
+    }
+}
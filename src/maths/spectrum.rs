@@ -0,0 +1,231 @@
+//! Magnitude and phase spectra of sampled time series.
+//!
+//! Probe histories are recorded once per rendered frame, so their time steps follow the frame
+//! rate. The series is resampled onto a uniform grid before a Hann-windowed radix-2 FFT.
+
+use nalgebra::Complex;
+use std::f64::consts::PI;
+
+/// Fewer samples than this do not resolve anything useful.
+pub const MIN_SPECTRUM_SAMPLES: usize = 8;
+
+/// One-sided spectrum of a real series.
+///
+/// `phases[k]` is the phase `phi` of the `amplitudes[k] cos(omega t + phi)` component, measured
+/// against `t = 0` rather than against the start of the series.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spectrum {
+    pub angular_frequencies: Vec<f64>,
+    pub amplitudes: Vec<f64>,
+    pub phases: Vec<f64>,
+}
+
+/// Strongest non-constant component of a spectrum.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpectralPeak {
+    /// Index of the strongest bin, shared by spectra of series recorded at the same times.
+    pub bin: usize,
+    /// Peak angular frequency, refined between bins by a parabolic fit.
+    pub angular_frequency: f64,
+    pub amplitude: f64,
+    pub phase: f64,
+}
+
+impl Spectrum {
+    /// Computes the spectrum of `values` sampled at increasing `times`.
+    ///
+    /// Returns `None` for series that are too short, not strictly increasing, or span no time.
+    pub fn from_samples(times: &[f64], values: &[f64]) -> Option<Self> {
+        let count = times.len().min(values.len());
+        if count < MIN_SPECTRUM_SAMPLES || times.windows(2).any(|pair| pair[1] <= pair[0]) {
+            return None;
+        }
+        let (start, end) = (times[0], times[count - 1]);
+        let size = count.next_power_of_two();
+        let step = (end - start) / (size - 1) as f64;
+
+        let uniform = resample_uniform(&times[..count], &values[..count], start, step, size);
+        let mean = uniform.iter().sum::<f64>() / size as f64;
+        let window = (0..size)
+            .map(|index| 0.5 * (1.0 - (2.0 * PI * index as f64 / (size - 1) as f64).cos()))
+            .collect::<Vec<_>>();
+        let gain = window.iter().sum::<f64>();
+        let mut buffer = uniform
+            .iter()
+            .zip(&window)
+            .map(|(value, weight)| Complex::new((value - mean) * weight, 0.0))
+            .collect::<Vec<_>>();
+        fft(&mut buffer);
+
+        let bins = size / 2 + 1;
+        let angular_frequencies = (0..bins)
+            .map(|bin| 2.0 * PI * bin as f64 / (size as f64 * step))
+            .collect::<Vec<_>>();
+        let amplitudes = buffer[..bins]
+            .iter()
+            .enumerate()
+            .map(|(bin, value)| {
+                let scale = if bin == 0 || 2 * bin == size {
+                    1.0
+                } else {
+                    2.0
+                };
+                scale * value.norm() / gain
+            })
+            .collect();
+        let phases = buffer[..bins]
+            .iter()
+            .zip(&angular_frequencies)
+            .map(|(value, omega)| wrap_phase(value.arg() - omega * start))
+            .collect();
+
+        Some(Self {
+            angular_frequencies,
+            amplitudes,
+            phases,
+        })
+    }
+
+    /// Returns the strongest component above the constant bin, if any has non-zero amplitude.
+    pub fn dominant(&self) -> Option<SpectralPeak> {
+        let (bin, amplitude) = self
+            .amplitudes
+            .iter()
+            .enumerate()
+            .skip(1)
+            .max_by(|left, right| left.1.total_cmp(right.1))?;
+        if *amplitude <= 0.0 {
+            return None;
+        }
+
+        let mut offset = 0.0;
+        if let (Some(&left), Some(&right)) =
+            (self.amplitudes.get(bin - 1), self.amplitudes.get(bin + 1))
+        {
+            let curvature = left - 2.0 * amplitude + right;
+            if curvature < 0.0 {
+                offset = (0.5 * (left - right) / curvature).clamp(-0.5, 0.5);
+            }
+        }
+        let spacing = self.angular_frequencies.get(1).copied().unwrap_or(0.0);
+        Some(SpectralPeak {
+            bin,
+            angular_frequency: self.angular_frequencies[bin] + offset * spacing,
+            amplitude: *amplitude,
+            phase: self.phases[bin],
+        })
+    }
+}
+
+/// Wraps an angle into `(-pi, pi]`.
+pub fn wrap_phase(angle: f64) -> f64 {
+    let wrapped = angle.rem_euclid(2.0 * PI);
+    if wrapped > PI {
+        wrapped - 2.0 * PI
+    } else {
+        wrapped
+    }
+}
+
+/// Linearly interpolates the series at `start + k * step` for `k < size`.
+fn resample_uniform(times: &[f64], values: &[f64], start: f64, step: f64, size: usize) -> Vec<f64> {
+    let mut segment = 0;
+    (0..size)
+        .map(|index| {
+            let time = start + index as f64 * step;
+            while segment + 2 < times.len() && times[segment + 1] < time {
+                segment += 1;
+            }
+            let (t0, t1) = (times[segment], times[segment + 1]);
+            let fraction = ((time - t0) / (t1 - t0)).clamp(0.0, 1.0);
+            values[segment] + fraction * (values[segment + 1] - values[segment])
+        })
+        .collect()
+}
+
+/// In-place iterative radix-2 FFT; `buffer.len()` must be a power of two.
+fn fft(buffer: &mut [Complex<f64>]) {
+    let size = buffer.len();
+    let bits = size.trailing_zeros();
+    for index in 0..size {
+        let reversed = index.reverse_bits() >> (usize::BITS - bits);
+        if reversed > index {
+            buffer.swap(index, reversed);
+        }
+    }
+
+    let mut length = 2;
+    while length <= size {
+        let angle = -2.0 * PI / length as f64;
+        let root = Complex::new(angle.cos(), angle.sin());
+        for chunk in buffer.chunks_mut(length) {
+            let (low, high) = chunk.split_at_mut(length / 2);
+            let mut twiddle = Complex::new(1.0, 0.0);
+            for (even, odd) in low.iter_mut().zip(high.iter_mut()) {
+                let product = *odd * twiddle;
+                *odd = *even - product;
+                *even += product;
+                twiddle *= root;
+            }
+        }
+        length *= 2;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{wrap_phase, Spectrum};
+
+    /// Irregular frame times, as recorded by the render loop.
+    fn frame_times(count: usize) -> Vec<f64> {
+        let mut time = 0.3;
+        (0..count)
+            .map(|index| {
+                time += 0.016 + 0.004 * ((index * 7) % 5) as f64 / 4.0;
+                time
+            })
+            .collect()
+    }
+
+    #[test]
+    fn dominant_peak_recovers_frequency_amplitude_and_phase() {
+        let times = frame_times(700);
+        let values = times
+            .iter()
+            .map(|t| 0.5 + 2.0 * (3.0 * t + 0.4).cos())
+            .collect::<Vec<_>>();
+
+        let peak = Spectrum::from_samples(&times, &values)
+            .unwrap()
+            .dominant()
+            .unwrap();
+
+        assert!((peak.angular_frequency - 3.0).abs() < 0.05, "{peak:?}");
+        assert!((peak.amplitude - 2.0).abs() < 0.2, "{peak:?}");
+        assert!((wrap_phase(peak.phase - 0.4)).abs() < 0.1, "{peak:?}");
+    }
+
+    #[test]
+    fn phase_difference_between_two_series_is_their_lag() {
+        let times = frame_times(700);
+        let series = |lag: f64| {
+            times
+                .iter()
+                .map(|t| (5.0 * t - lag).sin())
+                .collect::<Vec<_>>()
+        };
+
+        let lead = Spectrum::from_samples(&times, &series(0.0)).unwrap();
+        let lag = Spectrum::from_samples(&times, &series(0.9)).unwrap();
+        let difference = lead.dominant().unwrap().phase - lag.dominant().unwrap().phase;
+
+        assert!((wrap_phase(difference) - 0.9).abs() < 1.0e-6);
+    }
+
+    #[test]
+    fn short_or_unordered_series_have_no_spectrum() {
+        assert!(Spectrum::from_samples(&[0.0, 1.0, 2.0], &[1.0, 2.0, 3.0]).is_none());
+        let times = [0.0, 1.0, 2.0, 3.0, 3.0, 5.0, 6.0, 7.0];
+        assert!(Spectrum::from_samples(&times, &[0.0; 8]).is_none());
+    }
+}
@@ -1,17 +1,22 @@
 //! Renderer for grid segments under the active scene transform.
 
+use crate::app::applied_config::AppliedLineColoring;
 use crate::app::grid::Grid;
 use crate::app::grid::SegmentDir;
 use crate::app::tangent_space::SceneSpaceTransform;
+use crate::app::ui::MorphHomotopy;
 use crate::render::grid_shader::GridShader;
 use crate::toolbox::opengl::shader::shader_program::Shader;
 use gl::types::GLsizei;
-use nalgebra::Matrix4;
+use nalgebra::{Matrix4, Vector3};
 use std::ptr::null;
 
 pub struct GridRenderer {
     shader: GridShader,
     projection: Matrix4<f64>, // Should use reference but lifetime needs to be handled
+    morph_s: f64,
+    time: f64,
+    line_range: (f64, f64),
 }
 
 impl GridRenderer {
@@ -21,7 +26,13 @@ impl GridRenderer {
         shader.bind();
         shader.load_projection_matrix(&projection);
         shader.unbind();
-        GridRenderer { shader, projection }
+        GridRenderer {
+            shader,
+            projection,
+            morph_s: 0.0,
+            time: 0.0,
+            line_range: (0.0, 1.0),
+        }
     }
 
     /// Draws every cached grid segment with the current view matrix and scene transform.
@@ -32,6 +43,21 @@ impl GridRenderer {
         scene_transform: &SceneSpaceTransform,
     ) {
         self.prepare(view_matrix, scene_transform);
+        self.draw_segments(grid, None);
+        self.unprepare();
+    }
+
+    /// Draws every cached grid segment in one `color`, untouched by the tangent blend.
+    pub fn render_tinted(&self, grid: &Grid, view_matrix: &Matrix4<f64>, color: Vector3<f64>) {
+        self.prepare(view_matrix, &SceneSpaceTransform::identity());
+        self.shader.load_color(color);
+        self.draw_segments(grid, Some(color));
+        self.unprepare();
+    }
+
+    /// Issues the segment draws; without a `tint`, each segment takes its axis color unless the
+    /// shader colors the lines by a quantity.
+    fn draw_segments(&self, grid: &Grid, tint: Option<Vector3<f64>>) {
         let data = grid.get_data();
         for (key, values) in data.iter() {
             let vao = key
@@ -41,7 +67,7 @@ impl GridRenderer {
             vao.binds(&[0]);
             let mut last_dir: Option<SegmentDir> = None;
             for (transform, dir) in values {
-                if last_dir != Some(*dir) {
+                if tint.is_none() && last_dir != Some(*dir) {
                     self.shader.load_color_from_dir(*dir);
                     last_dir = Some(*dir);
                 }
@@ -50,17 +76,49 @@ impl GridRenderer {
             }
             vao.unbinds(&[0]);
         }
-        self.unprepare();
     }
 
-    /// Rebuilds the editable grid vertex shader from the latest coordinate equations.
+    /// Rebuilds the editable grid vertex shader from GLSL of the latest coordinate equations.
     pub fn update_shader_eqs(&mut self, new_eqs: &[String; 3]) {
-        self.shader.edit_eqs(new_eqs);
+        self.update_morph_shader_eqs(
+            new_eqs,
+            new_eqs,
+            MorphHomotopy::Linear,
+            &AppliedLineColoring::default(),
+        );
+    }
+
+    /// Rebuilds the grid vertex shader to draw the morph from `new_eqs` toward `target_eqs`,
+    /// with lines colored as `coloring` asks.
+    pub fn update_morph_shader_eqs(
+        &mut self,
+        new_eqs: &[String; 3],
+        target_eqs: &[String; 3],
+        homotopy: MorphHomotopy,
+        coloring: &AppliedLineColoring,
+    ) {
+        self.shader
+            .edit_eqs(new_eqs, target_eqs, homotopy, coloring);
         self.shader.bind();
         self.shader.load_projection_matrix(&self.projection);
         self.shader.unbind();
     }
 
+    /// Sets the morph parameter drawn from the next frame on; cheap enough to call every frame.
+    pub fn set_morph_s(&mut self, s: f64) {
+        self.morph_s = s;
+    }
+
+    /// Sets the range of the line quantity that spans the colormap.
+    pub fn set_line_range(&mut self, range: (f64, f64)) {
+        self.line_range = range;
+    }
+
+    /// Sets the scene time moving embeddings are drawn at from the next frame on.
+    pub fn set_time(&mut self, time: f64) {
+        self.time = time;
+    }
+
     #[allow(dead_code)]
     /// Updates the projection matrix used by the grid renderer.
     pub fn update_projection(&mut self, projection: Matrix4<f64>) {
@@ -70,11 +128,15 @@ impl GridRenderer {
         self.shader.unbind();
     }
 
-    /// Binds the grid shader and loads the current view and scene-transform uniforms.
+    /// Binds the grid shader and loads the current view, scene-transform, morph, time and
+    /// line-range uniforms.
     fn prepare(&self, view_matrix: &Matrix4<f64>, scene_transform: &SceneSpaceTransform) {
         self.shader.bind();
         self.shader.load_view_matrix(view_matrix);
         self.shader.load_scene_transform(scene_transform);
+        self.shader.load_morph_s(self.morph_s);
+        self.shader.load_time(self.time);
+        self.shader.load_line_range(self.line_range);
     }
 
     /// Unbinds the grid shader after the grid pass.
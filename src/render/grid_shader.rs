@@ -1,7 +1,9 @@
 //! Shader wrapper for the editable grid vertex program and tangent blend uniforms.
 
+use crate::app::applied_config::AppliedLineColoring;
 use crate::app::grid::SegmentDir;
 use crate::app::tangent_space::SceneSpaceTransform;
+use crate::app::ui::{Colormap, LineQuantity, MorphHomotopy};
 use crate::toolbox::logging::LOGGER;
 use crate::toolbox::opengl::shader::shader_program::{Shader, ShaderProgram};
 use crate::toolbox::opengl::shader::uniform::floatuniform::FloatUniform;
@@ -23,6 +25,10 @@ pub struct GridShader {
     tangent_basis_z: Vec3Uniform,
     tangent_position_scale: FloatUniform,
     tangent_local_radius: FloatUniform,
+    morph_s: FloatUniform,
+    time: FloatUniform,
+    line_min: FloatUniform,
+    line_max: FloatUniform,
 }
 
 impl GridShader {
@@ -44,6 +50,10 @@ impl GridShader {
             tangent_basis_z: Vec3Uniform::new("tangent_basis_z"),
             tangent_position_scale: FloatUniform::new("tangent_position_scale"),
             tangent_local_radius: FloatUniform::new("tangent_local_radius"),
+            morph_s: FloatUniform::new("morph_s"),
+            time: FloatUniform::new("time"),
+            line_min: FloatUniform::new("line_min"),
+            line_max: FloatUniform::new("line_max"),
         }
     }
 
@@ -52,12 +62,50 @@ impl GridShader {
     /// The grid geometry cache stores abstract-space vertices and per-segment transforms, while
     /// the actual embedding equations are injected into the vertex shader at runtime. Updating the
     /// equations here keeps CPU-side segment topology and GPU-side embedding logic in sync.
-    pub fn edit_eqs(&mut self, new_eqs: &[String; 3]) {
+    ///
+    /// `new_eqs` and `target_eqs` must be GLSL float expressions over `x`, `y`, `z` and `t`, as
+    /// produced by `maths::glsl::expr_to_glsl`; user text is never pasted in directly. The drawn
+    /// embedding moves from `new_eqs` to `target_eqs` along `homotopy` as `morph_s` goes from 0
+    /// to 1. The line quantity and colormap of `coloring` are compiled in as constants.
+    pub fn edit_eqs(
+        &mut self,
+        new_eqs: &[String; 3],
+        target_eqs: &[String; 3],
+        homotopy: MorphHomotopy,
+        coloring: &AppliedLineColoring,
+    ) {
+        let homotopy_id = match homotopy {
+            MorphHomotopy::Linear => "0",
+            MorphHomotopy::Eased => "1",
+            MorphHomotopy::Radial => "2",
+        };
+        let quantity_id = match coloring.quantity {
+            LineQuantity::Axis => "0",
+            LineQuantity::ScaleFactor => "1",
+            LineQuantity::JacobianDeterminant => "2",
+            LineQuantity::Curvature => "3",
+            LineQuantity::ScalarField => "4",
+        };
+        let colormap_id = match coloring.colormap {
+            Colormap::Diverging => "0",
+            Colormap::Viridis => "1",
+            Colormap::Grayscale => "2",
+        };
         let src = self
             .vertex_editable_src
+            .replace("{{x_target}}", &target_eqs[0])
+            .replace("{{y_target}}", &target_eqs[1])
+            .replace("{{z_target}}", &target_eqs[2])
             .replace("{{x}}", &new_eqs[0])
             .replace("{{y}}", &new_eqs[1])
-            .replace("{{z}}", &new_eqs[2]);
+            .replace("{{z}}", &new_eqs[2])
+            .replace("{{homotopy}}", homotopy_id)
+            .replace("{{line_quantity}}", quantity_id)
+            .replace("{{colormap}}", colormap_id)
+            .replace(
+                "{{scalar}}",
+                coloring.scalar_glsl.as_deref().unwrap_or("0.0"),
+            );
         LOGGER.debug(src.as_str());
         self.shader_program.edit_vert_src(src);
         self.shader_program.bind_attrib(0, "position");
@@ -109,8 +157,23 @@ impl GridShader {
             .load_float_to_uniform(scene_transform.tangent_local_radius);
     }
 
+    /// Uploads the morph parameter `s`.
+    pub fn load_morph_s(&self, s: f64) {
+        self.morph_s.load_float_to_uniform(s);
+    }
+
+    /// Uploads the scene time read as `t` by moving embeddings.
+    pub fn load_time(&self, time: f64) {
+        self.time.load_float_to_uniform(time);
+    }
+
+    /// Uploads the range of the line quantity that spans the colormap.
+    pub fn load_line_range(&self, (min_value, max_value): (f64, f64)) {
+        self.line_min.load_float_to_uniform(min_value);
+        self.line_max.load_float_to_uniform(max_value);
+    }
+
     /// Uploads an explicit segment color.
-    #[allow(dead_code)]
     pub fn load_color(&self, color: Vector3<f64>) {
         self.color.load_vector_to_uniform(color);
     }
@@ -155,6 +218,10 @@ impl Shader for GridShader {
                 &mut self.tangent_basis_z.uniform,
                 &mut self.tangent_position_scale.uniform,
                 &mut self.tangent_local_radius.uniform,
+                &mut self.morph_s.uniform,
+                &mut self.time.uniform,
+                &mut self.line_min.uniform,
+                &mut self.line_max.uniform,
             ]);
         self.shader_program.store_all_uniforms(&mut uniforms);
     }
@@ -9,10 +9,12 @@ use crate::render::field_shader::FieldShader;
 use crate::render::grid_renderer::GridRenderer;
 use crate::render::grid_shader::GridShader;
 use crate::render::renderer::Renderer;
+use crate::render::surface_renderer::SurfaceRenderer;
+use crate::render::surface_shader::SurfaceShader;
 use crate::toolbox::camera::Camera;
 use crate::toolbox::opengl::open_gl_utils::open_gl_utils::clear_gl;
 use crate::toolbox::opengl::shader::shader_program::ShaderProgram;
-use nalgebra::{Matrix4, Perspective3};
+use nalgebra::{Matrix4, Perspective3, Vector3};
 
 const NEAR: f64 = 0.01;
 const FAR: f64 = 750.0;
@@ -21,8 +23,11 @@ const TANGENT_FOVY: f64 = 0.72;
 
 pub struct MasterRenderer {
     pub grid_renderer: GridRenderer,
+    /// One grid pass per overlay chart, each compiled with that chart's embedding.
+    chart_renderers: Vec<GridRenderer>,
     pub renderer: Renderer,
     pub field_renderer: FieldRenderer,
+    pub surface_renderer: SurfaceRenderer,
     pub projection: Matrix4<f64>,
     aspect_ratio: f64,
 }
@@ -32,10 +37,16 @@ impl MasterRenderer {
     pub fn new(w: f64, h: f64) -> Self {
         let aspect_ratio = aspect_ratio_for(w, h);
         let (grid_renderer, renderer, field_renderer, projection) = Self::init(aspect_ratio);
+        let surface_renderer = SurfaceRenderer::new(
+            SurfaceShader::new(ShaderProgram::new("surface")),
+            &projection,
+        );
         Self {
             grid_renderer,
+            chart_renderers: Vec::new(),
             renderer,
             field_renderer,
+            surface_renderer,
             projection,
             aspect_ratio,
         }
@@ -66,8 +77,12 @@ impl MasterRenderer {
         self.projection = projection_for_zoom_mix(self.aspect_ratio, mix);
         self.grid_renderer
             .update_projection(self.projection.clone());
+        for chart_renderer in &mut self.chart_renderers {
+            chart_renderer.update_projection(self.projection.clone());
+        }
         self.renderer.update_projection(&self.projection);
         self.field_renderer.update_projection(&self.projection);
+        self.surface_renderer.update_projection(&self.projection);
     }
 
     /// Runs the frame render passes in the correct order for the current scene state.
@@ -97,6 +112,52 @@ impl MasterRenderer {
         if let Some(sphere) = sphere {
             self.renderer.draw_point(sphere, &view_matrix);
         }
+        if show_grid {
+            self.surface_renderer.render(&view_matrix);
+            self.renderer.draw_walls(&view_matrix);
+        }
+    }
+
+    /// Keeps one chart grid pass per overlay chart, compiling new passes on demand.
+    pub fn resize_chart_renderers(&mut self, count: usize) {
+        self.chart_renderers.truncate(count);
+        while self.chart_renderers.len() < count {
+            let shader = GridShader::new(ShaderProgram::new("grid"));
+            self.chart_renderers
+                .push(GridRenderer::new(shader, self.projection.clone()));
+        }
+    }
+
+    /// Recompiles the grid pass of chart `index` for new embedding GLSL.
+    pub fn update_chart_eqs(&mut self, index: usize, eqs: &[String; 3]) {
+        self.chart_renderers[index].update_shader_eqs(eqs);
+    }
+
+    /// Draws each overlay chart grid in its color, then the field pushed onto one of them.
+    pub fn render_charts<'a>(
+        &self,
+        charts: impl IntoIterator<Item = (&'a Grid, Vector3<f64>)>,
+        pushed_field: &[RenderVField],
+        camera: &Camera,
+    ) {
+        let view_matrix = camera.get_view_matrix();
+        for (chart_renderer, (grid, color)) in self.chart_renderers.iter().zip(charts) {
+            chart_renderer.render_tinted(grid, &view_matrix, color);
+        }
+        self.field_renderer.render(pushed_field, &view_matrix);
+    }
+
+    /// Draws the singular sets of the embedding: red segments and red point markers.
+    pub fn render_singularities(&self, markers: &[Sphere], camera: &Camera) {
+        let view_matrix = camera.get_view_matrix();
+        self.renderer.draw_singular_lines(&view_matrix);
+        self.renderer.draw_points(markers, &view_matrix);
+    }
+
+    /// Draws probe markers on top of the frame rendered by `render`.
+    pub fn render_markers(&self, markers: &[Sphere], camera: &Camera) {
+        self.renderer
+            .draw_points(markers, &camera.get_view_matrix());
     }
 }
 
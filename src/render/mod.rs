@@ -5,3 +5,5 @@ pub mod grid_renderer;
 pub mod grid_shader;
 pub mod master_render;
 pub mod renderer;
+pub mod surface_renderer;
+pub mod surface_shader;
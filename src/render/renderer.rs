@@ -1,6 +1,8 @@
 #![allow(dead_code)]
 //! Shared mesh renderer used for spheres and other classic shaded draw calls.
 
+use crate::app::pec_walls::WallMesh;
+use crate::app::singularities::SingularityMesh;
 use crate::graphics::model::{Model, Sphere};
 use crate::render::classic_shader::ClassicShader;
 use crate::toolbox::opengl::open_gl_utils::open_gl_utils::set_wireframe_mode;
@@ -8,13 +10,26 @@ use crate::toolbox::opengl::shader::shader_program::Shader;
 use crate::toolbox::opengl::vao::VAO;
 use gl::types::GLsizei;
 use gl::{DrawElements, TRIANGLES, UNSIGNED_INT};
-use nalgebra::Matrix4;
+use nalgebra::{Matrix4, Vector4};
 use rustc_hash::FxHashMap;
 use std::ops::AddAssign;
 
+/// Translucent tint of conducting walls; low alpha keeps the field arrows behind them readable.
+const WALL_COLOR: Vector4<f64> = Vector4::new(0.75, 0.78, 0.85, 0.18);
+/// Copper tint of conductor surfaces, kept apart from the neutral grid walls.
+const CONDUCTOR_COLOR: Vector4<f64> = Vector4::new(0.85, 0.55, 0.30, 0.30);
+/// Dim tint of the region masked out of the source domain.
+const MASK_COLOR: Vector4<f64> = Vector4::new(0.35, 0.35, 0.40, 0.22);
+/// Opaque red of the singular sets of the coordinate embedding.
+pub const SINGULAR_COLOR: Vector4<f64> = Vector4::new(0.95, 0.10, 0.10, 1.0);
+
 pub struct Renderer {
     shader: ClassicShader,
     pub sphere_vao: VAO,
+    wall_vao: Option<VAO>,
+    conductor_vao: Option<VAO>,
+    mask_vao: Option<VAO>,
+    singular_vao: Option<VAO>,
     time: f64,
 }
 
@@ -29,6 +44,10 @@ impl Renderer {
         Renderer {
             shader,
             sphere_vao: point_vao,
+            wall_vao: None,
+            conductor_vao: None,
+            mask_vao: None,
+            singular_vao: None,
             time: 0.0,
         }
     }
@@ -106,6 +125,103 @@ impl Renderer {
         self.finish();
     }
 
+    /// Replaces the conducting-wall mesh, releasing the previous VAO.
+    pub fn update_walls(&mut self, mesh: WallMesh) {
+        self.wall_vao = Self::surface_vao(mesh);
+    }
+
+    /// Replaces the conductor and domain-mask surface meshes.
+    pub fn update_regions(&mut self, conductors: WallMesh, mask: WallMesh) {
+        self.conductor_vao = Self::surface_vao(conductors);
+        self.mask_vao = Self::surface_vao(mask);
+    }
+
+    fn surface_vao(mesh: WallMesh) -> Option<VAO> {
+        (!mesh.is_empty()).then(|| {
+            let mut vao = VAO::create_vao().expect("Error creating VAO");
+            vao.store_data(0, 3, mesh.vertices);
+            vao.store_indices(mesh.indices);
+            vao
+        })
+    }
+
+    /// Draws the conducting walls, conductors and masked region as a translucent overlay.
+    ///
+    /// Meant to run after the opaque passes: blending is enabled and depth writes are disabled
+    /// for the draw, so walls tint what lies behind them without hiding later geometry.
+    pub fn draw_walls(&self, view_matrix: &Matrix4<f64>) {
+        let surfaces = [
+            (&self.wall_vao, WALL_COLOR),
+            (&self.conductor_vao, CONDUCTOR_COLOR),
+            (&self.mask_vao, MASK_COLOR),
+        ];
+        if surfaces.iter().all(|(vao, _)| vao.is_none()) {
+            return;
+        }
+
+        self.prepare(view_matrix);
+        set_wireframe_mode(false);
+        unsafe {
+            gl::Enable(gl::BLEND);
+            gl::BlendFunc(gl::SRC_ALPHA, gl::ONE_MINUS_SRC_ALPHA);
+            gl::DepthMask(gl::FALSE);
+        }
+        self.shader.load_transformation_matrix(&Matrix4::identity());
+        for (vao, color) in surfaces {
+            let Some(vao) = vao else {
+                continue;
+            };
+            vao.binds(&[0]);
+            self.shader.load_color(color);
+            unsafe {
+                DrawElements(
+                    TRIANGLES,
+                    vao.get_vertex_count() as GLsizei,
+                    UNSIGNED_INT,
+                    0 as *const _,
+                );
+            }
+            vao.unbinds(&[0]);
+        }
+        unsafe {
+            gl::DepthMask(gl::TRUE);
+            gl::Disable(gl::BLEND);
+        }
+        self.finish();
+    }
+
+    /// Replaces the line segments drawn over the singular sets; their point markers are drawn
+    /// as spheres by the caller.
+    pub fn update_singular_lines(&mut self, mesh: &SingularityMesh) {
+        self.singular_vao = (!mesh.indices.is_empty()).then(|| {
+            let mut vao = VAO::create_vao().expect("Error creating VAO");
+            vao.store_data(0, 3, mesh.vertices.clone());
+            vao.store_indices_line(mesh.indices.clone());
+            vao
+        });
+    }
+
+    /// Draws the singular-set segments as opaque red lines.
+    pub fn draw_singular_lines(&self, view_matrix: &Matrix4<f64>) {
+        let Some(vao) = &self.singular_vao else {
+            return;
+        };
+        self.prepare(view_matrix);
+        self.shader.load_transformation_matrix(&Matrix4::identity());
+        self.shader.load_color(SINGULAR_COLOR);
+        vao.binds(&[0]);
+        unsafe {
+            DrawElements(
+                gl::LINES,
+                vao.get_vertex_count() as GLsizei,
+                UNSIGNED_INT,
+                0 as *const _,
+            );
+        }
+        vao.unbinds(&[0]);
+        self.finish();
+    }
+
     /// Updates the classic shader projection matrix used by subsequent draws.
     pub fn update_projection(&mut self, projection: &Matrix4<f64>) {
         self.shader.bind();
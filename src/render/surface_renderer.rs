@@ -0,0 +1,101 @@
+//! Renderer for the shaded constant-coordinate surfaces.
+
+use crate::app::coordinate_surfaces::SurfaceMesh;
+use crate::render::surface_shader::SurfaceShader;
+use crate::toolbox::opengl::open_gl_utils::open_gl_utils::set_wireframe_mode;
+use crate::toolbox::opengl::shader::shader_program::Shader;
+use crate::toolbox::opengl::vao::VAO;
+use gl::types::GLsizei;
+use nalgebra::{Matrix4, Vector3};
+
+/// Camera-space direction of the headlight: from above and left of the viewer, into the scene.
+const LIGHT_DIRECTION: Vector3<f64> = Vector3::new(0.35, -0.5, -1.0);
+
+pub struct SurfaceRenderer {
+    shader: SurfaceShader,
+    vao: Option<VAO>,
+    opacity: f64,
+}
+
+impl SurfaceRenderer {
+    /// Creates the surface renderer with its initial projection matrix and no mesh.
+    pub fn new(mut shader: SurfaceShader, projection: &Matrix4<f64>) -> SurfaceRenderer {
+        shader.bind();
+        shader.store_all_uniforms();
+        shader.load_projection_matrix(projection);
+        shader.load_light_direction(LIGHT_DIRECTION);
+        shader.unbind();
+        SurfaceRenderer {
+            shader,
+            vao: None,
+            opacity: 1.0,
+        }
+    }
+
+    /// Replaces the surface mesh, releasing the previous VAO.
+    pub fn update_mesh(&mut self, mesh: SurfaceMesh) {
+        self.vao = (!mesh.is_empty()).then(|| {
+            let mut vao = VAO::create_vao().expect("Error creating VAO");
+            vao.store_data(0, 3, mesh.vertices);
+            vao.store_data(1, 3, mesh.normals);
+            vao.store_data(2, 3, mesh.colors);
+            vao.store_indices(mesh.indices);
+            vao
+        });
+    }
+
+    /// Sets the opacity used by the next draws, clamped to `[0, 1]`.
+    pub fn set_opacity(&mut self, opacity: f64) {
+        self.opacity = opacity.clamp(0.0, 1.0);
+    }
+
+    /// Draws the surfaces after the opaque passes.
+    ///
+    /// Opaque surfaces write depth like any mesh. Translucent ones blend without writing depth,
+    /// so the grid and arrows stay visible through them whatever the draw order.
+    pub fn render(&self, view_matrix: &Matrix4<f64>) {
+        let Some(vao) = &self.vao else {
+            return;
+        };
+        if self.opacity <= 0.0 {
+            return;
+        }
+        let translucent = self.opacity < 1.0;
+
+        self.shader.bind();
+        self.shader.load_view_matrix(view_matrix);
+        self.shader.load_opacity(self.opacity);
+        set_wireframe_mode(false);
+        if translucent {
+            unsafe {
+                gl::Enable(gl::BLEND);
+                gl::BlendFunc(gl::SRC_ALPHA, gl::ONE_MINUS_SRC_ALPHA);
+                gl::DepthMask(gl::FALSE);
+            }
+        }
+        vao.binds(&[0, 1, 2]);
+        unsafe {
+            gl::DrawElements(
+                gl::TRIANGLES,
+                vao.get_vertex_count() as GLsizei,
+                gl::UNSIGNED_INT,
+                0 as *const _,
+            );
+        }
+        vao.unbinds(&[0, 1, 2]);
+        if translucent {
+            unsafe {
+                gl::DepthMask(gl::TRUE);
+                gl::Disable(gl::BLEND);
+            }
+        }
+        self.shader.unbind();
+    }
+
+    /// Updates the projection matrix used by the surface pass.
+    pub fn update_projection(&mut self, projection: &Matrix4<f64>) {
+        self.shader.bind();
+        self.shader.load_projection_matrix(projection);
+        self.shader.unbind();
+    }
+}
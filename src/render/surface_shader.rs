@@ -0,0 +1,74 @@
+//! Typed uniform wrapper around the Phong-lit coordinate-surface shader.
+
+use crate::toolbox::opengl::shader::shader_program::{Shader, ShaderProgram};
+use crate::toolbox::opengl::shader::uniform::floatuniform::FloatUniform;
+use crate::toolbox::opengl::shader::uniform::matrix4uniform::Matrix4Uniform;
+use crate::toolbox::opengl::shader::uniform::uniform::Uniform;
+use crate::toolbox::opengl::shader::uniform::vec3uniform::Vec3Uniform;
+use nalgebra::{Matrix4, Vector3};
+
+pub struct SurfaceShader {
+    shader_program: ShaderProgram,
+    projection_matrix: Matrix4Uniform,
+    view_matrix: Matrix4Uniform,
+    light_direction: Vec3Uniform,
+    opacity: FloatUniform,
+}
+
+impl SurfaceShader {
+    /// Creates the surface shader wrapper and prepares its typed uniform handles.
+    ///
+    /// Attribute locations are fixed in `surface.vert`: position, normal, then color.
+    pub fn new(program: ShaderProgram) -> SurfaceShader {
+        SurfaceShader {
+            shader_program: program,
+            projection_matrix: Matrix4Uniform::new("projection_matrix"),
+            view_matrix: Matrix4Uniform::new("view_matrix"),
+            light_direction: Vec3Uniform::new("light_direction"),
+            opacity: FloatUniform::new("opacity"),
+        }
+    }
+
+    /// Uploads the projection matrix uniform.
+    pub fn load_projection_matrix(&self, matrix: &Matrix4<f64>) {
+        self.projection_matrix.load_matrix_to_uniform(matrix);
+    }
+
+    /// Uploads the current view matrix uniform.
+    pub fn load_view_matrix(&self, matrix: &Matrix4<f64>) {
+        self.view_matrix.load_matrix_to_uniform(matrix);
+    }
+
+    /// Uploads the direction light travels in, in camera space.
+    pub fn load_light_direction(&self, direction: Vector3<f64>) {
+        self.light_direction.load_vector_to_uniform(direction);
+    }
+
+    /// Uploads the surface opacity, from fully transparent `0` to opaque `1`.
+    pub fn load_opacity(&self, opacity: f64) {
+        self.opacity.load_float_to_uniform(opacity);
+    }
+}
+
+impl Shader for SurfaceShader {
+    /// Binds the underlying shader program.
+    fn bind(&self) {
+        self.shader_program.bind()
+    }
+
+    /// Unbinds the underlying shader program.
+    fn unbind(&self) {
+        self.shader_program.unbind()
+    }
+
+    /// Caches all uniform locations needed by the surface shader wrapper.
+    fn store_all_uniforms(&mut self) {
+        let mut uniforms: Box<[&mut Uniform]> = Box::new([
+            &mut self.projection_matrix.uniform,
+            &mut self.view_matrix.uniform,
+            &mut self.light_direction.uniform,
+            &mut self.opacity.uniform,
+        ]);
+        self.shader_program.store_all_uniforms(&mut uniforms);
+    }
+}
@@ -2,10 +2,10 @@
 
 uniform mat4 transformation_matrix;
 
-uniform vec3 segment_color;
+in vec3 line_color;
 
 out vec4 FragColor;
 
 void main() {
-    FragColor = vec4(segment_color, 1.0);
+    FragColor = vec4(line_color, 1.0);
 }
@@ -11,6 +11,9 @@ uniform vec3 tangent_basis_y;
 uniform vec3 tangent_basis_z;
 uniform float tangent_position_scale;
 uniform float tangent_local_radius;
+uniform vec3 segment_color;
+
+out vec3 line_color;
 
 float f(vec3 pos) {
 //    return pos.x;
@@ -48,6 +51,7 @@ bool segment_intersects_local_box(vec3 p0, vec3 p1, float radius) {
 }
 
 void main() {
+    line_color = segment_color;
     vec3 abstract_pos = (transformation_matrix * vec4(position, 1.0)).xyz;
     vec3 segment_start = (transformation_matrix * vec4(0.0, 0.0, 0.0, 1.0)).xyz;
     vec3 segment_end = (transformation_matrix * vec4(1.0, 0.0, 0.0, 1.0)).xyz;
@@ -11,31 +11,165 @@ uniform vec3 tangent_basis_y;
 uniform vec3 tangent_basis_z;
 uniform float tangent_position_scale;
 uniform float tangent_local_radius;
+uniform float morph_s;
+// Scene time, the `t` of moving embeddings.
+uniform float time;
+uniform vec3 segment_color;
+// Range of the line quantity mapped onto the colormap.
+uniform float line_min;
+uniform float line_max;
+
+out vec3 line_color;
+
+// 0: linear, 1: eased, 2: radial; see MorphHomotopy.
+const int MORPH_HOMOTOPY = {{homotopy}};
+// 0: axis color, 1: h_i, 2: det J, 3: curvature, 4: scalar field; see LineQuantity.
+const int LINE_QUANTITY = {{line_quantity}};
+// 0: diverging, 1: viridis, 2: grayscale; see Colormap and ui::legend::colormap_color.
+const int COLORMAP = {{colormap}};
+// Central-difference step along a line, in abstract units; see app::line_coloring.
+const float DIFF_STEP = 0.01;
+
+// Integer power that, unlike pow, is defined for negative bases.
+float int_pow(float base, int exponent) {
+    float magnitude = pow(abs(base), float(exponent));
+    return (base < 0.0 && abs(exponent) % 2 == 1) ? -magnitude : magnitude;
+}
 
 float f(vec3 pos) {
     float x = pos.x;
     float y = pos.y;
     float z = pos.z;
+    float t = time;
     return {{x}};
 }
 float g(vec3 pos) {
     float x = pos.x;
     float y = pos.y;
     float z = pos.z;
+    float t = time;
     return {{y}};
 }
 float h(vec3 pos) {
     float x = pos.x;
     float y = pos.y;
     float z = pos.z;
+    float t = time;
     return {{z}};
 }
 
+float f_target(vec3 pos) {
+    float x = pos.x;
+    float y = pos.y;
+    float z = pos.z;
+    float t = time;
+    return {{x_target}};
+}
+float g_target(vec3 pos) {
+    float x = pos.x;
+    float y = pos.y;
+    float z = pos.z;
+    float t = time;
+    return {{y_target}};
+}
+float h_target(vec3 pos) {
+    float x = pos.x;
+    float y = pos.y;
+    float z = pos.z;
+    float t = time;
+    return {{z_target}};
+}
+
+vec3 morph(vec3 source, vec3 target, float s) {
+    if (s <= 0.0) {
+        return source;
+    }
+    if (s >= 1.0) {
+        return target;
+    }
+    float weight = MORPH_HOMOTOPY == 1 ? smoothstep(0.0, 1.0, s) : s;
+    vec3 blended = mix(source, target, weight);
+    if (MORPH_HOMOTOPY != 2 || length(blended) < 1e-6) {
+        return blended;
+    }
+    return blended * (mix(length(source), length(target), weight) / length(blended));
+}
+
 vec3 coordinate_transform(vec3 pos) {
-    float x = f(pos);
-    float y = g(pos);
-    float z = h(pos);
-    return vec3(x, y, z);
+    vec3 source = vec3(f(pos), g(pos), h(pos));
+    vec3 target = vec3(f_target(pos), g_target(pos), h_target(pos));
+    return morph(source, target, morph_s);
+}
+
+float scalar_field(vec3 pos) {
+    float x = pos.x;
+    float y = pos.y;
+    float z = pos.z;
+    float t = time;
+    return {{scalar}};
+}
+
+vec3 partial_derivative(vec3 pos, vec3 axis) {
+    vec3 ahead = coordinate_transform(pos + axis * DIFF_STEP);
+    vec3 behind = coordinate_transform(pos - axis * DIFF_STEP);
+    return (ahead - behind) / (2.0 * DIFF_STEP);
+}
+
+float line_quantity(vec3 pos, vec3 axis) {
+    if (LINE_QUANTITY == 4) {
+        return scalar_field(pos);
+    }
+    if (LINE_QUANTITY == 2) {
+        return determinant(mat3(
+            partial_derivative(pos, vec3(1.0, 0.0, 0.0)),
+            partial_derivative(pos, vec3(0.0, 1.0, 0.0)),
+            partial_derivative(pos, vec3(0.0, 0.0, 1.0))
+        ));
+    }
+    vec3 velocity = partial_derivative(pos, axis);
+    float speed = length(velocity);
+    if (LINE_QUANTITY == 1) {
+        return speed;
+    }
+    if (speed < 1e-6) {
+        return 0.0;
+    }
+    vec3 acceleration = (coordinate_transform(pos + axis * DIFF_STEP)
+        - 2.0 * coordinate_transform(pos)
+        + coordinate_transform(pos - axis * DIFF_STEP)) / (DIFF_STEP * DIFF_STEP);
+    return length(cross(velocity, acceleration)) / (speed * speed * speed);
+}
+
+vec3 ramp(vec3 stops[5], int count, float value) {
+    float position = clamp(value, 0.0, 1.0) * float(count - 1);
+    int index = min(int(floor(position)), count - 2);
+    return mix(stops[index], stops[index + 1], position - float(index));
+}
+
+vec3 colormap(float value) {
+    float range = line_max - line_min;
+    float mix_value = range > 1e-6 ? (value - line_min) / range : 0.5;
+    if (COLORMAP == 1) {
+        vec3 viridis[5] = vec3[5](
+            vec3(0.267, 0.005, 0.329),
+            vec3(0.229, 0.322, 0.546),
+            vec3(0.128, 0.567, 0.551),
+            vec3(0.369, 0.789, 0.383),
+            vec3(0.993, 0.906, 0.144)
+        );
+        return ramp(viridis, 5, mix_value);
+    }
+    if (COLORMAP == 2) {
+        return vec3(mix(0.1, 0.95, clamp(mix_value, 0.0, 1.0)));
+    }
+    vec3 diverging[5] = vec3[5](
+        vec3(0.08, 0.22, 1.0),
+        vec3(0.95, 0.95, 1.0),
+        vec3(1.0, 0.18, 0.08),
+        vec3(0.0),
+        vec3(0.0)
+    );
+    return ramp(diverging, 3, mix_value);
 }
 
 vec3 tangent_transform(vec3 pos) {
@@ -57,6 +191,10 @@ void main() {
     vec3 abstract_pos = (transformation_matrix * vec4(position, 1.0)).xyz;
     vec3 segment_start = (transformation_matrix * vec4(0.0, 0.0, 0.0, 1.0)).xyz;
     vec3 segment_end = (transformation_matrix * vec4(1.0, 0.0, 0.0, 1.0)).xyz;
+    vec3 segment_axis = normalize(segment_end - segment_start);
+    line_color = LINE_QUANTITY == 0
+        ? segment_color
+        : colormap(line_quantity(abstract_pos, segment_axis));
     vec3 local_start = (segment_start - tangent_anchor_abstract) * tangent_position_scale;
     vec3 local_end = (segment_end - tangent_anchor_abstract) * tangent_position_scale;
     if (tangent_mix > 0.0 && !segment_intersects_local_box(local_start, local_end, tangent_local_radius)) {
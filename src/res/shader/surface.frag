@@ -0,0 +1,29 @@
+#version 330 core
+
+in vec3 view_position;
+in vec3 view_normal;
+in vec3 surface_color;
+
+uniform vec3 light_direction;
+uniform float opacity;
+
+out vec4 FragColor;
+
+const float AMBIENT = 0.25;
+const float DIFFUSE = 0.75;
+const float SPECULAR = 0.35;
+const float SHININESS = 32.0;
+
+void main() {
+    vec3 to_eye = normalize(-view_position);
+    vec3 normal = normalize(view_normal);
+    // Surfaces are seen from both sides; light the side facing the camera.
+    if (dot(normal, to_eye) < 0.0) {
+        normal = -normal;
+    }
+    vec3 to_light = normalize(-light_direction);
+    float diffuse = max(dot(normal, to_light), 0.0);
+    float specular = pow(max(dot(reflect(-to_light, normal), to_eye), 0.0), SHININESS);
+    vec3 lit = surface_color * (AMBIENT + DIFFUSE * diffuse) + vec3(SPECULAR * specular);
+    FragColor = vec4(lit, opacity);
+}
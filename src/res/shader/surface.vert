@@ -0,0 +1,22 @@
+#version 330 core
+
+// Explicit locations: the program is linked before attributes could be bound by name.
+layout(location = 0) in vec3 position;
+layout(location = 1) in vec3 normal;
+layout(location = 2) in vec3 color;
+
+uniform mat4 projection_matrix;
+uniform mat4 view_matrix;
+
+out vec3 view_position;
+out vec3 view_normal;
+out vec3 surface_color;
+
+void main() {
+    vec4 eye_position = view_matrix * vec4(position, 1.0);
+    view_position = eye_position.xyz;
+    // The camera view is rigid, so its rotation part also transforms normals.
+    view_normal = mat3(view_matrix) * normal;
+    surface_color = color;
+    gl_Position = projection_matrix * eye_position;
+}
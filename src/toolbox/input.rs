@@ -9,6 +9,7 @@ pub struct Input {
     pub d_mouse_pos: (f64, f64),
     left_mouse_button: bool,
     right_mouse_button: bool,
+    buttons_pressed_this_frame: Vec<glfw::MouseButton>,
 }
 
 impl Input {
@@ -21,14 +22,16 @@ impl Input {
             d_mouse_pos: (0.0, 0.0),
             left_mouse_button: false,
             right_mouse_button: false,
+            buttons_pressed_this_frame: Vec::new(),
         }
     }
 
-    /// Clears the edge-triggered keyboard state for the new frame.
+    /// Clears the edge-triggered keyboard and mouse state for the new frame.
     ///
-    /// Keys that remain held stay in `input_keyboard`; only the one-frame press edges are reset.
+    /// Keys and buttons that remain held stay pressed; only the one-frame press edges are reset.
     pub fn begin_frame(&mut self) {
         self.pressed_this_frame.clear();
+        self.buttons_pressed_this_frame.clear();
     }
 
     /// Updates the cached keyboard state from one GLFW key event.
@@ -56,6 +59,9 @@ impl Input {
 
     /// Updates the cached mouse button state from one GLFW mouse event.
     pub fn mouse_button_handler(&mut self, action: Action, button: glfw::MouseButton) {
+        if action == Action::Press && !self.buttons_pressed_this_frame.contains(&button) {
+            self.buttons_pressed_this_frame.push(button);
+        }
         match button {
             glfw::MouseButton::Left => {
                 if action == Action::Press {
@@ -93,12 +99,17 @@ impl Input {
             _ => false,
         }
     }
+
+    /// Returns whether the mouse button transitioned to pressed during the current frame.
+    pub fn is_mouse_button_just_pressed(&self, button: glfw::MouseButton) -> bool {
+        self.buttons_pressed_this_frame.contains(&button)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::Input;
-    use glfw::{Action, Key};
+    use glfw::{Action, Key, MouseButton};
 
     #[test]
     fn just_pressed_only_last_for_one_frame() {
@@ -133,4 +144,21 @@ mod tests {
         input.key_handler(Action::Release, Key::T);
         assert!(!input.is_key_pressed(Key::T));
     }
+
+    #[test]
+    fn mouse_press_edge_lasts_one_frame_while_the_button_is_held() {
+        let mut input = Input::new();
+
+        input.begin_frame();
+        input.mouse_button_handler(Action::Press, MouseButton::Right);
+
+        assert!(input.is_mouse_button_pressed(MouseButton::Right));
+        assert!(input.is_mouse_button_just_pressed(MouseButton::Right));
+        assert!(!input.is_mouse_button_just_pressed(MouseButton::Left));
+
+        input.begin_frame();
+
+        assert!(input.is_mouse_button_pressed(MouseButton::Right));
+        assert!(!input.is_mouse_button_just_pressed(MouseButton::Right));
+    }
 }
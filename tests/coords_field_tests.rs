@@ -1,6 +1,7 @@
 use mathhook_core::Parser;
 use nalgebra::{vector, Vector3};
-use render_engine::app::coords_sys::CoordsSys;
+use render_engine::app::coords_sys::{CoordsSys, InverseFailure};
+use render_engine::app::grid_world::{GridSample, GridWorld};
 use render_engine::maths::differential::Form;
 use render_engine::maths::expr_to_fastexpr3d;
 use render_engine::maths::field::VectorField;
@@ -517,3 +518,48 @@ fn two_form_to_otn_base_handles_scaled_orthogonal_metric() {
     assert_close(eval(1), 0.0, "transformed yz component");
     assert_close(eval(2), 0.0, "transformed zx component");
 }
+
+#[test]
+fn inverse_recovers_cylindrical_coordinates_and_wraps_the_angle_into_the_bounds() {
+    let coords = CoordsSys::new(parse("x*cos(y)"), parse("x*sin(y)"), parse("z"));
+    let seed = vector![2.0, -0.25, 0.5];
+    let grid_world = GridWorld::from_samples(vec![GridSample {
+        world_pos: coords.eval_position(seed),
+        abstract_pos: seed,
+    }]);
+    let bounds = [(0.0, 3.0), (0.0, 2.0 * std::f64::consts::PI), (-1.0, 1.0)];
+
+    let world = coords.eval_position(vector![2.2, -0.3, 0.4]);
+    let inverse = coords.inverse(world, &grid_world, &bounds).unwrap();
+
+    assert_vec3_close(
+        inverse,
+        vector![2.2, 2.0 * std::f64::consts::PI - 0.3, 0.4],
+        "wrapped preimage",
+    );
+
+    let beyond = coords.eval_position(vector![2.0, -0.25, 2.0]);
+    assert!(matches!(
+        coords.inverse(beyond, &grid_world, &bounds),
+        Err(InverseFailure::OutsideBounds { .. })
+    ));
+    assert_eq!(
+        coords.inverse(vector![f64::NAN, 0.0, 0.0], &grid_world, &bounds),
+        Err(InverseFailure::NoSeed)
+    );
+}
+
+#[test]
+fn pull_back_cartesian_turns_a_rigid_rotation_into_its_polar_one_form() {
+    let polar = CoordsSys::new(parse("x*cos(y)"), parse("x*sin(y)"), parse("z"));
+    let (minus_y, x, zero) = (parse("-Y"), parse("X"), parse("0"));
+    let form = polar.pull_back_cartesian([&minus_y, &x, &zero], ["X", "Y", "Z"]);
+
+    // `-Y dX + X dY = r² dθ` in polar coordinates.
+    for (r, theta) in [(1.0, 0.3), (2.5, -1.2)] {
+        let component = |i: usize| expr_to_fastexpr3d(form.get_expr(i).clone())(r, theta, 0.7);
+        assert_close(component(0), 0.0, "pulled-back r component");
+        assert_close(component(1), r * r, "pulled-back theta component");
+        assert_close(component(2), 0.0, "pulled-back z component");
+    }
+}
@@ -0,0 +1,193 @@
+use mathhook_core::Parser;
+use render_engine::maths::differential::{basis_elements, Form, FormBasis};
+use render_engine::maths::space::{Space, Variables};
+use render_engine::maths::spacetime::{Spacetime, LORENTZIAN_SIGNATURE};
+use render_engine::maths::{num, Expr, ExternalDerivative};
+use std::collections::HashMap;
+
+const EPS: f64 = 1.0e-9;
+
+fn parse(expr: &str) -> Expr {
+    Parser::default().parse(expr).unwrap()
+}
+
+fn eval_at(expr: &Expr, values: &[(&str, f64)]) -> f64 {
+    let vars = values
+        .iter()
+        .map(|(name, value)| (name.to_string(), num(*value)))
+        .collect::<HashMap<_, _>>();
+    expr.substitute_and_simplify(&vars)
+        .evaluate_to_f64()
+        .unwrap()
+}
+
+fn assert_close(actual: f64, expected: f64, context: &str) {
+    assert!(
+        (actual - expected).abs() <= EPS,
+        "{context}: expected {expected:.8}, got {actual:.8}"
+    );
+}
+
+fn txyz() -> Variables {
+    Variables::new(["t", "x", "y", "z"])
+}
+
+#[test]
+fn basis_elements_keep_cyclic_3d_two_forms_and_lexicographic_otherwise() {
+    assert_eq!(
+        basis_elements(3, 2),
+        vec![vec![0, 1], vec![1, 2], vec![2, 0]]
+    );
+    assert_eq!(
+        basis_elements(4, 2),
+        vec![
+            vec![0, 1],
+            vec![0, 2],
+            vec![0, 3],
+            vec![1, 2],
+            vec![1, 3],
+            vec![2, 3],
+        ]
+    );
+    assert_eq!(basis_elements(4, 3).len(), 4);
+    assert_eq!(basis_elements(2, 0), vec![Vec::<usize>::new()]);
+}
+
+#[test]
+fn four_dimensional_exterior_derivative_of_a_one_form() {
+    // A = x t dy, so dA = x dt^dy + t dx^dy.
+    let mut potential = Form::new_over(
+        txyz(),
+        vec![parse("0"), parse("0"), parse("x*t"), parse("0")],
+        1,
+        FormBasis::Natural,
+    );
+
+    let field = potential.d();
+    let at = [("t", 2.0), ("x", 3.0), ("y", 0.5), ("z", -1.0)];
+
+    assert_eq!(field.n_forms(), 2);
+    assert_close(eval_at(&field.component(&[0, 2]), &at), 3.0, "F_ty");
+    assert_close(eval_at(&field.component(&[2, 0]), &at), -3.0, "F_yt");
+    assert_close(eval_at(&field.component(&[1, 2]), &at), 2.0, "F_xy");
+    assert_close(eval_at(&field.component(&[0, 1]), &at), 0.0, "F_tx");
+    assert_close(eval_at(&field.component(&[1, 1]), &at), 0.0, "F_xx");
+}
+
+#[test]
+fn exterior_derivative_squares_to_zero_in_four_dimensions() {
+    let mut potential = Form::new_over(
+        txyz(),
+        vec![
+            parse("-x*t"),
+            parse("y*z"),
+            parse("sin(z - t)"),
+            parse("x*x*y"),
+        ],
+        1,
+        FormBasis::Natural,
+    );
+
+    assert!(potential.d().d().is_zero());
+}
+
+#[test]
+fn hodge_star_3d_follows_the_orthonormal_rules() {
+    // 2-forms are stored as (e1^e2, e2^e3, e3^e1).
+    let two_form = Form::new_otn(vec![parse("1"), parse("2"), parse("3")], 2);
+    let one_form = Form::new_otn(vec![parse("4"), parse("5"), parse("6")], 1);
+    let three_form = Form::new_otn(vec![parse("7")], 3);
+
+    let cases = [
+        (
+            two_form.hodge_star_otn_3d(),
+            vec![2.0, 3.0, 1.0],
+            "*(e2^e3) = e1",
+        ),
+        (
+            one_form.hodge_star_otn_3d(),
+            vec![6.0, 4.0, 5.0],
+            "*e1 = e2^e3",
+        ),
+        (three_form.hodge_star_otn_3d(), vec![7.0], "*(e1^e2^e3) = 1"),
+    ];
+    for (star, expected, label) in cases {
+        for (index, value) in expected.into_iter().enumerate() {
+            assert_close(eval_at(star.get_expr(index), &[]), value, label);
+        }
+    }
+}
+
+#[test]
+fn top_degree_basis_change_uses_the_full_determinant() {
+    // A sheared frame: the product of the first column's entries is 0, the determinant is -10.
+    let matrix = [[1.0, 2.0, 0.0], [3.0, 4.0, 0.0], [0.0, 0.0, 5.0]];
+    let three_form = Form::new_in_basis(vec![parse("2")], 3, FormBasis::Natural);
+
+    let changed = three_form.change_basis(
+        |row, column| Expr::number(matrix[row][column]),
+        FormBasis::Orthonormal,
+    );
+
+    assert_close(eval_at(changed.get_expr(0), &[]), -20.0, "det(M) * 2");
+}
+
+#[test]
+fn lorentzian_hodge_star_squares_to_minus_one_on_two_forms() {
+    let two_form = Form::new_over(
+        txyz(),
+        ["1", "2", "3", "4", "5", "6"].map(parse).to_vec(),
+        2,
+        FormBasis::Orthonormal,
+    );
+
+    let twice = two_form
+        .hodge_star_otn(&LORENTZIAN_SIGNATURE)
+        .hodge_star_otn(&LORENTZIAN_SIGNATURE);
+
+    for index in 0..6 {
+        assert_close(
+            eval_at(twice.get_expr(index), &[]),
+            -eval_at(two_form.get_expr(index), &[]),
+            "**F",
+        );
+    }
+}
+
+#[test]
+fn space_from_embedding_supports_named_surface_coordinates() {
+    // Unit sphere over (theta, phi): g = diag(1, sin(theta)^2).
+    let space = Space::from_embedding(
+        Variables::new(["theta", "phi"]),
+        vec![
+            parse("sin(theta)*cos(phi)"),
+            parse("sin(theta)*sin(phi)"),
+            parse("cos(theta)"),
+        ],
+    );
+    let at = [("theta", 0.7), ("phi", 1.3)];
+    let metric = space.get_metric();
+
+    assert_eq!(space.dim(), 2);
+    assert_eq!(space.variables().name(1), "phi");
+    assert_close(eval_at(&metric.get_element(0, 0), &at), 1.0, "g_tt");
+    assert_close(eval_at(&metric.get_element(0, 1), &at), 0.0, "g_tp");
+    assert_close(
+        eval_at(&metric.get_element(1, 1), &at),
+        0.7_f64.sin().powi(2),
+        "g_pp",
+    );
+}
+
+#[test]
+fn spacetime_basis_changes_scale_time_by_light_speed() {
+    let space = Space::new(parse("x"), parse("y"), parse("z"));
+    let spacetime = Spacetime::new(&space, 2.0);
+    let one_form = spacetime.form(["3", "1", "1", "1"].map(parse).to_vec(), 1);
+
+    let otn = spacetime.to_otn(&one_form);
+    let back = spacetime.to_natural(&otn);
+
+    assert_close(eval_at(otn.get_expr(0), &[]), 1.5, "A_0 in c dt coframe");
+    assert_close(eval_at(back.get_expr(0), &[]), 3.0, "A_t round trip");
+}